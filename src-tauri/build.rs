@@ -1,3 +1,43 @@
+use std::path::Path;
+use std::process::Command;
+
+// The clip `run_benchmark` transcribes. It is bundled on macOS only (see
+// tauri.macos.conf.json) and synthesized here with `say` the first time, so
+// tauri-build always finds it and every machine benchmarks the same audio.
+const BENCHMARK_SAMPLE: &str = "resources/benchmark-sample.wav";
+const BENCHMARK_SAMPLE_TEXT: &str = "Thanks for making time today. Last week we talked about planning the product launch, and I said I would \
+follow up with the marketing team before Friday. I did meet with them on Wednesday. The good news is that the \
+budget was approved, but the timeline is tighter than we hoped. We need the final designs by the end of the \
+month, and the engineering team wants two more weeks for testing. So my question for you is how we balance \
+quality against the launch date without burning everyone out.";
+
+fn synthesize_benchmark_sample(voice: Option<&str>) -> bool {
+    let mut command = Command::new("say");
+    if let Some(voice) = voice {
+        command.args(["-v", voice]);
+    }
+    command
+        .args([
+            "-o",
+            BENCHMARK_SAMPLE,
+            "--file-format=WAVE",
+            "--data-format=LEI16@16000",
+            BENCHMARK_SAMPLE_TEXT,
+        ])
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
 fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", BENCHMARK_SAMPLE);
+    let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    if target_os == "macos" && !Path::new(BENCHMARK_SAMPLE).exists() {
+        std::fs::create_dir_all("resources").expect("failed to create resources directory");
+        if !synthesize_benchmark_sample(Some("Samantha")) && !synthesize_benchmark_sample(None) {
+            panic!("failed to synthesize {} with say", BENCHMARK_SAMPLE);
+        }
+    }
+
     tauri_build::build()
 }
@@ -3,6 +3,7 @@ import CoreGraphics
 import CoreMedia
 import Darwin
 import Foundation
+import LocalAuthentication
 import ScreenCaptureKit
 
 struct CaptureResult: Encodable {
@@ -17,11 +18,12 @@ enum CaptureError: LocalizedError {
     case noDisplayAvailable
     case writerConfigurationFailed
     case noAudioCaptured
+    case authenticationUnavailable(String)
 
     var errorDescription: String? {
         switch self {
         case .invalidArguments:
-            return "Usage: system-audio-capture record --output <path>"
+            return "Usage: system-audio-capture record --output <path> [--sample-rate <hz>] [--channels <count>] | authenticate [--reason <text>]"
         case .missingOutputPath:
             return "Missing required --output <path> argument."
         case .unsupportedMacOSVersion:
@@ -34,12 +36,26 @@ enum CaptureError: LocalizedError {
             return "Failed to configure audio writer."
         case .noAudioCaptured:
             return "No shared audio was captured."
+        case .authenticationUnavailable(let reason):
+            return "Device authentication is unavailable: \(reason)"
         }
     }
 }
 
 struct CLIArguments {
     let outputPath: String
+    let sampleRate: Int
+    let channelCount: Int
+}
+
+func integerArgument(_ args: [String], _ flag: String, default defaultValue: Int) throws -> Int {
+    guard let flagIndex = args.firstIndex(of: flag) else {
+        return defaultValue
+    }
+    guard flagIndex + 1 < args.count, let value = Int(args[flagIndex + 1]), value > 0 else {
+        throw CaptureError.invalidArguments
+    }
+    return value
 }
 
 func parseArguments() throws -> CLIArguments {
@@ -57,7 +73,31 @@ func parseArguments() throws -> CLIArguments {
         throw CaptureError.missingOutputPath
     }
 
-    return CLIArguments(outputPath: args[outputIndex])
+    return CLIArguments(
+        outputPath: args[outputIndex],
+        sampleRate: try integerArgument(args, "--sample-rate", default: 16_000),
+        channelCount: try integerArgument(args, "--channels", default: 1)
+    )
+}
+
+func authenticationReason() -> String {
+    let args = CommandLine.arguments
+    if let reasonFlagIndex = args.firstIndex(of: "--reason"), reasonFlagIndex + 1 < args.count {
+        return args[reasonFlagIndex + 1]
+    }
+    return "unlock your CoachNotes sessions"
+}
+
+func authenticateDeviceOwner(reason: String) async throws {
+    let context = LAContext()
+    var policyError: NSError?
+    guard context.canEvaluatePolicy(.deviceOwnerAuthentication, error: &policyError) else {
+        throw CaptureError.authenticationUnavailable(
+            policyError?.localizedDescription ?? "no passcode or biometrics are configured"
+        )
+    }
+
+    _ = try await context.evaluatePolicy(.deviceOwnerAuthentication, localizedReason: reason)
 }
 
 func ensureScreenRecordingPermission() throws {
@@ -80,7 +120,7 @@ final class StreamAudioWriter: NSObject, SCStreamOutput, SCStreamDelegate {
     private(set) var streamError: Error?
     private(set) var firstAudioWallTimeMs: Int64?
 
-    init(outputURL: URL) throws {
+    init(outputURL: URL, sampleRate: Int, channelCount: Int) throws {
         if FileManager.default.fileExists(atPath: outputURL.path) {
             try? FileManager.default.removeItem(at: outputURL)
         }
@@ -89,8 +129,8 @@ final class StreamAudioWriter: NSObject, SCStreamOutput, SCStreamDelegate {
 
         let settings: [String: Any] = [
             AVFormatIDKey: kAudioFormatLinearPCM,
-            AVSampleRateKey: 16_000,
-            AVNumberOfChannelsKey: 1,
+            AVSampleRateKey: sampleRate,
+            AVNumberOfChannelsKey: channelCount,
             AVLinearPCMBitDepthKey: 16,
             AVLinearPCMIsFloatKey: false,
             AVLinearPCMIsBigEndianKey: false,
@@ -179,11 +219,15 @@ final class SystemAudioRecorder {
     private let outputURL: URL
     private let writer: StreamAudioWriter
     private let stopSignal = DispatchSemaphore(value: 0)
+    private let sampleRate: Int
+    private let channelCount: Int
     private var stream: SCStream?
 
-    init(outputPath: String) throws {
+    init(outputPath: String, sampleRate: Int, channelCount: Int) throws {
         outputURL = URL(fileURLWithPath: outputPath)
-        writer = try StreamAudioWriter(outputURL: outputURL)
+        self.sampleRate = sampleRate
+        self.channelCount = channelCount
+        writer = try StreamAudioWriter(outputURL: outputURL, sampleRate: sampleRate, channelCount: channelCount)
     }
 
     func runUntilStdinClosed() async throws -> CaptureResult {
@@ -196,8 +240,8 @@ final class SystemAudioRecorder {
         let config = SCStreamConfiguration()
         config.capturesAudio = true
         config.excludesCurrentProcessAudio = false
-        config.sampleRate = 16_000
-        config.channelCount = 1
+        config.sampleRate = sampleRate
+        config.channelCount = channelCount
         config.queueDepth = 3
         config.minimumFrameInterval = CMTime(value: 1, timescale: 60)
         config.width = display.width
@@ -242,6 +286,11 @@ final class SystemAudioRecorder {
 struct SystemAudioCaptureMain {
     static func main() async {
         do {
+            if CommandLine.arguments.count >= 2, CommandLine.arguments[1] == "authenticate" {
+                try await authenticateDeviceOwner(reason: authenticationReason())
+                exit(0)
+            }
+
             guard #available(macOS 13.0, *) else {
                 throw CaptureError.unsupportedMacOSVersion
             }
@@ -249,7 +298,11 @@ struct SystemAudioCaptureMain {
             let cli = try parseArguments()
             try ensureScreenRecordingPermission()
 
-            let recorder = try SystemAudioRecorder(outputPath: cli.outputPath)
+            let recorder = try SystemAudioRecorder(
+                outputPath: cli.outputPath,
+                sampleRate: cli.sampleRate,
+                channelCount: cli.channelCount
+            )
             let result = try await recorder.runUntilStdinClosed()
             let data = try JSONEncoder().encode(result)
             FileHandle.standardOutput.write(data)
@@ -1,27 +1,302 @@
-use futures_util::StreamExt;
+use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ffi::OsString;
 use std::fs;
-use std::io::Read;
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command as StdCommand, Stdio};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
-use tauri::{AppHandle, Emitter, Manager, State};
+use tauri::{AppHandle, Emitter, Listener, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_shell::process::CommandEvent;
 use tauri_plugin_shell::ShellExt;
-use time::{format_description::well_known::Rfc3339, macros::format_description, OffsetDateTime};
+use tauri_plugin_updater::UpdaterExt;
+use time::{
+    format_description::well_known::Rfc3339, macros::format_description, Date, Month,
+    OffsetDateTime, PrimitiveDateTime, Time, UtcOffset,
+};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 const COACHNOTES_DELETED_DIR: &str = "Deleted Notes";
+const CLIENT_INDEX_FILE: &str = "_index.md";
+const DIGEST_UNASSIGNED_CLIENT: &str = "No client";
 const SPEAKER_TURN_MARKER: &str = "[SPEAKER_TURN]";
 const SYSTEM_AUDIO_CAPTURE_PLACEHOLDER_MARKER: &str = "system-audio-capture sidecar placeholder";
 const BLANK_AUDIO_MARKER: &str = "[BLANK_AUDIO]";
+const SUPPORTED_LOCALES: [&str; 4] = ["en", "es", "fr", "de"];
+// Bundled with the macOS app (synthesized by build.rs) so every machine
+// benchmarks the same audio.
+const BENCHMARK_SAMPLE_RESOURCE: &str = "resources/benchmark-sample.wav";
+const BENCHMARK_MEMORY_POLL: Duration = Duration::from_millis(100);
+const WAV_HEADER_BYTES: usize = 44;
+// How much of a staged WAV is read to find its `data` chunk.
+const WAV_HEADER_PROBE_BYTES: usize = 64 * 1024;
+const WAV_SAMPLE_RATE: u32 = 16_000;
+const WAV_BYTES_PER_SAMPLE: usize = 2;
+const DENOISE_UPSAMPLE_FACTOR: usize = 3;
+const NORMALIZE_TARGET_PEAK: f32 = 0.89;
+const NORMALIZE_MAX_GAIN: f32 = 10.0;
+const CHAPTER_MIN_TRANSCRIPT_MS: u64 = 10 * 60 * 1000;
+const CHAPTER_MIN_LENGTH_MS: u64 = 3 * 60 * 1000;
+const CHAPTER_MAX_LENGTH_MS: u64 = 15 * 60 * 1000;
+const CHAPTER_MIN_PAUSE_MS: u64 = 1500;
+const CHAPTER_FORCED_PAUSE_MS: u64 = 500;
+const CHAPTER_WINDOW_WORDS: usize = 120;
+const CHAPTER_MAX_SIMILARITY: f64 = 0.12;
+const STOPWORDS: &[&str] = &[
+    "the",
+    "and",
+    "that",
+    "have",
+    "for",
+    "not",
+    "with",
+    "you",
+    "this",
+    "but",
+    "his",
+    "from",
+    "they",
+    "say",
+    "her",
+    "she",
+    "will",
+    "one",
+    "all",
+    "would",
+    "there",
+    "their",
+    "what",
+    "out",
+    "about",
+    "who",
+    "get",
+    "which",
+    "when",
+    "make",
+    "can",
+    "like",
+    "time",
+    "just",
+    "him",
+    "know",
+    "take",
+    "into",
+    "your",
+    "some",
+    "could",
+    "them",
+    "see",
+    "other",
+    "than",
+    "then",
+    "now",
+    "look",
+    "only",
+    "come",
+    "its",
+    "over",
+    "think",
+    "also",
+    "back",
+    "after",
+    "use",
+    "how",
+    "our",
+    "well",
+    "way",
+    "even",
+    "want",
+    "because",
+    "yeah",
+    "okay",
+    "really",
+    "going",
+    "gonna",
+    "thing",
+    "things",
+    "something",
+    "actually",
+    "right",
+    "mean",
+    "kind",
+    "sort",
+    "little",
+    "pretty",
+    "doing",
+    "being",
+    "been",
+    "were",
+    "said",
+    "more",
+    "very",
+    "much",
+    "here",
+    "where",
+    "these",
+    "those",
+    "does",
+    "did",
+    "didn't",
+    "don't",
+    "i'm",
+    "it's",
+    "that's",
+    "there's",
+    "you're",
+    "we're",
+    "they're",
+    "i've",
+    "maybe",
+    "probably",
+    "should",
+    "still",
+    "through",
+    "good",
+];
+const TOPIC_MIN_WORD_LEN: usize = 4;
+const TOPIC_MAX_PHRASE_WORDS: usize = 3;
+const TOPIC_MIN_OCCURRENCES: usize = 2;
+const TOPIC_LIMIT: usize = 8;
+
+const DEFAULT_TRANSCRIPT_TITLE: &str = "Session Transcript";
+const SUMMARY_MAX_SENTENCES: usize = 3;
+const SUMMARY_MIN_WORDS: usize = 6;
+const SUMMARY_MAX_WORDS: usize = 40;
+const PULL_QUOTE_LIMIT: usize = 5;
+const PULL_QUOTE_MIN_WORDS: usize = 8;
+const PULL_QUOTE_MAX_WORDS: usize = 30;
+const TITLE_MAX_WORDS: usize = 8;
+const TITLE_MIN_WORDS: usize = 4;
+const TITLE_MIN_CONTENT_WORDS: usize = 2;
+const TITLE_SCAN_SENTENCES: usize = 40;
+const TITLE_SLUG_MAX_LEN: usize = 60;
+const TITLE_FILLER_WORDS: &[&str] = &[
+    "so", "um", "uh", "okay", "ok", "well", "yeah", "yes", "right", "alright", "hi", "hello",
+    "hey", "like", "and", "but", "oh", "thanks",
+];
+const SENTIMENT_NORMALIZATION_ALPHA: f64 = 15.0;
+const SENTIMENT_NEUTRAL_BAND: f64 = 0.05;
+const SENTIMENT_NEGATION_WINDOW: usize = 3;
+const SENTIMENT_NEGATIONS: &[&str] = &[
+    "not",
+    "no",
+    "never",
+    "don't",
+    "didn't",
+    "doesn't",
+    "isn't",
+    "wasn't",
+    "aren't",
+    "can't",
+    "won't",
+    "couldn't",
+    "wouldn't",
+    "shouldn't",
+    "nothing",
+    "without",
+];
+const SENTIMENT_LEXICON: &[(&str, i32)] = &[
+    ("amazing", 4),
+    ("awesome", 4),
+    ("excellent", 3),
+    ("fantastic", 4),
+    ("great", 3),
+    ("love", 3),
+    ("loved", 3),
+    ("wonderful", 4),
+    ("excited", 3),
+    ("exciting", 3),
+    ("happy", 3),
+    ("glad", 2),
+    ("proud", 2),
+    ("confident", 2),
+    ("grateful", 3),
+    ("thankful", 2),
+    ("hopeful", 2),
+    ("calm", 2),
+    ("relieved", 2),
+    ("progress", 2),
+    ("success", 2),
+    ("successful", 3),
+    ("better", 2),
+    ("good", 2),
+    ("nice", 2),
+    ("enjoy", 2),
+    ("enjoyed", 2),
+    ("helpful", 2),
+    ("clear", 1),
+    ("clarity", 2),
+    ("motivated", 2),
+    ("energized", 2),
+    ("win", 3),
+    ("breakthrough", 3),
+    ("easier", 2),
+    ("fine", 1),
+    ("okay", 1),
+    ("agree", 1),
+    ("interesting", 2),
+    ("safe", 1),
+    ("bad", -3),
+    ("terrible", -3),
+    ("awful", -3),
+    ("horrible", -3),
+    ("hate", -3),
+    ("sad", -2),
+    ("angry", -3),
+    ("upset", -2),
+    ("frustrated", -2),
+    ("frustrating", -2),
+    ("stressed", -2),
+    ("stress", -2),
+    ("stressful", -2),
+    ("anxious", -2),
+    ("anxiety", -2),
+    ("worried", -2),
+    ("worry", -2),
+    ("afraid", -2),
+    ("scared", -2),
+    ("fear", -2),
+    ("overwhelmed", -2),
+    ("tired", -2),
+    ("exhausted", -2),
+    ("burnout", -3),
+    ("difficult", -1),
+    ("hard", -1),
+    ("struggle", -2),
+    ("struggling", -2),
+    ("problem", -2),
+    ("problems", -2),
+    ("fail", -2),
+    ("failed", -2),
+    ("failure", -2),
+    ("stuck", -2),
+    ("confused", -2),
+    ("lonely", -2),
+    ("hurt", -2),
+    ("disappointed", -2),
+    ("disappointing", -2),
+    ("annoyed", -2),
+    ("conflict", -2),
+    ("worse", -2),
+    ("worst", -3),
+    ("wrong", -2),
+    ("unhappy", -2),
+    ("guilty", -2),
+];
 
 #[derive(Debug, Clone, Copy)]
 struct ModelCatalogEntry {
     id: &'static str,
     label: &'static str,
     size_mb: u32,
+    // Approximate peak memory whisper.cpp needs to load and run the model.
+    ram_mb: u32,
     url: &'static str,
     sha256: &'static str,
 }
@@ -31,13 +306,15 @@ const MODEL_CATALOG: [ModelCatalogEntry; 5] = [
         id: "tiny",
         label: "Tiny (fastest, lowest accuracy)",
         size_mb: 75,
+        ram_mb: 390,
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin",
         sha256: "be07e048e1e599ad46341c8d2a135645097a538221678b7acdd1b1919c6e1b21",
     },
     ModelCatalogEntry {
         id: "base",
-        label: "Base (recommended on MacBook Air)",
+        label: "Base (balanced speed and accuracy)",
         size_mb: 142,
+        ram_mb: 500,
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin",
         sha256: "60ed5bc3dd14eea856493d334349b405782ddcaf0028d4b5df4088345fba2efe",
     },
@@ -45,6 +322,7 @@ const MODEL_CATALOG: [ModelCatalogEntry; 5] = [
         id: "small",
         label: "Small (higher quality)",
         size_mb: 466,
+        ram_mb: 1000,
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin",
         sha256: "1be3a9b2063867b937e64e2ec7483364a79917e157fa98c5d94b5c1fffea987b",
     },
@@ -52,6 +330,7 @@ const MODEL_CATALOG: [ModelCatalogEntry; 5] = [
         id: "medium",
         label: "Medium (best quality, slower)",
         size_mb: 1500,
+        ram_mb: 2600,
         url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.bin",
         sha256: "6c14d5adee5f86394037b4e4e8b59f1673b6cee10e3cf0b11bbdbee79c156208",
     },
@@ -59,6 +338,7 @@ const MODEL_CATALOG: [ModelCatalogEntry; 5] = [
         id: "small.en-tdrz",
         label: "Small.en + tdrz (experimental 2-speaker, English)",
         size_mb: 466,
+        ram_mb: 1000,
         url: "https://huggingface.co/akashmjn/tinydiarize-whisper.cpp/resolve/main/ggml-small.en-tdrz.bin",
         sha256: "ceac3ec06d1d98ef71aec665283564631055fd6129b79d8e1be4f9cc33cc54b4",
     },
@@ -75,6 +355,197 @@ struct AppSettings {
     diarization_mode: String,
     #[serde(default)]
     diarization_mode_configured: bool,
+    #[serde(default)]
+    prewarm_on_launch: bool,
+    #[serde(default = "default_locale")]
+    locale: String,
+    #[serde(default)]
+    timezone: Option<String>,
+    #[serde(default = "default_date_format")]
+    date_format: String,
+    #[serde(default)]
+    custom_frontmatter: BTreeMap<String, String>,
+    #[serde(default)]
+    auto_title: bool,
+    #[serde(default)]
+    title_in_filename: bool,
+    #[serde(default)]
+    auto_split_minutes: Option<u32>,
+    // Transcribe microphone recordings in chunks while they are running.
+    #[serde(default)]
+    rolling_transcription: bool,
+    #[serde(default = "default_max_recording_minutes")]
+    max_recording_minutes: u32,
+    #[serde(default = "default_max_audio_mb")]
+    max_audio_mb: u32,
+    #[serde(default)]
+    fallback_to_smaller_model: bool,
+    #[serde(default)]
+    auto_download_models: bool,
+    // Language code -> model used when `selected_model` is "auto".
+    #[serde(default)]
+    language_models: BTreeMap<String, String>,
+    #[serde(default)]
+    extra_whisper_args: Vec<String>,
+    // Entries of `extra_whisper_args` dropped when settings.json was read,
+    // reported until the flags are saved again.
+    #[serde(skip)]
+    ignored_whisper_args: Vec<String>,
+    #[serde(default)]
+    spell_correction: bool,
+    // Names and terms whisper tends to mishear, one per entry.
+    #[serde(default)]
+    spell_dictionary: Vec<String>,
+    #[serde(default)]
+    text_normalization: TextNormalization,
+    #[serde(default)]
+    client_prompts: BTreeMap<String, String>,
+    #[serde(default)]
+    client_templates: BTreeMap<String, String>,
+    #[serde(default)]
+    client_languages: BTreeMap<String, ClientLanguage>,
+    #[serde(default = "default_duplicate_check")]
+    duplicate_check: String,
+    #[serde(default)]
+    notify_on_completion: bool,
+    #[serde(default)]
+    completion_sound: Option<String>,
+    #[serde(default = "default_completion_sound_min_seconds")]
+    completion_sound_min_seconds: u32,
+    #[serde(default)]
+    battery_saver: bool,
+    #[serde(default)]
+    pause_queue_on_battery: bool,
+    #[serde(default)]
+    deferred_window: Option<DeferredWindow>,
+    #[serde(default = "default_update_channel")]
+    update_channel: String,
+    #[serde(default)]
+    keep_raw_whisper_output: bool,
+    #[serde(default)]
+    raw_output_dir: Option<String>,
+    #[serde(default)]
+    coachnotes_append_same_day: bool,
+    #[serde(default)]
+    verify_models_in_background: bool,
+    #[serde(default)]
+    redownload_corrupted_models: bool,
+    #[serde(default)]
+    download_limit_kbps: u32,
+    #[serde(default)]
+    zoom_recordings_dir: Option<String>,
+    #[serde(default)]
+    zoom_client_map: BTreeMap<String, String>,
+    #[serde(default)]
+    client_detection_enabled: bool,
+    #[serde(default)]
+    client_detection_calendar: bool,
+    // Meeting or event title text -> client, like `zoom_client_map`.
+    #[serde(default)]
+    client_detection_map: BTreeMap<String, String>,
+    #[serde(default)]
+    recording_presets: Vec<RecordingPreset>,
+    #[serde(default)]
+    active_recording_preset: Option<String>,
+    // The active workspace's values live in the top-level fields above; this
+    // list keeps the others, and a possibly stale copy of the active one.
+    #[serde(default)]
+    workspaces: Vec<Workspace>,
+    #[serde(default = "default_workspace_name")]
+    active_workspace: String,
+    #[serde(default = "default_recorder_volume_patterns")]
+    recorder_volume_patterns: Vec<String>,
+    #[serde(default)]
+    recorder_move_originals: bool,
+    #[serde(default)]
+    recorder_imported_until: BTreeMap<String, u64>,
+    #[serde(default)]
+    app_lock_enabled: bool,
+    #[serde(default)]
+    app_lock_touch_id: bool,
+    #[serde(default)]
+    offline_mode: bool,
+    #[serde(default)]
+    verbatim: bool,
+    #[serde(default)]
+    timestamp_interval_seconds: Option<u32>,
+    #[serde(default = "default_share_title_template")]
+    share_title_template: String,
+    #[serde(default = "default_share_tags")]
+    share_tags: Vec<String>,
+    #[serde(default)]
+    event_bridge_enabled: bool,
+    #[serde(default = "default_event_bridge_port")]
+    event_bridge_port: u16,
+    #[serde(default)]
+    webhooks: Vec<Webhook>,
+    #[serde(default)]
+    drive_client_id: Option<String>,
+    #[serde(default = "default_drive_folder_name")]
+    drive_folder_name: String,
+    // Id of the folder Echo Scribe created for `drive_folder_name`.
+    #[serde(default)]
+    drive_folder_id: Option<String>,
+    #[serde(default)]
+    drive_as_google_doc: bool,
+    // A refresh token is in the credential store.
+    #[serde(default)]
+    drive_connected: bool,
+    // A Hugging Face access token is in the credential store.
+    #[serde(default)]
+    huggingface_token_set: bool,
+    #[serde(default)]
+    coachnotes_inline_session_links: bool,
+    #[serde(default)]
+    markdown_escape: MarkdownEscape,
+}
+
+fn default_max_recording_minutes() -> u32 {
+    180
+}
+
+fn default_max_audio_mb() -> u32 {
+    1024
+}
+
+fn default_completion_sound_min_seconds() -> u32 {
+    60
+}
+
+// Volume names field recorders and their SD cards mount under.
+fn default_recorder_volume_patterns() -> Vec<String> {
+    ["ZOOM*", "TASCAM*", "*RECORDER*", "IC RECORDER", "NO NAME"]
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+fn default_event_bridge_port() -> u16 {
+    8765
+}
+
+fn default_share_title_template() -> String {
+    "{title}".to_string()
+}
+
+fn default_share_tags() -> Vec<String> {
+    vec!["echo-scribe".to_string(), "{client}".to_string()]
+}
+
+fn default_workspace_name() -> String {
+    DEFAULT_WORKSPACE.to_string()
+}
+
+fn default_update_channel() -> String {
+    "stable".to_string()
+}
+
+fn default_locale() -> String {
+    "en".to_string()
+}
+
+fn default_date_format() -> String {
+    "ymd".to_string()
 }
 
 impl Default for AppSettings {
@@ -88,49 +559,285 @@ impl Default for AppSettings {
             coachnotes_client: None,
             diarization_mode: "source_aware_2speaker".to_string(),
             diarization_mode_configured: false,
+            prewarm_on_launch: false,
+            locale: default_locale(),
+            timezone: None,
+            date_format: default_date_format(),
+            custom_frontmatter: BTreeMap::new(),
+            auto_title: false,
+            title_in_filename: false,
+            auto_split_minutes: None,
+            rolling_transcription: false,
+            max_recording_minutes: default_max_recording_minutes(),
+            max_audio_mb: default_max_audio_mb(),
+            fallback_to_smaller_model: false,
+            auto_download_models: false,
+            language_models: BTreeMap::new(),
+            extra_whisper_args: Vec::new(),
+            ignored_whisper_args: Vec::new(),
+            spell_correction: false,
+            spell_dictionary: Vec::new(),
+            text_normalization: TextNormalization::default(),
+            client_prompts: BTreeMap::new(),
+            client_templates: BTreeMap::new(),
+            client_languages: BTreeMap::new(),
+            duplicate_check: default_duplicate_check(),
+            notify_on_completion: false,
+            completion_sound: None,
+            completion_sound_min_seconds: default_completion_sound_min_seconds(),
+            battery_saver: false,
+            pause_queue_on_battery: false,
+            deferred_window: None,
+            update_channel: default_update_channel(),
+            keep_raw_whisper_output: false,
+            raw_output_dir: None,
+            coachnotes_append_same_day: false,
+            verify_models_in_background: false,
+            redownload_corrupted_models: false,
+            download_limit_kbps: 0,
+            zoom_recordings_dir: None,
+            zoom_client_map: BTreeMap::new(),
+            client_detection_enabled: false,
+            client_detection_calendar: false,
+            client_detection_map: BTreeMap::new(),
+            recording_presets: Vec::new(),
+            active_recording_preset: None,
+            workspaces: Vec::new(),
+            active_workspace: default_workspace_name(),
+            recorder_volume_patterns: default_recorder_volume_patterns(),
+            recorder_move_originals: false,
+            recorder_imported_until: BTreeMap::new(),
+            app_lock_enabled: false,
+            app_lock_touch_id: false,
+            offline_mode: false,
+            verbatim: false,
+            timestamp_interval_seconds: None,
+            share_title_template: default_share_title_template(),
+            share_tags: default_share_tags(),
+            event_bridge_enabled: false,
+            event_bridge_port: default_event_bridge_port(),
+            webhooks: Vec::new(),
+            drive_client_id: None,
+            drive_folder_name: default_drive_folder_name(),
+            drive_folder_id: None,
+            drive_as_google_doc: false,
+            drive_connected: false,
+            huggingface_token_set: false,
+            coachnotes_inline_session_links: false,
+            markdown_escape: MarkdownEscape::default(),
         }
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 pub struct ModelState {
     id: String,
     label: String,
     size_mb: u32,
+    ram_mb: u32,
     downloaded: bool,
     path: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, Copy)]
+pub struct DiarizationEngine {
+    // Value of `diarization_mode` that selects this engine.
+    mode: &'static str,
+    label: &'static str,
+    // Most speakers the engine tells apart; 1 means no speaker labels.
+    max_speakers: u32,
+    // Empty means every language the selected model supports.
+    languages: &'static [&'static str],
+    required_model: Option<&'static str>,
+    // Needs system audio and the microphone recorded as separate channels.
+    requires_separate_channels: bool,
+    experimental: bool,
+}
+
+const DIARIZATION_ENGINES: [DiarizationEngine; 3] = [
+    DiarizationEngine {
+        mode: "none",
+        label: "Standard transcription",
+        max_speakers: 1,
+        languages: &[],
+        required_model: None,
+        requires_separate_channels: false,
+        experimental: false,
+    },
+    DiarizationEngine {
+        mode: "source_aware_2speaker",
+        label: "Two-speaker from system + microphone (recommended)",
+        max_speakers: 2,
+        languages: &[],
+        required_model: None,
+        requires_separate_channels: true,
+        experimental: false,
+    },
+    DiarizationEngine {
+        mode: "tdrz_2speaker",
+        label: "Whisper diarization fallback (experimental, English only)",
+        max_speakers: 2,
+        languages: &["en"],
+        required_model: Some("small.en-tdrz"),
+        requires_separate_channels: false,
+        experimental: true,
+    },
+];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiarizationEngineState {
+    #[serde(flatten)]
+    engine: DiarizationEngine,
+    // The required model, if any, is downloaded.
+    ready: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
 pub struct DiarizationCapabilities {
-    tdrz_english_only: bool,
+    engines: Vec<DiarizationEngineState>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
+pub struct HardwareInfo {
+    cpu_cores: usize,
+    memory_gb: Option<u64>,
+    cpu_brand: Option<String>,
+    apple_silicon_generation: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WhisperBinaryFeatures {
+    metal: bool,
+    coreml: bool,
+    cuda: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AccelerationInfo {
+    metal_available: bool,
+    coreml_available: bool,
+    cuda_available: bool,
+    whisper_binary: String,
+    whisper_binary_found: bool,
+    whisper_binary_features: WhisperBinaryFeatures,
+    coreml_encoder_models: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
 pub struct SetupState {
     selected_model: String,
     transcript_dir: String,
     transcript_format: String,
     models_dir: String,
+    app_data_dir: String,
     models: Vec<ModelState>,
     ready: bool,
     sidecar_ready: bool,
+    ffmpeg_ready: bool,
     coachnotes_enabled: bool,
     coachnotes_root_dir: Option<String>,
     coachnotes_clients: Vec<String>,
     coachnotes_client: Option<String>,
     diarization_mode: String,
     diarization_capabilities: DiarizationCapabilities,
+    hardware: HardwareInfo,
+    recommended_model: String,
+    prewarm_on_launch: bool,
+    engine_status: EngineStatus,
+    acceleration: AccelerationInfo,
+    locale: String,
+    supported_locales: Vec<String>,
+    timezone: Option<String>,
+    date_format: String,
+    custom_frontmatter: BTreeMap<String, String>,
+    auto_title: bool,
+    title_in_filename: bool,
+    auto_split_minutes: Option<u32>,
+    rolling_transcription: bool,
+    max_recording_minutes: u32,
+    max_audio_mb: u32,
+    fallback_to_smaller_model: bool,
+    auto_download_models: bool,
+    language_models: BTreeMap<String, String>,
+    extra_whisper_args: Vec<String>,
+    ignored_whisper_args: Vec<String>,
+    spell_correction: bool,
+    spell_dictionary: Vec<String>,
+    text_normalization: TextNormalization,
+    client_prompts: BTreeMap<String, String>,
+    client_templates: BTreeMap<String, String>,
+    client_languages: BTreeMap<String, ClientLanguage>,
+    duplicate_check: String,
+    notify_on_completion: bool,
+    completion_sound: Option<String>,
+    completion_sound_min_seconds: u32,
+    battery_saver: bool,
+    power: PowerState,
+    pause_queue_on_battery: bool,
+    deferred_window: Option<DeferredWindow>,
+    update_channel: String,
+    keep_raw_whisper_output: bool,
+    raw_output_dir: Option<String>,
+    coachnotes_append_same_day: bool,
+    verify_models_in_background: bool,
+    redownload_corrupted_models: bool,
+    download_limit_kbps: u32,
+    zoom_recordings_dir: Option<String>,
+    zoom_client_map: BTreeMap<String, String>,
+    client_detection_enabled: bool,
+    client_detection_calendar: bool,
+    client_detection_map: BTreeMap<String, String>,
+    recording_presets: Vec<RecordingPreset>,
+    active_recording_preset: Option<String>,
+    workspaces: Vec<String>,
+    active_workspace: String,
+    recorder_volume_patterns: Vec<String>,
+    recorder_move_originals: bool,
+    recorder_imported_until: BTreeMap<String, u64>,
+    app_lock_enabled: bool,
+    app_lock_touch_id: bool,
+    app_locked: bool,
+    touch_id_available: bool,
+    offline_mode: bool,
+    verbatim: bool,
+    timestamp_interval_seconds: Option<u32>,
+    share_title_template: String,
+    share_tags: Vec<String>,
+    event_bridge_enabled: bool,
+    event_bridge_port: u16,
+    webhooks: Vec<Webhook>,
+    drive_client_id: Option<String>,
+    drive_folder_name: String,
+    drive_as_google_doc: bool,
+    drive_connected: bool,
+    huggingface_token_set: bool,
+    coachnotes_inline_session_links: bool,
+    markdown_escape: MarkdownEscape,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct TranscriptionOptions {
+    // Audio is kept in WAV files next to a saved job's `options.json`.
+    #[serde(default, skip_serializing)]
     audio_data: Vec<u8>,
-    #[serde(default)]
+    #[serde(default, skip_serializing)]
     microphone_audio_data: Vec<u8>,
-    #[serde(default)]
+    #[serde(default, skip_serializing)]
     system_audio_data: Vec<u8>,
     #[serde(default)]
+    audio_upload_id: Option<String>,
+    #[serde(default)]
+    microphone_upload_id: Option<String>,
+    #[serde(default)]
+    system_upload_id: Option<String>,
+    // Staged uploads the job reads in place of the audio fields above.
+    #[serde(skip)]
+    audio_path: Option<PathBuf>,
+    #[serde(skip)]
+    microphone_audio_path: Option<PathBuf>,
+    #[serde(skip)]
+    system_audio_path: Option<PathBuf>,
+    #[serde(default)]
     system_audio_offset_ms: u64,
     model: String,
     language: String,
@@ -140,22 +847,127 @@ pub struct TranscriptionOptions {
     output_mode: String,
     client: Option<String>,
     diarization_mode: String,
+    #[serde(default)]
+    trim_start_seconds: Option<f64>,
+    #[serde(default)]
+    trim_end_seconds: Option<f64>,
+    #[serde(default)]
+    denoise: bool,
+    #[serde(default)]
+    normalize_loudness: bool,
+    #[serde(default)]
+    generate_chapters: bool,
+    #[serde(default)]
+    analyze_sentiment: bool,
+    #[serde(default)]
+    summarize: bool,
+    #[serde(default)]
+    custom_frontmatter: BTreeMap<String, String>,
+    #[serde(default)]
+    save_json_sidecar: bool,
+    #[serde(default)]
+    session_title: Option<String>,
+    #[serde(default)]
+    session_notes: Option<String>,
+    #[serde(default)]
+    include_notes_section: bool,
+    // Keep whisper's text exactly as printed, one timestamped line per
+    // segment, instead of the cleaned-up, merged paragraphs.
+    #[serde(default)]
+    verbatim: bool,
+    // Also save a podcast show-notes bundle: chapters, summary, pull quotes,
+    // and SRT captions.
+    #[serde(default)]
+    show_notes: bool,
+    #[serde(default)]
+    job_id: Option<String>,
+    // Set for jobs that did not come from the recorder window.
+    #[serde(default)]
+    background: bool,
+    // Replace files that already exist at the planned names. Off by default,
+    // so an existing file keeps its name and the new one gets a suffix.
+    #[serde(default)]
+    overwrite: bool,
+    // Save into this workspace instead of the active one.
+    #[serde(default)]
+    workspace: Option<String>,
+    // Upload the saved transcript to Google Drive afterwards.
+    #[serde(default)]
+    export_to_drive: bool,
+    // `urgent`, `normal` (the default), or `low`.
+    #[serde(default)]
+    priority: String,
+    // Rolling session whose segments cover the start of this recording.
+    #[serde(default, skip_serializing)]
+    rolling_session: Option<String>,
+    // Have whisper translate the speech into English.
+    #[serde(default)]
+    translate: bool,
 }
 
 #[derive(Debug, Serialize)]
 pub struct TranscriptionResult {
+    job_id: String,
+    duration_seconds: u64,
+    model_used: String,
     transcript: String,
+    title: String,
     saved_path: Option<String>,
+    sidecar_path: Option<String>,
+    show_notes_path: Option<String>,
+    part_paths: Vec<String>,
     saved_audio_paths: Vec<String>,
     format: String,
     diarization_applied: bool,
     speaker_mode_used: String,
     warnings: Vec<String>,
+    topics: Vec<String>,
+    summary: Option<String>,
+    sentiment: Option<SentimentSummary>,
+    segment_sentiment: Vec<SegmentSentiment>,
+    speech_stats: SpeechStatistics,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SpeechStatistics {
+    word_count: usize,
+    words_per_minute: f64,
+    average_pause_ms: u64,
+    question_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SegmentSentiment {
+    speaker: String,
+    start_ms: u64,
+    end_ms: u64,
+    score: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SentimentSummary {
+    overall: f64,
+    label: String,
+    positive_segments: usize,
+    negative_segments: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BenchmarkResult {
+    model: String,
+    audio_seconds: f64,
+    wall_seconds: f64,
+    real_time_factor: f64,
+    peak_memory_mb: Option<u64>,
+    error: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ModelDownloadOptions {
     model: String,
+    // Replace a model file that fails verification instead of refusing.
+    #[serde(default)]
+    overwrite: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -169,6 +981,10 @@ pub struct CoachNotesSettingsInput {
     enabled: bool,
     root_dir: Option<String>,
     client: Option<String>,
+    #[serde(default)]
+    append_same_day: bool,
+    #[serde(default)]
+    inline_session_links: bool,
 }
 
 #[derive(Clone, Serialize)]
@@ -177,6 +993,22 @@ struct ProgressPayload {
     message: String,
 }
 
+#[derive(Clone, Serialize)]
+struct ModelVerifiedPayload {
+    model: String,
+    path: String,
+    verified: bool,
+    message: String,
+}
+
+#[derive(Clone, Serialize)]
+struct ModelCorruptedPayload {
+    model: String,
+    path: String,
+    redownloading: bool,
+    message: String,
+}
+
 #[derive(Clone, Serialize)]
 struct ModelDownloadPayload {
     model: String,
@@ -188,6 +1020,7 @@ struct ModelDownloadPayload {
 
 struct WhisperOutput {
     success: bool,
+    terminated: bool,
     stdout: Vec<u8>,
     stderr: Vec<u8>,
     used_sidecar: bool,
@@ -216,6 +1049,103 @@ struct SystemAudioCaptureState {
     session: Mutex<Option<SystemAudioCaptureSession>>,
 }
 
+#[derive(Debug, Serialize, Clone)]
+pub struct EngineStatus {
+    state: String,
+    model: Option<String>,
+    message: String,
+}
+
+impl Default for EngineStatus {
+    fn default() -> Self {
+        Self {
+            state: "cold".to_string(),
+            model: None,
+            message: "Model has not been loaded yet.".to_string(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct EngineState {
+    status: Mutex<EngineStatus>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct JobEvent {
+    job_id: String,
+    model: String,
+    queued_at_ms: u64,
+    started_at_ms: Option<u64>,
+    finished_at_ms: Option<u64>,
+    wait_ms: Option<u64>,
+    run_ms: Option<u64>,
+    saved_path: Option<String>,
+    error: Option<String>,
+    // `on_battery`, `battery_saver`, or `scheduled` while a job is held back.
+    paused_reason: Option<String>,
+    // Start of the deferred window (`HH:MM`) a scheduled job waits for.
+    scheduled_for: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobRecord {
+    job_id: String,
+    model: String,
+    outcome: String,
+    app_version: String,
+    queued_at_ms: u64,
+    started_at_ms: u64,
+    finished_at_ms: u64,
+    audio_seconds: Option<u64>,
+    wall_seconds: f64,
+    real_time_factor: Option<f64>,
+    #[serde(default)]
+    warnings: Vec<String>,
+    error: Option<String>,
+    saved_path: Option<String>,
+    #[serde(default)]
+    retry_of: Option<String>,
+    #[serde(default)]
+    retryable: bool,
+    // `transcript_sha256` of what was saved, for spotting the same transcript
+    // saved twice.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    transcript_sha256: Option<String>,
+}
+
+// Transcriptions run one at a time; later requests wait here and are reported
+// as queued until the slot frees up. Waiting jobs get the slot in the order of
+// `waiting`, not in the order they asked for it.
+#[derive(Default)]
+struct TranscriptionQueue {
+    slot: tokio::sync::Mutex<()>,
+    next_id: AtomicU64,
+    pending: AtomicU64,
+    waiting: Mutex<Vec<QueuedJob>>,
+    // Woken when the order changes or a job gives the slot back.
+    turn: tokio::sync::Notify,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QueuedJob {
+    job_id: String,
+    // Session title, client, or the job id, for the queue list.
+    label: String,
+    priority: String,
+    queued_at_ms: u64,
+    // Set while the job waits for the deferred window; other jobs pass it.
+    scheduled_for: Option<String>,
+}
+
+// Audio sent from the webview in raw chunks and staged in temp files, so a long
+// recording never has to cross IPC as one JSON number array.
+#[derive(Default)]
+struct AudioUploadState {
+    uploads: Mutex<HashMap<String, PathBuf>>,
+    next_id: AtomicU64,
+}
+
 struct TempFileCleanup {
     paths: Vec<PathBuf>,
 }
@@ -229,106 +1159,688 @@ impl TempFileCleanup {
 impl Drop for TempFileCleanup {
     fn drop(&mut self) {
         for path in &self.paths {
-            let _ = fs::remove_file(path);
+            if path.is_dir() {
+                let _ = fs::remove_dir_all(path);
+            } else {
+                let _ = fs::remove_file(path);
+            }
         }
     }
 }
 
 #[derive(Clone, Copy)]
 enum WhisperFileFormat {
-    Txt,
     Srt,
 }
 
 impl WhisperFileFormat {
     fn cli_flag(self) -> &'static str {
         match self {
-            Self::Txt => "-otxt",
             Self::Srt => "-osrt",
         }
     }
 
     fn extension(self) -> &'static str {
         match self {
-            Self::Txt => "txt",
             Self::Srt => "srt",
         }
     }
 }
 
+// Everything whisper can write next to its main output; kept when the user
+// asks for the raw files instead of only the rendered transcript.
+const RAW_WHISPER_OUTPUT_FORMATS: [(&str, &str); 3] =
+    [("-otxt", "txt"), ("-osrt", "srt"), ("-oj", "json")];
+
 struct WhisperTranscriptOutput {
     content: String,
     used_sidecar: bool,
+    // Mean token probability per segment, keyed by its (start_ms, end_ms).
+    confidences: HashMap<(u64, u64), f32>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 struct TimestampedSegment {
     speaker: String,
     start_ms: u64,
     end_ms: u64,
     text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    confidence: Option<f32>,
 }
 
-#[derive(Clone)]
-struct WordSpan {
-    start: usize,
-    end: usize,
-    normalized: String,
-}
-
-fn emit_progress(app: &AppHandle, percent: u32, message: &str) {
-    let _ = app.emit(
-        "progress",
-        ProgressPayload {
-            percent,
-            message: message.to_string(),
-        },
-    );
+/// Keep-range on the microphone/mixed timeline. `end_ms` is an absolute
+/// position, not an amount to cut from the end.
+#[derive(Clone, Copy, Default)]
+struct AudioTrim {
+    start_ms: u64,
+    end_ms: Option<u64>,
 }
 
-fn emit_model_download_progress(
-    app: &AppHandle,
-    model: &str,
-    percent: u32,
-    downloaded_bytes: u64,
-    total_bytes: Option<u64>,
-    message: &str,
-) {
-    let _ = app.emit(
-        "model-download-progress",
-        ModelDownloadPayload {
-            model: model.to_string(),
-            percent,
-            downloaded_bytes,
-            total_bytes,
-            message: message.to_string(),
-        },
-    );
-}
+impl AudioTrim {
+    fn from_seconds(start_seconds: Option<f64>, end_seconds: Option<f64>) -> Result<Self, String> {
+        let to_ms = |label: &str, value: Option<f64>| -> Result<Option<u64>, String> {
+            match value {
+                None => Ok(None),
+                Some(seconds) if seconds.is_finite() && seconds >= 0.0 => {
+                    Ok(Some((seconds * 1000.0).round() as u64))
+                }
+                Some(seconds) => Err(format!("Invalid {} value: {}", label, seconds)),
+            }
+        };
 
-fn sanitize_non_empty(value: Option<String>) -> Option<String> {
-    value.and_then(|v| {
-        let trimmed = v.trim();
-        if trimmed.is_empty() {
-            None
-        } else {
-            Some(trimmed.to_string())
+        let start_ms = to_ms("trim_start_seconds", start_seconds)?.unwrap_or(0);
+        let end_ms = to_ms("trim_end_seconds", end_seconds)?;
+        if let Some(end_ms) = end_ms {
+            if end_ms <= start_ms {
+                return Err("trim_end_seconds must be greater than trim_start_seconds.".to_string());
+            }
         }
-    })
-}
 
-fn now_local_or_utc() -> OffsetDateTime {
-    OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc())
-}
+        Ok(Self { start_ms, end_ms })
+    }
 
-fn format_date(now: OffsetDateTime) -> String {
-    now.format(format_description!("[year]-[month]-[day]"))
-        .unwrap_or_else(|_| "1970-01-01".to_string())
+    fn is_active(self) -> bool {
+        self.start_ms > 0 || self.end_ms.is_some()
+    }
+
+    /// Re-expresses the range for a channel that starts `offset_ms` later
+    /// than the microphone (the system audio channel).
+    fn shifted(self, offset_ms: u64) -> Self {
+        Self {
+            start_ms: self.start_ms.saturating_sub(offset_ms),
+            end_ms: self.end_ms.map(|end_ms| end_ms.saturating_sub(offset_ms)),
+        }
+    }
 }
 
-fn format_time_compact(now: OffsetDateTime) -> String {
-    now.format(format_description!("[hour][minute][second]"))
-        .unwrap_or_else(|_| "000000".to_string())
+#[derive(Clone, Copy, Default)]
+struct AudioPreprocessing {
+    denoise: bool,
+    normalize_loudness: bool,
+}
+
+impl AudioPreprocessing {
+    fn is_active(self) -> bool {
+        self.denoise || self.normalize_loudness
+    }
+
+    fn apply(self, wav_data: &[u8]) -> Cow<'_, [u8]> {
+        if !self.is_active() || wav_pcm_data(wav_data).is_empty() {
+            return Cow::Borrowed(wav_data);
+        }
+
+        let mut samples = wav_pcm_samples(wav_data);
+        if self.denoise {
+            samples = denoise_samples(&samples);
+        }
+        if self.normalize_loudness {
+            normalize_peak_samples(&mut samples);
+        }
+
+        Cow::Owned(encode_samples_wav(&samples))
+    }
+}
+
+#[derive(Serialize)]
+struct TranscriptSidecar<'a> {
+    title: &'a str,
+    part: Option<PartLinks<'a>>,
+    notes: Option<&'a str>,
+    source_app: &'a str,
+    created_at: &'a str,
+    date: &'a str,
+    client: Option<&'a str>,
+    model: &'a str,
+    language: &'a str,
+    diarization_mode: &'a str,
+    diarization_applied: bool,
+    duration_seconds: u64,
+    transcript_path: &'a str,
+    transcript: &'a str,
+    segments: &'a [TimestampedSegment],
+    warnings: &'a [String],
+    topics: &'a [String],
+    summary: Option<&'a str>,
+    sentiment: Option<&'a SentimentSummary>,
+    segment_sentiment: &'a [SegmentSentiment],
+    speech_stats: &'a SpeechStatistics,
+    custom_frontmatter: BTreeMap<&'a str, &'a str>,
+}
+
+#[derive(Clone, Copy, Serialize)]
+struct PartLinks<'a> {
+    number: usize,
+    count: usize,
+    previous: Option<&'a str>,
+    next: Option<&'a str>,
+}
+
+struct MarkdownMetadata<'a> {
+    title: &'a str,
+    part: Option<PartLinks<'a>>,
+    client: Option<&'a str>,
+    model: &'a str,
+    language: &'a str,
+    diarization_mode: &'a str,
+    created_at: &'a str,
+    date: &'a str,
+    duration_seconds: u64,
+    coachnotes: bool,
+    speaker_labels: Option<(&'a str, &'a str)>,
+    topics: &'a [String],
+    summary: Option<&'a str>,
+    sentiment: Option<&'a SentimentSummary>,
+    speech_stats: &'a SpeechStatistics,
+    custom_fields: &'a [(String, String)],
+    notes: Option<&'a str>,
+    notes_section: bool,
+    verbatim: bool,
+    translated: bool,
+    // File name of the client's previous session, linked from frontmatter
+    // and, with `inline_session_links`, from the top of the body.
+    previous_session: Option<&'a str>,
+    inline_session_links: bool,
+    // Body of a note prepared with `prepare_session_note`, kept below the
+    // transcript when the session is merged into it.
+    session_prep: Option<&'a str>,
+}
+
+#[derive(Clone, Copy)]
+enum Message {
+    PreparingSpeakerChannels,
+    TranscribingSystemAudio,
+    MergingSpeakerTranscripts,
+    PreparingDiarization,
+    PreparingRecording,
+    ReadingTranscript,
+    TranscriptionComplete,
+    VerifyingExistingModel,
+    ModelAlreadyDownloaded,
+    StartingDownload,
+    DownloadingModel,
+    ModelDownloadComplete,
+    SourceAwareNeedsBothChannels,
+    TdrzEnglishOnly,
+    TdrzModelRequired,
+    DebugWhisperFallback,
+    MicrophoneNoSegments,
+    SystemNoSegments,
+    NoSpeakerTurns,
+    CoachnotesIncomplete,
+    NoChapterBoundaries,
+    LocalTimezoneUnavailable,
+    NoAudioData,
+    TrimLeavesNoAudio,
+    ModelNotDownloaded,
+    DownloadingMissingModel,
+    RecordingTooLong,
+    AudioTooLarge,
+    ModelFallback,
+    InsufficientMemory,
+    LowMemory,
+    EmptyTranscript,
+    SpellingCorrected,
+    RollingTranscriptionDiscarded,
+    DuplicateTranscript,
+    DuplicateTranscriptSkipped,
+    NotifyTranscriptSaved,
+    NotifyTranscriptionFailed,
+    NotifyModelDownloaded,
+    ModelFileExists,
+    FileExistsSavedAs,
+    AppendedToSameDayTranscript,
+    ConvertedAudioFormat,
+    RecordingTrack,
+    MicrophoneTrack,
+    SystemAudioTrack,
+    AudioNotWav,
+    AudioHeaderDamaged,
+    AudioDataEmpty,
+    AudioCodecUnsupported,
+    AudioTooShort,
+    ReceivingAudio,
+    CheckingAudio,
+    WritingTempAudio,
+    ModelVerifiedOnDisk,
+    ModelCorruptOnDisk,
+    ModelCorruptRedownloading,
+    PartialTranscriptKept,
+    ResumedFromCheckpoint,
+    AppLocked,
+    IncorrectPasscode,
+    UnlockReason,
+    OfflineModeBlocked,
+}
+
+impl Message {
+    fn english(self) -> &'static str {
+        match self {
+            Self::PreparingSpeakerChannels => "Preparing separate speaker channels...",
+            Self::TranscribingSystemAudio => "Transcribing client system audio...",
+            Self::MergingSpeakerTranscripts => "Merging separate speaker transcripts...",
+            Self::PreparingDiarization => "Preparing diarization fallback...",
+            Self::PreparingRecording => "Preparing recording...",
+            Self::ReadingTranscript => "Reading transcript...",
+            Self::TranscriptionComplete => "Transcription complete!",
+            Self::VerifyingExistingModel => "Verifying existing model ({percent}%)...",
+            Self::ModelAlreadyDownloaded => "Model already downloaded.",
+            Self::StartingDownload => "Starting download...",
+            Self::DownloadingModel => "Downloading model...",
+            Self::ModelDownloadComplete => "Model download complete.",
+            Self::SourceAwareNeedsBothChannels => "Two-speaker source-aware mode requires both microphone and system audio capture. Falling back to standard transcription.",
+            Self::TdrzEnglishOnly => "Whisper diarization fallback is English-only. Falling back to standard transcription.",
+            Self::TdrzModelRequired => "Whisper diarization fallback requires the small.en-tdrz model. Falling back to standard transcription.",
+            Self::DebugWhisperFallback => "Using local whisper binary fallback in debug mode. Release builds use sidecar.",
+            Self::MicrophoneNoSegments => "Microphone channel did not produce timestamped transcript segments.",
+            Self::SystemNoSegments => "System audio channel did not produce timestamped transcript segments.",
+            Self::NoSpeakerTurns => "Whisper diarization fallback did not produce speaker boundaries because whisper.cpp returned no [SPEAKER_TURN] markers. Output is unsegmented. This is common when voices are too similar/overlapped or only one voice is dominant; try clearer turn-taking, louder remote audio, or use source-aware mode with separate system + microphone capture.",
+            Self::CoachnotesIncomplete => "CoachNotes mode is enabled but root/client is incomplete. Saving to standard transcript folder instead.",
+            Self::NoChapterBoundaries => "No chapter boundaries were detected. Transcript saved without chapters.",
+            Self::LocalTimezoneUnavailable => "Local timezone could not be determined. Dates use UTC; set a timezone override in Storage settings.",
+            Self::NoAudioData => "No audio data provided. Record audio first.",
+            Self::TrimLeavesNoAudio => "Trim range leaves no audio to transcribe.",
+            Self::ModelNotDownloaded => "Model '{model}' is not downloaded yet. Use Setup to download it first.",
+            Self::DownloadingMissingModel => "Model '{model}' is not downloaded yet. Downloading it before transcribing...",
+            Self::RecordingTooLong => "Recording is {duration}, the limit is {limit}. Enable auto-split in Storage settings or raise the limit.",
+            Self::AudioTooLarge => "Recording audio is {size}, the limit is {limit}. Trim the recording or raise the limit in Storage settings.",
+            Self::ModelFallback => "Model '{model}' ran out of memory or failed to load; the session was transcribed with '{fallback}' instead.",
+            Self::InsufficientMemory => "Model '{model}' needs about {required} of free memory but only {available} is available. Close other apps or pick a smaller model such as '{suggestion}'.",
+            Self::LowMemory => "Only {available} of memory is free for model '{model}'. Close other apps if transcription stalls or fails.",
+            Self::EmptyTranscript => "Whisper returned an empty transcript.",
+            Self::SpellingCorrected => "Spell check replaced '{from}' with '{to}' ({count}x).",
+            Self::RollingTranscriptionDiscarded => "The chunks transcribed during recording were not reused because the model or transcription settings changed; the whole recording was transcribed again.",
+            Self::DuplicateTranscript => "This transcript is identical or nearly identical to {path}, saved earlier. Check that the recording was not submitted twice.",
+            Self::DuplicateTranscriptSkipped => "Not saved: this transcript is identical or nearly identical to {path}, saved earlier.",
+            Self::NotifyTranscriptSaved => "Transcript saved",
+            Self::NotifyTranscriptionFailed => "Transcription failed",
+            Self::NotifyModelDownloaded => "Model '{model}' downloaded",
+            Self::ModelFileExists => "A different file for model '{model}' already exists at {path}. Download again with overwrite enabled to replace it.",
+            Self::FileExistsSavedAs => "{path} already exists and was left untouched; saved as {saved} instead.",
+            Self::AppendedToSameDayTranscript => "Added this session to {path} as Part {part}.",
+            Self::ConvertedAudioFormat => "Converted audio from {format} to 16 kHz 16-bit mono for whisper.",
+            Self::RecordingTrack => "recording",
+            Self::MicrophoneTrack => "microphone track",
+            Self::SystemAudioTrack => "system audio track",
+            Self::AudioNotWav => "The {track} is not a WAV file. Record again or convert it to WAV first.",
+            Self::AudioHeaderDamaged => "The {track} has a damaged WAV header: {detail}",
+            Self::AudioDataEmpty => "The {track} contains no audio samples.",
+            Self::AudioCodecUnsupported => "The {track} uses an unsupported WAV encoding ({format}). Export it as PCM or float WAV.",
+            Self::AudioTooShort => "The {track} is only {seconds} s long. At least 1 second of audio is needed.",
+            Self::ReceivingAudio => "Receiving audio ({received} of {total} MB)...",
+            Self::CheckingAudio => "Checking audio format...",
+            Self::WritingTempAudio => "Writing audio for whisper ({percent}%)...",
+            Self::ModelVerifiedOnDisk => "Model '{model}' passed its check on disk.",
+            Self::ModelCorruptOnDisk => "Model '{model}' on disk does not match its checksum. Download it again.",
+            Self::ModelCorruptRedownloading => "Model '{model}' on disk does not match its checksum. Downloading it again...",
+            Self::PartialTranscriptKept => "The transcript so far is kept in {path}.",
+            Self::ResumedFromCheckpoint => "Resumed from part {part} of {parts}; earlier parts were kept from the interrupted run.",
+            Self::AppLocked => "Echo Scribe is locked. Unlock it to open CoachNotes sessions.",
+            Self::IncorrectPasscode => "Incorrect passcode.",
+            Self::UnlockReason => "unlock your CoachNotes sessions",
+            Self::OfflineModeBlocked => "Offline mode is on, so Echo Scribe will not connect to the internet for this. Turn it off in Settings to continue.",
+        }
+    }
+
+    fn spanish(self) -> &'static str {
+        match self {
+            Self::PreparingSpeakerChannels => "Preparando canales de hablantes separados...",
+            Self::TranscribingSystemAudio => "Transcribiendo el audio del sistema del cliente...",
+            Self::MergingSpeakerTranscripts => "Combinando las transcripciones de cada hablante...",
+            Self::PreparingDiarization => "Preparando la diarización alternativa...",
+            Self::PreparingRecording => "Preparando la grabación...",
+            Self::ReadingTranscript => "Leyendo la transcripción...",
+            Self::TranscriptionComplete => "¡Transcripción completa!",
+            Self::VerifyingExistingModel => "Verificando el modelo existente ({percent}%)...",
+            Self::ModelAlreadyDownloaded => "El modelo ya está descargado.",
+            Self::StartingDownload => "Iniciando la descarga...",
+            Self::DownloadingModel => "Descargando el modelo...",
+            Self::ModelDownloadComplete => "Descarga del modelo completa.",
+            Self::SourceAwareNeedsBothChannels => "El modo de dos hablantes por fuente requiere capturar el micrófono y el audio del sistema. Se usará la transcripción estándar.",
+            Self::TdrzEnglishOnly => "La diarización alternativa de Whisper solo funciona en inglés. Se usará la transcripción estándar.",
+            Self::TdrzModelRequired => "La diarización alternativa de Whisper requiere el modelo small.en-tdrz. Se usará la transcripción estándar.",
+            Self::DebugWhisperFallback => "Usando el binario local de whisper en modo de depuración. Las versiones publicadas usan el sidecar.",
+            Self::MicrophoneNoSegments => "El canal del micrófono no produjo segmentos con marcas de tiempo.",
+            Self::SystemNoSegments => "El canal de audio del sistema no produjo segmentos con marcas de tiempo.",
+            Self::NoSpeakerTurns => "La diarización alternativa no detectó cambios de hablante porque whisper.cpp no devolvió marcas [SPEAKER_TURN]. El resultado no está segmentado. Suele ocurrir cuando las voces se parecen o se solapan; prueba turnos más claros, audio remoto más alto o el modo por fuente con captura separada de sistema y micrófono.",
+            Self::CoachnotesIncomplete => "El modo CoachNotes está activado pero falta la carpeta raíz o el cliente. Se guardará en la carpeta estándar de transcripciones.",
+            Self::NoChapterBoundaries => "No se detectaron límites de capítulo. La transcripción se guardó sin capítulos.",
+            Self::LocalTimezoneUnavailable => "No se pudo determinar la zona horaria local. Las fechas usan UTC; configura una zona horaria en los ajustes de almacenamiento.",
+            Self::NoAudioData => "No se recibió audio. Graba primero.",
+            Self::TrimLeavesNoAudio => "El recorte no deja audio para transcribir.",
+            Self::ModelNotDownloaded => "El modelo '{model}' aún no está descargado. Descárgalo primero en Configuración.",
+            Self::DownloadingMissingModel => "El modelo '{model}' aún no está descargado. Descargándolo antes de transcribir...",
+            Self::RecordingTooLong => "La grabación dura {duration} y el límite es {limit}. Activa la división automática en los ajustes de almacenamiento o aumenta el límite.",
+            Self::AudioTooLarge => "El audio ocupa {size} y el límite es {limit}. Recorta la grabación o aumenta el límite en los ajustes de almacenamiento.",
+            Self::ModelFallback => "El modelo '{model}' se quedó sin memoria o no se pudo cargar; la sesión se transcribió con '{fallback}'.",
+            Self::InsufficientMemory => "El modelo '{model}' necesita unos {required} de memoria libre, pero solo hay {available} disponibles. Cierra otras aplicaciones o elige un modelo más pequeño, como '{suggestion}'.",
+            Self::LowMemory => "Solo hay {available} de memoria libre para el modelo '{model}'. Cierra otras aplicaciones si la transcripción se detiene o falla.",
+            Self::EmptyTranscript => "Whisper devolvió una transcripción vacía.",
+            Self::SpellingCorrected => "La corrección ortográfica cambió '{from}' por '{to}' ({count}x).",
+            Self::RollingTranscriptionDiscarded => "No se reutilizaron los fragmentos transcritos durante la grabación porque cambió el modelo o la configuración de transcripción; se transcribió de nuevo toda la grabación.",
+            Self::DuplicateTranscript => "Esta transcripción es idéntica o casi idéntica a {path}, guardada antes. Comprueba que la grabación no se envió dos veces.",
+            Self::DuplicateTranscriptSkipped => "No se guardó: esta transcripción es idéntica o casi idéntica a {path}, guardada antes.",
+            Self::NotifyTranscriptSaved => "Transcripción guardada",
+            Self::NotifyTranscriptionFailed => "La transcripción falló",
+            Self::NotifyModelDownloaded => "Modelo '{model}' descargado",
+            Self::ModelFileExists => "Ya existe otro archivo para el modelo '{model}' en {path}. Descárgalo de nuevo con la sobrescritura activada para reemplazarlo.",
+            Self::FileExistsSavedAs => "{path} ya existe y no se modificó; se guardó como {saved}.",
+            Self::AppendedToSameDayTranscript => "Esta sesión se añadió a {path} como Parte {part}.",
+            Self::ConvertedAudioFormat => "El audio se convirtió de {format} a 16 kHz, 16 bits y mono para whisper.",
+            Self::RecordingTrack => "grabación",
+            Self::MicrophoneTrack => "pista del micrófono",
+            Self::SystemAudioTrack => "pista de audio del sistema",
+            Self::AudioNotWav => "La {track} no es un archivo WAV. Graba de nuevo o conviértela a WAV primero.",
+            Self::AudioHeaderDamaged => "La {track} tiene un encabezado WAV dañado: {detail}",
+            Self::AudioDataEmpty => "La {track} no contiene muestras de audio.",
+            Self::AudioCodecUnsupported => "La {track} usa una codificación WAV no compatible ({format}). Expórtala como WAV PCM o de coma flotante.",
+            Self::AudioTooShort => "La {track} solo dura {seconds} s. Se necesita al menos 1 segundo de audio.",
+            Self::ReceivingAudio => "Recibiendo audio ({received} de {total} MB)...",
+            Self::CheckingAudio => "Comprobando el formato de audio...",
+            Self::WritingTempAudio => "Escribiendo el audio para whisper ({percent}%)...",
+            Self::ModelVerifiedOnDisk => "El modelo '{model}' superó la comprobación en disco.",
+            Self::ModelCorruptOnDisk => "El modelo '{model}' en disco no coincide con su suma de verificación. Descárgalo de nuevo.",
+            Self::ModelCorruptRedownloading => "El modelo '{model}' en disco no coincide con su suma de verificación. Descargándolo de nuevo...",
+            Self::PartialTranscriptKept => "La transcripción hasta este punto se conserva en {path}.",
+            Self::ResumedFromCheckpoint => "Reanudado desde la parte {part} de {parts}; las partes anteriores se conservaron de la ejecución interrumpida.",
+            Self::AppLocked => "Echo Scribe está bloqueado. Desbloquéalo para abrir las sesiones de CoachNotes.",
+            Self::IncorrectPasscode => "Código incorrecto.",
+            Self::UnlockReason => "desbloquear tus sesiones de CoachNotes",
+            Self::OfflineModeBlocked => "El modo sin conexión está activado, así que Echo Scribe no se conectará a internet para esto. Desactívalo en Ajustes para continuar.",
+        }
+    }
+
+    fn french(self) -> &'static str {
+        match self {
+            Self::PreparingSpeakerChannels => "Préparation des canaux de locuteurs séparés...",
+            Self::TranscribingSystemAudio => "Transcription de l'audio système du client...",
+            Self::MergingSpeakerTranscripts => "Fusion des transcriptions de chaque locuteur...",
+            Self::PreparingDiarization => "Préparation de la diarisation de secours...",
+            Self::PreparingRecording => "Préparation de l'enregistrement...",
+            Self::ReadingTranscript => "Lecture de la transcription...",
+            Self::TranscriptionComplete => "Transcription terminée !",
+            Self::VerifyingExistingModel => "Vérification du modèle existant ({percent} %)...",
+            Self::ModelAlreadyDownloaded => "Modèle déjà téléchargé.",
+            Self::StartingDownload => "Démarrage du téléchargement...",
+            Self::DownloadingModel => "Téléchargement du modèle...",
+            Self::ModelDownloadComplete => "Téléchargement du modèle terminé.",
+            Self::SourceAwareNeedsBothChannels => "Le mode deux locuteurs par source nécessite la capture du micro et de l'audio système. Retour à la transcription standard.",
+            Self::TdrzEnglishOnly => "La diarisation de secours de Whisper ne fonctionne qu'en anglais. Retour à la transcription standard.",
+            Self::TdrzModelRequired => "La diarisation de secours de Whisper nécessite le modèle small.en-tdrz. Retour à la transcription standard.",
+            Self::DebugWhisperFallback => "Utilisation du binaire whisper local en mode débogage. Les versions publiées utilisent le sidecar.",
+            Self::MicrophoneNoSegments => "Le canal du micro n'a produit aucun segment horodaté.",
+            Self::SystemNoSegments => "Le canal audio système n'a produit aucun segment horodaté.",
+            Self::NoSpeakerTurns => "La diarisation de secours n'a détecté aucun changement de locuteur car whisper.cpp n'a renvoyé aucun marqueur [SPEAKER_TURN]. Le résultat n'est pas segmenté. C'est fréquent quand les voix se ressemblent ou se chevauchent ; essayez des tours de parole plus nets, un audio distant plus fort ou le mode par source avec capture séparée système + micro.",
+            Self::CoachnotesIncomplete => "Le mode CoachNotes est activé mais le dossier racine ou le client est incomplet. Enregistrement dans le dossier de transcriptions standard.",
+            Self::NoChapterBoundaries => "Aucune limite de chapitre détectée. Transcription enregistrée sans chapitres.",
+            Self::LocalTimezoneUnavailable => "Impossible de déterminer le fuseau horaire local. Les dates utilisent UTC ; définissez un fuseau horaire dans les réglages de stockage.",
+            Self::NoAudioData => "Aucune donnée audio fournie. Enregistrez d'abord.",
+            Self::TrimLeavesNoAudio => "La plage de découpe ne laisse aucun audio à transcrire.",
+            Self::ModelNotDownloaded => "Le modèle '{model}' n'est pas encore téléchargé. Téléchargez-le d'abord dans la configuration.",
+            Self::DownloadingMissingModel => "Le modèle '{model}' n'est pas encore téléchargé. Téléchargement avant la transcription...",
+            Self::RecordingTooLong => "L'enregistrement dure {duration}, la limite est {limit}. Activez le découpage automatique dans les réglages de stockage ou augmentez la limite.",
+            Self::AudioTooLarge => "L'audio occupe {size}, la limite est {limit}. Coupez l'enregistrement ou augmentez la limite dans les réglages de stockage.",
+            Self::ModelFallback => "Le modèle '{model}' a manqué de mémoire ou n'a pas pu être chargé ; la session a été transcrite avec '{fallback}'.",
+            Self::InsufficientMemory => "Le modèle '{model}' a besoin d'environ {required} de mémoire libre, mais seulement {available} sont disponibles. Fermez d'autres applications ou choisissez un modèle plus petit, comme '{suggestion}'.",
+            Self::LowMemory => "Seulement {available} de mémoire libre pour le modèle '{model}'. Fermez d'autres applications si la transcription se bloque ou échoue.",
+            Self::EmptyTranscript => "Whisper a renvoyé une transcription vide.",
+            Self::SpellingCorrected => "La correction orthographique a remplacé '{from}' par '{to}' ({count}x).",
+            Self::RollingTranscriptionDiscarded => "Les extraits transcrits pendant l'enregistrement n'ont pas été réutilisés car le modèle ou les réglages de transcription ont changé ; tout l'enregistrement a été retranscrit.",
+            Self::DuplicateTranscript => "Cette transcription est identique ou presque identique à {path}, enregistrée plus tôt. Vérifiez que l'enregistrement n'a pas été envoyé deux fois.",
+            Self::DuplicateTranscriptSkipped => "Non enregistrée : cette transcription est identique ou presque identique à {path}, enregistrée plus tôt.",
+            Self::NotifyTranscriptSaved => "Transcription enregistrée",
+            Self::NotifyTranscriptionFailed => "La transcription a échoué",
+            Self::NotifyModelDownloaded => "Modèle '{model}' téléchargé",
+            Self::ModelFileExists => "Un autre fichier pour le modèle '{model}' existe déjà dans {path}. Téléchargez-le à nouveau avec l'écrasement activé pour le remplacer.",
+            Self::FileExistsSavedAs => "{path} existe déjà et n'a pas été modifié ; enregistré sous {saved}.",
+            Self::AppendedToSameDayTranscript => "Cette séance a été ajoutée à {path} comme Partie {part}.",
+            Self::ConvertedAudioFormat => "L'audio a été converti de {format} en 16 kHz, 16 bits mono pour whisper.",
+            Self::RecordingTrack => "enregistrement",
+            Self::MicrophoneTrack => "piste du micro",
+            Self::SystemAudioTrack => "piste audio système",
+            Self::AudioNotWav => "L'audio ({track}) n'est pas un fichier WAV. Enregistrez à nouveau ou convertissez-le d'abord en WAV.",
+            Self::AudioHeaderDamaged => "L'audio ({track}) a un en-tête WAV endommagé : {detail}",
+            Self::AudioDataEmpty => "L'audio ({track}) ne contient aucun échantillon audio.",
+            Self::AudioCodecUnsupported => "L'audio ({track}) utilise un encodage WAV non pris en charge ({format}). Exportez-le en WAV PCM ou flottant.",
+            Self::AudioTooShort => "L'audio ({track}) ne dure que {seconds} s. Il faut au moins 1 seconde d'audio.",
+            Self::ReceivingAudio => "Réception de l'audio ({received} sur {total} Mo)...",
+            Self::CheckingAudio => "Vérification du format audio...",
+            Self::WritingTempAudio => "Écriture de l'audio pour whisper ({percent} %)...",
+            Self::ModelVerifiedOnDisk => "Le modèle « {model} » a passé la vérification sur le disque.",
+            Self::ModelCorruptOnDisk => "Le modèle « {model} » sur le disque ne correspond pas à sa somme de contrôle. Téléchargez-le à nouveau.",
+            Self::ModelCorruptRedownloading => "Le modèle « {model} » sur le disque ne correspond pas à sa somme de contrôle. Nouveau téléchargement...",
+            Self::PartialTranscriptKept => "La transcription obtenue jusqu'ici est conservée dans {path}.",
+            Self::ResumedFromCheckpoint => "Reprise à la partie {part} sur {parts} ; les parties précédentes proviennent de l'exécution interrompue.",
+            Self::AppLocked => "Echo Scribe est verrouillé. Déverrouillez-le pour ouvrir les séances CoachNotes.",
+            Self::IncorrectPasscode => "Code incorrect.",
+            Self::UnlockReason => "déverrouiller vos séances CoachNotes",
+            Self::OfflineModeBlocked => "Le mode hors ligne est activé : Echo Scribe ne se connectera pas à Internet pour cela. Désactivez-le dans les réglages pour continuer.",
+        }
+    }
+
+    fn german(self) -> &'static str {
+        match self {
+            Self::PreparingSpeakerChannels => "Getrennte Sprecherkanäle werden vorbereitet...",
+            Self::TranscribingSystemAudio => "Systemaudio des Klienten wird transkribiert...",
+            Self::MergingSpeakerTranscripts => "Sprechertranskripte werden zusammengeführt...",
+            Self::PreparingDiarization => "Ersatz-Diarisierung wird vorbereitet...",
+            Self::PreparingRecording => "Aufnahme wird vorbereitet...",
+            Self::ReadingTranscript => "Transkript wird gelesen...",
+            Self::TranscriptionComplete => "Transkription abgeschlossen!",
+            Self::VerifyingExistingModel => "Vorhandenes Modell wird überprüft ({percent} %)...",
+            Self::ModelAlreadyDownloaded => "Modell ist bereits heruntergeladen.",
+            Self::StartingDownload => "Download wird gestartet...",
+            Self::DownloadingModel => "Modell wird heruntergeladen...",
+            Self::ModelDownloadComplete => "Modell-Download abgeschlossen.",
+            Self::SourceAwareNeedsBothChannels => "Der quellenbasierte Zwei-Sprecher-Modus benötigt Mikrofon- und Systemaudio. Es wird die Standardtranskription verwendet.",
+            Self::TdrzEnglishOnly => "Die Whisper-Ersatz-Diarisierung unterstützt nur Englisch. Es wird die Standardtranskription verwendet.",
+            Self::TdrzModelRequired => "Die Whisper-Ersatz-Diarisierung benötigt das Modell small.en-tdrz. Es wird die Standardtranskription verwendet.",
+            Self::DebugWhisperFallback => "Im Debug-Modus wird ein lokales whisper-Programm verwendet. Release-Builds nutzen den Sidecar.",
+            Self::MicrophoneNoSegments => "Der Mikrofonkanal hat keine Segmente mit Zeitstempeln geliefert.",
+            Self::SystemNoSegments => "Der Systemaudiokanal hat keine Segmente mit Zeitstempeln geliefert.",
+            Self::NoSpeakerTurns => "Die Ersatz-Diarisierung hat keine Sprecherwechsel erkannt, weil whisper.cpp keine [SPEAKER_TURN]-Marker geliefert hat. Die Ausgabe ist nicht segmentiert. Das passiert oft bei ähnlichen oder überlappenden Stimmen; versuche klarere Sprecherwechsel, lauteres Remote-Audio oder den quellenbasierten Modus mit getrennter System- und Mikrofonaufnahme.",
+            Self::CoachnotesIncomplete => "Der CoachNotes-Modus ist aktiv, aber Stammordner oder Klient fehlen. Es wird im Standardordner für Transkripte gespeichert.",
+            Self::NoChapterBoundaries => "Keine Kapitelgrenzen erkannt. Das Transkript wurde ohne Kapitel gespeichert.",
+            Self::LocalTimezoneUnavailable => "Die lokale Zeitzone konnte nicht ermittelt werden. Datumsangaben verwenden UTC; lege in den Speicher-Einstellungen eine Zeitzone fest.",
+            Self::NoAudioData => "Keine Audiodaten vorhanden. Bitte zuerst aufnehmen.",
+            Self::TrimLeavesNoAudio => "Der Zuschnitt lässt kein Audio zum Transkribieren übrig.",
+            Self::ModelNotDownloaded => "Das Modell '{model}' ist noch nicht heruntergeladen. Lade es zuerst im Setup herunter.",
+            Self::DownloadingMissingModel => "Das Modell '{model}' ist noch nicht heruntergeladen. Es wird vor dem Transkribieren heruntergeladen...",
+            Self::RecordingTooLong => "Die Aufnahme dauert {duration}, das Limit ist {limit}. Aktiviere die automatische Aufteilung in den Speicher-Einstellungen oder erhöhe das Limit.",
+            Self::AudioTooLarge => "Die Audiodaten sind {size} groß, das Limit ist {limit}. Kürze die Aufnahme oder erhöhe das Limit in den Speicher-Einstellungen.",
+            Self::ModelFallback => "Das Modell '{model}' hatte zu wenig Speicher oder ließ sich nicht laden; die Sitzung wurde mit '{fallback}' transkribiert.",
+            Self::InsufficientMemory => "Das Modell '{model}' benötigt etwa {required} freien Speicher, verfügbar sind nur {available}. Schließe andere Apps oder wähle ein kleineres Modell wie '{suggestion}'.",
+            Self::LowMemory => "Für das Modell '{model}' sind nur {available} Speicher frei. Schließe andere Apps, falls die Transkription hängt oder fehlschlägt.",
+            Self::EmptyTranscript => "Whisper hat ein leeres Transkript geliefert.",
+            Self::SpellingCorrected => "Die Rechtschreibkorrektur hat '{from}' durch '{to}' ersetzt ({count}x).",
+            Self::RollingTranscriptionDiscarded => "Die während der Aufnahme transkribierten Abschnitte wurden nicht übernommen, weil sich das Modell oder die Transkriptionseinstellungen geändert haben; die ganze Aufnahme wurde neu transkribiert.",
+            Self::DuplicateTranscript => "Dieses Transkript ist identisch oder nahezu identisch mit {path}, das bereits gespeichert wurde. Prüfen Sie, ob die Aufnahme doppelt übermittelt wurde.",
+            Self::DuplicateTranscriptSkipped => "Nicht gespeichert: Dieses Transkript ist identisch oder nahezu identisch mit {path}, das bereits gespeichert wurde.",
+            Self::NotifyTranscriptSaved => "Transkript gespeichert",
+            Self::NotifyTranscriptionFailed => "Transkription fehlgeschlagen",
+            Self::NotifyModelDownloaded => "Modell '{model}' heruntergeladen",
+            Self::ModelFileExists => "Unter {path} liegt bereits eine andere Datei für das Modell '{model}'. Lade es mit aktiviertem Überschreiben erneut herunter, um sie zu ersetzen.",
+            Self::FileExistsSavedAs => "{path} existiert bereits und wurde nicht verändert; stattdessen als {saved} gespeichert.",
+            Self::AppendedToSameDayTranscript => "Diese Sitzung wurde als Teil {part} an {path} angehängt.",
+            Self::ConvertedAudioFormat => "Audio wurde von {format} für whisper in 16 kHz, 16 Bit, Mono umgewandelt.",
+            Self::RecordingTrack => "Aufnahme",
+            Self::MicrophoneTrack => "Mikrofonspur",
+            Self::SystemAudioTrack => "Systemaudiospur",
+            Self::AudioNotWav => "Die {track} ist keine WAV-Datei. Bitte neu aufnehmen oder zuerst in WAV umwandeln.",
+            Self::AudioHeaderDamaged => "Die {track} hat einen beschädigten WAV-Header: {detail}",
+            Self::AudioDataEmpty => "Die {track} enthält keine Audiodaten.",
+            Self::AudioCodecUnsupported => "Die {track} verwendet eine nicht unterstützte WAV-Kodierung ({format}). Bitte als PCM- oder Float-WAV exportieren.",
+            Self::AudioTooShort => "Die {track} ist nur {seconds} s lang. Es wird mindestens 1 Sekunde Audio benötigt.",
+            Self::ReceivingAudio => "Audio wird empfangen ({received} von {total} MB)...",
+            Self::CheckingAudio => "Audioformat wird geprüft...",
+            Self::WritingTempAudio => "Audio für whisper wird geschrieben ({percent} %)...",
+            Self::ModelVerifiedOnDisk => "Modell '{model}' hat die Prüfung auf der Festplatte bestanden.",
+            Self::ModelCorruptOnDisk => "Modell '{model}' auf der Festplatte stimmt nicht mit seiner Prüfsumme überein. Bitte erneut herunterladen.",
+            Self::ModelCorruptRedownloading => "Modell '{model}' auf der Festplatte stimmt nicht mit seiner Prüfsumme überein. Wird erneut heruntergeladen...",
+            Self::PartialTranscriptKept => "Das bisherige Transkript liegt in {path}.",
+            Self::ResumedFromCheckpoint => "Ab Teil {part} von {parts} fortgesetzt; frühere Teile stammen aus dem unterbrochenen Lauf.",
+            Self::AppLocked => "Echo Scribe ist gesperrt. Entsperre es, um CoachNotes-Sitzungen zu öffnen.",
+            Self::IncorrectPasscode => "Falscher Code.",
+            Self::UnlockReason => "deine CoachNotes-Sitzungen zu entsperren",
+            Self::OfflineModeBlocked => "Der Offlinemodus ist aktiv, daher verbindet sich Echo Scribe dafür nicht mit dem Internet. Schalte ihn in den Einstellungen aus, um fortzufahren.",
+        }
+    }
+}
+
+#[derive(Clone)]
+struct WordSpan {
+    start: usize,
+    end: usize,
+    normalized: String,
+}
+
+fn validate_locale(locale: &str) -> &'static str {
+    let language = locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    SUPPORTED_LOCALES
+        .iter()
+        .copied()
+        .find(|supported| *supported == language)
+        .unwrap_or("en")
+}
+
+fn localize(locale: &str, message: Message) -> String {
+    match validate_locale(locale) {
+        "es" => message.spanish(),
+        "fr" => message.french(),
+        "de" => message.german(),
+        _ => message.english(),
+    }
+    .to_string()
+}
+
+fn localize_with(locale: &str, message: Message, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(localize(locale, message), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+fn emit_progress(app: &AppHandle, percent: u32, message: &str) {
+    let _ = app.emit(
+        "progress",
+        ProgressPayload {
+            percent,
+            message: message.to_string(),
+        },
+    );
+}
+
+fn emit_model_download_progress(
+    app: &AppHandle,
+    model: &str,
+    percent: u32,
+    downloaded_bytes: u64,
+    total_bytes: Option<u64>,
+    message: &str,
+) {
+    let _ = app.emit(
+        "model-download-progress",
+        ModelDownloadPayload {
+            model: model.to_string(),
+            percent,
+            downloaded_bytes,
+            total_bytes,
+            message: message.to_string(),
+        },
+    );
+}
+
+fn sanitize_non_empty(value: Option<String>) -> Option<String> {
+    value.and_then(|v| {
+        let trimmed = v.trim();
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed.to_string())
+        }
+    })
+}
+
+// Accepts "UTC", "Z", "+05:30", "-0800", "UTC+2" and similar fixed offsets.
+// Zone names such as "Europe/Berlin" are not supported: the override does not
+// follow daylight saving time.
+fn parse_utc_offset(value: &str) -> Option<UtcOffset> {
+    let upper = value.trim().to_ascii_uppercase();
+    let rest = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper);
+    if rest.is_empty() || rest == "Z" {
+        return Some(UtcOffset::UTC);
+    }
+
+    let (sign, digits) = match rest.as_bytes()[0] {
+        b'+' => (1, &rest[1..]),
+        b'-' => (-1, &rest[1..]),
+        _ => return None,
+    };
+    if !digits
+        .bytes()
+        .all(|byte| byte.is_ascii_digit() || byte == b':')
+    {
+        return None;
+    }
+    let (hours, minutes) = match digits.split_once(':') {
+        Some(parts) => parts,
+        None if digits.len() > 2 => digits.split_at(digits.len() - 2),
+        None => (digits, "0"),
+    };
+    let hours: i8 = hours.parse().ok()?;
+    let minutes: i8 = minutes.parse().ok()?;
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+
+    UtcOffset::from_hms(sign * hours, sign * minutes, 0).ok()
+}
+
+fn validate_timezone(timezone: Option<String>) -> Option<String> {
+    sanitize_non_empty(timezone).filter(|value| parse_utc_offset(value).is_some())
+}
+
+fn validate_date_format(format: &str) -> &'static str {
+    match format {
+        "dmy" => "dmy",
+        "mdy" => "mdy",
+        _ => "ymd",
+    }
+}
+
+// Returns the current time in the configured timezone, or the system timezone when no
+// override is set. The flag is true when the system offset was unavailable and UTC was used.
+fn now_for_settings(settings: &AppSettings) -> (OffsetDateTime, bool) {
+    let now = OffsetDateTime::now_utc();
+    if let Some(offset) = settings.timezone.as_deref().and_then(parse_utc_offset) {
+        return (now.to_offset(offset), false);
+    }
+
+    match OffsetDateTime::now_local() {
+        Ok(local) => (local, false),
+        Err(_) => (now, true),
+    }
+}
+
+fn format_date(now: OffsetDateTime, date_format: &str) -> String {
+    let formatted = match validate_date_format(date_format) {
+        "dmy" => now.format(format_description!("[day]-[month]-[year]")),
+        "mdy" => now.format(format_description!("[month]-[day]-[year]")),
+        _ => now.format(format_description!("[year]-[month]-[day]")),
+    };
+    formatted.unwrap_or_else(|_| "1970-01-01".to_string())
+}
+
+fn format_time_compact(now: OffsetDateTime) -> String {
+    now.format(format_description!("[hour][minute][second]"))
+        .unwrap_or_else(|_| "000000".to_string())
 }
 
 fn format_iso8601(now: OffsetDateTime) -> String {
@@ -336,6 +1848,29 @@ fn format_iso8601(now: OffsetDateTime) -> String {
         .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string())
 }
 
+// Reads back a `created_at` written by `format_iso8601`, keeping its offset so
+// the wall-clock time matches the one in the original filename.
+fn parse_iso8601(value: &str) -> Option<OffsetDateTime> {
+    let (date, rest) = value.trim().split_once('T')?;
+    let offset_start = rest.find(['Z', 'z', '+', '-'])?;
+    let (clock, offset) = rest.split_at(offset_start);
+
+    let mut date_parts = date.splitn(3, '-');
+    let year = date_parts.next()?.parse::<i32>().ok()?;
+    let month = Month::try_from(date_parts.next()?.parse::<u8>().ok()?).ok()?;
+    let day = date_parts.next()?.parse::<u8>().ok()?;
+
+    let clock = clock.split('.').next()?;
+    let mut clock_parts = clock.splitn(3, ':').map(|part| part.parse::<u8>().ok());
+    let hour = clock_parts.next()??;
+    let minute = clock_parts.next()??;
+    let second = clock_parts.next()??;
+
+    let date = Date::from_calendar_date(year, month, day).ok()?;
+    let time = Time::from_hms(hour, minute, second).ok()?;
+    Some(PrimitiveDateTime::new(date, time).assume_offset(parse_utc_offset(offset)?))
+}
+
 fn unix_timestamp_secs() -> Result<u64, String> {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -343,10 +1878,76 @@ fn unix_timestamp_secs() -> Result<u64, String> {
         .map_err(|e| format!("System clock error: {}", e))
 }
 
+fn unix_timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
 fn find_model(model_id: &str) -> Option<&'static ModelCatalogEntry> {
     MODEL_CATALOG.iter().find(|entry| entry.id == model_id)
 }
 
+fn is_resource_failure(error: &str) -> bool {
+    const MARKERS: [&str; 8] = [
+        "out of memory",
+        "cannot allocate memory",
+        "failed to allocate",
+        "bad_alloc",
+        "failed to load model",
+        "failed to initialize whisper context",
+        "not enough space in the context",
+        "insufficient memory",
+    ];
+
+    let error = error.to_lowercase();
+    MARKERS.iter().any(|marker| error.contains(marker))
+}
+
+// whisper.cpp errors that point at an unreadable model file rather than at
+// memory, which the same file would hit again on every run.
+fn is_model_load_failure(error: &str) -> bool {
+    const MARKERS: [&str; 4] = [
+        "failed to load model",
+        "failed to initialize whisper context",
+        "invalid model",
+        "bad magic",
+    ];
+
+    let error = error.to_lowercase();
+    MARKERS.iter().any(|marker| error.contains(marker))
+}
+
+// Picks the largest downloaded model smaller than `model_id`, optionally one
+// that also fits in `available_mb` of memory.
+fn next_smaller_downloaded_model(
+    app: &AppHandle,
+    model_id: &str,
+    available_mb: Option<u64>,
+) -> Option<&'static ModelCatalogEntry> {
+    let current = find_model(model_id)?;
+    MODEL_CATALOG
+        .iter()
+        .filter(|entry| entry.size_mb < current.size_mb && !entry.id.contains("tdrz"))
+        .filter(|entry| available_mb.map_or(true, |available| u64::from(entry.ram_mb) <= available))
+        .filter(|entry| {
+            model_file_path(app, entry.id)
+                .map(|path| path.exists())
+                .unwrap_or(false)
+        })
+        .max_by_key(|entry| entry.size_mb)
+}
+
+fn suggest_model_for_memory(available_mb: u64) -> &'static str {
+    MODEL_CATALOG
+        .iter()
+        .filter(|entry| !entry.id.contains("tdrz") && u64::from(entry.ram_mb) <= available_mb)
+        .max_by_key(|entry| entry.ram_mb)
+        .map(|entry| entry.id)
+        .unwrap_or("tiny")
+}
+
 fn validate_model(model_id: &str) -> Result<&'static ModelCatalogEntry, String> {
     find_model(model_id).ok_or_else(|| {
         format!(
@@ -361,6 +1962,35 @@ fn validate_model(model_id: &str) -> Result<&'static ModelCatalogEntry, String>
     })
 }
 
+// Selecting this instead of a model picks one per session from
+// `language_models`, by the session language.
+const AUTO_MODEL: &str = "auto";
+const DEFAULT_AUTO_MODEL: &str = "base";
+
+fn validate_model_choice(model_id: &str) -> Result<(), String> {
+    if model_id == AUTO_MODEL {
+        return Ok(());
+    }
+    validate_model(model_id).map(|_| ())
+}
+
+// The model an "auto" session runs with: the one preferred for its language,
+// then the one set for "auto" (auto-detect and unmapped languages), then base.
+fn auto_model_for_language<'a>(settings: &'a AppSettings, language: &str) -> &'a str {
+    settings
+        .language_models
+        .get(language)
+        .or_else(|| settings.language_models.get("auto"))
+        .map(String::as_str)
+        .unwrap_or(DEFAULT_AUTO_MODEL)
+}
+
+fn resolve_auto_model(settings: &AppSettings, options: &mut TranscriptionOptions) {
+    if options.model == AUTO_MODEL {
+        options.model = auto_model_for_language(settings, &options.language).to_string();
+    }
+}
+
 fn validate_output_mode(mode: &str) -> &'static str {
     match mode {
         "coachnotes" => "coachnotes",
@@ -369,11 +1999,10 @@ fn validate_output_mode(mode: &str) -> &'static str {
 }
 
 fn validate_diarization_mode(mode: &str) -> &'static str {
-    match mode {
-        "source_aware_2speaker" => "source_aware_2speaker",
-        "tdrz_2speaker" => "tdrz_2speaker",
-        _ => "none",
-    }
+    DIARIZATION_ENGINES
+        .iter()
+        .find(|engine| engine.mode == mode)
+        .map_or("none", |engine| engine.mode)
 }
 
 fn echo_scribe_temp_dir() -> Result<PathBuf, String> {
@@ -383,1757 +2012,17708 @@ fn echo_scribe_temp_dir() -> Result<PathBuf, String> {
     Ok(temp_dir)
 }
 
-fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+fn default_app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
     app.path()
         .app_data_dir()
         .map_err(|e| format!("Failed to resolve app data directory: {}", e))
 }
 
-fn models_dir(app: &AppHandle) -> Result<PathBuf, String> {
-    Ok(app_data_dir(app)?.join("models"))
-}
+// Where app data lives after `move_app_data`. The file stays in the default
+// config directory, the one place that never moves.
+const APP_DATA_LOCATION_FILE: &str = "app-data-location.json";
 
-fn settings_path(app: &AppHandle) -> Result<PathBuf, String> {
-    Ok(app_data_dir(app)?.join("settings.json"))
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AppDataLocation {
+    dir: PathBuf,
+    // Models and the other re-downloadable files moved along.
+    #[serde(default)]
+    include_models: bool,
+    // Where the models stayed when a move left them outside the default cache
+    // directory, e.g. in the folder an earlier move put them in.
+    #[serde(default)]
+    cache_dir: Option<PathBuf>,
 }
 
-fn default_transcript_dir() -> PathBuf {
-    dirs::document_dir()
-        .or_else(dirs::home_dir)
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("EchoScribe Transcripts")
+#[derive(Default)]
+struct AppDataLocationState {
+    location: Mutex<Option<AppDataLocation>>,
 }
 
-fn resolve_transcript_dir(settings: &AppSettings) -> PathBuf {
-    if let Some(path) = &settings.transcript_dir {
-        let trimmed = path.trim();
-        if !trimmed.is_empty() {
-            return PathBuf::from(trimmed);
-        }
-    }
-    default_transcript_dir()
+fn app_data_location(app: &AppHandle) -> Option<AppDataLocation> {
+    app.state::<AppDataLocationState>()
+        .location
+        .lock()
+        .ok()?
+        .clone()
 }
 
-fn model_file_path(app: &AppHandle, model: &str) -> Result<PathBuf, String> {
-    Ok(models_dir(app)?.join(format!("ggml-{}.bin", model)))
+fn app_data_location_path(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_config_dir()
+        .map(|dir| dir.join(APP_DATA_LOCATION_FILE))
+        .map_err(|e| format!("Failed to resolve app config directory: {}", e))
 }
 
-fn load_settings(app: &AppHandle) -> Result<AppSettings, String> {
-    let path = settings_path(app)?;
-    if !path.exists() {
-        return Ok(AppSettings::default());
+// Read once at launch, before anything else touches app data. A location
+// on a volume that is not mounted is kept, so nothing is written to the
+// default directory in its place.
+fn load_app_data_location(app: &AppHandle) {
+    let location = app_data_location_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str::<AppDataLocation>(&raw).ok());
+    if let Ok(mut guard) = app.state::<AppDataLocationState>().location.lock() {
+        *guard = location;
     }
+}
 
-    let raw = fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read settings file ({}): {}", path.display(), e))?;
-
-    let mut settings: AppSettings =
-        serde_json::from_str(&raw).map_err(|e| format!("Invalid settings JSON: {}", e))?;
+fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    match app_data_location(app) {
+        Some(location) => Ok(location.dir),
+        None => default_app_data_dir(app),
+    }
+}
 
-    if find_model(&settings.selected_model).is_none() {
-        settings.selected_model = AppSettings::default().selected_model;
+// On Linux, settings live under XDG_CONFIG_HOME and re-downloadable files
+// (models, benchmark samples, dev binaries) under XDG_CACHE_HOME. Transcript
+// history stays in XDG_DATA_HOME. Other platforms keep the single app data
+// directory.
+fn app_config_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    if let Some(location) = app_data_location(app) {
+        return Ok(location.dir);
     }
-    settings.transcript_format = "md".to_string();
-    settings.diarization_mode = validate_diarization_mode(&settings.diarization_mode).to_string();
-    if !settings.diarization_mode_configured {
-        settings.diarization_mode = AppSettings::default().diarization_mode;
+
+    #[cfg(target_os = "linux")]
+    {
+        app.path()
+            .app_config_dir()
+            .map_err(|e| format!("Failed to resolve app config directory: {}", e))
     }
-    settings.coachnotes_root_dir = sanitize_non_empty(settings.coachnotes_root_dir.clone());
-    settings.coachnotes_client = sanitize_non_empty(settings.coachnotes_client.clone());
 
-    Ok(settings)
+    #[cfg(not(target_os = "linux"))]
+    {
+        default_app_data_dir(app)
+    }
 }
 
-fn save_settings(app: &AppHandle, settings: &AppSettings) -> Result<(), String> {
-    let app_dir = app_data_dir(app)?;
-    fs::create_dir_all(&app_dir).map_err(|e| {
-        format!(
-            "Failed to create app data directory ({}): {}",
-            app_dir.display(),
-            e
-        )
-    })?;
-
-    let path = settings_path(app)?;
-    let serialized = serde_json::to_string_pretty(settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
-
-    fs::write(&path, serialized)
-        .map_err(|e| format!("Failed to write settings file ({}): {}", path.display(), e))
+fn app_cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    match app_data_location(app) {
+        Some(location) if location.include_models => Ok(location.dir),
+        Some(AppDataLocation {
+            cache_dir: Some(cache_dir),
+            ..
+        }) => Ok(cache_dir),
+        _ => default_app_cache_dir(app),
+    }
 }
 
-async fn sha256_for_file(path: &Path) -> Result<String, String> {
-    let mut file = tokio::fs::File::open(path)
-        .await
-        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+fn default_app_cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    #[cfg(target_os = "linux")]
+    {
+        app.path()
+            .app_cache_dir()
+            .map_err(|e| format!("Failed to resolve app cache directory: {}", e))
+    }
 
-    let mut hasher = Sha256::new();
-    let mut buffer = [0u8; 64 * 1024];
+    #[cfg(not(target_os = "linux"))]
+    {
+        default_app_data_dir(app)
+    }
+}
 
-    loop {
-        let read_bytes = file
-            .read(&mut buffer)
-            .await
-            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+// Re-downloadable entries, which live in the cache directory.
+const CACHE_ENTRIES: [&str; 3] = ["models", "benchmark", "dev-binaries"];
+
+// Entries that older versions kept in the app data directory, with the
+// directory each one belongs in now.
+fn legacy_storage_moves(app: &AppHandle) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    let data_dir = app_data_dir(app)?;
+    let config_dir = app_config_dir(app)?;
+    let cache_dir = app_cache_dir(app)?;
+    Ok(std::iter::once(("settings.json", &config_dir))
+        .chain(CACHE_ENTRIES.iter().map(|name| (*name, &cache_dir)))
+        .map(|(name, dir)| (data_dir.join(name), dir.join(name)))
+        .filter(|(from, to)| from != to)
+        .collect())
+}
 
-        if read_bytes == 0 {
-            break;
+fn copy_recursively(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursively(&entry.path(), &to.join(entry.file_name()))?;
         }
-
-        hasher.update(&buffer[..read_bytes]);
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
     }
+}
 
-    Ok(format!("{:x}", hasher.finalize()))
+// Moves files from the single-directory layout into the config/data/cache
+// split. Only renames happen here, so launch is not held up; entries on
+// another filesystem are returned for `copy_legacy_storage`. Entries that
+// fail to move stay where they are and are still found through
+// `storage_path`.
+fn migrate_storage_layout(app: &AppHandle) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    let mut copies = Vec::new();
+    for (from, to) in legacy_storage_moves(app)? {
+        if !from.exists() || to.exists() {
+            continue;
+        }
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory ({}): {}", parent.display(), e))?;
+        }
+        if fs::rename(&from, &to).is_err() {
+            copies.push((from, to));
+        }
+    }
+    Ok(copies)
 }
 
-fn sidecar_binary_path() -> Option<PathBuf> {
-    let exe = std::env::current_exe().ok()?;
-    let parent = exe.parent()?;
-    Some(parent.join("whisper-cli"))
+// Copies the entries a rename could not move, in the background, reporting
+// each on `storage-migration-progress` and failures on
+// `storage-migration-failed`. Each copy is made under a temporary
+// name and checked against the original, so `storage_path` keeps using the
+// legacy entry until a complete copy takes its place.
+async fn copy_legacy_storage(app: AppHandle, copies: Vec<(PathBuf, PathBuf)>) {
+    let total = copies.len();
+    let mut failed = false;
+    for (index, (from, to)) in copies.into_iter().enumerate() {
+        let name = from
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let _ = app.emit(
+            "storage-migration-progress",
+            ProgressPayload {
+                percent: ((index * 100) / total) as u32,
+                message: format!("Moving {} to its new folder...", name),
+            },
+        );
+        let moved = tauri::async_runtime::spawn_blocking(move || -> Result<(), String> {
+            let mut temp_name = to.file_name().unwrap_or_default().to_os_string();
+            temp_name.push(".migrating");
+            let temp = to.with_file_name(temp_name);
+            let _ = fs::remove_dir_all(&temp).or_else(|_| fs::remove_file(&temp));
+            let copied = copy_recursively(&from, &temp).and_then(|_| {
+                if copy_matches(&from, &temp)? {
+                    fs::rename(&temp, &to)
+                } else {
+                    Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "the copy does not match",
+                    ))
+                }
+            });
+            copied.map_err(|e| {
+                let _ = fs::remove_dir_all(&temp).or_else(|_| fs::remove_file(&temp));
+                format!(
+                    "Failed to move {} to {}: {}",
+                    from.display(),
+                    to.display(),
+                    e
+                )
+            })?;
+            let removed = if from.is_dir() {
+                fs::remove_dir_all(&from)
+            } else {
+                fs::remove_file(&from)
+            };
+            removed.map_err(|e| format!("Failed to remove {}: {}", from.display(), e))
+        })
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result);
+        if let Err(error) = moved {
+            failed = true;
+            let _ = app.emit("storage-migration-failed", error);
+        }
+    }
+    if total > 0 && !failed {
+        let _ = app.emit(
+            "storage-migration-progress",
+            ProgressPayload {
+                percent: 100,
+                message: "Storage migration complete.".to_string(),
+            },
+        );
+    }
 }
 
-#[cfg(debug_assertions)]
-fn debug_whisper_fallback_path() -> PathBuf {
-    let home = dirs::home_dir().unwrap_or_default();
-
-    let local = home.join("whisper.cpp/build/bin/whisper-cli");
-    if local.exists() {
-        return local;
+// Resolves `name` in its current directory, falling back to the legacy app
+// data location while an unmigrated copy is still there.
+fn storage_path(app: &AppHandle, dir: PathBuf, name: &str) -> Result<PathBuf, String> {
+    let path = dir.join(name);
+    let legacy = app_data_dir(app)?.join(name);
+    if !path.exists() && legacy.exists() {
+        return Ok(legacy);
     }
+    Ok(path)
+}
 
-    let local_old = home.join("whisper.cpp/main");
-    if local_old.exists() {
-        return local_old;
-    }
+fn file_sha256(path: &Path) -> io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
 
-    let brew = PathBuf::from("/opt/homebrew/bin/whisper-cpp");
-    if brew.exists() {
-        return brew;
+// Whether `to` holds the same files as `from`, compared by sha256.
+fn copy_matches(from: &Path, to: &Path) -> io::Result<bool> {
+    if from.is_dir() {
+        let names = |dir: &Path| -> io::Result<BTreeSet<OsString>> {
+            fs::read_dir(dir)?
+                .map(|entry| entry.map(|entry| entry.file_name()))
+                .collect()
+        };
+        let from_names = names(from)?;
+        if !to.is_dir() || from_names != names(to)? {
+            return Ok(false);
+        }
+        for name in from_names {
+            if !copy_matches(&from.join(&name), &to.join(&name))? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    } else {
+        Ok(to.is_file() && file_sha256(from)? == file_sha256(to)?)
     }
+}
 
-    PathBuf::from("whisper-cli")
+#[derive(Debug, Serialize)]
+pub struct AppDataMove {
+    dir: String,
+    moved: Vec<String>,
+    warnings: Vec<String>,
 }
 
-fn system_audio_capture_sidecar_candidates() -> Vec<PathBuf> {
-    let mut candidates = Vec::new();
+// Copies settings, job history, failed jobs, logs, and with `include_models`
+// the models into `new_dir`, checks every copied file against its original,
+// and only then points the app at the new folder and deletes the originals.
+// A failed copy or check removes what was copied and changes nothing.
+#[tauri::command]
+async fn move_app_data(
+    app: AppHandle,
+    new_dir: String,
+    include_models: bool,
+) -> Result<AppDataMove, String> {
+    require_unlocked(&app, None)?;
+    let new_dir = PathBuf::from(new_dir.trim());
+    if !new_dir.is_absolute() {
+        return Err("Choose a full folder path for app data.".to_string());
+    }
+    let recording = app
+        .state::<SystemAudioCaptureState>()
+        .session
+        .lock()
+        .map_or(true, |session| session.is_some())
+        || app
+            .state::<RollingTranscriptionState>()
+            .sessions
+            .lock()
+            .map_or(true, |sessions| !sessions.is_empty());
+    if recording {
+        return Err("Stop recording before moving app data.".to_string());
+    }
+    let queue = app.state::<TranscriptionQueue>();
+    if queue.waiting.lock().map_or(true, |waiting| {
+        waiting.iter().any(|job| job.scheduled_for.is_some())
+    }) {
+        return Err(
+            "A job is scheduled for the overnight window. Run or cancel it before moving app data."
+                .to_string(),
+        );
+    }
+    if queue.pending.load(Ordering::SeqCst) > 0 {
+        return Err("Wait for queued transcriptions to finish before moving app data.".to_string());
+    }
 
-    if let Ok(exe) = std::env::current_exe() {
-        if let Some(parent) = exe.parent() {
-            candidates.push(parent.join("system-audio-capture"));
+    let current_cache_dir = app_cache_dir(&app)?;
+    let mut sources = vec![app_config_dir(&app)?, app_data_dir(&app)?];
+    if include_models {
+        sources.push(current_cache_dir.clone());
+    }
+    sources.sort();
+    sources.dedup();
+    if let Some(source) = sources
+        .iter()
+        .find(|source| new_dir.starts_with(source) || source.starts_with(&new_dir))
+    {
+        return Err(format!(
+            "{} overlaps the current app data folder ({}). Choose a separate folder.",
+            new_dir.display(),
+            source.display()
+        ));
+    }
+    if new_dir.exists()
+        && fs::read_dir(&new_dir).map_or(true, |mut entries| entries.next().is_some())
+    {
+        return Err(format!(
+            "{} is not empty. Choose an empty or new folder.",
+            new_dir.display()
+        ));
+    }
+
+    let mut entries = Vec::new();
+    for source in &sources {
+        let Ok(dir_entries) = fs::read_dir(source) else {
+            continue;
+        };
+        for entry in dir_entries.filter_map(|entry| entry.ok()) {
+            let name = entry.file_name();
+            let name_str = name.to_string_lossy();
+            if name_str == APP_DATA_LOCATION_FILE
+                || (!include_models && CACHE_ENTRIES.contains(&name_str.as_ref()))
+            {
+                continue;
+            }
+            if entries
+                .iter()
+                .any(|(_, existing): &(PathBuf, PathBuf)| existing.file_name() == Some(&name))
+            {
+                return Err(format!(
+                    "{} exists in more than one app data folder; move it aside first.",
+                    name_str
+                ));
+            }
+            entries.push((entry.path(), new_dir.join(&name)));
         }
     }
 
-    if let Ok(cwd) = std::env::current_dir() {
-        candidates.push(cwd.join("binaries/system-audio-capture-aarch64-apple-darwin"));
-        candidates.push(cwd.join("src-tauri/binaries/system-audio-capture-aarch64-apple-darwin"));
-        candidates
-            .push(cwd.join("../src-tauri/binaries/system-audio-capture-aarch64-apple-darwin"));
+    let copies = entries.clone();
+    let target = new_dir.clone();
+    tauri::async_runtime::spawn_blocking(move || -> Result<(), String> {
+        let copied = fs::create_dir_all(&target).and_then(|_| {
+            for (from, to) in &copies {
+                copy_recursively(from, to)?;
+                if !copy_matches(from, to)? {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("the copy of {} does not match", from.display()),
+                    ));
+                }
+            }
+            Ok(())
+        });
+        copied.map_err(|e| {
+            for (_, to) in &copies {
+                let _ = fs::remove_dir_all(to).or_else(|_| fs::remove_file(to));
+            }
+            format!("Failed to move app data to {}: {}", target.display(), e)
+        })
+    })
+    .await
+    .map_err(|e| format!("App data move stopped unexpectedly: {}", e))??;
+
+    // Switched under the lock, so every path lookup sees either the old
+    // folder or the new one, and the new one only once it is complete.
+    // Models left behind are tracked where they are, so they are not lost
+    // when an earlier move had put them in the folder being left.
+    let cache_dir = (!include_models && current_cache_dir != default_app_cache_dir(&app)?)
+        .then_some(current_cache_dir);
+    let location = AppDataLocation {
+        dir: new_dir.clone(),
+        include_models,
+        cache_dir,
+    };
+    {
+        let state = app.state::<AppDataLocationState>();
+        let mut guard = state
+            .location
+            .lock()
+            .map_err(|_| "Failed to lock app data location.".to_string())?;
+        let path = app_data_location_path(&app)?;
+        let temp_path = path.with_extension("json.tmp");
+        let json = serde_json::to_string_pretty(&location)
+            .map_err(|e| format!("Failed to encode app data location: {}", e))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create directory ({}): {}", dir.display(), e))?;
+        }
+        fs::write(&temp_path, json)
+            .and_then(|_| fs::rename(&temp_path, &path))
+            .map_err(|e| {
+                format!(
+                    "Failed to save app data location ({}): {}",
+                    path.display(),
+                    e
+                )
+            })?;
+        *guard = Some(location);
+    }
+    if let Ok(mut cached) = app.state::<SettingsState>().cached.lock() {
+        *cached = None;
     }
 
-    candidates.push(PathBuf::from("system-audio-capture"));
-    candidates
+    let mut warnings = Vec::new();
+    for (from, _) in &entries {
+        let removed = if from.is_dir() {
+            fs::remove_dir_all(from)
+        } else {
+            fs::remove_file(from)
+        };
+        if let Err(e) = removed {
+            warnings.push(format!(
+                "Moved, but could not delete the original {}: {}",
+                from.display(),
+                e
+            ));
+        }
+    }
+
+    Ok(AppDataMove {
+        dir: new_dir.to_string_lossy().to_string(),
+        moved: entries
+            .iter()
+            .filter_map(|(from, _)| from.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .collect(),
+        warnings,
+    })
 }
 
-fn file_contains_marker(path: &Path, marker: &str) -> bool {
-    fs::read_to_string(path)
-        .map(|content| content.contains(marker))
-        .unwrap_or(false)
+fn models_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    storage_path(app, app_cache_dir(app)?, "models")
 }
 
-#[cfg(debug_assertions)]
-fn debug_system_audio_capture_binary_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_dir = app_data_dir(app)?;
-    let dev_dir = app_dir.join("dev-binaries");
-    fs::create_dir_all(&dev_dir).map_err(|e| {
-        format!(
-            "Failed to create dev binary directory ({}): {}",
-            dev_dir.display(),
-            e
-        )
-    })?;
-    Ok(dev_dir.join("system-audio-capture"))
+fn settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    storage_path(app, app_config_dir(app)?, "settings.json")
 }
 
-#[cfg(debug_assertions)]
-fn debug_system_audio_capture_source_path() -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("native/system_audio_capture.swift")
+fn default_transcript_dir() -> PathBuf {
+    dirs::document_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("EchoScribe Transcripts")
 }
 
-#[cfg(debug_assertions)]
-fn should_rebuild_debug_binary(source_path: &Path, binary_path: &Path) -> bool {
-    if !binary_path.exists() {
-        return true;
+fn resolve_transcript_dir(settings: &AppSettings) -> PathBuf {
+    if let Some(path) = &settings.transcript_dir {
+        let trimmed = path.trim();
+        if !trimmed.is_empty() {
+            return PathBuf::from(trimmed);
+        }
     }
-
-    let Ok(source_meta) = fs::metadata(source_path) else {
-        return false;
-    };
-    let Ok(binary_meta) = fs::metadata(binary_path) else {
-        return true;
-    };
-    let Ok(source_modified) = source_meta.modified() else {
-        return false;
-    };
-    let Ok(binary_modified) = binary_meta.modified() else {
-        return true;
-    };
-
-    source_modified > binary_modified
+    default_transcript_dir()
 }
 
-#[cfg(debug_assertions)]
-fn ensure_debug_system_audio_capture_binary(app: &AppHandle) -> Result<PathBuf, String> {
-    let source_path = debug_system_audio_capture_source_path();
-    if !source_path.exists() {
-        return Err(format!(
-            "System audio capture source file is missing: {}",
-            source_path.display()
-        ));
-    }
+fn model_file_path(app: &AppHandle, model: &str) -> Result<PathBuf, String> {
+    Ok(models_dir(app)?.join(format!("ggml-{}.bin", model)))
+}
 
-    let binary_path = debug_system_audio_capture_binary_path(app)?;
-    if should_rebuild_debug_binary(&source_path, &binary_path) {
-        let output = StdCommand::new("swiftc")
-            .args([
-                "-O",
-                "-parse-as-library",
-                "-framework",
-                "AVFoundation",
-                "-framework",
-                "CoreGraphics",
-                "-framework",
-                "CoreMedia",
-                "-framework",
-                "ScreenCaptureKit",
-            ])
-            .arg(&source_path)
-            .arg("-o")
-            .arg(&binary_path)
-            .output()
-            .map_err(|e| format!("Failed to launch swiftc for system audio helper: {}", e))?;
+// Settings as last read or saved. Commands read this copy instead of parsing
+// settings.json again; `save_settings` writes through and updates it.
+#[derive(Default)]
+struct SettingsState {
+    cached: Mutex<Option<CachedSettings>>,
+}
 
-        if !output.status.success() {
-            return Err(format!(
-                "Failed to build local system audio capture helper: {}",
-                process_output_detail(&output.stdout, &output.stderr)
-            ));
-        }
-    }
+struct CachedSettings {
+    settings: AppSettings,
+    // settings.json's modification time when it was read or written, so an
+    // edit made outside the app is picked up before the next save.
+    modified: Option<SystemTime>,
+}
 
-    Ok(binary_path)
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsReloadFailedPayload {
+    path: String,
+    error: String,
 }
 
-fn resolve_system_audio_capture_sidecar_path(_app_handle: &AppHandle) -> Result<PathBuf, String> {
-    for candidate in system_audio_capture_sidecar_candidates() {
-        if candidate.exists() {
-            if file_contains_marker(&candidate, SYSTEM_AUDIO_CAPTURE_PLACEHOLDER_MARKER) {
-                #[cfg(debug_assertions)]
-                {
-                    return ensure_debug_system_audio_capture_binary(_app_handle);
-                }
+fn settings_modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
 
-                #[cfg(not(debug_assertions))]
-                {
-                    continue;
-                }
-            }
+fn load_settings(app: &AppHandle) -> Result<AppSettings, String> {
+    let path = settings_path(app)?;
+    let modified = settings_modified_time(&path);
+    let state = app.state::<SettingsState>();
+    let mut cached = state
+        .cached
+        .lock()
+        .map_err(|_| "Failed to lock settings state.".to_string())?;
+    let previous = match cached.take() {
+        Some(entry) if entry.modified == modified => {
+            let settings = entry.settings.clone();
+            *cached = Some(entry);
+            return Ok(settings);
+        }
+        previous => previous,
+    };
 
-            return Ok(candidate);
+    // The file changed since it was last read or written, or was never read.
+    // An edit that does not validate keeps the settings in use and is
+    // reported once; the next save replaces it.
+    let (settings, changed) = match (read_settings_file(app), previous) {
+        (Ok(settings), None) => (settings, false),
+        (Ok(settings), Some(previous)) => {
+            let changed = serde_json::to_string(&settings).ok()
+                != serde_json::to_string(&previous.settings).ok();
+            (settings, changed)
         }
+        (Err(error), None) => return Err(error),
+        (Err(error), Some(previous)) => {
+            let _ = app.emit(
+                "settings-reload-failed",
+                SettingsReloadFailedPayload {
+                    path: path.to_string_lossy().to_string(),
+                    error,
+                },
+            );
+            (previous.settings, false)
+        }
+    };
+    *cached = Some(CachedSettings {
+        settings: settings.clone(),
+        modified,
+    });
+    drop(cached);
+    if changed {
+        emit_settings_changed(app);
     }
+    Ok(settings)
+}
 
-    #[cfg(debug_assertions)]
-    {
-        return ensure_debug_system_audio_capture_binary(_app_handle);
-    }
+const SETTINGS_WATCH_INTERVAL_SECS: u64 = 2;
 
-    #[cfg(not(debug_assertions))]
-    {
-        Ok(PathBuf::from("system-audio-capture"))
+// Picks up settings.json edits made outside the app, such as a dotfiles sync
+// or a manual edit. `load_settings` notices the new modification time,
+// reloads and validates the file, and emits `settings-changed`.
+async fn watch_settings_file(app: AppHandle) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(SETTINGS_WATCH_INTERVAL_SECS)).await;
+        let _ = load_settings(&app);
     }
 }
 
-fn is_sidecar_available() -> bool {
-    sidecar_binary_path().map(|p| p.exists()).unwrap_or(false)
-}
-
-fn process_output_detail(stdout: &[u8], stderr: &[u8]) -> String {
-    let stderr_text = String::from_utf8_lossy(stderr).trim().to_string();
-    if !stderr_text.is_empty() {
-        return stderr_text;
+fn read_settings_file(app: &AppHandle) -> Result<AppSettings, String> {
+    let path = settings_path(app)?;
+    if !path.exists() {
+        return Ok(AppSettings::default());
     }
 
-    let stdout_text = String::from_utf8_lossy(stdout).trim().to_string();
-    if !stdout_text.is_empty() {
-        return stdout_text;
-    }
-
-    "process exited without output".to_string()
-}
+    let raw = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read settings file ({}): {}", path.display(), e))?;
 
-fn list_coachnotes_clients_from_root(root_dir: &Path) -> Result<Vec<String>, String> {
-    if !root_dir.exists() {
-        return Err(format!(
-            "CoachNotes root does not exist: {}",
-            root_dir.display()
-        ));
+    let mut settings: AppSettings =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid settings JSON: {}", e))?;
+    settings.auto_split_minutes = validate_auto_split_minutes(settings.auto_split_minutes)
+        .ok()
+        .flatten();
+    settings.timestamp_interval_seconds =
+        validate_timestamp_interval(settings.timestamp_interval_seconds)
+            .ok()
+            .flatten();
+
+    if settings.selected_model != AUTO_MODEL && find_model(&settings.selected_model).is_none() {
+        settings.selected_model = AppSettings::default().selected_model;
+    }
+    settings
+        .language_models
+        .retain(|language, model| validate_language_model(language, model).is_ok());
+    settings.transcript_format = "md".to_string();
+    settings.diarization_mode = validate_diarization_mode(&settings.diarization_mode).to_string();
+    if !settings.diarization_mode_configured {
+        settings.diarization_mode = AppSettings::default().diarization_mode;
+    }
+    settings.coachnotes_root_dir = sanitize_non_empty(settings.coachnotes_root_dir.clone());
+    settings.coachnotes_client = sanitize_non_empty(settings.coachnotes_client.clone());
+    settings.locale = validate_locale(&settings.locale).to_string();
+    settings.timezone = validate_timezone(settings.timezone.clone());
+    settings.date_format = validate_date_format(&settings.date_format).to_string();
+    settings
+        .custom_frontmatter
+        .retain(|key, value| validate_frontmatter_key(key).is_ok() && !value.trim().is_empty());
+    // A hand-edited entry that is not allowed is dropped on its own and
+    // reported, keeping the valid flags around it.
+    let (extra_whisper_args, ignored_whisper_args): (Vec<String>, Vec<String>) = settings
+        .extra_whisper_args
+        .iter()
+        .map(|arg| arg.trim().to_string())
+        .filter(|arg| !arg.is_empty())
+        .partition(|arg| validate_extra_whisper_arg(arg).is_ok());
+    settings.extra_whisper_args = extra_whisper_args;
+    settings.ignored_whisper_args = ignored_whisper_args;
+    settings.spell_dictionary = normalize_spell_dictionary(&settings.spell_dictionary);
+    settings
+        .spell_dictionary
+        .truncate(MAX_SPELL_DICTIONARY_TERMS);
+    settings
+        .client_prompts
+        .retain(|client, prompt| !client.trim().is_empty() && !prompt.trim().is_empty());
+    settings
+        .client_templates
+        .retain(|client, template| !client.trim().is_empty() && !template.trim().is_empty());
+    settings.client_languages.retain(|client, language| {
+        !client.trim().is_empty() && validate_language(&language.language).is_ok()
+    });
+    settings.duplicate_check = validate_duplicate_check(&settings.duplicate_check).to_string();
+    settings.completion_sound =
+        validate_completion_sound(settings.completion_sound.clone()).unwrap_or_default();
+    settings.update_channel = validate_update_channel(&settings.update_channel).to_string();
+    settings.raw_output_dir = sanitize_non_empty(settings.raw_output_dir.clone());
+    settings.drive_client_id = sanitize_non_empty(settings.drive_client_id.clone());
+    settings.drive_folder_name = sanitize_non_empty(Some(settings.drive_folder_name.clone()))
+        .unwrap_or_else(default_drive_folder_name);
+    settings
+        .recording_presets
+        .retain(|preset| validate_recording_preset(preset.clone()).is_ok());
+    if !settings
+        .active_recording_preset
+        .as_ref()
+        .is_some_and(|name| find_recording_preset(&settings, name).is_some())
+    {
+        settings.active_recording_preset = None;
     }
+    settings.active_workspace = validate_workspace_name(&settings.active_workspace)
+        .unwrap_or_else(|_| default_workspace_name());
+    let mut seen_workspaces = HashSet::new();
+    settings.workspaces.retain(|workspace| {
+        validate_workspace_name(&workspace.name).is_ok()
+            && seen_workspaces.insert(workspace.name.to_lowercase())
+    });
 
-    let mut clients = Vec::new();
-    let entries = fs::read_dir(root_dir)
-        .map_err(|e| format!("Failed to read {}: {}", root_dir.display(), e))?;
+    Ok(settings)
+}
 
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let file_type = entry
-            .file_type()
-            .map_err(|e| format!("Failed to read file type: {}", e))?;
-        if !file_type.is_dir() {
-            continue;
-        }
+const JOB_HISTORY_LIMIT: usize = 1000;
 
-        let name = entry.file_name().to_string_lossy().to_string();
-        if name.starts_with('.') || name == COACHNOTES_DELETED_DIR {
-            continue;
-        }
+fn job_history_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app_data_dir(app)?.join("job-history.json"))
+}
 
-        clients.push(name);
+fn load_job_history(app: &AppHandle) -> Result<Vec<JobRecord>, String> {
+    let path = job_history_path(app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
     }
 
-    clients.sort_by_key(|name| name.to_ascii_lowercase());
-    Ok(clients)
+    let raw = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read job history ({}): {}", path.display(), e))?;
+    serde_json::from_str(&raw).map_err(|e| format!("Invalid job history JSON: {}", e))
 }
 
-async fn run_whisper(app: &AppHandle, args: &[String]) -> Result<WhisperOutput, String> {
-    #[cfg(not(debug_assertions))]
-    {
-        let command = app
-            .shell()
-            .sidecar("whisper-cli")
-            .map_err(|e| format!("Whisper sidecar is unavailable: {}", e))?;
-
-        let output = command
-            .args(args)
-            .output()
-            .await
-            .map_err(|e| format!("Failed to execute whisper sidecar: {}", e))?;
-
-        return Ok(WhisperOutput {
-            success: output.status.success(),
-            stdout: output.stdout,
-            stderr: output.stderr,
-            used_sidecar: true,
-        });
+fn append_job_record(app: &AppHandle, record: JobRecord) -> Result<(), String> {
+    let mut history = load_job_history(app)?;
+    history.push(record);
+    if history.len() > JOB_HISTORY_LIMIT {
+        history.drain(..history.len() - JOB_HISTORY_LIMIT);
     }
 
-    #[cfg(debug_assertions)]
-    {
-        let mut sidecar_failure: Option<String> = None;
+    let app_dir = app_data_dir(app)?;
+    fs::create_dir_all(&app_dir).map_err(|e| {
+        format!(
+            "Failed to create app data directory ({}): {}",
+            app_dir.display(),
+            e
+        )
+    })?;
 
-        if let Ok(command) = app.shell().sidecar("whisper-cli") {
-            match command.args(args).output().await {
-                Ok(output) => {
-                    if output.status.success() {
-                        return Ok(WhisperOutput {
-                            success: true,
-                            stdout: output.stdout,
-                            stderr: output.stderr,
-                            used_sidecar: true,
-                        });
-                    }
+    let path = job_history_path(app)?;
+    let serialized = serde_json::to_string_pretty(&history)
+        .map_err(|e| format!("Failed to serialize job history: {}", e))?;
+    fs::write(&path, serialized)
+        .map_err(|e| format!("Failed to write job history ({}): {}", path.display(), e))
+}
 
-                    sidecar_failure = Some(format!(
-                        "Debug sidecar failed: {}",
-                        process_output_detail(&output.stdout, &output.stderr)
-                    ));
-                }
-                Err(error) => {
-                    sidecar_failure = Some(format!("Debug sidecar could not run: {}", error));
-                }
-            }
-        }
+// Append-only record of what happens to saved transcripts and the files made
+// from them, so users in regulated settings can show where session notes
+// went. One JSON object per line in `audit-log.jsonl`; lines are only ever
+// added, never rewritten or pruned.
+fn audit_log_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app_data_dir(app)?.join("audit-log.jsonl"))
+}
 
-        let whisper_path = debug_whisper_fallback_path();
-        let fallback = StdCommand::new(&whisper_path)
-            .args(args)
-            .output()
-            .map_err(|e| {
-                format!(
-                    "Failed to run whisper fallback binary ({}): {}",
-                    whisper_path.display(),
-                    e
-                )
-            })?;
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum AuditAction {
+    Create,
+    // A same-day session added to an existing transcript.
+    Append,
+    Move,
+    Delete,
+    Export,
+    // Echo Scribe changed an existing note, such as linking its next session.
+    Update,
+}
 
-        if fallback.status.success() {
-            return Ok(WhisperOutput {
-                success: true,
-                stdout: fallback.stdout,
-                stderr: fallback.stderr,
-                used_sidecar: false,
-            });
-        }
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditEntry {
+    // RFC 3339, in the timezone set when the entry was written.
+    at: String,
+    action: AuditAction,
+    // The file acted on: the one created, appended to, deleted, or the
+    // source of a move or export.
+    path: String,
+    // Where a move or export put it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    destination: Option<String>,
+}
 
-        let mut detail = format!(
-            "Whisper fallback failed ({}): {}",
-            whisper_path.display(),
-            process_output_detail(&fallback.stdout, &fallback.stderr)
-        );
-        if let Some(sidecar_failure) = sidecar_failure {
-            detail = format!("{} | {}", sidecar_failure, detail);
-        }
+fn record_audit(
+    app: &AppHandle,
+    action: AuditAction,
+    path: &Path,
+    destination: Option<&Path>,
+) -> Result<(), String> {
+    let settings = load_settings(app)?;
+    let (now, _) = now_for_settings(&settings);
+    let entry = AuditEntry {
+        at: format_iso8601(now),
+        action,
+        path: path.to_string_lossy().to_string(),
+        destination: destination.map(|path| path.to_string_lossy().to_string()),
+    };
+    let line = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to encode audit log entry: {}", e))?;
 
-        Ok(WhisperOutput {
-            success: false,
-            stdout: detail.into_bytes(),
-            stderr: Vec::new(),
-            used_sidecar: false,
-        })
+    let path = audit_log_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| {
+            format!(
+                "Failed to create app data directory ({}): {}",
+                dir.display(),
+                e
+            )
+        })?;
     }
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(format!("{}\n", line).as_bytes()))
+        .map_err(|e| format!("Failed to write audit log ({}): {}", path.display(), e))
 }
 
-async fn transcribe_with_temp_output(
-    app: &AppHandle,
-    model_path: &Path,
-    wav_data: &[u8],
-    language: &str,
-    diarization_mode: &str,
-    format: WhisperFileFormat,
-    stem: &str,
-) -> Result<WhisperTranscriptOutput, String> {
-    let temp_dir = echo_scribe_temp_dir()?;
-    let wav_path = temp_dir.join(format!("{}.wav", stem));
-    let output_base = temp_dir.join(stem);
-    let transcript_path = temp_dir.join(format!("{}.{}", stem, format.extension()));
-    let _cleanup = TempFileCleanup::new(vec![wav_path.clone(), transcript_path.clone()]);
+fn save_settings(app: &AppHandle, settings: &AppSettings) -> Result<(), String> {
+    let path = settings_path(app)?;
+    if let Some(config_dir) = path.parent() {
+        fs::create_dir_all(config_dir).map_err(|e| {
+            format!(
+                "Failed to create app config directory ({}): {}",
+                config_dir.display(),
+                e
+            )
+        })?;
+    }
 
-    fs::write(&wav_path, wav_data).map_err(|e| {
-        format!(
-            "Failed to write temporary audio file ({}): {}",
-            wav_path.display(),
-            e
-        )
-    })?;
+    let serialized = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
 
-    let mut whisper_args = vec![
-        "-m".to_string(),
-        model_path.to_string_lossy().to_string(),
-        "-f".to_string(),
-        wav_path.to_string_lossy().to_string(),
-        format.cli_flag().to_string(),
-        "-of".to_string(),
-        output_base.to_string_lossy().to_string(),
-    ];
+    fs::write(&path, serialized)
+        .map_err(|e| format!("Failed to write settings file ({}): {}", path.display(), e))?;
+
+    let state = app.state::<SettingsState>();
+    *state
+        .cached
+        .lock()
+        .map_err(|_| "Failed to lock settings state.".to_string())? = Some(CachedSettings {
+        settings: settings.clone(),
+        modified: settings_modified_time(&path),
+    });
+    emit_settings_changed(app);
+    Ok(())
+}
 
-    if language != "auto" {
-        whisper_args.push("-l".to_string());
-        whisper_args.push(language.to_string());
+// Tells every window the settings changed, with the new setup state, so open
+// windows other than the one that made the change stay in step.
+fn emit_settings_changed(app: &AppHandle) {
+    if let Ok(setup_state) = build_setup_state(app) {
+        let _ = app.emit("settings-changed", setup_state);
     }
+}
 
-    if diarization_mode == "tdrz_2speaker" {
-        whisper_args.push("-tdrz".to_string());
-    }
+// Tokens for integrations live in the system credential store (macOS
+// Keychain, Windows Credential Manager, or the Secret Service on Linux),
+// never in settings.json, which may sit in a synced folder.
+const SECRET_NAME_MAX_LEN: usize = 64;
 
-    let whisper_output = run_whisper(app, &whisper_args).await?;
-    if !whisper_output.success {
+fn validate_secret_name(name: &str) -> Result<&str, String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty()
+        || trimmed.len() > SECRET_NAME_MAX_LEN
+        || !trimmed
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+    {
         return Err(format!(
-            "Whisper failed: {}",
-            process_output_detail(&whisper_output.stdout, &whisper_output.stderr)
+            "Invalid secret name ({}). Use up to {} letters, numbers, '.', '-' or '_'.",
+            name, SECRET_NAME_MAX_LEN
         ));
     }
+    Ok(trimmed)
+}
 
-    let content = fs::read_to_string(&transcript_path).map_err(|e| {
-        format!(
-            "Whisper ran but transcript file could not be read ({}): {}",
-            transcript_path.display(),
-            e
-        )
-    })?;
+fn secret_entry(app: &AppHandle, name: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(&app.config().identifier, validate_secret_name(name)?)
+        .map_err(|e| format!("Failed to open credential store: {}", e))
+}
 
-    Ok(WhisperTranscriptOutput {
-        content,
-        used_sidecar: whisper_output.used_sidecar,
-    })
+fn read_secret(app: &AppHandle, name: &str) -> Result<Option<String>, String> {
+    match secret_entry(app, name)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(format!("Failed to read secret '{}': {}", name, error)),
+    }
 }
 
-fn parse_srt_timestamp(raw: &str) -> Option<u64> {
-    let cleaned = raw.trim().replace(',', ".");
-    let parts = cleaned.split(':').collect::<Vec<&str>>();
-    if parts.len() != 3 {
-        return None;
+fn delete_secret_entry(app: &AppHandle, name: &str) -> Result<(), String> {
+    match secret_entry(app, name)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(error) => Err(format!("Failed to delete secret '{}': {}", name, error)),
     }
+}
 
-    let hours = parts[0].parse::<u64>().ok()?;
-    let minutes = parts[1].parse::<u64>().ok()?;
-    let sec_parts = parts[2].split('.').collect::<Vec<&str>>();
-    if sec_parts.len() != 2 {
-        return None;
+// Secrets the backend manages itself, which the frontend can't read or replace.
+fn ensure_integration_secret(name: &str) -> Result<(), String> {
+    if [
+        APP_LOCK_SECRET_NAME,
+        DRIVE_REFRESH_TOKEN_SECRET,
+        DRIVE_CLIENT_SECRET_SECRET,
+        HUGGINGFACE_TOKEN_SECRET,
+        EVENT_BRIDGE_TOKEN_SECRET,
+    ]
+    .contains(&validate_secret_name(name)?)
+    {
+        return Err(format!("The secret '{}' is managed by Echo Scribe.", name));
     }
+    Ok(())
+}
 
-    let seconds = sec_parts[0].parse::<u64>().ok()?;
-    let millis = match sec_parts[1].len() {
-        0 => 0,
-        1 => sec_parts[1].parse::<u64>().ok()? * 100,
-        2 => sec_parts[1].parse::<u64>().ok()? * 10,
-        _ => sec_parts[1]
-            .chars()
-            .take(3)
-            .collect::<String>()
-            .parse::<u64>()
-            .ok()?,
-    };
+// An empty value removes the secret.
+#[tauri::command]
+async fn set_secret(app: AppHandle, name: String, value: String) -> Result<(), String> {
+    require_unlocked(&app, None)?;
+    ensure_integration_secret(&name)?;
+    if value.is_empty() {
+        return delete_secret_entry(&app, &name);
+    }
+    secret_entry(&app, &name)?
+        .set_password(&value)
+        .map_err(|e| format!("Failed to save secret '{}': {}", name, e))
+}
 
-    Some((((hours * 60) + minutes) * 60 + seconds) * 1000 + millis)
+#[tauri::command]
+async fn get_secret(app: AppHandle, name: String) -> Result<Option<String>, String> {
+    require_unlocked(&app, None)?;
+    ensure_integration_secret(&name)?;
+    read_secret(&app, &name)
 }
 
-fn parse_srt_segments(text: &str, speaker: &str) -> Vec<TimestampedSegment> {
-    let normalized = text.replace("\r\n", "\n");
-    let mut segments = Vec::new();
+#[tauri::command]
+async fn delete_secret(app: AppHandle, name: String) -> Result<(), String> {
+    require_unlocked(&app, None)?;
+    ensure_integration_secret(&name)?;
+    delete_secret_entry(&app, &name)
+}
 
-    for block in normalized.split("\n\n") {
-        let lines = block
-            .lines()
-            .map(str::trim)
-            .filter(|line| !line.is_empty())
-            .collect::<Vec<&str>>();
+// With the app lock on, commands that read or list CoachNotes sessions fail
+// with `[app_locked]` until the passcode or Touch ID unlocks them. The lock
+// applies again on every launch and whenever `lock_app` is called.
+const APP_LOCK_SECRET_NAME: &str = "app-lock-passcode";
+const APP_LOCK_MIN_PASSCODE_LEN: usize = 4;
+// Slows down guessing from a script running in the webview.
+const APP_LOCK_FAILURE_DELAY_MS: u64 = 1000;
 
-        if lines.len() < 2 {
-            continue;
+#[derive(Default)]
+struct AppLockState {
+    unlocked: AtomicBool,
+}
+
+fn is_app_unlocked(app: &AppHandle) -> bool {
+    app.state::<AppLockState>().unlocked.load(Ordering::SeqCst)
+}
+
+fn set_app_unlocked(app: &AppHandle, unlocked: bool) {
+    app.state::<AppLockState>()
+        .unlocked
+        .store(unlocked, Ordering::SeqCst);
+}
+
+// `path` limits the check to files inside any workspace's CoachNotes root;
+// without one, the caller reads sessions or changes settings and is always
+// checked.
+fn require_unlocked(app: &AppHandle, path: Option<&Path>) -> Result<(), String> {
+    let settings = load_settings(app)?;
+    if !settings.app_lock_enabled || is_app_unlocked(app) {
+        return Ok(());
+    }
+    if let Some(path) = path {
+        let in_coachnotes_root = std::iter::once(settings.coachnotes_root_dir.clone())
+            .chain(
+                settings
+                    .workspaces
+                    .iter()
+                    .map(|workspace| workspace.coachnotes_root_dir.clone()),
+            )
+            .filter_map(sanitize_non_empty)
+            .any(|root| is_within(path, Path::new(&root)));
+        if !in_coachnotes_root {
+            return Ok(());
         }
+    }
+    Err(format!(
+        "[app_locked] {}",
+        localize(&settings.locale, Message::AppLocked)
+    ))
+}
+
+// Stored as `pbkdf2-sha256:<iterations>:<salt>:<hex key>` so the credential
+// store never holds the passcode itself and a copied record is slow to guess.
+const PASSCODE_RECORD_PREFIX: &str = "pbkdf2-sha256";
+const PASSCODE_KDF_ITERATIONS: u32 = 600_000;
+
+fn new_passcode_record(passcode: &str) -> Result<String, String> {
+    let salt = random_url_token(16)?;
+    let mut key = [0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        std::num::NonZeroU32::new(PASSCODE_KDF_ITERATIONS).unwrap_or(std::num::NonZeroU32::MIN),
+        salt.as_bytes(),
+        passcode.as_bytes(),
+        &mut key,
+    );
+    let key = key
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    Ok(format!(
+        "{}:{}:{}:{}",
+        PASSCODE_RECORD_PREFIX, PASSCODE_KDF_ITERATIONS, salt, key
+    ))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).ok())
+        .collect()
+}
+
+fn passcode_matches(record: &str, passcode: &str) -> bool {
+    let parts = record.split(':').collect::<Vec<&str>>();
+    let [_, iterations, salt, key] = parts.as_slice() else {
+        return false;
+    };
+    let (Some(iterations), Some(key)) = (
+        iterations
+            .parse::<u32>()
+            .ok()
+            .and_then(std::num::NonZeroU32::new),
+        decode_hex(key),
+    ) else {
+        return false;
+    };
+    ring::pbkdf2::verify(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt.as_bytes(),
+        passcode.as_bytes(),
+        &key,
+    )
+    .is_ok()
+}
+
+#[cfg(target_os = "macos")]
+async fn authenticate_device_owner(app: &AppHandle, reason: &str) -> Result<(), String> {
+    let helper = resolve_system_audio_capture_sidecar_path(app)?;
+    let output = tokio::process::Command::new(&helper)
+        .args(["authenticate", "--reason", reason])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to start Touch ID prompt: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(process_output_detail(&output.stdout, &output.stderr))
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+async fn authenticate_device_owner(_app: &AppHandle, _reason: &str) -> Result<(), String> {
+    Err("Touch ID is only available on macOS.".to_string())
+}
+
+// Turning the lock on needs a passcode unless one is already stored; changing
+// or removing it needs the app to be unlocked first.
+#[tauri::command]
+async fn set_app_lock(
+    app: AppHandle,
+    enabled: bool,
+    passcode: Option<String>,
+    touch_id: bool,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let passcode = passcode.filter(|value| !value.is_empty());
+    if let Some(passcode) = &passcode {
+        if passcode.chars().count() < APP_LOCK_MIN_PASSCODE_LEN {
+            return Err(format!(
+                "The passcode needs at least {} characters.",
+                APP_LOCK_MIN_PASSCODE_LEN
+            ));
+        }
+    }
+
+    if enabled {
+        match passcode {
+            Some(passcode) => {
+                let record =
+                    tauri::async_runtime::spawn_blocking(move || new_passcode_record(&passcode))
+                        .await
+                        .map_err(|e| format!("Failed to save the passcode: {}", e))??;
+                secret_entry(&app, APP_LOCK_SECRET_NAME)?
+                    .set_password(&record)
+                    .map_err(|e| format!("Failed to save the passcode: {}", e))?
+            }
+            None if read_secret(&app, APP_LOCK_SECRET_NAME)?.is_none() => {
+                return Err("Choose a passcode to turn on the app lock.".to_string());
+            }
+            None => {}
+        }
+    } else {
+        delete_secret_entry(&app, APP_LOCK_SECRET_NAME)?;
+    }
+
+    let mut settings = load_settings(&app)?;
+    settings.app_lock_enabled = enabled;
+    settings.app_lock_touch_id = enabled && touch_id && cfg!(target_os = "macos");
+    // The person who just set the lock stays unlocked until it is locked
+    // again or the app restarts.
+    set_app_unlocked(&app, true);
+    save_settings(&app, &settings)?;
+    build_setup_state(&app)
+}
+
+// Without a passcode, asks for Touch ID (or the account password) instead.
+#[tauri::command]
+async fn unlock_app(app: AppHandle, passcode: Option<String>) -> Result<SetupState, String> {
+    let settings = load_settings(&app)?;
+    if settings.app_lock_enabled && !is_app_unlocked(&app) {
+        match passcode {
+            Some(passcode) => {
+                let record = read_secret(&app, APP_LOCK_SECRET_NAME)?.ok_or_else(|| {
+                    "No app lock passcode is stored. Turn the lock off and on again.".to_string()
+                })?;
+                let matches = tauri::async_runtime::spawn_blocking(move || {
+                    passcode_matches(&record, &passcode)
+                })
+                .await
+                .map_err(|e| format!("Failed to check the passcode: {}", e))?;
+                if !matches {
+                    tokio::time::sleep(Duration::from_millis(APP_LOCK_FAILURE_DELAY_MS)).await;
+                    return Err(localize(&settings.locale, Message::IncorrectPasscode));
+                }
+            }
+            None => {
+                if !settings.app_lock_touch_id {
+                    return Err("Touch ID unlock is turned off.".to_string());
+                }
+                authenticate_device_owner(&app, &localize(&settings.locale, Message::UnlockReason))
+                    .await?;
+            }
+        }
+        set_app_unlocked(&app, true);
+        emit_settings_changed(&app);
+    }
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn lock_app(app: AppHandle) -> Result<SetupState, String> {
+    set_app_unlocked(&app, false);
+    emit_settings_changed(&app);
+    build_setup_state(&app)
+}
+
+// `on_progress` receives the hashed share of the file, once per percent.
+async fn sha256_for_file(path: &Path, mut on_progress: impl FnMut(u32)) -> Result<String, String> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let total_bytes = file
+        .metadata()
+        .await
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    let mut hashed_bytes: u64 = 0;
+    let mut reported_percent = None;
+
+    loop {
+        let read_bytes = file
+            .read(&mut buffer)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+        if read_bytes == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..read_bytes]);
+        hashed_bytes += read_bytes as u64;
+        let percent = (hashed_bytes.saturating_mul(100) / total_bytes.max(1)).min(100) as u32;
+        if reported_percent != Some(percent) {
+            reported_percent = Some(percent);
+            on_progress(percent);
+        }
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn sidecar_binary_path() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let parent = exe.parent()?;
+    Some(parent.join("whisper-cli"))
+}
+
+#[cfg(debug_assertions)]
+fn debug_whisper_fallback_path() -> PathBuf {
+    let home = dirs::home_dir().unwrap_or_default();
+
+    let local = home.join("whisper.cpp/build/bin/whisper-cli");
+    if local.exists() {
+        return local;
+    }
+
+    let local_old = home.join("whisper.cpp/main");
+    if local_old.exists() {
+        return local_old;
+    }
+
+    let brew = PathBuf::from("/opt/homebrew/bin/whisper-cpp");
+    if brew.exists() {
+        return brew;
+    }
+
+    PathBuf::from("whisper-cli")
+}
+
+#[cfg(debug_assertions)]
+fn debug_ffmpeg_fallback_path() -> PathBuf {
+    let brew = PathBuf::from("/opt/homebrew/bin/ffmpeg");
+    if brew.exists() {
+        return brew;
+    }
+
+    let usr_local = PathBuf::from("/usr/local/bin/ffmpeg");
+    if usr_local.exists() {
+        return usr_local;
+    }
+
+    PathBuf::from("ffmpeg")
+}
+
+fn ffmpeg_sidecar_path() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let parent = exe.parent()?;
+    Some(parent.join("ffmpeg"))
+}
+
+fn system_audio_capture_sidecar_candidates() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    if let Ok(exe) = std::env::current_exe() {
+        if let Some(parent) = exe.parent() {
+            candidates.push(parent.join("system-audio-capture"));
+        }
+    }
+
+    if let Ok(cwd) = std::env::current_dir() {
+        candidates.push(cwd.join("binaries/system-audio-capture-aarch64-apple-darwin"));
+        candidates.push(cwd.join("src-tauri/binaries/system-audio-capture-aarch64-apple-darwin"));
+        candidates
+            .push(cwd.join("../src-tauri/binaries/system-audio-capture-aarch64-apple-darwin"));
+    }
+
+    candidates.push(PathBuf::from("system-audio-capture"));
+    candidates
+}
+
+fn file_contains_marker(path: &Path, marker: &str) -> bool {
+    fs::read_to_string(path)
+        .map(|content| content.contains(marker))
+        .unwrap_or(false)
+}
+
+#[cfg(debug_assertions)]
+fn debug_system_audio_capture_binary_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dev_dir = storage_path(app, app_cache_dir(app)?, "dev-binaries")?;
+    fs::create_dir_all(&dev_dir).map_err(|e| {
+        format!(
+            "Failed to create dev binary directory ({}): {}",
+            dev_dir.display(),
+            e
+        )
+    })?;
+    Ok(dev_dir.join("system-audio-capture"))
+}
+
+#[cfg(debug_assertions)]
+fn debug_system_audio_capture_source_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("native/system_audio_capture.swift")
+}
+
+#[cfg(debug_assertions)]
+fn should_rebuild_debug_binary(source_path: &Path, binary_path: &Path) -> bool {
+    if !binary_path.exists() {
+        return true;
+    }
+
+    let Ok(source_meta) = fs::metadata(source_path) else {
+        return false;
+    };
+    let Ok(binary_meta) = fs::metadata(binary_path) else {
+        return true;
+    };
+    let Ok(source_modified) = source_meta.modified() else {
+        return false;
+    };
+    let Ok(binary_modified) = binary_meta.modified() else {
+        return true;
+    };
+
+    source_modified > binary_modified
+}
+
+#[cfg(debug_assertions)]
+fn ensure_debug_system_audio_capture_binary(app: &AppHandle) -> Result<PathBuf, String> {
+    let source_path = debug_system_audio_capture_source_path();
+    if !source_path.exists() {
+        return Err(format!(
+            "System audio capture source file is missing: {}",
+            source_path.display()
+        ));
+    }
+
+    let binary_path = debug_system_audio_capture_binary_path(app)?;
+    if should_rebuild_debug_binary(&source_path, &binary_path) {
+        let output = StdCommand::new("swiftc")
+            .args([
+                "-O",
+                "-parse-as-library",
+                "-framework",
+                "AVFoundation",
+                "-framework",
+                "CoreGraphics",
+                "-framework",
+                "CoreMedia",
+                "-framework",
+                "LocalAuthentication",
+                "-framework",
+                "ScreenCaptureKit",
+            ])
+            .arg(&source_path)
+            .arg("-o")
+            .arg(&binary_path)
+            .output()
+            .map_err(|e| format!("Failed to launch swiftc for system audio helper: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to build local system audio capture helper: {}",
+                process_output_detail(&output.stdout, &output.stderr)
+            ));
+        }
+    }
+
+    Ok(binary_path)
+}
+
+fn resolve_system_audio_capture_sidecar_path(_app_handle: &AppHandle) -> Result<PathBuf, String> {
+    for candidate in system_audio_capture_sidecar_candidates() {
+        if candidate.exists() {
+            if file_contains_marker(&candidate, SYSTEM_AUDIO_CAPTURE_PLACEHOLDER_MARKER) {
+                #[cfg(debug_assertions)]
+                {
+                    return ensure_debug_system_audio_capture_binary(_app_handle);
+                }
+
+                #[cfg(not(debug_assertions))]
+                {
+                    continue;
+                }
+            }
+
+            return Ok(candidate);
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    {
+        return ensure_debug_system_audio_capture_binary(_app_handle);
+    }
+
+    #[cfg(not(debug_assertions))]
+    {
+        Ok(PathBuf::from("system-audio-capture"))
+    }
+}
+
+fn whisper_binary_path() -> PathBuf {
+    if let Some(path) = sidecar_binary_path().filter(|path| path.exists()) {
+        return path;
+    }
+
+    #[cfg(debug_assertions)]
+    {
+        debug_whisper_fallback_path()
+    }
+
+    #[cfg(not(debug_assertions))]
+    {
+        PathBuf::from("whisper-cli")
+    }
+}
+
+fn is_sidecar_available() -> bool {
+    sidecar_binary_path().map(|p| p.exists()).unwrap_or(false)
+}
+
+fn process_output_detail(stdout: &[u8], stderr: &[u8]) -> String {
+    let stderr_text = String::from_utf8_lossy(stderr).trim().to_string();
+    if !stderr_text.is_empty() {
+        return stderr_text;
+    }
+
+    let stdout_text = String::from_utf8_lossy(stdout).trim().to_string();
+    if !stdout_text.is_empty() {
+        return stdout_text;
+    }
+
+    "process exited without output".to_string()
+}
+
+#[cfg(target_os = "macos")]
+fn sysctl_value(name: &str) -> Option<String> {
+    let output = StdCommand::new("sysctl").args(["-n", name]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+fn total_memory_bytes() -> Option<u64> {
+    #[cfg(target_os = "macos")]
+    {
+        sysctl_value("hw.memsize").and_then(|value| value.parse::<u64>().ok())
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        fs::read_to_string("/proc/meminfo")
+            .ok()?
+            .lines()
+            .find(|line| line.starts_with("MemTotal:"))
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|kilobytes| kilobytes.parse::<u64>().ok())
+            .map(|kilobytes| kilobytes * 1024)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+// Memory that can be handed to a new process without swapping. On macOS this
+// counts free, inactive, speculative, and purgeable pages from `vm_stat`.
+fn available_memory_bytes() -> Option<u64> {
+    #[cfg(target_os = "macos")]
+    {
+        let output = StdCommand::new("vm_stat").output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout).to_string();
+        let page_size = text
+            .lines()
+            .next()?
+            .split("page size of ")
+            .nth(1)?
+            .split_whitespace()
+            .next()?
+            .parse::<u64>()
+            .ok()?;
+        let pages = text
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .filter(|(name, _)| {
+                matches!(
+                    name.trim(),
+                    "Pages free" | "Pages inactive" | "Pages speculative" | "Pages purgeable"
+                )
+            })
+            .filter_map(|(_, value)| value.trim().trim_end_matches('.').parse::<u64>().ok())
+            .sum::<u64>();
+        Some(pages * page_size)
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        fs::read_to_string("/proc/meminfo")
+            .ok()?
+            .lines()
+            .find(|line| line.starts_with("MemAvailable:"))
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|kilobytes| kilobytes.parse::<u64>().ok())
+            .map(|kilobytes| kilobytes * 1024)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+// How long a background job (dropped file, Quick Action, automation link)
+// waits for mains power in battery saver mode before running anyway.
+const BATTERY_SAVER_DEFER_SECS: u64 = 15 * 60;
+const POWER_POLL_INTERVAL_SECS: u64 = 30;
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct PowerState {
+    on_battery: bool,
+    low_power_mode: bool,
+    battery_percent: Option<u8>,
+}
+
+impl PowerState {
+    fn constrained(&self) -> bool {
+        self.on_battery || self.low_power_mode
+    }
+}
+
+// How long a power reading is reused. Setup refreshes and the hold loop both
+// ask for it, and on macOS each fresh reading spawns `pmset` twice.
+const POWER_STATE_CACHE_SECS: u64 = 5;
+
+fn power_state() -> PowerState {
+    static CACHE: OnceLock<Mutex<Option<(Instant, PowerState)>>> = OnceLock::new();
+    let Ok(mut cached) = CACHE.get_or_init(|| Mutex::new(None)).lock() else {
+        return read_power_state();
+    };
+    if let Some((read_at, state)) = *cached {
+        if read_at.elapsed() < Duration::from_secs(POWER_STATE_CACHE_SECS) {
+            return state;
+        }
+    }
+    let state = read_power_state();
+    *cached = Some((Instant::now(), state));
+    state
+}
+
+fn read_power_state() -> PowerState {
+    #[cfg(target_os = "macos")]
+    {
+        let mut state = PowerState::default();
+        if let Ok(output) = StdCommand::new("pmset").args(["-g", "batt"]).output() {
+            let text = String::from_utf8_lossy(&output.stdout);
+            state.on_battery = text.contains("'Battery Power'");
+            state.battery_percent = text
+                .split_whitespace()
+                .find_map(|word| word.strip_suffix("%;"))
+                .and_then(|percent| percent.parse::<u8>().ok());
+        }
+        if let Ok(output) = StdCommand::new("pmset").arg("-g").output() {
+            state.low_power_mode = String::from_utf8_lossy(&output.stdout).lines().any(|line| {
+                let mut words = line.split_whitespace();
+                words.next() == Some("lowpowermode") && words.next() == Some("1")
+            });
+        }
+        state
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let mut state = PowerState::default();
+        let Ok(entries) = fs::read_dir("/sys/class/power_supply") else {
+            return state;
+        };
+        let mut has_mains = false;
+        let mut mains_online = false;
+        for entry in entries.flatten() {
+            let dir = entry.path();
+            let read = |name: &str| {
+                fs::read_to_string(dir.join(name))
+                    .map(|value| value.trim().to_string())
+                    .unwrap_or_default()
+            };
+            match read("type").as_str() {
+                "Mains" => {
+                    has_mains = true;
+                    mains_online |= read("online") == "1";
+                }
+                "Battery" => {
+                    state.battery_percent = read("capacity").parse::<u8>().ok();
+                }
+                _ => {}
+            }
+        }
+        state.on_battery = has_mains && !mains_online && state.battery_percent.is_some();
+        state
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        PowerState::default()
+    }
+}
+
+// Thread count for whisper while battery saver is active: half of whisper's
+// own default of min(4, cores).
+fn battery_saver_threads() -> usize {
+    let cores = std::thread::available_parallelism()
+        .map(|cores| cores.get())
+        .unwrap_or(4);
+    (cores.min(4) / 2).max(1)
+}
+
+// Why a background job should not start yet, if at all. Pausing on battery
+// has no time limit; battery saver gives up after `BATTERY_SAVER_DEFER_SECS`.
+fn background_hold_reason(
+    settings: &AppSettings,
+    power: PowerState,
+    waited: Duration,
+) -> Option<&'static str> {
+    if settings.pause_queue_on_battery && power.on_battery {
+        return Some("on_battery");
+    }
+    if settings.battery_saver
+        && power.constrained()
+        && waited < Duration::from_secs(BATTERY_SAVER_DEFER_SECS)
+    {
+        return Some("battery_saver");
+    }
+    None
+}
+
+// Holds a background job (dropped file, Quick Action, automation link) until
+// power allows it, emitting `job-paused` and `job-resumed` as the reason
+// changes. Interactive sessions from the recorder never wait here.
+async fn hold_background_job(app: &AppHandle, event: &mut JobEvent) {
+    let started = Instant::now();
+    loop {
+        let reason = load_settings(app).ok().and_then(|settings| {
+            background_hold_reason(&settings, power_state(), started.elapsed())
+        });
+        if event.paused_reason.as_deref() != reason {
+            event.paused_reason = reason.map(str::to_string);
+            let name = if reason.is_some() {
+                "job-paused"
+            } else {
+                "job-resumed"
+            };
+            let _ = app.emit(name, event.clone());
+        }
+        if reason.is_none() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_secs(POWER_POLL_INTERVAL_SECS)).await;
+    }
+}
+
+// Hours when heavy jobs may run, in the settings timezone. `start` later than
+// `end` wraps past midnight, as in the usual 23:00 to 06:00.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeferredWindow {
+    start: String,
+    end: String,
+}
+
+// Retries on a model at least this large count as heavy: medium and up.
+const DEFERRED_MODEL_MIN_MB: u32 = 1500;
+const DEFERRED_POLL_SECS: u64 = 30;
+
+fn clock_minutes(value: &str) -> Option<u32> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    if minutes.len() != 2 {
+        return None;
+    }
+    let hours = hours.parse::<u32>().ok().filter(|hours| *hours < 24)?;
+    let minutes = minutes
+        .parse::<u32>()
+        .ok()
+        .filter(|minutes| *minutes < 60)?;
+    Some(hours * 60 + minutes)
+}
+
+fn validate_deferred_window(window: DeferredWindow) -> Result<DeferredWindow, String> {
+    let normalize = |value: &str| {
+        clock_minutes(value)
+            .map(|minutes| format!("{:02}:{:02}", minutes / 60, minutes % 60))
+            .ok_or_else(|| format!("Invalid time '{}'. Use HH:MM, for example 23:00.", value))
+    };
+    let window = DeferredWindow {
+        start: normalize(&window.start)?,
+        end: normalize(&window.end)?,
+    };
+    if window.start == window.end {
+        return Err("The deferred window must end at a different time than it starts.".to_string());
+    }
+    Ok(window)
+}
+
+fn window_contains(window: &DeferredWindow, minute_of_day: u32) -> bool {
+    let (Some(start), Some(end)) = (clock_minutes(&window.start), clock_minutes(&window.end))
+    else {
+        return true;
+    };
+    if start < end {
+        (start..end).contains(&minute_of_day)
+    } else {
+        minute_of_day >= start || minute_of_day < end
+    }
+}
+
+// Batch imports and large-model re-transcriptions. Urgent jobs never wait.
+fn is_heavy_job(options: &TranscriptionOptions, retry_of: Option<&str>) -> bool {
+    if validate_job_priority(&options.priority) == "urgent" {
+        return false;
+    }
+    let large_retry = retry_of.is_some()
+        && find_model(&options.model).is_some_and(|entry| entry.size_mb >= DEFERRED_MODEL_MIN_MB);
+    options.background || large_retry
+}
+
+// The window start a heavy job has to wait for, or `None` when it can run now.
+fn deferred_until(
+    settings: &AppSettings,
+    options: &TranscriptionOptions,
+    retry_of: Option<&str>,
+) -> Option<String> {
+    let window = settings.deferred_window.as_ref()?;
+    if !is_heavy_job(options, retry_of) {
+        return None;
+    }
+    let (now, _) = now_for_settings(settings);
+    let minute_of_day = u32::from(now.hour()) * 60 + u32::from(now.minute());
+    (!window_contains(window, minute_of_day)).then(|| window.start.clone())
+}
+
+fn set_scheduled_for(queue: &TranscriptionQueue, job_id: &str, scheduled_for: Option<String>) {
+    if let Ok(mut waiting) = queue.waiting.lock() {
+        if let Some(job) = waiting.iter_mut().find(|job| job.job_id == job_id) {
+            job.scheduled_for = scheduled_for;
+        }
+    }
+}
+
+// Keeps a queued job marked as scheduled until the deferred window opens, the
+// window is turned off, or `reorder_queue` moves the job up. Emits
+// `job-paused` with reason `scheduled` on the way in and `job-resumed` on the
+// way out.
+async fn hold_until_window(
+    app: &AppHandle,
+    queue: &TranscriptionQueue,
+    event: &mut JobEvent,
+    scheduled_for: String,
+    options: &TranscriptionOptions,
+    retry_of: Option<&str>,
+) {
+    event.paused_reason = Some("scheduled".to_string());
+    event.scheduled_for = Some(scheduled_for);
+    let _ = app.emit("job-paused", event.clone());
+    loop {
+        let _ = tokio::time::timeout(
+            Duration::from_secs(DEFERRED_POLL_SECS),
+            queue.turn.notified(),
+        )
+        .await;
+        let released = queue.waiting.lock().map_or(true, |waiting| {
+            waiting
+                .iter()
+                .find(|job| job.job_id == event.job_id)
+                .map_or(true, |job| job.scheduled_for.is_none())
+        });
+        let scheduled_for = load_settings(app)
+            .ok()
+            .and_then(|settings| deferred_until(&settings, options, retry_of));
+        if released || scheduled_for.is_none() {
+            break;
+        }
+        if scheduled_for != event.scheduled_for {
+            set_scheduled_for(queue, &event.job_id, scheduled_for.clone());
+            event.scheduled_for = scheduled_for;
+            emit_queue_changed(app, queue);
+        }
+    }
+    set_scheduled_for(queue, &event.job_id, None);
+    emit_queue_changed(app, queue);
+    event.paused_reason = None;
+    event.scheduled_for = None;
+    let _ = app.emit("job-resumed", event.clone());
+}
+
+fn cpu_brand() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        sysctl_value("machdep.cpu.brand_string")
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        fs::read_to_string("/proc/cpuinfo")
+            .ok()?
+            .lines()
+            .find(|line| line.starts_with("model name"))
+            .and_then(|line| line.split_once(':'))
+            .map(|(_, value)| value.trim().to_string())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+fn apple_silicon_generation(brand: &str) -> Option<u32> {
+    let rest = brand.strip_prefix("Apple M")?;
+    rest.chars()
+        .take_while(|char| char.is_ascii_digit())
+        .collect::<String>()
+        .parse::<u32>()
+        .ok()
+}
+
+fn detect_hardware() -> HardwareInfo {
+    static HARDWARE: OnceLock<HardwareInfo> = OnceLock::new();
+    HARDWARE
+        .get_or_init(|| {
+            let cpu_brand = cpu_brand();
+            HardwareInfo {
+                cpu_cores: std::thread::available_parallelism()
+                    .map(|cores| cores.get())
+                    .unwrap_or(1),
+                memory_gb: total_memory_bytes().map(|bytes| bytes / (1024 * 1024 * 1024)),
+                apple_silicon_generation: cpu_brand.as_deref().and_then(apple_silicon_generation),
+                cpu_brand,
+            }
+        })
+        .clone()
+}
+
+fn recommend_model(hardware: &HardwareInfo) -> &'static str {
+    let Some(memory_gb) = hardware.memory_gb else {
+        return "base";
+    };
+
+    if memory_gb < 4 {
+        return "tiny";
+    }
+    if memory_gb < 8 || hardware.cpu_cores <= 4 {
+        return "base";
+    }
+
+    if let Some(generation) = hardware.apple_silicon_generation {
+        let high_end = hardware
+            .cpu_brand
+            .as_deref()
+            .map(|brand| {
+                ["Pro", "Max", "Ultra"]
+                    .iter()
+                    .any(|tier| brand.contains(tier))
+            })
+            .unwrap_or(false);
+        if memory_gb >= 16 && (generation >= 2 || high_end) {
+            return "medium";
+        }
+        return "small";
+    }
+
+    if memory_gb >= 16 && hardware.cpu_cores >= 8 {
+        "small"
+    } else {
+        "base"
+    }
+}
+
+fn binary_contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+fn whisper_binary_features(binary: &Path) -> WhisperBinaryFeatures {
+    static FEATURES: OnceLock<WhisperBinaryFeatures> = OnceLock::new();
+    FEATURES
+        .get_or_init(|| {
+            let Ok(bytes) = fs::read(binary) else {
+                return WhisperBinaryFeatures::default();
+            };
+            WhisperBinaryFeatures {
+                metal: binary_contains(&bytes, b"ggml_metal"),
+                coreml: binary_contains(&bytes, b"whisper_coreml"),
+                cuda: binary_contains(&bytes, b"ggml_cuda"),
+            }
+        })
+        .clone()
+}
+
+fn cuda_available() -> bool {
+    static CUDA_AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *CUDA_AVAILABLE.get_or_init(|| {
+        StdCommand::new("nvidia-smi")
+            .arg("-L")
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    })
+}
+
+fn build_acceleration_info(app: &AppHandle) -> Result<AccelerationInfo, String> {
+    let hardware = detect_hardware();
+    let apple_silicon = hardware.apple_silicon_generation.is_some();
+    let binary = whisper_binary_path();
+    let models_directory = models_dir(app)?;
+
+    let coreml_encoder_models = MODEL_CATALOG
+        .iter()
+        .filter(|entry| {
+            models_directory
+                .join(format!("ggml-{}-encoder.mlmodelc", entry.id))
+                .exists()
+        })
+        .map(|entry| entry.id.to_string())
+        .collect::<Vec<String>>();
+
+    Ok(AccelerationInfo {
+        metal_available: cfg!(target_os = "macos"),
+        coreml_available: cfg!(target_os = "macos") && apple_silicon,
+        cuda_available: !cfg!(target_os = "macos") && cuda_available(),
+        whisper_binary_found: binary.exists(),
+        whisper_binary_features: whisper_binary_features(&binary),
+        whisper_binary: binary.to_string_lossy().to_string(),
+        coreml_encoder_models,
+    })
+}
+
+fn list_coachnotes_clients_from_root(root_dir: &Path) -> Result<Vec<String>, String> {
+    if !root_dir.exists() {
+        return Err(format!(
+            "CoachNotes root does not exist: {}",
+            root_dir.display()
+        ));
+    }
+
+    let mut clients = Vec::new();
+    let entries = fs::read_dir(root_dir)
+        .map_err(|e| format!("Failed to read {}: {}", root_dir.display(), e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let file_type = entry
+            .file_type()
+            .map_err(|e| format!("Failed to read file type: {}", e))?;
+        if !file_type.is_dir() {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') || name == COACHNOTES_DELETED_DIR {
+            continue;
+        }
+
+        clients.push(name);
+    }
+
+    clients.sort_by_key(|name| name.to_ascii_lowercase());
+    Ok(clients)
+}
+
+const COACHNOTES_WATCH_INTERVAL_SECS: u64 = 2;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoachnotesClientsEvent {
+    root_dir: String,
+    clients: Vec<String>,
+}
+
+fn current_coachnotes_clients(app: &AppHandle) -> Option<CoachnotesClientsEvent> {
+    let settings = load_settings(app).ok()?;
+    if !settings.coachnotes_enabled || require_unlocked(app, None).is_err() {
+        return None;
+    }
+    let root_dir = settings.coachnotes_root_dir?;
+    let clients = list_coachnotes_clients_from_root(Path::new(&root_dir)).unwrap_or_default();
+    Some(CoachnotesClientsEvent { root_dir, clients })
+}
+
+// Watches the CoachNotes root for client folders being added, renamed or
+// removed in Finder and emits `coachnotes-clients-changed` with the new list.
+// A short listing poll works on local and cloud-synced folders alike and only
+// reads one directory.
+async fn watch_coachnotes_root(app: AppHandle) {
+    let mut last = current_coachnotes_clients(&app);
+    loop {
+        tokio::time::sleep(Duration::from_secs(COACHNOTES_WATCH_INTERVAL_SECS)).await;
+        let current = current_coachnotes_clients(&app);
+        if current != last {
+            if let Some(event) = &current {
+                let _ = app.emit("coachnotes-clients-changed", event.clone());
+            }
+            last = current;
+        }
+    }
+}
+
+// A transcript saved by Echo Scribe, read back from its frontmatter.
+struct SavedTranscript {
+    path: PathBuf,
+    client: Option<String>,
+    title: String,
+    // `YYYY-MM-DD` from `created_at`, independent of the date format setting.
+    date: String,
+    created_at: String,
+    duration_seconds: u64,
+    word_count: u64,
+    model: String,
+    diarization_mode: String,
+    tags: Vec<String>,
+    topics: Vec<String>,
+    speakers: Vec<String>,
+    summary: Option<String>,
+    body: String,
+}
+
+fn read_saved_transcript(path: &Path) -> Option<SavedTranscript> {
+    let markdown = fs::read_to_string(path).ok()?;
+    let (frontmatter, body) = split_frontmatter(&markdown);
+    if frontmatter_value(frontmatter, "source_app").as_deref() != Some("Echo Scribe")
+        || frontmatter_value(frontmatter, "anonymized").is_some()
+    {
+        return None;
+    }
+
+    let model = frontmatter_value(frontmatter, "model")?;
+    let created_at = frontmatter_value(frontmatter, "created_at")?;
+    let date = created_at.get(..10)?.to_string();
+    if !is_iso_date(&date) {
+        return None;
+    }
+
+    let mut speakers = frontmatter_list(frontmatter, "speakers");
+    for label in DEFAULT_SPEAKER_LABELS {
+        if !speakers.iter().any(|speaker| speaker == label) {
+            speakers.push(label.to_string());
+        }
+    }
+
+    Some(SavedTranscript {
+        path: path.to_path_buf(),
+        client: frontmatter_value(frontmatter, "client"),
+        title: frontmatter_value(frontmatter, "title")
+            .unwrap_or_else(|| DEFAULT_TRANSCRIPT_TITLE.to_string()),
+        date,
+        created_at,
+        duration_seconds: frontmatter_value(frontmatter, "duration_seconds")
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(0),
+        word_count: frontmatter_nested_value(frontmatter, "speech_stats", "word_count")
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(0),
+        model,
+        diarization_mode: frontmatter_value(frontmatter, "diarization_mode")
+            .unwrap_or_else(|| "none".to_string()),
+        tags: frontmatter_list(frontmatter, "tags"),
+        topics: frontmatter_list(frontmatter, "topics"),
+        speakers,
+        summary: frontmatter_value(frontmatter, "summary"),
+        body: body.to_string(),
+    })
+}
+
+// Sync conflict copies and files still in the cloud are left out: the copies
+// hold stale or diverged text, reading a placeholder blocks on a download,
+// and `check_sync_health` reports both.
+fn markdown_files_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let synced = sync_provider(dir).is_some();
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .map_or(false, |extension| extension == "md")
+                && path
+                    .file_name()
+                    .map_or(false, |name| name != CLIENT_INDEX_FILE)
+                && sync_conflict_original(path, synced).is_none()
+                && !is_sync_placeholder(path)
+        })
+        .collect()
+}
+
+// Every transcript in the standard folder and, when configured, in each
+// CoachNotes client folder, oldest first.
+fn scan_saved_transcripts(settings: &AppSettings) -> Vec<SavedTranscript> {
+    let mut files = markdown_files_in(&resolve_transcript_dir(settings));
+    if let Some(root) = sanitize_non_empty(settings.coachnotes_root_dir.clone()) {
+        let root = PathBuf::from(root);
+        for client in list_coachnotes_clients_from_root(&root).unwrap_or_default() {
+            files.extend(markdown_files_in(&root.join(client)));
+        }
+    }
+
+    let mut transcripts = files
+        .iter()
+        .filter_map(|path| read_saved_transcript(path))
+        .collect::<Vec<SavedTranscript>>();
+    transcripts.sort_by(|left, right| left.created_at.cmp(&right.created_at));
+    transcripts
+}
+
+fn is_iso_date(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return false;
+    }
+    if !value
+        .chars()
+        .enumerate()
+        .all(|(index, char)| index == 4 || index == 7 || char.is_ascii_digit())
+    {
+        return false;
+    }
+    let month = value[5..7].parse::<u32>().unwrap_or(0);
+    let day = value[8..10].parse::<u32>().unwrap_or(0);
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
+
+const FFMPEG_AUDIO_CODECS: [&str; 4] = ["pcm_s16le", "flac", "aac", "opus"];
+
+// Output side of an ffmpeg conversion. Unset fields keep the input's values;
+// `start_ms`/`end_ms` cut the input to that range.
+#[derive(Debug, Default, Deserialize)]
+pub struct AudioOutputSpec {
+    path: String,
+    #[serde(default)]
+    sample_rate: Option<u32>,
+    #[serde(default)]
+    channels: Option<u16>,
+    #[serde(default)]
+    codec: Option<String>,
+    #[serde(default)]
+    bitrate_kbps: Option<u32>,
+    #[serde(default)]
+    start_ms: Option<u64>,
+    #[serde(default)]
+    end_ms: Option<u64>,
+}
+
+fn format_ffmpeg_seconds(ms: u64) -> String {
+    format!("{}.{:03}", ms / 1000, ms % 1000)
+}
+
+fn ffmpeg_convert_args(input: &Path, spec: &AudioOutputSpec) -> Result<Vec<String>, String> {
+    let mut args = vec![
+        "-hide_banner".to_string(),
+        "-nostdin".to_string(),
+        "-loglevel".to_string(),
+        "error".to_string(),
+        "-n".to_string(),
+        "-i".to_string(),
+        input.to_string_lossy().to_string(),
+        "-vn".to_string(),
+    ];
+
+    if let Some(start_ms) = spec.start_ms.filter(|start_ms| *start_ms > 0) {
+        args.push("-ss".to_string());
+        args.push(format_ffmpeg_seconds(start_ms));
+    }
+    if let Some(end_ms) = spec.end_ms {
+        if end_ms <= spec.start_ms.unwrap_or(0) {
+            return Err("Conversion end must be after its start.".to_string());
+        }
+        args.push("-to".to_string());
+        args.push(format_ffmpeg_seconds(end_ms));
+    }
+    if let Some(sample_rate) = spec.sample_rate {
+        args.push("-ar".to_string());
+        args.push(sample_rate.to_string());
+    }
+    if let Some(channels) = spec.channels {
+        args.push("-ac".to_string());
+        args.push(channels.to_string());
+    }
+    if let Some(codec) = spec.codec.as_deref() {
+        if !FFMPEG_AUDIO_CODECS.contains(&codec) {
+            return Err(format!(
+                "Unsupported audio codec '{}'. Valid values: {}",
+                codec,
+                FFMPEG_AUDIO_CODECS.join(", ")
+            ));
+        }
+        args.push("-c:a".to_string());
+        args.push(codec.to_string());
+    }
+    if let Some(bitrate_kbps) = spec.bitrate_kbps {
+        args.push("-b:a".to_string());
+        args.push(format!("{}k", bitrate_kbps));
+    }
+
+    args.push(spec.path.clone());
+    Ok(args)
+}
+
+async fn run_ffmpeg(app: &AppHandle, args: &[String]) -> Result<(), String> {
+    #[cfg(not(debug_assertions))]
+    {
+        let command = app
+            .shell()
+            .sidecar("ffmpeg")
+            .map_err(|e| format!("ffmpeg sidecar is unavailable: {}", e))?;
+
+        let output = command
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| format!("Failed to execute ffmpeg sidecar: {}", e))?;
+
+        if output.status.success() {
+            return Ok(());
+        }
+        return Err(format!(
+            "ffmpeg failed: {}",
+            process_output_detail(&output.stdout, &output.stderr)
+        ));
+    }
+
+    #[cfg(debug_assertions)]
+    {
+        let mut sidecar_failure: Option<String> = None;
+
+        if let Ok(command) = app.shell().sidecar("ffmpeg") {
+            match command.args(args).output().await {
+                Ok(output) => {
+                    if output.status.success() {
+                        return Ok(());
+                    }
+
+                    sidecar_failure = Some(format!(
+                        "Debug ffmpeg sidecar failed: {}",
+                        process_output_detail(&output.stdout, &output.stderr)
+                    ));
+                }
+                Err(error) => {
+                    sidecar_failure =
+                        Some(format!("Debug ffmpeg sidecar could not run: {}", error));
+                }
+            }
+        }
+
+        let ffmpeg_path = debug_ffmpeg_fallback_path();
+        let fallback = StdCommand::new(&ffmpeg_path)
+            .args(args)
+            .output()
+            .map_err(|e| {
+                format!(
+                    "Failed to run ffmpeg fallback binary ({}): {}",
+                    ffmpeg_path.display(),
+                    e
+                )
+            })?;
+
+        if fallback.status.success() {
+            return Ok(());
+        }
+
+        let mut detail = format!(
+            "ffmpeg fallback failed ({}): {}",
+            ffmpeg_path.display(),
+            process_output_detail(&fallback.stdout, &fallback.stderr)
+        );
+        if let Some(sidecar_failure) = sidecar_failure {
+            detail = format!("{} | {}", sidecar_failure, detail);
+        }
+        Err(detail)
+    }
+}
+
+// Converts `input` with the bundled ffmpeg sidecar. Existing output files are
+// never overwritten.
+async fn convert_audio_file(
+    app: &AppHandle,
+    input: &Path,
+    spec: &AudioOutputSpec,
+) -> Result<PathBuf, String> {
+    if !input.is_file() {
+        return Err(format!("Audio file not found ({})", input.display()));
+    }
+    let output = PathBuf::from(&spec.path);
+    if output.exists() {
+        return Err(format!("Output file already exists ({})", output.display()));
+    }
+    if let Some(parent) = output
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|e| {
+            format!(
+                "Failed to create output directory ({}): {}",
+                parent.display(),
+                e
+            )
+        })?;
+    }
+
+    let args = ffmpeg_convert_args(input, spec)?;
+    run_ffmpeg(app, &args).await?;
+    Ok(output)
+}
+
+// Runs the whisper sidecar to completion, handing each line whisper prints
+// to `on_line` as it arrives. whisper prints every segment to stdout the
+// moment it is decoded, which is what partial autosave reads.
+async fn run_whisper_sidecar(
+    command: tauri_plugin_shell::process::Command,
+    args: &[String],
+    on_line: &mut (dyn FnMut(&str) + Send),
+) -> Result<WhisperOutput, String> {
+    let (mut events, _child) = command.args(args).spawn().map_err(|e| e.to_string())?;
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut status = None;
+    while let Some(event) = events.recv().await {
+        match event {
+            CommandEvent::Stdout(line) => {
+                on_line(String::from_utf8_lossy(&line).trim_end());
+                stdout.extend_from_slice(&line);
+                if !line.ends_with(b"\n") {
+                    stdout.push(b'\n');
+                }
+            }
+            CommandEvent::Stderr(line) => {
+                stderr.extend_from_slice(&line);
+                if !line.ends_with(b"\n") {
+                    stderr.push(b'\n');
+                }
+            }
+            CommandEvent::Error(error) => return Err(error),
+            CommandEvent::Terminated(payload) => status = Some(payload),
+            _ => {}
+        }
+    }
+    let status = status.ok_or_else(|| "Whisper exited without a status".to_string())?;
+    Ok(WhisperOutput {
+        success: status.code == Some(0),
+        terminated: status.code.is_none(),
+        stdout,
+        stderr,
+        used_sidecar: true,
+    })
+}
+
+async fn run_whisper(
+    app: &AppHandle,
+    args: &[String],
+    on_line: &mut (dyn FnMut(&str) + Send),
+) -> Result<WhisperOutput, String> {
+    #[cfg(not(debug_assertions))]
+    {
+        let command = app
+            .shell()
+            .sidecar("whisper-cli")
+            .map_err(|e| format!("Whisper sidecar is unavailable: {}", e))?;
+
+        return run_whisper_sidecar(command, args, on_line)
+            .await
+            .map_err(|e| format!("Failed to execute whisper sidecar: {}", e));
+    }
+
+    #[cfg(debug_assertions)]
+    {
+        let mut sidecar_failure: Option<String> = None;
+
+        if let Ok(command) = app.shell().sidecar("whisper-cli") {
+            match run_whisper_sidecar(command, args, on_line).await {
+                Ok(output) => {
+                    if output.success {
+                        return Ok(output);
+                    }
+
+                    sidecar_failure = Some(format!(
+                        "Debug sidecar failed: {}",
+                        process_output_detail(&output.stdout, &output.stderr)
+                    ));
+                }
+                Err(error) => {
+                    sidecar_failure = Some(format!("Debug sidecar could not run: {}", error));
+                }
+            }
+        }
+
+        let whisper_path = debug_whisper_fallback_path();
+        let fallback = StdCommand::new(&whisper_path)
+            .args(args)
+            .output()
+            .map_err(|e| {
+                format!(
+                    "Failed to run whisper fallback binary ({}): {}",
+                    whisper_path.display(),
+                    e
+                )
+            })?;
+
+        if fallback.status.success() {
+            return Ok(WhisperOutput {
+                success: true,
+                terminated: false,
+                stdout: fallback.stdout,
+                stderr: fallback.stderr,
+                used_sidecar: false,
+            });
+        }
+
+        let mut detail = format!(
+            "Whisper fallback failed ({}): {}",
+            whisper_path.display(),
+            process_output_detail(&fallback.stdout, &fallback.stderr)
+        );
+        if let Some(sidecar_failure) = sidecar_failure {
+            detail = format!("{} | {}", sidecar_failure, detail);
+        }
+
+        Ok(WhisperOutput {
+            success: false,
+            terminated: fallback.status.code().is_none(),
+            stdout: detail.into_bytes(),
+            stderr: Vec::new(),
+            used_sidecar: false,
+        })
+    }
+}
+
+// whisper leaves out hesitations and restarts unless its prompt contains
+// some, so English verbatim jobs start from a disfluent example.
+const VERBATIM_PROMPT: &str =
+    "Umm, so, uh, I- I was, like, hmm... I mean, well, you know, it's- it's fine.";
+
+// Flags Echo Scribe sets itself (model, input, output files and formats,
+// language, diarization, prompt, battery-saver threads) or that stop whisper
+// from transcribing at all.
+const DENIED_WHISPER_FLAGS: [&str; 38] = [
+    "-m",
+    "--model",
+    "-f",
+    "--file",
+    "-of",
+    "--output-file",
+    "-otxt",
+    "--output-txt",
+    "-ovtt",
+    "--output-vtt",
+    "-osrt",
+    "--output-srt",
+    "-olrc",
+    "--output-lrc",
+    "-owts",
+    "--output-words",
+    "-ocsv",
+    "--output-csv",
+    "-oj",
+    "--output-json",
+    "-ojf",
+    "--output-json-full",
+    "-l",
+    "--language",
+    "-dl",
+    "--detect-language",
+    "-tr",
+    "--translate",
+    "-tdrz",
+    "--tinydiarize",
+    "--prompt",
+    "--prompt-file",
+    "-t",
+    "--threads",
+    "-h",
+    "--help",
+    "-np",
+    "--no-prints",
+    "-nt",
+    "--no-timestamps",
+];
+
+// Every entry is a flag, with any value attached as `--flag=value`. A bare
+// entry would reach whisper as another input file, and "-m /x" as one entry
+// would slip past the flag check.
+fn validate_extra_whisper_arg(arg: &str) -> Result<(), String> {
+    if !arg.starts_with('-') {
+        return Err(format!(
+            "Whisper flag '{}' does not start with '-'. Attach values to their flag, as in --max-len=60.",
+            arg
+        ));
+    }
+    if arg.contains(char::is_whitespace) {
+        return Err(format!(
+            "Whisper flag '{}' contains a space. Attach the value with '=', as in --max-len=60.",
+            arg
+        ));
+    }
+    let flag = arg.split('=').next().unwrap_or(arg);
+    if DENIED_WHISPER_FLAGS.contains(&flag) {
+        return Err(format!(
+            "Whisper flag '{}' is set by Echo Scribe and cannot be passed through.",
+            flag
+        ));
+    }
+    Ok(())
+}
+
+fn validate_extra_whisper_args(args: &[String]) -> Result<Vec<String>, String> {
+    let mut validated = Vec::new();
+    for arg in args {
+        let arg = arg.trim();
+        if arg.is_empty() {
+            continue;
+        }
+        validate_extra_whisper_arg(arg)?;
+        validated.push(arg.to_string());
+    }
+    Ok(validated)
+}
+
+// Writes the audio whisper reads in chunks, reporting each one at the job's
+// current progress so long recordings do not sit on one message.
+fn write_temp_audio(
+    job: &WhisperJob<'_>,
+    path: &Path,
+    wav_data: &[u8],
+    progress: u32,
+) -> io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    let chunk_count = wav_data.len().div_ceil(TEMP_AUDIO_WRITE_CHUNK_BYTES);
+    for (index, chunk) in wav_data.chunks(TEMP_AUDIO_WRITE_CHUNK_BYTES).enumerate() {
+        file.write_all(chunk)?;
+        if chunk_count > 1 {
+            emit_progress(
+                job.app,
+                progress,
+                &localize_with(
+                    job.locale,
+                    Message::WritingTempAudio,
+                    &[("percent", &(100 * (index + 1) / chunk_count).to_string())],
+                ),
+            );
+        }
+    }
+    file.flush()
+}
+
+async fn transcribe_with_temp_output(
+    job: &WhisperJob<'_>,
+    audio: &JobTrack<'_>,
+    diarization_mode: &str,
+    format: WhisperFileFormat,
+    stem: &str,
+    progress: u32,
+) -> Result<WhisperTranscriptOutput, String> {
+    let app = job.app;
+    let temp_dir = echo_scribe_temp_dir()?;
+    let wav_path = temp_dir.join(format!("{}.wav", stem));
+    let output_base = temp_dir.join(stem);
+    let transcript_path = temp_dir.join(format!("{}.{}", stem, format.extension()));
+    let json_path = temp_dir.join(format!("{}.json", stem));
+    let raw_output_paths = match job.raw_output_dir {
+        Some(_) => RAW_WHISPER_OUTPUT_FORMATS
+            .iter()
+            .map(|(_, extension)| temp_dir.join(format!("{}.{}", stem, extension)))
+            .collect::<Vec<PathBuf>>(),
+        None => Vec::new(),
+    };
+    let mut cleanup_paths = vec![wav_path.clone(), transcript_path.clone(), json_path.clone()];
+    cleanup_paths.extend(raw_output_paths.iter().cloned());
+    let _cleanup = TempFileCleanup::new(cleanup_paths);
+
+    let input_path = match audio {
+        JobTrack::File(path, _) if !job.preprocessing.is_active() => path.to_path_buf(),
+        _ => {
+            let mut wav_data = audio.bytes()?;
+            // Denoising a long recording takes seconds to minutes of CPU, so
+            // it runs off the async runtime.
+            if job.preprocessing.is_active() {
+                let preprocessing = job.preprocessing;
+                let raw = wav_data.into_owned();
+                wav_data = Cow::Owned(
+                    tauri::async_runtime::spawn_blocking(move || {
+                        preprocessing.apply(&raw).into_owned()
+                    })
+                    .await
+                    .map_err(|e| format!("Failed to clean up the audio: {}", e))?,
+                );
+            }
+            write_temp_audio(job, &wav_path, &wav_data, progress).map_err(|e| {
+                format!(
+                    "Failed to write temporary audio file ({}): {}",
+                    wav_path.display(),
+                    e
+                )
+            })?;
+            wav_path.clone()
+        }
+    };
+
+    let mut whisper_args = vec![
+        "-m".to_string(),
+        job.model_path.to_string_lossy().to_string(),
+        "-f".to_string(),
+        input_path.to_string_lossy().to_string(),
+        format.cli_flag().to_string(),
+        // Full JSON carries the token probabilities behind segment confidence.
+        "-ojf".to_string(),
+        "-of".to_string(),
+        output_base.to_string_lossy().to_string(),
+    ];
+
+    if job.language != "auto" {
+        whisper_args.push("-l".to_string());
+        whisper_args.push(job.language.to_string());
+    }
+
+    if job.translate {
+        whisper_args.push("-tr".to_string());
+    }
+
+    if job.raw_output_dir.is_some() {
+        whisper_args.extend(
+            RAW_WHISPER_OUTPUT_FORMATS
+                .iter()
+                .filter(|(flag, _)| *flag != format.cli_flag())
+                .map(|(flag, _)| flag.to_string()),
+        );
+    }
+
+    if diarization_mode == "tdrz_2speaker" {
+        whisper_args.push("-tdrz".to_string());
+    }
+
+    if let Some(prompt) = job.prompt {
+        whisper_args.push("--prompt".to_string());
+        whisper_args.push(prompt.to_string());
+    }
+
+    if let Ok(settings) = load_settings(app) {
+        if settings.battery_saver && power_state().constrained() {
+            whisper_args.push("-t".to_string());
+            whisper_args.push(battery_saver_threads().to_string());
+        }
+        // whisper-cli reads a flag's value from the next argument.
+        for arg in settings.extra_whisper_args {
+            match arg.split_once('=') {
+                Some((flag, value)) => {
+                    whisper_args.push(flag.to_string());
+                    whisper_args.push(value.to_string());
+                }
+                None => whisper_args.push(arg),
+            }
+        }
+    }
+
+    let (partial, captions) = (job.partial, job.captions);
+    let whisper_output = run_whisper(app, &whisper_args, &mut |line: &str| {
+        if let Some(partial) = partial {
+            partial.push_line(line);
+        }
+        if let Some(captions) = captions {
+            captions.push_line(line);
+        }
+    })
+    .await?;
+    if whisper_output.terminated {
+        return Err(format!(
+            "Whisper was terminated before finishing (likely out of memory): {}",
+            process_output_detail(&whisper_output.stdout, &whisper_output.stderr)
+        ));
+    }
+    if !whisper_output.success {
+        return Err(format!(
+            "Whisper failed: {}",
+            process_output_detail(&whisper_output.stdout, &whisper_output.stderr)
+        ));
+    }
+
+    let content = fs::read_to_string(&transcript_path).map_err(|e| {
+        format!(
+            "Whisper ran but transcript file could not be read ({}): {}",
+            transcript_path.display(),
+            e
+        )
+    })?;
+    let confidences = fs::read_to_string(&json_path)
+        .map(|json| parse_segment_confidences(&json))
+        .unwrap_or_default();
+
+    if let Some(raw_output_dir) = job.raw_output_dir {
+        for path in raw_output_paths.iter().filter(|path| path.exists()) {
+            let Some(file_name) = path.file_name() else {
+                continue;
+            };
+            let staged_path = raw_output_dir.join(file_name);
+            fs::copy(path, &staged_path).map_err(|e| {
+                format!(
+                    "Failed to keep raw whisper output ({}): {}",
+                    staged_path.display(),
+                    e
+                )
+            })?;
+        }
+    }
+
+    Ok(WhisperTranscriptOutput {
+        content,
+        used_sidecar: whisper_output.used_sidecar,
+        confidences,
+    })
+}
+
+// Whisper's full JSON lists each segment with its tokens; a segment's
+// confidence is the mean probability of its text tokens, leaving out the
+// `[_BEG_]`-style control tokens.
+fn parse_segment_confidences(json: &str) -> HashMap<(u64, u64), f32> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+        return HashMap::new();
+    };
+    let Some(entries) = value
+        .get("transcription")
+        .and_then(serde_json::Value::as_array)
+    else {
+        return HashMap::new();
+    };
+
+    let mut confidences = HashMap::new();
+    for entry in entries {
+        let offset = |key: &str| {
+            entry
+                .get("offsets")
+                .and_then(|offsets| offsets.get(key))
+                .and_then(serde_json::Value::as_u64)
+        };
+        let (Some(start_ms), Some(end_ms)) = (offset("from"), offset("to")) else {
+            continue;
+        };
+        let probabilities = entry
+            .get("tokens")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|token| {
+                !token
+                    .get("text")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default()
+                    .starts_with("[_")
+            })
+            .filter_map(|token| token.get("p").and_then(serde_json::Value::as_f64))
+            .collect::<Vec<f64>>();
+        if probabilities.is_empty() {
+            continue;
+        }
+        let mean = probabilities.iter().sum::<f64>() / probabilities.len() as f64;
+        confidences.insert((start_ms, end_ms), mean as f32);
+    }
+    confidences
+}
+
+fn apply_segment_confidences(
+    segments: &mut [TimestampedSegment],
+    confidences: &HashMap<(u64, u64), f32>,
+) {
+    for segment in segments {
+        segment.confidence = confidences
+            .get(&(segment.start_ms, segment.end_ms))
+            .copied();
+    }
+}
+
+// Word-weighted mean of two segments being joined; unknown if either side is.
+fn merged_confidence(
+    left: &TimestampedSegment,
+    right_text: &str,
+    right_confidence: Option<f32>,
+) -> Option<f32> {
+    let (left_confidence, right_confidence) = (left.confidence?, right_confidence?);
+    let left_words = left.text.split_whitespace().count().max(1) as f32;
+    let right_words = right_text.split_whitespace().count().max(1) as f32;
+    Some(
+        (left_confidence * left_words + right_confidence * right_words)
+            / (left_words + right_words),
+    )
+}
+
+const AUTO_SPLIT_MIN_MINUTES: u32 = 10;
+const SPLIT_SEARCH_WINDOW_MS: u64 = 60_000;
+const SPLIT_FRAME_MS: u64 = 250;
+
+fn format_duration_hm(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    if hours == 0 {
+        format!("{}m", minutes.max(1))
+    } else {
+        format!("{}h {:02}m", hours, minutes)
+    }
+}
+
+// Rejects recordings that would run for hours or exhaust memory before whisper
+// starts. A limit of 0 disables that check; the duration limit does not apply
+// when auto-split bounds each whisper run on its own.
+fn check_input_limits(
+    settings: &AppSettings,
+    locale: &str,
+    duration_ms: u64,
+    total_bytes: usize,
+) -> Result<(), String> {
+    let max_bytes = u64::from(settings.max_audio_mb) * 1024 * 1024;
+    if max_bytes > 0 && total_bytes as u64 > max_bytes {
+        let size = format!("{} MB", total_bytes / (1024 * 1024));
+        let limit = format!("{} MB", settings.max_audio_mb);
+        return Err(localize_with(
+            locale,
+            Message::AudioTooLarge,
+            &[("size", size.as_str()), ("limit", limit.as_str())],
+        ));
+    }
+
+    let max_duration_ms = u64::from(settings.max_recording_minutes) * 60_000;
+    if max_duration_ms > 0 && settings.auto_split_minutes.is_none() && duration_ms > max_duration_ms
+    {
+        let duration = format_duration_hm(duration_ms / 1000);
+        let limit = format_duration_hm(u64::from(settings.max_recording_minutes) * 60);
+        return Err(localize_with(
+            locale,
+            Message::RecordingTooLong,
+            &[("duration", duration.as_str()), ("limit", limit.as_str())],
+        ));
+    }
+
+    Ok(())
+}
+
+const TIMESTAMP_INTERVAL_MIN_SECONDS: u32 = 5;
+const TIMESTAMP_INTERVAL_MAX_SECONDS: u32 = 3600;
+
+fn validate_timestamp_interval(seconds: Option<u32>) -> Result<Option<u32>, String> {
+    match seconds {
+        None | Some(0) => Ok(None),
+        Some(seconds)
+            if (TIMESTAMP_INTERVAL_MIN_SECONDS..=TIMESTAMP_INTERVAL_MAX_SECONDS)
+                .contains(&seconds) =>
+        {
+            Ok(Some(seconds))
+        }
+        Some(_) => Err(format!(
+            "Timestamp interval must be between {} and {} seconds.",
+            TIMESTAMP_INTERVAL_MIN_SECONDS, TIMESTAMP_INTERVAL_MAX_SECONDS
+        )),
+    }
+}
+
+fn validate_auto_split_minutes(minutes: Option<u32>) -> Result<Option<u32>, String> {
+    match minutes {
+        None | Some(0) => Ok(None),
+        Some(minutes) if minutes < AUTO_SPLIT_MIN_MINUTES => Err(format!(
+            "Auto-split length must be at least {} minutes.",
+            AUTO_SPLIT_MIN_MINUTES
+        )),
+        Some(minutes) => Ok(Some(minutes)),
+    }
+}
+
+// Middle of the quietest frame in [from_ms, to_ms), by summed absolute amplitude.
+fn quietest_split_point(pcm: &[u8], from_ms: u64, to_ms: u64) -> u64 {
+    let mut best = (to_ms, u64::MAX);
+    let mut frame_start = from_ms;
+    while frame_start + SPLIT_FRAME_MS <= to_ms {
+        let start = ms_to_pcm_offset(frame_start).min(pcm.len());
+        let end = ms_to_pcm_offset(frame_start + SPLIT_FRAME_MS).min(pcm.len());
+        let energy = pcm[start..end]
+            .chunks_exact(WAV_BYTES_PER_SAMPLE)
+            .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]).unsigned_abs() as u64)
+            .sum::<u64>();
+        if energy < best.1 {
+            best = (frame_start + SPLIT_FRAME_MS / 2, energy);
+        }
+        frame_start += SPLIT_FRAME_MS;
+    }
+    best.0
+}
+
+// Start offsets of each part. Every split lands on the quietest frame in the
+// minute before the length limit, so parts break between sentences.
+fn plan_part_starts(wav_data: &[u8], max_part_ms: u64) -> Vec<u64> {
+    let duration_ms = estimate_duration_ms(wav_data);
+    let mut starts = vec![0];
+    if max_part_ms == 0 || duration_ms <= max_part_ms {
+        return starts;
+    }
+
+    let pcm = wav_pcm_data(wav_data);
+    let mut part_start = 0;
+    while duration_ms - part_start > max_part_ms {
+        let target = part_start + max_part_ms;
+        let window_start = target
+            .saturating_sub(SPLIT_SEARCH_WINDOW_MS)
+            .max(part_start + max_part_ms / 2);
+        part_start = quietest_split_point(pcm, window_start, target);
+        starts.push(part_start);
+    }
+    starts
+}
+
+// `name.md` becomes `name-part-1.md`, `name-part-2.md`, ...; the numeric
+// collision suffix is applied to the whole set so part links stay valid.
+fn part_paths_for(base: &Path, count: usize, overwrite: bool) -> Vec<PathBuf> {
+    let with_parts = |path: &Path| {
+        let stem = path
+            .file_stem()
+            .map(|value| value.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let extension = path
+            .extension()
+            .map(|value| format!(".{}", value.to_string_lossy()))
+            .unwrap_or_default();
+        (1..=count)
+            .map(|part| path.with_file_name(format!("{}-part-{}{}", stem, part, extension)))
+            .collect::<Vec<PathBuf>>()
+    };
+    if overwrite {
+        return with_parts(base);
+    }
+
+    (1..=MAX_COLLISION_SUFFIX)
+        .map(|attempt| with_parts(&numbered_path(base, attempt)))
+        .find(|paths| paths.iter().all(|path| !path.exists()))
+        .unwrap_or_else(|| with_parts(base))
+}
+
+struct WhisperJob<'a> {
+    app: &'a AppHandle,
+    model_path: &'a Path,
+    language: &'a str,
+    speaker_mode: &'a str,
+    preprocessing: AudioPreprocessing,
+    locale: &'a str,
+    prompt: Option<&'a str>,
+    // Staging folder that collects whisper's own txt/srt/json files for the
+    // whole job; `None` lets them be deleted with the temp audio.
+    raw_output_dir: Option<&'a Path>,
+    partial: Option<&'a PartialTranscript>,
+    captions: Option<&'a CaptionStream>,
+    verbatim: bool,
+    translate: bool,
+}
+
+const CAPTIONS_EVENT: &str = "captions";
+const CAPTION_WORDS: usize = 32;
+
+#[derive(Clone, Serialize)]
+struct CaptionsPayload {
+    job_id: String,
+    // Words of finished parts; they no longer change.
+    stable: String,
+    // Words whisper has printed for the part in progress. They are replaced
+    // by the part's final segments when it finishes.
+    tentative: String,
+    done: bool,
+}
+
+#[derive(Default)]
+struct CaptionState {
+    stable: Vec<String>,
+    tentative: Vec<String>,
+}
+
+// The last few words of a running job for the captions overlay, sent as a
+// `captions` event whenever whisper prints a segment or a part finishes.
+// Dropping the stream sends a final event with `done` set.
+struct CaptionStream {
+    app: AppHandle,
+    job_id: String,
+    state: Mutex<CaptionState>,
+}
+
+impl CaptionStream {
+    fn new(app: &AppHandle, job_id: &str) -> Self {
+        Self {
+            app: app.clone(),
+            job_id: job_id.to_string(),
+            state: Mutex::new(CaptionState::default()),
+        }
+    }
+
+    // Drops the part's tentative words, also when it restarts on a fallback model.
+    fn begin_part(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.tentative.clear();
+            self.emit(&state, false);
+        }
+    }
+
+    fn push_line(&self, line: &str) {
+        let Some(segment) = parse_whisper_output_line(line, "") else {
+            return;
+        };
+        if let Ok(mut state) = self.state.lock() {
+            state
+                .tentative
+                .extend(segment.text.split_whitespace().map(str::to_string));
+            keep_last_words(&mut state.tentative);
+            self.emit(&state, false);
+        }
+    }
+
+    fn finish_part(&self, segments: &[TimestampedSegment]) {
+        if let Ok(mut state) = self.state.lock() {
+            state.tentative.clear();
+            for segment in segments {
+                state
+                    .stable
+                    .extend(segment.text.split_whitespace().map(str::to_string));
+            }
+            keep_last_words(&mut state.stable);
+            self.emit(&state, false);
+        }
+    }
+
+    // The newest `CAPTION_WORDS` words overall: tentative words first, the
+    // rest filled from the end of the stable text.
+    fn emit(&self, state: &CaptionState, done: bool) {
+        let stable_words = CAPTION_WORDS.saturating_sub(state.tentative.len());
+        let stable = &state.stable[state.stable.len().saturating_sub(stable_words)..];
+        let _ = self.app.emit(
+            CAPTIONS_EVENT,
+            CaptionsPayload {
+                job_id: self.job_id.clone(),
+                stable: stable.join(" "),
+                tentative: state.tentative.join(" "),
+                done,
+            },
+        );
+    }
+}
+
+impl Drop for CaptionStream {
+    fn drop(&mut self) {
+        if let Ok(state) = self.state.lock() {
+            self.emit(&state, true);
+        }
+    }
+}
+
+fn keep_last_words(words: &mut Vec<String>) {
+    let excess = words.len().saturating_sub(CAPTION_WORDS);
+    words.drain(..excess);
+}
+
+const PARTIAL_SAVE_INTERVAL_SECS: u64 = 30;
+
+// The transcript-so-far of a running job. Segments whisper prints are
+// rewritten to `<name>.partial.md` next to the planned transcript at most
+// every 30 seconds and after each auto-split part, so a crash or power loss
+// late in a long job leaves the finished stretch on disk.
+struct PartialTranscript {
+    path: PathBuf,
+    state: Mutex<PartialTranscriptState>,
+}
+
+#[derive(Default)]
+struct PartialTranscriptState {
+    // Segments of finished parts, on the recording's timeline.
+    finished: Vec<TimestampedSegment>,
+    // Segments of the part in progress, already shifted onto that timeline.
+    streamed: Vec<TimestampedSegment>,
+    part_start_ms: u64,
+    pass_offset_ms: u64,
+    speaker: String,
+    last_write: Option<Instant>,
+}
+
+// One segment line of whisper's console output:
+// `[00:01:02.500 --> 00:01:05.000]  Text`.
+fn parse_whisper_output_line(line: &str, speaker: &str) -> Option<TimestampedSegment> {
+    let (times, text) = line.trim().strip_prefix('[')?.split_once(']')?;
+    let (start_raw, end_raw) = times.split_once("-->")?;
+    let text = sanitize_transcript_text(text.trim());
+    if text.is_empty() {
+        return None;
+    }
+    Some(TimestampedSegment {
+        speaker: speaker.to_string(),
+        start_ms: parse_srt_timestamp(start_raw)?,
+        end_ms: parse_srt_timestamp(end_raw)?,
+        text,
+        confidence: None,
+    })
+}
+
+impl PartialTranscript {
+    fn for_transcript(path: &Path) -> Self {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        Self {
+            path: path.with_file_name(format!("{}.partial.md", stem)),
+            state: Mutex::new(PartialTranscriptState::default()),
+        }
+    }
+
+    // Starts (or restarts, after a model fallback) the part at `start_ms`.
+    fn begin_part(&self, start_ms: u64) {
+        if let Ok(mut state) = self.state.lock() {
+            state.streamed.clear();
+            state.part_start_ms = start_ms;
+        }
+    }
+
+    // Starts one whisper run within the current part. Source-aware jobs run
+    // once per speaker, the client's track `offset_ms` behind the coach's.
+    fn begin_pass(&self, speaker: &str, offset_ms: u64) {
+        if let Ok(mut state) = self.state.lock() {
+            state.speaker = speaker.to_string();
+            state.pass_offset_ms = offset_ms;
+        }
+    }
+
+    fn push_line(&self, line: &str) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let Some(mut segment) = parse_whisper_output_line(line, &state.speaker) else {
+            return;
+        };
+        let shift = state.part_start_ms + state.pass_offset_ms;
+        segment.start_ms += shift;
+        segment.end_ms += shift;
+        state.streamed.push(segment);
+        let due = state.last_write.map_or(true, |last| {
+            last.elapsed() >= Duration::from_secs(PARTIAL_SAVE_INTERVAL_SECS)
+        });
+        if due {
+            self.write(&mut state);
+        }
+    }
+
+    // Replaces the streamed lines with the finished parts' real segments.
+    fn finish_part(&self, segments: &[TimestampedSegment]) {
+        if let Ok(mut state) = self.state.lock() {
+            state.finished = segments.to_vec();
+            state.streamed.clear();
+            self.write(&mut state);
+        }
+    }
+
+    // Best effort: a partial file that cannot be written must not fail the job.
+    fn write(&self, state: &mut PartialTranscriptState) {
+        state.last_write = Some(Instant::now());
+        let mut streamed = state.streamed.clone();
+        streamed.sort_by_key(|segment| segment.start_ms);
+        let mut segments = state.finished.clone();
+        segments.extend(streamed);
+        let Some(last) = segments.last() else {
+            return;
+        };
+        let markdown = format!(
+            "# Partial transcript\n\n_Transcription in progress, {} transcribed. This file is removed once the transcript is saved._\n\n{}\n",
+            format_clock_timestamp(last.end_ms),
+            render_segments(&segments)
+        );
+        if let Some(dir) = self.path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = fs::write(&self.path, markdown);
+    }
+
+    fn discard(&self) {
+        let _ = fs::remove_file(&self.path);
+    }
+
+    // Points a failed job's error at whatever was saved before it failed.
+    fn annotate_failure(&self, locale: &str, error: String) -> String {
+        if !self.path.exists() {
+            return error;
+        }
+        format!(
+            "{} {}",
+            error,
+            localize_with(
+                locale,
+                Message::PartialTranscriptKept,
+                &[("path", &self.path.to_string_lossy())],
+            )
+        )
+    }
+}
+
+struct PartAudio<'a> {
+    primary: &'a JobTrack<'a>,
+    microphone: &'a JobTrack<'a>,
+    system: &'a JobTrack<'a>,
+    system_offset_ms: u64,
+}
+
+fn scaled_progress(range: (u32, u32), percent: u32) -> u32 {
+    range.0 + (range.1 - range.0) * percent / 100
+}
+
+// Runs whisper for one part of a recording and returns its segments on the
+// part's own timeline, plus whether speaker labels were applied.
+async fn transcribe_part_segments(
+    job: &WhisperJob<'_>,
+    audio: PartAudio<'_>,
+    stem: &str,
+    progress: (u32, u32),
+    warnings: &mut Vec<String>,
+) -> Result<(Vec<TimestampedSegment>, bool), String> {
+    let mut diarization_applied = false;
+    let segments = if job.speaker_mode == "source_aware_2speaker" {
+        emit_progress(
+            job.app,
+            scaled_progress(progress, 5),
+            &localize(job.locale, Message::PreparingSpeakerChannels),
+        );
+
+        if let Some(partial) = job.partial {
+            partial.begin_pass("Coach", 0);
+        }
+        let microphone_output = transcribe_with_temp_output(
+            job,
+            audio.microphone,
+            "none",
+            WhisperFileFormat::Srt,
+            &format!("{}-coach-mic", stem),
+            scaled_progress(progress, 5),
+        )
+        .await?;
+
+        emit_progress(
+            job.app,
+            scaled_progress(progress, 50),
+            &localize(job.locale, Message::TranscribingSystemAudio),
+        );
+
+        if let Some(partial) = job.partial {
+            partial.begin_pass("Client", audio.system_offset_ms);
+        }
+        let system_output = transcribe_with_temp_output(
+            job,
+            audio.system,
+            "none",
+            WhisperFileFormat::Srt,
+            &format!("{}-client-system", stem),
+            scaled_progress(progress, 50),
+        )
+        .await?;
+
+        if !microphone_output.used_sidecar || !system_output.used_sidecar {
+            warnings.push(localize(job.locale, Message::DebugWhisperFallback));
+        }
+
+        let mut microphone_segments =
+            parse_srt_segments(&microphone_output.content, "Coach", job.verbatim);
+        apply_segment_confidences(&mut microphone_segments, &microphone_output.confidences);
+        let mut system_segments =
+            parse_srt_segments(&system_output.content, "Client", job.verbatim);
+        apply_segment_confidences(&mut system_segments, &system_output.confidences);
+        if !job.verbatim {
+            system_segments = coalesce_channel_segments(system_segments, 750);
+        }
+        shift_segments(&mut system_segments, audio.system_offset_ms);
+
+        if microphone_segments.is_empty() {
+            warnings.push(localize(job.locale, Message::MicrophoneNoSegments));
+        }
+        if system_segments.is_empty() {
+            warnings.push(localize(job.locale, Message::SystemNoSegments));
+        }
+
+        emit_progress(
+            job.app,
+            scaled_progress(progress, 85),
+            &localize(job.locale, Message::MergingSpeakerTranscripts),
+        );
+        diarization_applied = true;
+        merge_source_segments(microphone_segments, system_segments, job.verbatim)
+    } else {
+        emit_progress(
+            job.app,
+            scaled_progress(progress, 5),
+            &localize(
+                job.locale,
+                if job.speaker_mode == "tdrz_2speaker" {
+                    Message::PreparingDiarization
+                } else {
+                    Message::PreparingRecording
+                },
+            ),
+        );
+
+        if let Some(partial) = job.partial {
+            partial.begin_pass("", 0);
+        }
+        let transcript_output = transcribe_with_temp_output(
+            job,
+            audio.primary,
+            job.speaker_mode,
+            WhisperFileFormat::Srt,
+            stem,
+            scaled_progress(progress, 5),
+        )
+        .await?;
+
+        if !transcript_output.used_sidecar {
+            warnings.push(localize(job.locale, Message::DebugWhisperFallback));
+        }
+
+        emit_progress(
+            job.app,
+            scaled_progress(progress, 85),
+            &localize(job.locale, Message::ReadingTranscript),
+        );
+
+        let mut segments = parse_srt_segments(&transcript_output.content, "", job.verbatim);
+        apply_segment_confidences(&mut segments, &transcript_output.confidences);
+        if job.speaker_mode == "tdrz_2speaker" {
+            let (labeled, applied) = label_tdrz_segments(segments, job.verbatim);
+            if !applied {
+                warnings.push(localize(job.locale, Message::NoSpeakerTurns));
+            }
+            diarization_applied = applied;
+            labeled
+        } else {
+            segments
+        }
+    };
+
+    Ok((segments, diarization_applied))
+}
+
+fn parse_srt_timestamp(raw: &str) -> Option<u64> {
+    let cleaned = raw.trim().replace(',', ".");
+    let parts = cleaned.split(':').collect::<Vec<&str>>();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    let hours = parts[0].parse::<u64>().ok()?;
+    let minutes = parts[1].parse::<u64>().ok()?;
+    let sec_parts = parts[2].split('.').collect::<Vec<&str>>();
+    if sec_parts.len() != 2 {
+        return None;
+    }
+
+    let seconds = sec_parts[0].parse::<u64>().ok()?;
+    let millis = match sec_parts[1].len() {
+        0 => 0,
+        1 => sec_parts[1].parse::<u64>().ok()? * 100,
+        2 => sec_parts[1].parse::<u64>().ok()? * 10,
+        _ => sec_parts[1]
+            .chars()
+            .take(3)
+            .collect::<String>()
+            .parse::<u64>()
+            .ok()?,
+    };
+
+    Some((((hours * 60) + minutes) * 60 + seconds) * 1000 + millis)
+}
+
+// With `verbatim` the segment text is kept as whisper wrote it: no blank
+// audio markers removed, no whitespace collapsed, line breaks kept.
+fn parse_srt_segments(text: &str, speaker: &str, verbatim: bool) -> Vec<TimestampedSegment> {
+    let normalized = text.replace("\r\n", "\n");
+    let mut segments = Vec::new();
+
+    for block in normalized.split("\n\n") {
+        let lines = block
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<&str>>();
+
+        if lines.len() < 2 {
+            continue;
+        }
+
+        let time_index = if lines[0].chars().all(|char| char.is_ascii_digit()) {
+            1
+        } else {
+            0
+        };
+
+        if lines.len() <= time_index {
+            continue;
+        }
+
+        let Some((start_raw, end_raw)) = lines[time_index].split_once("-->") else {
+            continue;
+        };
+
+        let Some(start_ms) = parse_srt_timestamp(start_raw) else {
+            continue;
+        };
+        let Some(end_ms) = parse_srt_timestamp(end_raw) else {
+            continue;
+        };
+
+        let text_lines = lines
+            .iter()
+            .skip(time_index + 1)
+            .copied()
+            .collect::<Vec<&str>>();
+        let body = if verbatim {
+            text_lines.join("\n")
+        } else {
+            sanitize_transcript_text(text_lines.join(" ").trim())
+        };
+
+        if body.is_empty() {
+            continue;
+        }
+
+        segments.push(TimestampedSegment {
+            speaker: speaker.to_string(),
+            start_ms,
+            end_ms,
+            text: body,
+            confidence: None,
+        });
+    }
+
+    if segments.is_empty() {
+        let fallback = if verbatim {
+            text.trim().to_string()
+        } else {
+            normalize_transcript(text)
+        };
+        if !fallback.is_empty() {
+            segments.push(TimestampedSegment {
+                speaker: speaker.to_string(),
+                start_ms: 0,
+                end_ms: 0,
+                text: fallback,
+                confidence: None,
+            });
+        }
+    }
+
+    segments
+}
+
+fn coalesce_channel_segments(
+    segments: Vec<TimestampedSegment>,
+    max_gap_ms: u64,
+) -> Vec<TimestampedSegment> {
+    let mut merged: Vec<TimestampedSegment> = Vec::new();
+
+    for segment in segments {
+        if let Some(last) = merged.last_mut() {
+            if last.speaker == segment.speaker
+                && segment.start_ms >= last.start_ms
+                && segment.start_ms.saturating_sub(last.end_ms) <= max_gap_ms
+            {
+                last.confidence = merged_confidence(last, &segment.text, segment.confidence);
+                last.end_ms = last.end_ms.max(segment.end_ms);
+                last.text = format!("{} {}", last.text, segment.text).trim().to_string();
+                continue;
+            }
+        }
+
+        merged.push(segment);
+    }
+
+    merged
+}
+
+fn shift_segments(segments: &mut [TimestampedSegment], offset_ms: u64) {
+    if offset_ms == 0 {
+        return;
+    }
+
+    for segment in segments {
+        segment.start_ms = segment.start_ms.saturating_add(offset_ms);
+        segment.end_ms = segment.end_ms.saturating_add(offset_ms);
+    }
+}
+
+fn extract_word_spans(text: &str) -> Vec<WordSpan> {
+    let mut spans = Vec::new();
+    let mut token_start: Option<usize> = None;
+
+    for (index, ch) in text.char_indices() {
+        let is_word = ch.is_ascii_alphanumeric() || ch == '\'';
+        match (token_start, is_word) {
+            (None, true) => token_start = Some(index),
+            (Some(start), false) => {
+                let token = &text[start..index];
+                let normalized = token
+                    .chars()
+                    .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '\'')
+                    .collect::<String>()
+                    .to_ascii_lowercase();
+
+                if !normalized.is_empty() {
+                    spans.push(WordSpan {
+                        start,
+                        end: index,
+                        normalized,
+                    });
+                }
+                token_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(start) = token_start {
+        let token = &text[start..];
+        let normalized = token
+            .chars()
+            .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '\'')
+            .collect::<String>()
+            .to_ascii_lowercase();
+
+        if !normalized.is_empty() {
+            spans.push(WordSpan {
+                start,
+                end: text.len(),
+                normalized,
+            });
+        }
+    }
+
+    spans
+}
+
+fn collect_word_tokens(text: &str) -> Vec<String> {
+    extract_word_spans(text)
+        .into_iter()
+        .map(|span| span.normalized)
+        .collect()
+}
+
+fn collapse_spacing(text: &str) -> String {
+    let mut output = String::new();
+    let mut previous_was_space = false;
+
+    for ch in text.chars() {
+        if ch.is_whitespace() {
+            if !previous_was_space {
+                output.push(' ');
+                previous_was_space = true;
+            }
+            continue;
+        }
+
+        if matches!(ch, '.' | ',' | '!' | '?' | ';' | ':') && output.ends_with(' ') {
+            output.pop();
+        }
+
+        output.push(ch);
+        previous_was_space = false;
+    }
+
+    output.trim().to_string()
+}
+
+fn longest_duplicate_range(
+    spans: &[WordSpan],
+    reference_tokens: &[String],
+    min_match_tokens: usize,
+) -> Option<(usize, usize)> {
+    let mut best_range: Option<(usize, usize)> = None;
+    let mut best_len = 0usize;
+
+    for start_index in 0..spans.len() {
+        for reference_start in 0..reference_tokens.len() {
+            if spans[start_index].normalized != reference_tokens[reference_start] {
+                continue;
+            }
+
+            let mut match_len = 0usize;
+            while start_index + match_len < spans.len()
+                && reference_start + match_len < reference_tokens.len()
+                && spans[start_index + match_len].normalized
+                    == reference_tokens[reference_start + match_len]
+            {
+                match_len += 1;
+            }
+
+            if match_len >= min_match_tokens && match_len > best_len {
+                best_len = match_len;
+                let start_byte = spans[start_index].start;
+                let end_byte = spans[start_index + match_len - 1].end;
+                best_range = Some((start_byte, end_byte));
+            }
+        }
+    }
+
+    best_range
+}
+
+fn segments_overlap_with_margin(
+    left_start: u64,
+    left_end: u64,
+    right_start: u64,
+    right_end: u64,
+    margin_ms: u64,
+) -> bool {
+    let left_start = left_start.saturating_sub(margin_ms);
+    let left_end = left_end.saturating_add(margin_ms);
+    let right_start = right_start.saturating_sub(margin_ms);
+    let right_end = right_end.saturating_add(margin_ms);
+
+    left_start <= right_end && right_start <= left_end
+}
+
+fn strip_reference_leakage_from_segment(
+    text: &str,
+    reference_segments: &[TimestampedSegment],
+    start_ms: u64,
+    end_ms: u64,
+) -> String {
+    let overlapping_reference = reference_segments
+        .iter()
+        .filter(|segment| {
+            segments_overlap_with_margin(start_ms, end_ms, segment.start_ms, segment.end_ms, 1200)
+        })
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<&str>>();
+
+    if overlapping_reference.is_empty() {
+        return text.trim().to_string();
+    }
+
+    let reference_tokens = collect_word_tokens(&overlapping_reference.join(" "));
+    if reference_tokens.len() < 4 {
+        return text.trim().to_string();
+    }
+
+    let mut cleaned = text.trim().to_string();
+    loop {
+        let spans = extract_word_spans(&cleaned);
+        if spans.len() < 4 {
+            break;
+        }
+
+        let Some((start_byte, end_byte)) = longest_duplicate_range(&spans, &reference_tokens, 4)
+        else {
+            break;
+        };
+
+        cleaned.replace_range(start_byte..end_byte, " ");
+        cleaned = collapse_spacing(&cleaned);
+    }
+
+    cleaned
+}
+
+// Verbatim jobs still drop the other speaker's words that leaked into the
+// microphone, but keep every segment separate instead of merging turns.
+fn merge_source_segments(
+    microphone_segments: Vec<TimestampedSegment>,
+    system_segments: Vec<TimestampedSegment>,
+    verbatim: bool,
+) -> Vec<TimestampedSegment> {
+    let mut segments = Vec::new();
+
+    for mut segment in microphone_segments {
+        let cleaned = strip_reference_leakage_from_segment(
+            &segment.text,
+            &system_segments,
+            segment.start_ms,
+            segment.end_ms,
+        );
+
+        if extract_word_spans(&cleaned).is_empty() {
+            continue;
+        }
+
+        segment.text = cleaned;
+        segments.push(segment);
+    }
+
+    segments.extend(system_segments);
+    segments.sort_by(|left, right| {
+        left.start_ms
+            .cmp(&right.start_ms)
+            .then_with(|| left.end_ms.cmp(&right.end_ms))
+            .then_with(|| left.speaker.cmp(&right.speaker))
+    });
+    if verbatim {
+        return segments;
+    }
+
+    let mut merged: Vec<TimestampedSegment> = Vec::new();
+    for segment in segments {
+        if let Some(last) = merged.last_mut() {
+            let same_speaker = last.speaker == segment.speaker;
+            let gap_ms = segment.start_ms.saturating_sub(last.end_ms);
+            if same_speaker && gap_ms <= 500 {
+                last.confidence = merged_confidence(last, &segment.text, segment.confidence);
+                last.end_ms = last.end_ms.max(segment.end_ms);
+                last.text = format!("{} {}", last.text, segment.text).trim().to_string();
+                continue;
+            }
+        }
+
+        merged.push(segment);
+    }
+
+    merged
+}
+
+fn render_segments(segments: &[TimestampedSegment]) -> String {
+    if segments.iter().all(|segment| segment.speaker.is_empty()) {
+        return segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<Vec<&str>>()
+            .join(" ");
+    }
+
+    segments
+        .iter()
+        .map(|segment| format!("{}: {}", segment.speaker, segment.text))
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+// One line per whisper segment, each with its own timestamps:
+// `[00:01:02.500 --> 00:01:05.000] Speaker A: uh, I- I think`.
+fn render_verbatim_segments(segments: &[TimestampedSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| {
+            let speaker = if segment.speaker.is_empty() {
+                String::new()
+            } else {
+                format!("{}: ", segment.speaker)
+            };
+            format!(
+                "[{} --> {}] {}{}",
+                format_precise_timestamp(segment.start_ms),
+                format_precise_timestamp(segment.end_ms),
+                speaker,
+                segment.text
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+#[derive(Clone, Copy)]
+struct TranscriptLayout {
+    verbatim: bool,
+    // Deposition-style `[HH:MM:SS]` markers at this spacing.
+    timestamp_interval_ms: Option<u64>,
+    // Applied to segment text only, for transcripts written into a note.
+    markdown_escape: MarkdownEscape,
+}
+
+// How much of whisper's text is escaped before it goes into a markdown body.
+// `standard` stops a segment from starting a heading, list, quote, table or
+// `---` divider and from opening raw HTML; `strict` also escapes inline
+// markup such as emphasis, links, code and Obsidian math.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MarkdownEscape {
+    Off,
+    #[default]
+    Standard,
+    Strict,
+}
+
+const MARKDOWN_LINE_START_CHARS: &str = "#>-+*=|`~_";
+const MARKDOWN_INLINE_CHARS: &str = "\\`*_[]<>|~$";
+
+fn escape_markdown_line(line: &str, level: MarkdownEscape) -> String {
+    let mut escaped = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    let mut at_line_start = true;
+    while let Some(char) = chars.next() {
+        if level == MarkdownEscape::Strict && MARKDOWN_INLINE_CHARS.contains(char) {
+            escaped.push('\\');
+            escaped.push(char);
+        } else if at_line_start && MARKDOWN_LINE_START_CHARS.contains(char) {
+            escaped.push('\\');
+            escaped.push(char);
+        } else if char == '<'
+            && chars.peek().map_or(false, |next| {
+                next.is_ascii_alphabetic() || "/!?".contains(*next)
+            })
+        {
+            escaped.push_str("&lt;");
+        } else {
+            escaped.push(char);
+        }
+        at_line_start = false;
+    }
+
+    // `1.` or `1)` at the start of a line would begin an ordered list.
+    let digits = escaped.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 && matches!(escaped[digits..].chars().next(), Some('.' | ')')) {
+        escaped.insert(digits, '\\');
+    }
+    escaped
+}
+
+fn escape_markdown_text(text: &str, level: MarkdownEscape) -> String {
+    if level == MarkdownEscape::Off {
+        return text.to_string();
+    }
+    text.lines()
+        .map(|line| escape_markdown_line(line.trim_start(), level))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn render_transcript(segments: &[TimestampedSegment], layout: TranscriptLayout) -> String {
+    let escaped;
+    let segments = if layout.markdown_escape == MarkdownEscape::Off {
+        segments
+    } else {
+        escaped = segments
+            .iter()
+            .map(|segment| TimestampedSegment {
+                text: escape_markdown_text(&segment.text, layout.markdown_escape),
+                ..segment.clone()
+            })
+            .collect::<Vec<TimestampedSegment>>();
+        &escaped
+    };
+    let render = |segments: &[TimestampedSegment]| {
+        if layout.verbatim {
+            render_verbatim_segments(segments)
+        } else {
+            render_segments(segments)
+        }
+    };
+    let Some(interval_ms) = layout.timestamp_interval_ms.filter(|ms| *ms > 0) else {
+        return render(segments);
+    };
+
+    // A new stretch starts at the first segment on or after each interval
+    // boundary and is marked with that boundary's time. Boundaries that fall
+    // inside one long segment or a silence get no marker of their own.
+    let mut stretches: Vec<(u64, usize)> = Vec::new();
+    for (index, segment) in segments.iter().enumerate() {
+        let boundary = segment.start_ms / interval_ms * interval_ms;
+        if stretches.last().map_or(true, |(last, _)| boundary > *last) {
+            stretches.push((boundary, index));
+        }
+    }
+    stretches
+        .iter()
+        .enumerate()
+        .map(|(position, &(boundary, start))| {
+            let end = stretches
+                .get(position + 1)
+                .map_or(segments.len(), |(_, next)| *next);
+            format!(
+                "[{}] {}",
+                format_clock_timestamp(boundary),
+                render(&segments[start..end])
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+fn format_precise_timestamp(ms: u64) -> String {
+    format!("{}.{:03}", format_clock_timestamp(ms), ms % 1000)
+}
+
+fn format_clock_timestamp(ms: u64) -> String {
+    let total_seconds = ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total_seconds / 3600,
+        (total_seconds / 60) % 60,
+        total_seconds % 60
+    )
+}
+
+fn markdown_heading_anchor(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|char| {
+            if char.is_alphanumeric() || char == '-' || char == '_' {
+                Some(char)
+            } else if char == ' ' {
+                Some('-')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn content_tokens(text: &str) -> Vec<String> {
+    collect_word_tokens(text)
+        .into_iter()
+        .filter(|token| token.len() >= 3 && !STOPWORDS.contains(&token.as_str()))
+        .collect()
+}
+
+fn trim_title_word(word: &str) -> String {
+    word.trim_matches(|char: char| !char.is_alphanumeric())
+        .to_lowercase()
+}
+
+// Picks the first sentence that says something: leading filler ("so", "um",
+// "okay") is dropped, greetings and one-word replies are skipped, and the
+// result is cut to a few words without ending on a stopword.
+fn generate_title(segments: &[TimestampedSegment]) -> Option<String> {
+    let text = segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    for sentence in text
+        .split_inclusive(['.', '?', '!'])
+        .take(TITLE_SCAN_SENTENCES)
+    {
+        let mut words = sentence
+            .split_whitespace()
+            .skip_while(|word| {
+                let normalized = trim_title_word(word);
+                normalized.is_empty() || TITLE_FILLER_WORDS.contains(&normalized.as_str())
+            })
+            .collect::<Vec<&str>>();
+        if words.len() < TITLE_MIN_WORDS
+            || content_tokens(&words.join(" ")).len() < TITLE_MIN_CONTENT_WORDS
+        {
+            continue;
+        }
+
+        words.truncate(TITLE_MAX_WORDS);
+        while words.len() > 1 {
+            let last = trim_title_word(words[words.len() - 1]);
+            if last.is_empty() || STOPWORDS.contains(&last.as_str()) {
+                words.pop();
+            } else {
+                break;
+            }
+        }
+
+        let title = words
+            .join(" ")
+            .trim_end_matches(|char: char| !char.is_alphanumeric())
+            .to_string();
+        let mut chars = title.chars();
+        if let Some(first) = chars.next() {
+            return Some(first.to_uppercase().chain(chars).collect());
+        }
+    }
+
+    None
+}
+
+// Extractive summary: the sentences densest in the session's recurring
+// content words, kept in their original order.
+fn summarize_segments(segments: &[TimestampedSegment]) -> Option<String> {
+    let text = segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    let mut frequency: HashMap<String, usize> = HashMap::new();
+    for token in collect_word_tokens(&text) {
+        if is_topic_word(&token) {
+            *frequency.entry(token).or_default() += 1;
+        }
+    }
+
+    let mut scored = text
+        .split_inclusive(['.', '?', '!'])
+        .map(str::trim)
+        .enumerate()
+        .filter_map(|(index, sentence)| {
+            let tokens = collect_word_tokens(sentence);
+            if tokens.len() < SUMMARY_MIN_WORDS || tokens.len() > SUMMARY_MAX_WORDS {
+                return None;
+            }
+            let weight = tokens
+                .iter()
+                .filter_map(|token| frequency.get(token))
+                .map(|count| count.saturating_sub(1) as f64)
+                .sum::<f64>();
+            (weight > 0.0).then(|| (index, sentence, weight / (tokens.len() as f64).sqrt()))
+        })
+        .collect::<Vec<(usize, &str, f64)>>();
+    if scored.is_empty() {
+        return None;
+    }
+
+    scored.sort_by(|left, right| {
+        right
+            .2
+            .partial_cmp(&left.2)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| left.0.cmp(&right.0))
+    });
+    scored.truncate(SUMMARY_MAX_SENTENCES);
+    scored.sort_by_key(|(index, _, _)| *index);
+
+    Some(
+        scored
+            .iter()
+            .map(|(_, sentence, _)| *sentence)
+            .collect::<Vec<&str>>()
+            .join(" "),
+    )
+}
+
+struct PullQuote<'a> {
+    speaker: &'a str,
+    start_ms: u64,
+    text: &'a str,
+}
+
+// Quotable sentences for show notes: complete statements of a tweetable
+// length from a single segment, ranked like summary sentences by how much of
+// the episode's recurring vocabulary they carry, kept in episode order.
+fn pull_quote_candidates(segments: &[TimestampedSegment]) -> Vec<PullQuote<'_>> {
+    let mut frequency: HashMap<String, usize> = HashMap::new();
+    for segment in segments {
+        for token in collect_word_tokens(&segment.text) {
+            if is_topic_word(&token) {
+                *frequency.entry(token).or_default() += 1;
+            }
+        }
+    }
+
+    let mut scored = Vec::new();
+    for segment in segments {
+        for sentence in segment.text.split_inclusive(['.', '?', '!']).map(str::trim) {
+            if !sentence.ends_with(['.', '!']) {
+                continue;
+            }
+            let first_word = sentence
+                .split_whitespace()
+                .next()
+                .map(trim_title_word)
+                .unwrap_or_default();
+            if TITLE_FILLER_WORDS.contains(&first_word.as_str()) {
+                continue;
+            }
+            let tokens = collect_word_tokens(sentence);
+            if tokens.len() < PULL_QUOTE_MIN_WORDS || tokens.len() > PULL_QUOTE_MAX_WORDS {
+                continue;
+            }
+            let weight = tokens
+                .iter()
+                .filter_map(|token| frequency.get(token))
+                .map(|count| count.saturating_sub(1) as f64)
+                .sum::<f64>();
+            if weight > 0.0 {
+                let quote = PullQuote {
+                    speaker: &segment.speaker,
+                    start_ms: segment.start_ms,
+                    text: sentence,
+                };
+                scored.push((quote, weight / (tokens.len() as f64).sqrt()));
+            }
+        }
+    }
+
+    scored.sort_by(|left, right| {
+        right
+            .1
+            .partial_cmp(&left.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| left.0.start_ms.cmp(&right.0.start_ms))
+    });
+    scored.truncate(PULL_QUOTE_LIMIT);
+    scored.sort_by_key(|(quote, _)| quote.start_ms);
+    scored.into_iter().map(|(quote, _)| quote).collect()
+}
+
+fn format_srt_timestamp(ms: u64) -> String {
+    format!("{},{:03}", format_clock_timestamp(ms), ms % 1000)
+}
+
+fn render_srt(segments: &[TimestampedSegment]) -> String {
+    segments
+        .iter()
+        .filter(|segment| segment.end_ms > segment.start_ms)
+        .enumerate()
+        .map(|(index, segment)| {
+            let text = if segment.speaker.is_empty() {
+                segment.text.clone()
+            } else {
+                format!("{}: {}", segment.speaker, segment.text)
+            };
+            format!(
+                "{}\n{} --> {}\n{}\n",
+                index + 1,
+                format_srt_timestamp(segment.start_ms),
+                format_srt_timestamp(segment.end_ms),
+                text
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Chapters use the podcast-app convention: the first starts at 00:00:00 and
+// each is titled from its own opening sentence where one stands out.
+fn render_show_notes(title: &str, segments: &[TimestampedSegment]) -> String {
+    let mut sections = vec![format!("# {}", title)];
+
+    if let Some(summary) = summarize_segments(segments) {
+        sections.push(format!("## Summary\n\n{}", summary));
+    }
+
+    let chapter_starts = detect_chapter_starts(segments);
+    let chapters = chapter_starts
+        .iter()
+        .enumerate()
+        .filter(|(_, start)| **start < segments.len())
+        .map(|(chapter_index, &start)| {
+            let end = chapter_starts
+                .get(chapter_index + 1)
+                .copied()
+                .unwrap_or(segments.len());
+            let start_ms = if chapter_index == 0 {
+                0
+            } else {
+                segments[start].start_ms
+            };
+            let heading = generate_title(&segments[start..end])
+                .unwrap_or_else(|| format!("Chapter {}", chapter_index + 1));
+            format!("- {} {}", format_clock_timestamp(start_ms), heading)
+        })
+        .collect::<Vec<String>>();
+    if !chapters.is_empty() {
+        sections.push(format!("## Chapters\n\n{}", chapters.join("\n")));
+    }
+
+    let quotes = pull_quote_candidates(segments)
+        .iter()
+        .map(|quote| {
+            let attribution = if quote.speaker.is_empty() {
+                String::new()
+            } else {
+                format!("{}, ", quote.speaker)
+            };
+            format!(
+                "> {}\n>\n> — {}{}",
+                quote.text,
+                attribution,
+                format_clock_timestamp(quote.start_ms)
+            )
+        })
+        .collect::<Vec<String>>();
+    if !quotes.is_empty() {
+        sections.push(format!("## Pull quotes\n\n{}", quotes.join("\n\n")));
+    }
+
+    format!("{}\n", sections.join("\n\n"))
+}
+
+struct ClientIndexEntry<'a> {
+    client: Option<&'a str>,
+    date: &'a str,
+    title: &'a str,
+    summary: &'a str,
+}
+
+// Appends a dated summary with a link to `transcript_path` to the client
+// folder's `_index.md`, creating the note on first use.
+fn append_client_index(transcript_path: &Path, entry: &ClientIndexEntry) -> Result<(), String> {
+    let Some(client_dir) = transcript_path.parent() else {
+        return Ok(());
+    };
+    let index_path = client_dir.join(CLIENT_INDEX_FILE);
+    let file_name = transcript_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut text = String::new();
+    if !index_path.exists() {
+        text.push_str(&format!("# {}\n", entry.client.unwrap_or("Sessions")));
+    }
+    text.push_str(&format!(
+        "\n## {} - {}\n\n{}\n\n[Transcript](<{}>)\n",
+        entry.date, entry.title, entry.summary, file_name
+    ));
+
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&index_path)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .map_err(|e| {
+            format!(
+                "Failed to update client index ({}): {}",
+                index_path.display(),
+                e
+            )
+        })
+}
+
+fn title_filename_suffix(title: &str) -> String {
+    if title == DEFAULT_TRANSCRIPT_TITLE {
+        return String::new();
+    }
+
+    let mut slug = sanitize_filename_component(title);
+    if slug.len() > TITLE_SLUG_MAX_LEN {
+        slug.truncate(TITLE_SLUG_MAX_LEN);
+        slug = slug.trim_end_matches('-').to_string();
+    }
+    if slug.is_empty() {
+        String::new()
+    } else {
+        format!("-{}", slug)
+    }
+}
+
+fn is_topic_word(token: &str) -> bool {
+    token.len() >= TOPIC_MIN_WORD_LEN
+        && !token.contains('\'')
+        && !token.chars().all(|char| char.is_ascii_digit())
+        && !STOPWORDS.contains(&token)
+}
+
+// RAKE-style extraction: runs of non-stopwords are scored by word
+// degree/frequency, and every short n-gram within a run is weighted by how
+// often it recurs.
+fn extract_topics(text: &str) -> Vec<String> {
+    let mut runs: Vec<Vec<String>> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+
+    for token in collect_word_tokens(text) {
+        if is_topic_word(&token) {
+            current.push(token);
+        } else if !current.is_empty() {
+            runs.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        runs.push(current);
+    }
+
+    let mut word_frequency: HashMap<&str, usize> = HashMap::new();
+    let mut word_degree: HashMap<&str, usize> = HashMap::new();
+    let mut phrase_counts: HashMap<String, (usize, &[String])> = HashMap::new();
+
+    for run in &runs {
+        for word in run {
+            *word_frequency.entry(word.as_str()).or_default() += 1;
+            *word_degree.entry(word.as_str()).or_default() += run.len().min(TOPIC_MAX_PHRASE_WORDS);
+        }
+        for length in 1..=TOPIC_MAX_PHRASE_WORDS.min(run.len()) {
+            for words in run.windows(length) {
+                phrase_counts.entry(words.join(" ")).or_insert((0, words)).0 += 1;
+            }
+        }
+    }
+
+    let mut scored = phrase_counts
+        .into_iter()
+        .filter(|(_, (count, _))| *count >= TOPIC_MIN_OCCURRENCES)
+        .map(|(key, (count, words))| {
+            let word_score = words
+                .iter()
+                .map(|word| {
+                    word_degree[word.as_str()] as f64 / word_frequency[word.as_str()] as f64
+                })
+                .sum::<f64>();
+            (key, word_score * count as f64)
+        })
+        .collect::<Vec<(String, f64)>>();
+
+    scored.sort_by(|left, right| {
+        right
+            .1
+            .partial_cmp(&left.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| left.0.cmp(&right.0))
+    });
+
+    let mut topics: Vec<String> = Vec::new();
+    for (phrase, _) in scored {
+        let overlaps = topics.iter().any(|existing| {
+            existing
+                .split(' ')
+                .any(|word| phrase.split(' ').any(|other| other == word))
+        });
+        if overlaps {
+            continue;
+        }
+
+        topics.push(phrase);
+        if topics.len() == TOPIC_LIMIT {
+            break;
+        }
+    }
+
+    topics
+}
+
+fn sentiment_score(text: &str) -> f64 {
+    let tokens = collect_word_tokens(text);
+    let mut total = 0i32;
+    let mut negation_remaining = 0usize;
+
+    for token in &tokens {
+        if SENTIMENT_NEGATIONS.contains(&token.as_str()) {
+            negation_remaining = SENTIMENT_NEGATION_WINDOW;
+            continue;
+        }
+
+        if let Some((_, weight)) = SENTIMENT_LEXICON
+            .iter()
+            .find(|(word, _)| *word == token.as_str())
+        {
+            total += if negation_remaining > 0 {
+                -weight
+            } else {
+                *weight
+            };
+        }
+        negation_remaining = negation_remaining.saturating_sub(1);
+    }
+
+    if total == 0 {
+        return 0.0;
+    }
+
+    let total = total as f64;
+    total / (total * total + SENTIMENT_NORMALIZATION_ALPHA).sqrt()
+}
+
+fn sentiment_label(score: f64) -> &'static str {
+    if score >= SENTIMENT_NEUTRAL_BAND {
+        "positive"
+    } else if score <= -SENTIMENT_NEUTRAL_BAND {
+        "negative"
+    } else {
+        "neutral"
+    }
+}
+
+fn round_to(value: f64, decimals: i32) -> f64 {
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+fn analyze_segment_sentiment(
+    segments: &[TimestampedSegment],
+) -> (Vec<SegmentSentiment>, SentimentSummary) {
+    let scored = segments
+        .iter()
+        .map(|segment| SegmentSentiment {
+            speaker: segment.speaker.clone(),
+            start_ms: segment.start_ms,
+            end_ms: segment.end_ms,
+            score: round_to(sentiment_score(&segment.text), 3),
+        })
+        .collect::<Vec<SegmentSentiment>>();
+
+    let weights = segments
+        .iter()
+        .map(|segment| extract_word_spans(&segment.text).len() as f64)
+        .collect::<Vec<f64>>();
+    let total_weight = weights.iter().sum::<f64>();
+    let overall = if total_weight > 0.0 {
+        scored
+            .iter()
+            .zip(&weights)
+            .map(|(segment, weight)| segment.score * weight)
+            .sum::<f64>()
+            / total_weight
+    } else {
+        0.0
+    };
+
+    let summary = SentimentSummary {
+        overall: round_to(overall, 3),
+        label: sentiment_label(overall).to_string(),
+        positive_segments: scored
+            .iter()
+            .filter(|segment| sentiment_label(segment.score) == "positive")
+            .count(),
+        negative_segments: scored
+            .iter()
+            .filter(|segment| sentiment_label(segment.score) == "negative")
+            .count(),
+    };
+
+    (scored, summary)
+}
+
+fn compute_speech_statistics(
+    segments: &[TimestampedSegment],
+    duration_seconds: u64,
+) -> SpeechStatistics {
+    let word_count = segments
+        .iter()
+        .map(|segment| extract_word_spans(&segment.text).len())
+        .sum::<usize>();
+    let question_count = segments
+        .iter()
+        .map(|segment| segment.text.matches('?').count())
+        .sum::<usize>();
+
+    let mut timed = segments
+        .iter()
+        .filter(|segment| segment.end_ms > segment.start_ms)
+        .collect::<Vec<&TimestampedSegment>>();
+    timed.sort_by_key(|segment| segment.start_ms);
+
+    let pauses = timed
+        .windows(2)
+        .map(|pair| pair[1].start_ms.saturating_sub(pair[0].end_ms))
+        .filter(|gap| *gap > 0)
+        .collect::<Vec<u64>>();
+    let average_pause_ms = if pauses.is_empty() {
+        0
+    } else {
+        pauses.iter().sum::<u64>() / pauses.len() as u64
+    };
+
+    let spoken_span_ms = match (timed.first(), timed.last()) {
+        (Some(first), Some(last)) => last.end_ms.saturating_sub(first.start_ms),
+        _ => 0,
+    };
+    let minutes = if duration_seconds > 0 {
+        duration_seconds as f64 / 60.0
+    } else {
+        spoken_span_ms as f64 / 60_000.0
+    };
+    let words_per_minute = if minutes > 0.0 {
+        round_to(word_count as f64 / minutes, 1)
+    } else {
+        0.0
+    };
+
+    SpeechStatistics {
+        word_count,
+        words_per_minute,
+        average_pause_ms,
+        question_count,
+    }
+}
+
+fn window_vocabulary(
+    segment_tokens: &[Vec<String>],
+    indices: impl Iterator<Item = usize>,
+) -> HashSet<String> {
+    let mut vocabulary = HashSet::new();
+    let mut word_count = 0usize;
+
+    for index in indices {
+        vocabulary.extend(segment_tokens[index].iter().cloned());
+        word_count += segment_tokens[index].len();
+        if word_count >= CHAPTER_WINDOW_WORDS {
+            break;
+        }
+    }
+
+    vocabulary
+}
+
+fn vocabulary_similarity(left: &HashSet<String>, right: &HashSet<String>) -> f64 {
+    if left.is_empty() || right.is_empty() {
+        return 1.0;
+    }
+
+    let shared = left.intersection(right).count();
+    let total = left.union(right).count();
+    shared as f64 / total as f64
+}
+
+// A chapter starts at a long-enough pause where the vocabulary on either side
+// stops overlapping, or at any short pause once a chapter has run too long.
+fn detect_chapter_starts(segments: &[TimestampedSegment]) -> Vec<usize> {
+    let mut starts = vec![0];
+    let (Some(first), Some(last)) = (segments.first(), segments.last()) else {
+        return starts;
+    };
+    if last.end_ms.saturating_sub(first.start_ms) < CHAPTER_MIN_TRANSCRIPT_MS {
+        return starts;
+    }
+
+    let segment_tokens = segments
+        .iter()
+        .map(|segment| content_tokens(&segment.text))
+        .collect::<Vec<Vec<String>>>();
+
+    for index in 1..segments.len() {
+        let chapter_start = *starts.last().unwrap_or(&0);
+        let elapsed_ms = segments[index]
+            .start_ms
+            .saturating_sub(segments[chapter_start].start_ms);
+        if elapsed_ms < CHAPTER_MIN_LENGTH_MS {
+            continue;
+        }
+
+        let pause_ms = segments[index]
+            .start_ms
+            .saturating_sub(segments[index - 1].end_ms);
+        if elapsed_ms >= CHAPTER_MAX_LENGTH_MS && pause_ms >= CHAPTER_FORCED_PAUSE_MS {
+            starts.push(index);
+            continue;
+        }
+        if pause_ms < CHAPTER_MIN_PAUSE_MS {
+            continue;
+        }
+
+        let before = window_vocabulary(&segment_tokens, (chapter_start..index).rev());
+        let after = window_vocabulary(&segment_tokens, index..segments.len());
+        if vocabulary_similarity(&before, &after) <= CHAPTER_MAX_SIMILARITY {
+            starts.push(index);
+        }
+    }
+
+    starts
+}
+
+fn render_chaptered_transcript(
+    segments: &[TimestampedSegment],
+    chapter_starts: &[usize],
+    layout: TranscriptLayout,
+) -> String {
+    let mut contents = vec!["## Contents".to_string(), String::new()];
+    let mut chapters = Vec::new();
+
+    for (chapter_index, &start) in chapter_starts.iter().enumerate() {
+        let end = chapter_starts
+            .get(chapter_index + 1)
+            .copied()
+            .unwrap_or(segments.len());
+        let heading = format!(
+            "Chapter {} ({})",
+            chapter_index + 1,
+            format_clock_timestamp(segments[start].start_ms)
+        );
+
+        contents.push(format!(
+            "- [{}](#{})",
+            heading,
+            markdown_heading_anchor(&heading)
+        ));
+        chapters.push(format!(
+            "## {}\n\n{}",
+            heading,
+            render_transcript(&segments[start..end], layout)
+        ));
+    }
+
+    format!("{}\n\n{}", contents.join("\n"), chapters.join("\n\n"))
+}
+
+fn sanitize_filename_component(value: &str) -> String {
+    let sanitized = value
+        .trim()
+        .chars()
+        .map(|char| {
+            if char.is_ascii_alphanumeric() {
+                char.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+
+    sanitized
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+const MAX_COLLISION_SUFFIX: u32 = 999;
+
+fn numbered_path(path: &Path, attempt: u32) -> PathBuf {
+    if attempt <= 1 {
+        return path.to_path_buf();
+    }
+
+    let stem = path
+        .file_stem()
+        .map(|value| value.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file_name = match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, attempt, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, attempt),
+    };
+    path.with_file_name(file_name)
+}
+
+// Writes to `path`, or `name-2.ext`, `name-3.ext`, ... when it already exists.
+fn write_new_file(path: &Path, contents: &[u8]) -> io::Result<PathBuf> {
+    let (path, mut file) = create_new_file(path)?;
+    file.write_all(contents)?;
+    Ok(path)
+}
+
+// Creates `path` or the first free numbered name. The file is created with
+// `create_new`, so an existing transcript is never overwritten even when two
+// jobs finish in the same second.
+fn create_new_file(path: &Path) -> io::Result<(PathBuf, fs::File)> {
+    for attempt in 1..=MAX_COLLISION_SUFFIX {
+        let candidate = numbered_path(path, attempt);
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(file) => return Ok((candidate, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "too many files with the same name",
+    ))
+}
+
+// Writes next to `path` and renames over it, so a crash or a sync client never
+// sees a half-written note.
+fn replace_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    fs::write(&temp_path, contents)
+        .and_then(|_| fs::rename(&temp_path, path))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })
+}
+
+// Replaces an existing file only when `overwrite` is set; otherwise the new
+// contents go to the first free numbered name.
+fn save_file(path: &Path, contents: &[u8], overwrite: bool) -> io::Result<PathBuf> {
+    if overwrite {
+        fs::write(path, contents)?;
+        Ok(path.to_path_buf())
+    } else {
+        write_new_file(path, contents)
+    }
+}
+
+// `save_file` for job audio; a staged upload is copied file to file.
+fn save_audio_file(path: &Path, audio: SourceAudio<'_>, overwrite: bool) -> io::Result<PathBuf> {
+    let source = match audio {
+        SourceAudio::Bytes(wav_data) => return save_file(path, wav_data, overwrite),
+        SourceAudio::Staged(source) => source,
+    };
+    if overwrite {
+        fs::copy(source, path)?;
+        return Ok(path.to_path_buf());
+    }
+    let (path, mut file) = create_new_file(path)?;
+    io::copy(&mut fs::File::open(source)?, &mut file)?;
+    Ok(path)
+}
+
+fn note_renamed_save(locale: &str, planned: &Path, saved: &Path, warnings: &mut Vec<String>) {
+    if planned != saved {
+        let planned = planned.to_string_lossy();
+        let saved = saved.to_string_lossy();
+        warnings.push(localize_with(
+            locale,
+            Message::FileExistsSavedAs,
+            &[("path", planned.as_ref()), ("saved", saved.as_ref())],
+        ));
+    }
+}
+
+// Saves the show-notes bundle for the whole recording in a
+// `<transcript>-show-notes` folder next to it, holding `show-notes.md` and
+// `<transcript>.srt`. Returns the folder.
+fn save_show_notes(
+    app: &AppHandle,
+    transcript_path: &Path,
+    part_count: usize,
+    title: &str,
+    segments: &[TimestampedSegment],
+    overwrite: bool,
+) -> Result<PathBuf, String> {
+    let transcript_stem = transcript_path
+        .file_stem()
+        .map(|value| value.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let base_name = match transcript_stem.strip_suffix("-part-1") {
+        Some(stem) if part_count > 1 => stem.to_string(),
+        _ => transcript_stem,
+    };
+    let bundle_dir = transcript_path.with_file_name(format!("{}-show-notes", base_name));
+    fs::create_dir_all(&bundle_dir).map_err(|e| {
+        format!(
+            "Failed to create show notes folder ({}): {}",
+            bundle_dir.display(),
+            e
+        )
+    })?;
+
+    let files = [
+        (
+            "show-notes.md".to_string(),
+            render_show_notes(title, segments),
+        ),
+        (format!("{}.srt", base_name), render_srt(segments)),
+    ];
+    for (file_name, contents) in files {
+        let planned_path = bundle_dir.join(file_name);
+        let path = save_file(&planned_path, contents.as_bytes(), overwrite).map_err(|e| {
+            format!(
+                "Failed to write show notes file ({}): {}",
+                planned_path.display(),
+                e
+            )
+        })?;
+        record_audit(app, AuditAction::Create, &path, None)?;
+    }
+
+    Ok(bundle_dir)
+}
+
+// Moves the staged whisper files next to the transcript (or into the raw
+// output folder), renamed after it: `recording-<ts>-part-2.srt` becomes
+// `<transcript>-whisper-part-2.srt`.
+fn save_raw_whisper_outputs(
+    settings: &AppSettings,
+    staging_dir: &Path,
+    staged_prefix: &str,
+    transcript_path: &Path,
+    part_count: usize,
+    overwrite: bool,
+) -> Result<(), String> {
+    let output_dir = match &settings.raw_output_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => transcript_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| resolve_transcript_dir(settings)),
+    };
+    fs::create_dir_all(&output_dir).map_err(|e| {
+        format!(
+            "Failed to create raw output folder ({}): {}",
+            output_dir.display(),
+            e
+        )
+    })?;
+
+    let transcript_stem = transcript_path
+        .file_stem()
+        .map(|value| value.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let base_name = match transcript_stem.strip_suffix("-part-1") {
+        Some(stem) if part_count > 1 => stem.to_string(),
+        _ => transcript_stem,
+    };
+
+    let entries = fs::read_dir(staging_dir).map_err(|e| {
+        format!(
+            "Failed to read raw output folder ({}): {}",
+            staging_dir.display(),
+            e
+        )
+    })?;
+    let mut staged_paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect::<Vec<PathBuf>>();
+    staged_paths.sort();
+
+    for staged_path in staged_paths {
+        let file_name = staged_path
+            .file_name()
+            .map(|value| value.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let rest = file_name.strip_prefix(staged_prefix).unwrap_or(&file_name);
+        let target_path = output_dir.join(format!("{}-whisper{}", base_name, rest));
+        let contents = fs::read(&staged_path).map_err(|e| {
+            format!(
+                "Failed to read raw whisper output ({}): {}",
+                staged_path.display(),
+                e
+            )
+        })?;
+        save_file(&target_path, &contents, overwrite).map_err(|e| {
+            format!(
+                "Failed to write raw whisper output ({}): {}",
+                target_path.display(),
+                e
+            )
+        })?;
+    }
+
+    Ok(())
+}
+
+fn save_raw_audio_copies(
+    settings: &AppSettings,
+    base_name: &str,
+    coachnotes_client: Option<&str>,
+    primary_audio: SourceAudio<'_>,
+    microphone_audio: SourceAudio<'_>,
+    system_audio: SourceAudio<'_>,
+    overwrite: bool,
+) -> Result<Vec<String>, String> {
+    let audio_dir = resolve_transcript_dir(settings);
+    fs::create_dir_all(&audio_dir).map_err(|e| {
+        format!(
+            "Failed to create transcript directory ({}): {}",
+            audio_dir.display(),
+            e
+        )
+    })?;
+
+    let client_suffix = coachnotes_client
+        .map(sanitize_filename_component)
+        .filter(|value| !value.is_empty())
+        .map(|value| format!("-{}", value))
+        .unwrap_or_default();
+
+    let stem = format!("{}{}", base_name, client_suffix);
+    let mut saved_paths = Vec::new();
+
+    let primary_path = audio_dir.join(format!("{}-recording.wav", stem));
+    let primary_path = save_audio_file(&primary_path, primary_audio, overwrite).map_err(|e| {
+        format!(
+            "Failed to write raw audio file ({}): {}",
+            primary_path.display(),
+            e
+        )
+    })?;
+    saved_paths.push(primary_path.to_string_lossy().to_string());
+
+    if !microphone_audio.is_empty() && !system_audio.is_empty() {
+        let microphone_path = audio_dir.join(format!("{}-coach-mic.wav", stem));
+        let microphone_path = save_audio_file(&microphone_path, microphone_audio, overwrite)
+            .map_err(|e| {
+                format!(
+                    "Failed to write microphone audio file ({}): {}",
+                    microphone_path.display(),
+                    e
+                )
+            })?;
+        saved_paths.push(microphone_path.to_string_lossy().to_string());
+
+        let system_path = audio_dir.join(format!("{}-client-system.wav", stem));
+        let system_path = save_audio_file(&system_path, system_audio, overwrite).map_err(|e| {
+            format!(
+                "Failed to write system audio file ({}): {}",
+                system_path.display(),
+                e
+            )
+        })?;
+        saved_paths.push(system_path.to_string_lossy().to_string());
+    }
+
+    Ok(saved_paths)
+}
+
+// Where each transcript segment sits in an archived recording, kept next to
+// it as `<recording>.alignment.json`. Offsets include the trim, so they index
+// the untrimmed file that was archived.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AudioAlignment {
+    audio_path: String,
+    transcript_paths: Vec<String>,
+    // Index of the first segment of each transcript part.
+    part_starts: Vec<usize>,
+    // `[start_ms, end_ms]` per segment, in transcript order.
+    segments: Vec<[u64; 2]>,
+}
+
+fn audio_alignment_path(audio_path: &Path) -> PathBuf {
+    audio_path.with_extension("alignment.json")
+}
+
+fn save_audio_alignment(alignment: &AudioAlignment, overwrite: bool) -> Result<PathBuf, String> {
+    let path = audio_alignment_path(Path::new(&alignment.audio_path));
+    let json = serde_json::to_vec(alignment)
+        .map_err(|e| format!("Failed to encode audio alignment: {}", e))?;
+    save_file(&path, &json, overwrite).map_err(|e| {
+        format!(
+            "Failed to write audio alignment ({}): {}",
+            path.display(),
+            e
+        )
+    })
+}
+
+fn read_audio_alignment(path: &Path) -> Result<AudioAlignment, String> {
+    let json = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read audio alignment ({}): {}", path.display(), e))?;
+    serde_json::from_str(&json)
+        .map_err(|e| format!("Invalid audio alignment ({}): {}", path.display(), e))
+}
+
+// Recordings are archived in the standard transcript folder whatever folder
+// the transcript went to, so a transcript's alignment is found by scanning it.
+fn find_transcript_alignment(
+    settings: &AppSettings,
+    transcript_path: &Path,
+) -> Result<AudioAlignment, String> {
+    let audio_dir = resolve_transcript_dir(settings);
+    let entries = fs::read_dir(&audio_dir).map_err(|e| {
+        format!(
+            "Failed to read transcript directory ({}): {}",
+            audio_dir.display(),
+            e
+        )
+    })?;
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.to_string_lossy().ends_with(".alignment.json"))
+        .filter_map(|path| read_audio_alignment(&path).ok())
+        .find(|alignment| {
+            alignment
+                .transcript_paths
+                .iter()
+                .any(|path| Path::new(path) == transcript_path)
+        })
+        .ok_or_else(|| {
+            format!(
+                "No archived audio alignment found for {}.",
+                transcript_path.display()
+            )
+        })
+}
+
+const WAV_FORMAT_PCM: u16 = 1;
+const WAV_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAV_FORMAT_EXTENSIBLE: u16 = 0xfffe;
+const MIN_AUDIO_DURATION_MS: u64 = 1000;
+
+#[derive(Debug, Clone, Copy)]
+struct WavInfo {
+    format_tag: u16,
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+    data_offset: usize,
+    data_len: usize,
+}
+
+impl WavInfo {
+    fn frame_bytes(self) -> usize {
+        self.channels as usize * (self.bits_per_sample as usize).div_ceil(8)
+    }
+
+    fn duration_ms(self) -> u64 {
+        let frames = self.data_len / self.frame_bytes();
+        frames as u64 * 1000 / u64::from(self.sample_rate)
+    }
+
+    // whisper.cpp reads 16 kHz 16-bit mono PCM; anything else is converted.
+    fn is_whisper_ready(self) -> bool {
+        self.format_tag == WAV_FORMAT_PCM
+            && self.channels == 1
+            && self.sample_rate == WAV_SAMPLE_RATE
+            && self.bits_per_sample == 16
+    }
+
+    fn describe(self) -> String {
+        let encoding = if self.format_tag == WAV_FORMAT_IEEE_FLOAT {
+            " float"
+        } else {
+            ""
+        };
+        format!(
+            "{} Hz {}-channel {}-bit{}",
+            self.sample_rate, self.channels, self.bits_per_sample, encoding
+        )
+    }
+}
+
+fn read_u16_le(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+// Walks the RIFF chunks for the `fmt ` and `data` chunks, so headers with
+// extra chunks (LIST, fact, ...) or an extensible format parse correctly. A
+// data size past the end of the file, as streaming writers leave it, is
+// clamped to the bytes actually present.
+fn parse_wav_header(wav_data: &[u8]) -> Result<WavInfo, String> {
+    if wav_data.get(0..4) != Some(&b"RIFF"[..]) || wav_data.get(8..12) != Some(&b"WAVE"[..]) {
+        return Err("Audio is not a WAV file (missing RIFF/WAVE header).".to_string());
+    }
+
+    let mut format = None;
+    let mut offset = 12;
+    while let (Some(chunk_id), Some(chunk_size)) = (
+        wav_data.get(offset..offset + 4),
+        read_u32_le(wav_data, offset + 4),
+    ) {
+        let chunk_start = offset + 8;
+        let chunk_size = chunk_size as usize;
+        match chunk_id {
+            b"fmt " => {
+                let chunk = wav_data
+                    .get(chunk_start..chunk_start + chunk_size)
+                    .filter(|chunk| chunk.len() >= 16)
+                    .ok_or_else(|| "WAV format chunk is truncated.".to_string())?;
+                let mut format_tag = read_u16_le(chunk, 0).unwrap_or(0);
+                if format_tag == WAV_FORMAT_EXTENSIBLE {
+                    format_tag = read_u16_le(chunk, 24).unwrap_or(format_tag);
+                }
+                format = Some((
+                    format_tag,
+                    read_u16_le(chunk, 2).unwrap_or(0),
+                    read_u32_le(chunk, 4).unwrap_or(0),
+                    read_u16_le(chunk, 14).unwrap_or(0),
+                ));
+            }
+            b"data" => {
+                let (format_tag, channels, sample_rate, bits_per_sample) =
+                    format.ok_or_else(|| {
+                        "WAV file has no format chunk before its audio data.".to_string()
+                    })?;
+                if channels == 0 || sample_rate == 0 || bits_per_sample == 0 {
+                    return Err(format!(
+                        "WAV header is invalid ({} channels, {} Hz, {}-bit).",
+                        channels, sample_rate, bits_per_sample
+                    ));
+                }
+                let data_offset = chunk_start.min(wav_data.len());
+                return Ok(WavInfo {
+                    format_tag,
+                    channels,
+                    sample_rate,
+                    bits_per_sample,
+                    data_offset,
+                    data_len: chunk_size.min(wav_data.len() - data_offset),
+                });
+            }
+            _ => {}
+        }
+        offset = chunk_start + chunk_size + chunk_size % 2;
+    }
+
+    Err("WAV file has no audio data chunk.".to_string())
+}
+
+// The sample bytes of a WAV, or nothing when the header does not parse.
+fn wav_pcm_data(wav_data: &[u8]) -> &[u8] {
+    match parse_wav_header(wav_data) {
+        Ok(info) => &wav_data[info.data_offset..info.data_offset + info.data_len],
+        Err(_) => &[],
+    }
+}
+
+// Sample reader for the encodings the converter handles, scaled to -1..1.
+fn sample_decoder(info: WavInfo) -> Option<fn(&[u8]) -> f32> {
+    let decode: fn(&[u8]) -> f32 = match (info.format_tag, info.bits_per_sample) {
+        (WAV_FORMAT_PCM, 8) => |bytes| (f32::from(bytes[0]) - 128.0) / 128.0,
+        (WAV_FORMAT_PCM, 16) => {
+            |bytes| f32::from(i16::from_le_bytes([bytes[0], bytes[1]])) / 32_768.0
+        }
+        (WAV_FORMAT_PCM, 24) => |bytes| {
+            (i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8) as f32 / 8_388_608.0
+        },
+        (WAV_FORMAT_PCM, 32) => |bytes| {
+            i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32 / 2_147_483_648.0
+        },
+        (WAV_FORMAT_IEEE_FLOAT, 32) => {
+            |bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+        }
+        (WAV_FORMAT_IEEE_FLOAT, 64) => |bytes| {
+            bytes
+                .try_into()
+                .map_or(0.0, |bytes| f64::from_le_bytes(bytes) as f32)
+        },
+        _ => return None,
+    };
+    Some(decode)
+}
+
+// Mono samples in -1..1, with every channel averaged into one.
+fn decode_wav_mono(wav_data: &[u8], info: WavInfo) -> Result<Vec<f32>, String> {
+    let decode = sample_decoder(info).ok_or_else(|| {
+        format!(
+            "Unsupported WAV encoding ({}, format {}).",
+            info.describe(),
+            info.format_tag
+        )
+    })?;
+
+    let sample_bytes = (info.bits_per_sample as usize).div_ceil(8);
+    Ok(wav_pcm_data(wav_data)
+        .chunks_exact(info.frame_bytes())
+        .map(|frame| {
+            let sum = frame
+                .chunks_exact(sample_bytes)
+                .map(decode)
+                .filter(|sample| sample.is_finite())
+                .sum::<f32>();
+            (sum / f32::from(info.channels)).clamp(-1.0, 1.0)
+        })
+        .collect())
+}
+
+// Same approach as the recorder: averaging windows when downsampling,
+// linear interpolation when upsampling.
+fn resample_samples(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let ratio = f64::from(from_rate) / f64::from(to_rate);
+    let output_len = ((samples.len() as f64 / ratio).round() as usize).max(1);
+    if from_rate > to_rate {
+        (0..output_len)
+            .map(|index| {
+                let start = ((index as f64 * ratio).round() as usize).min(samples.len());
+                let end = (((index + 1) as f64 * ratio).round() as usize).min(samples.len());
+                let window = &samples[start..end];
+                if window.is_empty() {
+                    0.0
+                } else {
+                    window.iter().sum::<f32>() / window.len() as f32
+                }
+            })
+            .collect()
+    } else {
+        (0..output_len)
+            .map(|index| {
+                let position = index as f64 * ratio;
+                let lower = (position as usize).min(samples.len() - 1);
+                let upper = (lower + 1).min(samples.len() - 1);
+                let fraction = (position - lower as f64) as f32;
+                samples[lower] * (1.0 - fraction) + samples[upper] * fraction
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum AudioTrack {
+    Recording,
+    Microphone,
+    SystemAudio,
+}
+
+impl AudioTrack {
+    fn label(self, locale: &str) -> String {
+        localize(
+            locale,
+            match self {
+                Self::Recording => Message::RecordingTrack,
+                Self::Microphone => Message::MicrophoneTrack,
+                Self::SystemAudio => Message::SystemAudioTrack,
+            },
+        )
+    }
+}
+
+// Checked before any conversion, so a bad upload fails with a specific code
+// the frontend can match (`[audio_not_wav] ...`) instead of whisper failing
+// on it later. Only the primary track has to reach the minimum length.
+fn validate_wav(
+    wav_data: &[u8],
+    track: AudioTrack,
+    min_duration_ms: u64,
+    locale: &str,
+) -> Result<WavInfo, String> {
+    check_wav_header(wav_data, wav_data.len(), track, min_duration_ms, locale)
+}
+
+// `validate_wav` for a file on disk. Only the start of the file is read; the
+// length of the audio data comes from the file size.
+fn validate_wav_file(
+    path: &Path,
+    track: AudioTrack,
+    min_duration_ms: u64,
+    locale: &str,
+) -> Result<WavInfo, String> {
+    let read_error =
+        |e: io::Error| format!("Failed to read audio file ({}): {}", path.display(), e);
+    let file = fs::File::open(path).map_err(read_error)?;
+    let file_len = file.metadata().map_err(read_error)?.len() as usize;
+    let mut header = Vec::new();
+    file.take(WAV_HEADER_PROBE_BYTES as u64)
+        .read_to_end(&mut header)
+        .map_err(read_error)?;
+    check_wav_header(&header, file_len, track, min_duration_ms, locale)
+}
+
+// `wav_data` is the whole WAV, or its first bytes when `total_len` is larger.
+fn check_wav_header(
+    wav_data: &[u8],
+    total_len: usize,
+    track: AudioTrack,
+    min_duration_ms: u64,
+    locale: &str,
+) -> Result<WavInfo, String> {
+    let label = track.label(locale);
+    let fail = |code: &str, message: Message, args: &[(&str, &str)]| {
+        let args = [("track", label.as_str())]
+            .into_iter()
+            .chain(args.iter().copied())
+            .collect::<Vec<(&str, &str)>>();
+        format!("[{}] {}", code, localize_with(locale, message, &args))
+    };
+
+    if wav_data.get(0..4) != Some(&b"RIFF"[..]) || wav_data.get(8..12) != Some(&b"WAVE"[..]) {
+        return Err(fail("audio_not_wav", Message::AudioNotWav, &[]));
+    }
+    let mut info = parse_wav_header(wav_data).map_err(|error| {
+        fail(
+            "audio_malformed",
+            Message::AudioHeaderDamaged,
+            &[("detail", &error)],
+        )
+    })?;
+    if total_len > wav_data.len() {
+        let declared_len = read_u32_le(wav_data, info.data_offset - 4).unwrap_or(0) as usize;
+        info.data_len = declared_len.min(total_len - info.data_offset);
+    }
+    if info.data_len < info.frame_bytes() {
+        return Err(fail("audio_empty", Message::AudioDataEmpty, &[]));
+    }
+    if sample_decoder(info).is_none() {
+        return Err(fail(
+            "audio_unsupported_codec",
+            Message::AudioCodecUnsupported,
+            &[(
+                "format",
+                &format!("{}, format {}", info.describe(), info.format_tag),
+            )],
+        ));
+    }
+    let duration_ms = info.duration_ms();
+    if duration_ms < min_duration_ms {
+        return Err(fail(
+            "audio_too_short",
+            Message::AudioTooShort,
+            &[("seconds", &format!("{:.1}", duration_ms as f64 / 1000.0))],
+        ));
+    }
+    Ok(info)
+}
+
+// Converts a track to what whisper expects, with a warning naming the source
+// format. Tracks that are already 16 kHz 16-bit mono pass through untouched.
+fn convert_for_whisper<'a>(
+    wav_data: &'a [u8],
+    info: WavInfo,
+    locale: &str,
+) -> Result<(Cow<'a, [u8]>, Option<String>), String> {
+    if info.is_whisper_ready() {
+        return Ok((Cow::Borrowed(wav_data), None));
+    }
+
+    let samples = resample_samples(
+        &decode_wav_mono(wav_data, info)?,
+        info.sample_rate,
+        WAV_SAMPLE_RATE,
+    )
+    .into_iter()
+    .map(|sample| (sample * 32_767.0).round() as i16)
+    .collect::<Vec<i16>>();
+    let warning = localize_with(
+        locale,
+        Message::ConvertedAudioFormat,
+        &[("format", &info.describe())],
+    );
+    Ok((Cow::Owned(encode_samples_wav(&samples)), Some(warning)))
+}
+
+fn estimate_duration_ms(wav_data: &[u8]) -> u64 {
+    parse_wav_header(wav_data).map_or(0, WavInfo::duration_ms)
+}
+
+fn ms_to_pcm_offset(ms: u64) -> usize {
+    (ms as usize * WAV_SAMPLE_RATE as usize / 1000) * WAV_BYTES_PER_SAMPLE
+}
+
+fn encode_pcm16_wav(pcm: &[u8]) -> Vec<u8> {
+    let byte_rate = WAV_SAMPLE_RATE * WAV_BYTES_PER_SAMPLE as u32;
+    let data_len = pcm.len() as u32;
+    let mut wav = Vec::with_capacity(WAV_HEADER_BYTES + pcm.len());
+
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&WAV_SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&(WAV_BYTES_PER_SAMPLE as u16).to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.extend_from_slice(pcm);
+    wav
+}
+
+// Canonical 44-byte header for `data_len` bytes in `info`'s encoding.
+fn encode_wav_header(info: WavInfo, data_len: u32) -> Vec<u8> {
+    let block_align = info.frame_bytes() as u16;
+    let byte_rate = info.sample_rate * u32::from(block_align);
+    let mut wav = Vec::with_capacity(WAV_HEADER_BYTES + data_len as usize);
+
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&info.format_tag.to_le_bytes());
+    wav.extend_from_slice(&info.channels.to_le_bytes());
+    wav.extend_from_slice(&info.sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&info.bits_per_sample.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav
+}
+
+fn trim_wav(wav_data: &[u8], trim: AudioTrim) -> Cow<'_, [u8]> {
+    let pcm = wav_pcm_data(wav_data);
+    if !trim.is_active() || pcm.is_empty() {
+        return Cow::Borrowed(wav_data);
+    }
+
+    let start = ms_to_pcm_offset(trim.start_ms).min(pcm.len());
+    let end = trim
+        .end_ms
+        .map(ms_to_pcm_offset)
+        .unwrap_or(pcm.len())
+        .clamp(start, pcm.len());
+
+    if start == end {
+        return Cow::Owned(Vec::new());
+    }
+
+    Cow::Owned(encode_pcm16_wav(&pcm[start..end]))
+}
+
+fn wav_pcm_samples(wav_data: &[u8]) -> Vec<i16> {
+    wav_pcm_data(wav_data)
+        .chunks_exact(WAV_BYTES_PER_SAMPLE)
+        .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+        .collect()
+}
+
+const MAX_WAVEFORM_BUCKETS: u32 = 10_000;
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum WaveformSource {
+    Path(String),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Debug, Serialize)]
+pub struct WaveformPeak {
+    min: f32,
+    max: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Waveform {
+    duration_ms: u64,
+    peaks: Vec<WaveformPeak>,
+}
+
+// Min/max sample per bucket, scaled to -1..1. Short recordings get fewer
+// buckets than requested so no bucket is empty.
+fn waveform_peaks(samples: &[i16], buckets: usize) -> Vec<WaveformPeak> {
+    let buckets = buckets.min(samples.len());
+    (0..buckets)
+        .map(|index| {
+            let start = index * samples.len() / buckets;
+            let end = (index + 1) * samples.len() / buckets;
+            let bucket = &samples[start..end];
+            let min = bucket.iter().copied().min().unwrap_or(0);
+            let max = bucket.iter().copied().max().unwrap_or(0);
+            WaveformPeak {
+                min: f32::from(min) / 32_768.0,
+                max: f32::from(max) / 32_768.0,
+            }
+        })
+        .collect()
+}
+
+fn encode_samples_wav(samples: &[i16]) -> Vec<u8> {
+    let pcm = samples
+        .iter()
+        .flat_map(|sample| sample.to_le_bytes())
+        .collect::<Vec<u8>>();
+    encode_pcm16_wav(&pcm)
+}
+
+// RNNoise expects 48 kHz frames, so the 16 kHz recording is upsampled by
+// linear interpolation, denoised, then averaged back down.
+fn denoise_samples(samples: &[i16]) -> Vec<i16> {
+    let frame_size = nnnoiseless::DenoiseState::FRAME_SIZE;
+    let mut upsampled = Vec::with_capacity(samples.len() * DENOISE_UPSAMPLE_FACTOR + frame_size);
+
+    for (index, &sample) in samples.iter().enumerate() {
+        let current = sample as f32;
+        let next = samples
+            .get(index + 1)
+            .map(|&next| next as f32)
+            .unwrap_or(current);
+        for step in 0..DENOISE_UPSAMPLE_FACTOR {
+            let fraction = step as f32 / DENOISE_UPSAMPLE_FACTOR as f32;
+            upsampled.push(current + (next - current) * fraction);
+        }
+    }
+
+    let upsampled_len = upsampled.len();
+    upsampled.resize(upsampled_len.div_ceil(frame_size) * frame_size, 0.0);
+
+    let mut state = nnnoiseless::DenoiseState::new();
+    let mut denoised = vec![0.0f32; upsampled.len()];
+    for (input, output) in upsampled
+        .chunks_exact(frame_size)
+        .zip(denoised.chunks_exact_mut(frame_size))
+    {
+        state.process_frame(output, input);
+    }
+    denoised.truncate(upsampled_len);
+
+    denoised
+        .chunks(DENOISE_UPSAMPLE_FACTOR)
+        .map(|chunk| {
+            let average = chunk.iter().sum::<f32>() / chunk.len() as f32;
+            average.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16
+        })
+        .collect()
+}
+
+// Peak normalization to roughly -1 dBFS; the gain cap keeps near-silent
+// recordings from turning into amplified hiss.
+fn normalize_peak_samples(samples: &mut [i16]) {
+    let peak = samples
+        .iter()
+        .map(|sample| sample.unsigned_abs())
+        .max()
+        .unwrap_or(0);
+    if peak == 0 {
+        return;
+    }
+
+    let gain = (NORMALIZE_TARGET_PEAK * i16::MAX as f32 / peak as f32).min(NORMALIZE_MAX_GAIN);
+    if gain <= 1.0 {
+        return;
+    }
+
+    for sample in samples.iter_mut() {
+        *sample = (*sample as f32 * gain)
+            .round()
+            .clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+    }
+}
+
+fn yaml_quote(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+fn yaml_unquote(value: &str) -> String {
+    let value = value.trim();
+    let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        return value.to_string();
+    };
+
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            unquoted.push(char);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unquoted.push('\n'),
+            Some(escaped) => unquoted.push(escaped),
+            None => unquoted.push('\\'),
+        }
+    }
+    unquoted
+}
+
+// Splits a saved transcript into its frontmatter lines and the markdown body.
+// Files without frontmatter return an empty frontmatter.
+fn split_frontmatter(markdown: &str) -> (&str, &str) {
+    let Some(rest) = markdown.strip_prefix("---\n") else {
+        return ("", markdown);
+    };
+    match rest.find("\n---\n") {
+        Some(end) => (&rest[..end], &rest[end + 5..]),
+        None => ("", markdown),
+    }
+}
+
+// Frontmatter key holding the sha256 of the body as saved, so a transcript
+// edited afterwards or damaged by a sync can be told apart from the original.
+const BODY_HASH_KEY: &str = "body_sha256";
+
+fn body_sha256(body: &str) -> String {
+    format!("{:x}", Sha256::digest(body.as_bytes()))
+}
+
+// Sets the body hash in the frontmatter, replacing an earlier one. Used when
+// Echo Scribe writes a new note; notes without frontmatter are left as they
+// are.
+fn with_body_hash(markdown: &str) -> Result<String, String> {
+    let body = split_frontmatter(markdown).1;
+    with_frontmatter_value(markdown, BODY_HASH_KEY, &body_sha256(body))
+}
+
+// `valid`, `modified` (the body no longer matches its hash), or `unsigned`
+// (no hash, e.g. saved before hashes were added).
+fn body_hash_status(markdown: &str) -> &'static str {
+    let (frontmatter, body) = split_frontmatter(markdown);
+    match frontmatter_value(frontmatter, BODY_HASH_KEY) {
+        None => "unsigned",
+        Some(expected) if expected.eq_ignore_ascii_case(&body_sha256(body)) => "valid",
+        Some(_) => "modified",
+    }
+}
+
+// Re-hashes a saved note Echo Scribe changed, but only when it still matched
+// its hash before the change. A note edited or damaged elsewhere keeps its
+// old hash, so `verify_transcript` goes on reporting it as modified instead of
+// the change signing over it.
+fn with_updated_body_hash(original: &str, updated: &str) -> Result<String, String> {
+    match body_hash_status(original) {
+        "valid" => with_body_hash(updated),
+        _ => Ok(updated.to_string()),
+    }
+}
+
+// What to do when a new transcript matches one saved by a recent job: `warn`
+// (the default) saves it with a warning, `skip` does not save it, `off` does
+// not look.
+const DUPLICATE_CHECK_MODES: [&str; 3] = ["off", "warn", "skip"];
+const DUPLICATE_RECENT_JOBS: usize = 50;
+// Share of a new transcript's word trigrams found in a saved one from which
+// the two count as the same recording. The saved body may also hold notes and
+// links, so it can be up to `DUPLICATE_MAX_GROWTH` times larger.
+const DUPLICATE_SIMILARITY: f64 = 0.9;
+const DUPLICATE_MAX_GROWTH: f64 = 1.25;
+// Short transcripts ("Thank you.") match too easily to say anything.
+const DUPLICATE_MIN_WORDS: usize = 20;
+
+fn default_duplicate_check() -> String {
+    "warn".to_string()
+}
+
+fn validate_duplicate_check(mode: &str) -> &'static str {
+    DUPLICATE_CHECK_MODES
+        .iter()
+        .copied()
+        .find(|value| *value == mode.trim())
+        .unwrap_or("warn")
+}
+
+// Hash of the transcript text with whitespace collapsed, so a transcript
+// rendered again with other line breaks still matches.
+fn transcript_sha256(transcript: &str) -> String {
+    body_sha256(
+        &transcript
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" "),
+    )
+}
+
+fn word_trigrams(text: &str) -> HashSet<String> {
+    let words = text
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|char: char| !char.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<String>>();
+    words.windows(3).map(|window| window.join(" ")).collect()
+}
+
+fn is_near_duplicate(new: &HashSet<String>, saved: &HashSet<String>) -> bool {
+    if new.is_empty() || saved.len() as f64 > new.len() as f64 * DUPLICATE_MAX_GROWTH {
+        return false;
+    }
+    new.intersection(saved).count() as f64 / new.len() as f64 >= DUPLICATE_SIMILARITY
+}
+
+// A transcript saved by one of the last `DUPLICATE_RECENT_JOBS` jobs with the
+// same text, or with nearly all of its word trigrams in common.
+fn find_duplicate_transcript(app: &AppHandle, transcript: &str) -> Option<PathBuf> {
+    if transcript.split_whitespace().count() < DUPLICATE_MIN_WORDS {
+        return None;
+    }
+    let history = load_job_history(app).ok()?;
+    let recent = history
+        .iter()
+        .rev()
+        .filter(|record| record.outcome == "finished")
+        .filter_map(|record| Some((record, PathBuf::from(record.saved_path.as_ref()?))))
+        .filter(|(_, path)| path.exists())
+        .take(DUPLICATE_RECENT_JOBS)
+        .collect::<Vec<(&JobRecord, PathBuf)>>();
+
+    let hash = transcript_sha256(transcript);
+    if let Some((_, path)) = recent
+        .iter()
+        .find(|(record, _)| record.transcript_sha256.as_deref() == Some(hash.as_str()))
+    {
+        return Some(path.clone());
+    }
+    let trigrams = word_trigrams(transcript);
+    recent.into_iter().map(|(_, path)| path).find(|path| {
+        fs::read_to_string(path).is_ok_and(|markdown| {
+            is_near_duplicate(&trigrams, &word_trigrams(split_frontmatter(&markdown).1))
+        })
+    })
+}
+
+// Sets a top-level frontmatter key last, replacing an earlier one along with
+// any indented or list lines that continued it. Notes without frontmatter are
+// left as they are.
+fn with_frontmatter_value(markdown: &str, key: &str, value: &str) -> Result<String, String> {
+    let (frontmatter, body) = split_frontmatter(markdown);
+    if frontmatter.is_empty() {
+        return Ok(markdown.to_string());
+    }
+    let new_field = to_frontmatter_yaml(&BTreeMap::from([(key, value)]))?;
+    let mut replacing = false;
+    let lines = frontmatter
+        .lines()
+        .filter(|line| {
+            if line
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.starts_with(':'))
+            {
+                replacing = true;
+                return false;
+            }
+            replacing &= line.starts_with([' ', '\t']) || line.starts_with("- ");
+            !replacing
+        })
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+    Ok(format!("---\n{}{}---\n{}", lines, new_field, body))
+}
+
+// A YAML scalar as a plain string. Lists and mappings have no single value.
+fn yaml_scalar_string(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(value) => Some(value.clone()),
+        serde_yaml::Value::Number(value) => Some(value.to_string()),
+        serde_yaml::Value::Bool(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+// The readers below parse the frontmatter as YAML so any quoting style or
+// block scalar reads back. Frontmatter that is not valid YAML, such as a
+// hand-edited note, falls back to a line scan of Echo Scribe's own layout.
+fn frontmatter_value(frontmatter: &str, key: &str) -> Option<String> {
+    let value = match serde_yaml::from_str::<serde_yaml::Mapping>(frontmatter) {
+        Ok(mapping) => mapping.get(key).and_then(yaml_scalar_string),
+        Err(_) => frontmatter.lines().find_map(|line| {
+            let value = line.strip_prefix(key)?.strip_prefix(':')?;
+            Some(yaml_unquote(value))
+        }),
+    };
+    value.filter(|value| !value.is_empty())
+}
+
+fn frontmatter_list(frontmatter: &str, key: &str) -> Vec<String> {
+    if let Ok(mapping) = serde_yaml::from_str::<serde_yaml::Mapping>(frontmatter) {
+        return match mapping.get(key) {
+            Some(serde_yaml::Value::Sequence(values)) => {
+                values.iter().filter_map(yaml_scalar_string).collect()
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    let header = format!("{}:", key);
+    frontmatter
+        .lines()
+        .skip_while(|line| line.trim_end() != header)
+        .skip(1)
+        .map_while(|line| {
+            line.strip_prefix("  - ")
+                .or_else(|| line.strip_prefix("- "))
+        })
+        .map(yaml_unquote)
+        .collect()
+}
+
+// Reads `key` from an indented block such as `speech_stats:`.
+fn frontmatter_nested_value(frontmatter: &str, parent: &str, key: &str) -> Option<String> {
+    if let Ok(mapping) = serde_yaml::from_str::<serde_yaml::Mapping>(frontmatter) {
+        return mapping
+            .get(parent)
+            .and_then(|parent| parent.get(key))
+            .and_then(yaml_scalar_string)
+            .filter(|value| !value.is_empty());
+    }
+
+    let header = format!("{}:", parent);
+    frontmatter
+        .lines()
+        .skip_while(|line| line.trim_end() != header)
+        .skip(1)
+        .take_while(|line| line.starts_with("  "))
+        .find_map(|line| {
+            let value = line.trim_start().strip_prefix(key)?.strip_prefix(':')?;
+            Some(yaml_unquote(value)).filter(|value| !value.is_empty())
+        })
+}
+
+const DEFAULT_SPEAKER_LABELS: [&str; 4] = ["Coach", "Client", "Speaker A", "Speaker B"];
+
+// Groups `Speaker: text` paragraphs of a transcript body by speaker, in the
+// order speakers first appear. The notes section is not part of the transcript.
+fn collect_speaker_paragraphs(body: &str, speakers: &[String]) -> Vec<(String, Vec<String>)> {
+    let transcript = body.split("\n## Notes\n").next().unwrap_or(body);
+    let mut grouped: Vec<(String, Vec<String>)> = Vec::new();
+
+    for paragraph in transcript.split("\n\n").map(str::trim) {
+        let Some((speaker, text)) = speakers.iter().find_map(|speaker| {
+            paragraph
+                .strip_prefix(speaker.as_str())
+                .and_then(|rest| rest.strip_prefix(": "))
+                .map(|text| (speaker, text.trim()))
+        }) else {
+            continue;
+        };
+        if text.is_empty() {
+            continue;
+        }
+
+        match grouped.iter_mut().find(|(name, _)| name == speaker) {
+            Some((_, paragraphs)) => paragraphs.push(text.to_string()),
+            None => grouped.push((speaker.clone(), vec![text.to_string()])),
+        }
+    }
+
+    grouped
+}
+
+const ANONYMIZED_CLIENT_PLACEHOLDER: &str = "[client]";
+
+// Replaces `client` in every string of a frontmatter value, including lists
+// and nested blocks.
+fn mask_yaml_strings(value: &mut serde_yaml::Value, client: &str) {
+    match value {
+        serde_yaml::Value::String(text) => {
+            *text = text.replace(client, ANONYMIZED_CLIENT_PLACEHOLDER);
+        }
+        serde_yaml::Value::Sequence(values) => {
+            for value in values {
+                mask_yaml_strings(value, client);
+            }
+        }
+        serde_yaml::Value::Mapping(mapping) => {
+            for (_, value) in mapping.iter_mut() {
+                mask_yaml_strings(value, client);
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => mask_yaml_strings(&mut tagged.value, client),
+        _ => {}
+    }
+}
+
+// Replaces speaker labels with P1, P2, ... (frontmatter speaker order first,
+// then order of appearance), drops the client and notes, and masks the client
+// name wherever it appears in the other frontmatter fields or the transcript.
+fn anonymize_transcript(markdown: &str) -> Result<String, String> {
+    let (frontmatter, body) = split_frontmatter(markdown);
+    let mut fields = parse_frontmatter_mapping(frontmatter)?;
+    let client = frontmatter_value(frontmatter, "client");
+
+    let mut speakers = frontmatter_list(frontmatter, "speakers");
+    for label in DEFAULT_SPEAKER_LABELS {
+        if !speakers.iter().any(|speaker| speaker == label) {
+            speakers.push(label.to_string());
+        }
+    }
+    let mask_client = |text: &str| match client.as_deref() {
+        Some(client) => text.replace(client, ANONYMIZED_CLIENT_PLACEHOLDER),
+        None => text.to_string(),
+    };
+
+    let transcript = body.split("\n## Notes\n").next().unwrap_or(body);
+    let mut identifiers: Vec<String> = frontmatter_list(frontmatter, "speakers");
+    let paragraphs = transcript
+        .split("\n\n")
+        .map(|paragraph| {
+            let labelled = speakers.iter().find_map(|speaker| {
+                paragraph
+                    .strip_prefix(speaker.as_str())
+                    .and_then(|rest| rest.strip_prefix(": "))
+                    .map(|text| (speaker, text))
+            });
+            let Some((speaker, text)) = labelled else {
+                return mask_client(paragraph);
+            };
+            let index = match identifiers.iter().position(|name| name == speaker) {
+                Some(index) => index,
+                None => {
+                    identifiers.push(speaker.clone());
+                    identifiers.len() - 1
+                }
+            };
+            format!("P{}: {}", index + 1, mask_client(text))
+        })
+        .collect::<Vec<String>>();
+
+    fields.remove("client");
+    fields.remove("notes");
+    if let Some(speakers) = fields.get_mut("speakers") {
+        let count = frontmatter_list(frontmatter, "speakers").len();
+        *speakers = serde_yaml::Value::Sequence(
+            (1..=count)
+                .map(|index| serde_yaml::Value::String(format!("P{}", index)))
+                .collect(),
+        );
+    }
+    if let Some(client) = client.as_deref() {
+        for (_, value) in fields.iter_mut() {
+            mask_yaml_strings(value, client);
+        }
+    }
+    fields.insert("anonymized".into(), true.into());
+
+    Ok(format!(
+        "---\n{}---\n{}\n",
+        to_frontmatter_yaml(&fields)?,
+        paragraphs.join("\n\n").trim_end()
+    ))
+}
+
+fn normalize_transcript(text: &str) -> String {
+    sanitize_transcript_text(
+        &text
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<&str>>()
+            .join("\n")
+            .trim()
+            .to_string(),
+    )
+}
+
+fn sanitize_transcript_text(text: &str) -> String {
+    text.replace(BLANK_AUDIO_MARKER, "")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .trim()
+        .to_string()
+}
+
+// The system word list (macOS ships one). Only lowercase entries are kept so
+// proper names still count as unknown words.
+const SPELL_WORDLIST_PATH: &str = "/usr/share/dict/words";
+const MAX_SPELL_DICTIONARY_TERMS: usize = 2000;
+const MAX_SPELL_TERM_CHARS: usize = 80;
+// Endings tried when a word is not in the word list as written.
+const SPELL_WORD_SUFFIXES: [&str; 5] = ["ing", "ed", "es", "s", "ly"];
+
+struct SpellTerm {
+    text: String,
+    // Lowercase letters and digits only, so spacing and hyphens do not count
+    // as edits.
+    key: Vec<char>,
+    words: usize,
+}
+
+fn spell_wordlist() -> &'static HashSet<String> {
+    static WORDLIST: OnceLock<HashSet<String>> = OnceLock::new();
+    WORDLIST.get_or_init(|| {
+        fs::read_to_string(SPELL_WORDLIST_PATH)
+            .map(|text| {
+                text.lines()
+                    .map(str::trim)
+                    .filter(|word| word.starts_with(|char: char| char.is_lowercase()))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    })
+}
+
+fn is_known_word(wordlist: &HashSet<String>, word: &str) -> bool {
+    let word = word.to_lowercase();
+    wordlist.contains(&word)
+        || SPELL_WORD_SUFFIXES.iter().any(|suffix| {
+            word.strip_suffix(suffix)
+                .is_some_and(|stem| stem.len() > 2 && wordlist.contains(stem))
+        })
+}
+
+fn normalize_spell_dictionary(terms: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    terms
+        .iter()
+        .map(|term| term.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .filter(|term| !term.is_empty() && term.chars().count() <= MAX_SPELL_TERM_CHARS)
+        .filter(|term| seen.insert(term.clone()))
+        .collect()
+}
+
+fn spell_key(text: &str) -> Vec<char> {
+    text.chars()
+        .filter(|char| char.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+// Edits allowed between a heard word and a dictionary term of this many
+// letters. Very short terms only get their casing fixed.
+fn max_spell_edits(chars: usize) -> usize {
+    match chars {
+        0..=3 => 0,
+        4..=6 => 1,
+        7..=10 => 2,
+        _ => 3,
+    }
+}
+
+// Splits a token into leading punctuation, the word, and trailing punctuation
+// including a possessive `'s`.
+fn split_word_token(token: &str) -> (&str, &str, &str) {
+    let start = token.len()
+        - token
+            .trim_start_matches(|char: char| !char.is_alphanumeric())
+            .len();
+    let end = token
+        .trim_end_matches(|char: char| !char.is_alphanumeric())
+        .len();
+    if start >= end {
+        return (token, "", "");
+    }
+    let word = &token[start..end];
+    let word = word
+        .strip_suffix("'s")
+        .or_else(|| word.strip_suffix("\u{2019}s"))
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or(word);
+    (&token[..start], word, &token[start + word.len()..])
+}
+
+// The dictionary term the tokens at the start of `tokens` most likely stand
+// for, and how many tokens it replaces. A term may have been heard as one more
+// word than it has ("Kuber netties"). Windows made only of known words, or
+// with punctuation inside, are left alone, as are ones that already match.
+fn best_spell_match<'a>(
+    tokens: &[&str],
+    terms: &'a [SpellTerm],
+    wordlist: &HashSet<String>,
+) -> Option<(&'a SpellTerm, usize)> {
+    let mut best: Option<(&SpellTerm, usize, usize)> = None;
+    for term in terms {
+        for width in [term.words, term.words + 1] {
+            let Some(window) = tokens.get(..width) else {
+                continue;
+            };
+            let parts = window
+                .iter()
+                .map(|token| split_word_token(token))
+                .collect::<Vec<(&str, &str, &str)>>();
+            let spans_punctuation =
+                parts
+                    .iter()
+                    .enumerate()
+                    .any(|(index, (leading, word, trailing))| {
+                        word.is_empty()
+                            || (index > 0 && !leading.is_empty())
+                            || (index + 1 < width && !trailing.is_empty())
+                    });
+            if spans_punctuation {
+                continue;
+            }
+            let heard = parts
+                .iter()
+                .map(|(_, word, _)| *word)
+                .collect::<Vec<&str>>()
+                .join(" ");
+            if heard == term.text {
+                return None;
+            }
+            if parts
+                .iter()
+                .all(|(_, word, _)| is_known_word(wordlist, word))
+            {
+                continue;
+            }
+            let key = spell_key(&heard);
+            if key.first() != term.key.first() {
+                continue;
+            }
+            let distance = edit_distance(&key, &term.key);
+            if distance <= max_spell_edits(term.key.len())
+                && best.map_or(true, |(_, _, best_distance)| distance < best_distance)
+            {
+                best = Some((term, width, distance));
+            }
+        }
+    }
+    best.map(|(term, width, _)| (term, width))
+}
+
+// Replaces near-misses of dictionary terms in segment text, for example
+// "Kubernetis" with "Kubernetes", and returns one warning per distinct
+// replacement so each can be reviewed.
+fn correct_segment_spelling(
+    segments: &mut [TimestampedSegment],
+    dictionary: &[String],
+    locale: &str,
+) -> Vec<String> {
+    let terms = dictionary
+        .iter()
+        .map(|term| SpellTerm {
+            text: term.clone(),
+            key: spell_key(term),
+            words: term.split_whitespace().count(),
+        })
+        .filter(|term| !term.key.is_empty())
+        .collect::<Vec<SpellTerm>>();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let wordlist = spell_wordlist();
+    let mut corrections: BTreeMap<(String, String), usize> = BTreeMap::new();
+
+    for segment in segments.iter_mut() {
+        let tokens = segment.text.split_whitespace().collect::<Vec<&str>>();
+        let mut corrected = Vec::with_capacity(tokens.len());
+        let mut changed = false;
+        let mut index = 0;
+        while index < tokens.len() {
+            let Some((term, width)) = best_spell_match(&tokens[index..], &terms, wordlist) else {
+                corrected.push(tokens[index].to_string());
+                index += 1;
+                continue;
+            };
+            let window = &tokens[index..index + width];
+            let (leading, first_word, _) = split_word_token(window[0]);
+            let (_, last_word, trailing) = split_word_token(window[width - 1]);
+            let heard = if width == 1 {
+                first_word.to_string()
+            } else {
+                let inner = window[1..width - 1].iter().map(|token| token.to_string());
+                std::iter::once(first_word.to_string())
+                    .chain(inner)
+                    .chain(std::iter::once(last_word.to_string()))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            };
+            *corrections.entry((heard, term.text.clone())).or_insert(0) += 1;
+            corrected.push(format!("{}{}{}", leading, term.text, trailing));
+            changed = true;
+            index += width;
+        }
+        if changed {
+            segment.text = corrected.join(" ");
+        }
+    }
+
+    corrections
+        .into_iter()
+        .map(|((from, to), count)| {
+            localize_with(
+                locale,
+                Message::SpellingCorrected,
+                &[
+                    ("from", from.as_str()),
+                    ("to", to.as_str()),
+                    ("count", count.to_string().as_str()),
+                ],
+            )
+        })
+        .collect()
+}
+
+const UNIT_WORDS: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+// Twenty through ninety.
+const TENS_WORDS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+// First through nineteenth.
+const ORDINAL_WORDS: [&str; 19] = [
+    "first",
+    "second",
+    "third",
+    "fourth",
+    "fifth",
+    "sixth",
+    "seventh",
+    "eighth",
+    "ninth",
+    "tenth",
+    "eleventh",
+    "twelfth",
+    "thirteenth",
+    "fourteenth",
+    "fifteenth",
+    "sixteenth",
+    "seventeenth",
+    "eighteenth",
+    "nineteenth",
+];
+const SCALE_WORDS: [(&str, u64); 4] = [
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+    ("trillion", 1_000_000_000_000),
+];
+const CURRENCY_WORDS: [(&str, &str); 5] = [
+    ("dollars", "$"),
+    ("dollar", "$"),
+    ("bucks", "$"),
+    ("euros", "€"),
+    ("euro", "€"),
+];
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+// Which spoken forms the normalization pass rewrites as numerals. Each
+// workspace keeps its own choice.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextNormalization {
+    // "twenty five" -> "25", "three percent" -> "3%".
+    #[serde(default)]
+    numbers: bool,
+    // "twenty five thousand dollars" -> "$25,000".
+    #[serde(default)]
+    currency: bool,
+    // "March fifth twenty twenty four" -> "March 5, 2024", "three thirty pm"
+    // -> "3:30 PM".
+    #[serde(default)]
+    dates: bool,
+}
+
+impl TextNormalization {
+    fn any(self) -> bool {
+        self.numbers || self.currency || self.dates
+    }
+}
+
+struct WordToken<'a> {
+    leading: &'a str,
+    word: &'a str,
+    trailing: &'a str,
+    // `word` lowercased, for matching.
+    key: String,
+}
+
+impl<'a> WordToken<'a> {
+    fn new(token: &'a str) -> Self {
+        let (leading, word, trailing) = split_word_token(token);
+        WordToken {
+            leading,
+            word,
+            trailing,
+            key: word.to_lowercase(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum NumberPart {
+    Unit,
+    Tens,
+    Hundred,
+    Scale,
+}
+
+struct SpokenNumber {
+    integer: u64,
+    fraction: Option<String>,
+    // Kept as a word after a decimal: "2.5 million".
+    scale: Option<&'static str>,
+    tokens: usize,
+    // Spelled-out number words, so "25" alone is not reformatted.
+    number_words: usize,
+}
+
+impl SpokenNumber {
+    fn format(&self) -> String {
+        let mut text = group_thousands(self.integer);
+        if let Some(fraction) = &self.fraction {
+            text = format!("{}.{}", text, fraction);
+        }
+        if let Some(scale) = self.scale {
+            text = format!("{} {}", text, scale);
+        }
+        text
+    }
+}
+
+fn group_thousands(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+// 0-99 from a single word, including hyphenated ones like "twenty-five".
+fn small_number_word(word: &str) -> Option<u64> {
+    if let Some(value) = UNIT_WORDS.iter().position(|unit| *unit == word) {
+        return Some(value as u64);
+    }
+    if let Some(index) = TENS_WORDS.iter().position(|tens| *tens == word) {
+        return Some((index as u64 + 2) * 10);
+    }
+    let (tens, unit) = word.split_once('-')?;
+    let tens = TENS_WORDS.iter().position(|candidate| *candidate == tens)?;
+    let unit = UNIT_WORDS[1..10]
+        .iter()
+        .position(|candidate| *candidate == unit)?;
+    Some((tens as u64 + 2) * 10 + unit as u64 + 1)
+}
+
+fn digit_word(word: &str) -> Option<char> {
+    if word == "oh" {
+        return Some('0');
+    }
+    let value = UNIT_WORDS[..10].iter().position(|unit| *unit == word)?;
+    char::from_digit(value as u32, 10)
+}
+
+fn scale_word(word: &str) -> Option<(&'static str, u64)> {
+    SCALE_WORDS
+        .iter()
+        .copied()
+        .find(|(scale, _)| *scale == word)
+}
+
+// Reads a number from the start of `tokens`, spelled out ("two hundred and
+// five", "three point five million") or as digits followed by a scale
+// ("25 thousand"). Stops at punctuation between words.
+fn parse_spoken_number(tokens: &[WordToken]) -> Option<SpokenNumber> {
+    let mut total = 0u64;
+    let mut current = 0u64;
+    let mut last: Option<NumberPart> = None;
+    let mut last_scale = u64::MAX;
+    let mut number = SpokenNumber {
+        integer: 0,
+        fraction: None,
+        scale: None,
+        tokens: 0,
+        number_words: 0,
+    };
+
+    while let Some(token) = tokens.get(number.tokens) {
+        let index = number.tokens;
+        if index > 0 && (!tokens[index - 1].trailing.is_empty() || !token.leading.is_empty()) {
+            break;
+        }
+        let next = tokens.get(index + 1).map(|next| next.key.as_str());
+        let word = token.key.as_str();
+        if let Some(value) = small_number_word(word) {
+            let fits = match last {
+                None => true,
+                Some(NumberPart::Tens) => (1..10).contains(&value),
+                Some(NumberPart::Hundred | NumberPart::Scale) => current % 100 == 0,
+                Some(NumberPart::Unit) => false,
+            };
+            if !fits {
+                break;
+            }
+            current += value;
+            last = Some(if value >= 20 && value % 10 == 0 {
+                NumberPart::Tens
+            } else {
+                NumberPart::Unit
+            });
+            number.number_words += 1;
+        } else if index == 0
+            && word
+                .chars()
+                .all(|char| char.is_ascii_digit() || char == ',')
+        {
+            current = word.replace(',', "").parse().ok()?;
+            last = Some(NumberPart::Unit);
+        } else if index == 0
+            && word == "a"
+            && next.is_some_and(|next| next == "hundred" || scale_word(next).is_some())
+        {
+            current = 1;
+            last = Some(NumberPart::Unit);
+        } else if word == "hundred"
+            && matches!(last, Some(NumberPart::Unit | NumberPart::Tens))
+            && (1..100).contains(&current)
+        {
+            current *= 100;
+            last = Some(NumberPart::Hundred);
+            number.number_words += 1;
+        } else if let Some((_, scale)) = scale_word(word)
+            .filter(|(_, scale)| *scale < last_scale && last.is_some() && current > 0)
+        {
+            total = total.checked_add(current.checked_mul(scale)?)?;
+            current = 0;
+            last_scale = scale;
+            last = Some(NumberPart::Scale);
+            number.number_words += 1;
+        } else if word == "and"
+            && matches!(last, Some(NumberPart::Hundred | NumberPart::Scale))
+            && next.and_then(small_number_word).is_some()
+        {
+            number.tokens += 1;
+            continue;
+        } else if word == "point" && last.is_some() && token.trailing.is_empty() {
+            let digits = tokens[index + 1..]
+                .iter()
+                .enumerate()
+                .take_while(|(offset, digit)| {
+                    *offset == 0
+                        || tokens[index + *offset].trailing.is_empty() && digit.leading.is_empty()
+                })
+                .map_while(|(_, digit)| digit_word(&digit.key))
+                .collect::<String>();
+            if digits.is_empty() {
+                break;
+            }
+            number.tokens += 1 + digits.len();
+            number.number_words += 1 + digits.len();
+            number.fraction = Some(digits);
+            let after = number.tokens;
+            if tokens[after - 1].trailing.is_empty() {
+                if let Some((scale, _)) = tokens
+                    .get(after)
+                    .and_then(|token| scale_word(&token.key))
+                    .filter(|(_, scale)| *scale >= 1_000_000 && last_scale == u64::MAX)
+                {
+                    number.scale = Some(scale);
+                    number.tokens += 1;
+                }
+            }
+            break;
+        } else {
+            break;
+        }
+        number.tokens += 1;
+    }
+
+    if last.is_none() {
+        return None;
+    }
+    number.integer = total.checked_add(current)?;
+    Some(number)
+}
+
+fn ordinal_word(word: &str) -> Option<u32> {
+    if let Some(index) = ORDINAL_WORDS.iter().position(|ordinal| *ordinal == word) {
+        return Some(index as u32 + 1);
+    }
+    match word {
+        "twentieth" => return Some(20),
+        "thirtieth" => return Some(30),
+        _ => {}
+    }
+    let (tens, unit) = word.split_once('-')?;
+    Some(
+        tens_value(tens)?
+            + ORDINAL_WORDS[..9]
+                .iter()
+                .position(|ordinal| *ordinal == unit)? as u32
+            + 1,
+    )
+}
+
+fn tens_value(word: &str) -> Option<u32> {
+    match word {
+        "twenty" => Some(20),
+        "thirty" => Some(30),
+        _ => None,
+    }
+}
+
+// A day of the month: "fifth", "twenty first", "twenty-first", "5", "5th".
+fn parse_spoken_day(tokens: &[WordToken]) -> Option<(u32, usize)> {
+    let first = tokens.first()?;
+    let (day, used) = if let Some(day) = ordinal_word(&first.key) {
+        (day, 1)
+    } else if let Some(tens) = tens_value(&first.key).filter(|_| first.trailing.is_empty()) {
+        let unit = tokens.get(1).and_then(|token| ordinal_word(&token.key))?;
+        (tens + unit, 2)
+    } else {
+        let digits = first
+            .key
+            .trim_end_matches(|char: char| char.is_ascii_alphabetic());
+        let suffix = &first.key[digits.len()..];
+        if !["", "st", "nd", "rd", "th"].contains(&suffix) {
+            return None;
+        }
+        (digits.parse().ok()?, 1)
+    };
+    (1..=31).contains(&day).then_some((day, used))
+}
+
+// "2024", "twenty twenty four", "nineteen eighty five", "twenty oh five",
+// "two thousand and twenty four".
+fn parse_spoken_year(tokens: &[WordToken]) -> Option<(u64, usize)> {
+    let first = tokens.first()?;
+    if first.key.len() == 4 {
+        if let Some(year) = first
+            .key
+            .parse::<u64>()
+            .ok()
+            .filter(|year| (1000..3000).contains(year))
+        {
+            return Some((year, 1));
+        }
+    }
+    if let Some(number) = parse_spoken_number(tokens) {
+        if number.fraction.is_none()
+            && number.number_words >= 2
+            && (1000..3000).contains(&number.integer)
+        {
+            return Some((number.integer, number.tokens));
+        }
+    }
+    let century = small_number_word(&first.key).filter(|value| (10..=20).contains(value))?;
+    if !first.trailing.is_empty() {
+        return None;
+    }
+    let rest = &tokens[1..];
+    if rest
+        .first()
+        .is_some_and(|token| token.key == "oh" && token.trailing.is_empty())
+    {
+        let unit = rest
+            .get(1)
+            .and_then(|token| small_number_word(&token.key))
+            .filter(|unit| (1..10).contains(unit))?;
+        return Some((century * 100 + unit, 3));
+    }
+    let number = parse_spoken_number(rest)?;
+    (number.fraction.is_none()
+        && number.number_words == number.tokens
+        && (10..100).contains(&number.integer))
+    .then_some((century * 100 + number.integer, 1 + number.tokens))
+}
+
+fn month_name(token: &WordToken) -> Option<&'static str> {
+    // Only capitalized, so "may" the verb is left alone.
+    if !token.word.starts_with(|char: char| char.is_uppercase()) {
+        return None;
+    }
+    MONTH_NAMES
+        .iter()
+        .copied()
+        .find(|month| month.eq_ignore_ascii_case(token.word))
+}
+
+// "March fifth", "March fifth, twenty twenty four", "the fifth of March".
+fn parse_spoken_date(tokens: &[WordToken]) -> Option<(String, usize)> {
+    let (month, day, mut used) = if let Some(month) = tokens
+        .first()
+        .filter(|token| token.trailing.is_empty())
+        .and_then(month_name)
+    {
+        let (day, day_tokens) = parse_spoken_day(&tokens[1..])?;
+        (month, day, 1 + day_tokens)
+    } else {
+        let start = usize::from(tokens.first()?.key == "the");
+        let (day, day_tokens) = parse_spoken_day(&tokens[start..])?;
+        let of = tokens.get(start + day_tokens)?;
+        if of.key != "of" || !tokens[start + day_tokens - 1].trailing.is_empty() {
+            return None;
+        }
+        let month = tokens.get(start + day_tokens + 1).and_then(month_name)?;
+        (month, day, start + day_tokens + 2)
+    };
+    if tokens[..used]
+        .iter()
+        .skip(1)
+        .any(|token| !token.leading.is_empty())
+        || tokens[..used - 1]
+            .iter()
+            .any(|token| !token.trailing.is_empty())
+    {
+        return None;
+    }
+    let mut text = format!("{} {}", month, day);
+    if matches!(tokens[used - 1].trailing, "" | ",") {
+        if let Some((year, year_tokens)) = parse_spoken_year(&tokens[used..]) {
+            text = format!("{}, {}", text, year);
+            used += year_tokens;
+        }
+    }
+    Some((text, used))
+}
+
+// "three thirty pm", "seven a.m.", "ten o'clock", "nine oh five pm".
+fn parse_spoken_time(tokens: &[WordToken]) -> Option<(String, usize)> {
+    let first = tokens.first()?;
+    let hour = small_number_word(&first.key)
+        .or_else(|| first.key.parse().ok())
+        .filter(|hour| (1..=12).contains(hour))?;
+    let mut used = 1;
+    let mut minutes = None;
+    if tokens[0].trailing.is_empty() {
+        if let Some(token) = tokens.get(1) {
+            if token.key == "oh" && token.trailing.is_empty() {
+                let unit = tokens
+                    .get(2)
+                    .and_then(|token| small_number_word(&token.key))
+                    .filter(|unit| (1..10).contains(unit))?;
+                minutes = Some(unit);
+                used = 3;
+            } else if let Some(number) = parse_spoken_number(&tokens[1..]).filter(|number| {
+                number.fraction.is_none()
+                    && number.number_words == number.tokens
+                    && (10..60).contains(&number.integer)
+            }) {
+                minutes = Some(number.integer);
+                used += number.tokens;
+            }
+        }
+    }
+    if !tokens[used - 1].trailing.is_empty() {
+        return None;
+    }
+    let marker = tokens.get(used)?;
+    let text = match marker.key.replace('.', "").as_str() {
+        "am" | "pm" => {
+            let suffix = marker.key.replace('.', "").to_uppercase();
+            match minutes {
+                Some(minutes) => format!("{}:{:02} {}", hour, minutes, suffix),
+                None => format!("{} {}", hour, suffix),
+            }
+        }
+        "o'clock" | "o\u{2019}clock" if minutes.is_none() => format!("{}:00", hour),
+        _ => return None,
+    };
+    Some((text, used + 1))
+}
+
+// "$25,000", "$3.50", "12%", or the numerals for a spelled-out number. Small
+// numbers on their own ("one of them") stay words.
+fn parse_spoken_amount(
+    tokens: &[WordToken],
+    normalization: TextNormalization,
+) -> Option<(String, usize)> {
+    let number = parse_spoken_number(tokens)?;
+    let mut used = number.tokens;
+    let unit = tokens
+        .get(used)
+        .filter(|_| tokens[used - 1].trailing.is_empty())
+        .map(|token| token.key.as_str());
+
+    if let Some(symbol) = unit
+        .and_then(|unit| CURRENCY_WORDS.iter().find(|(word, _)| *word == unit))
+        .map(|(_, symbol)| *symbol)
+        .filter(|_| normalization.currency)
+    {
+        used += 1;
+        let mut text = format!("{}{}", symbol, number.format());
+        // "three dollars and fifty cents" -> "$3.50".
+        if number.fraction.is_none()
+            && number.scale.is_none()
+            && tokens[used - 1].trailing.is_empty()
+        {
+            let cents = tokens
+                .get(used)
+                .filter(|token| token.key == "and")
+                .and_then(|_| parse_spoken_number(&tokens[used + 1..]))
+                .filter(|cents| cents.fraction.is_none() && cents.integer < 100)
+                .filter(|cents| {
+                    tokens[used + cents.tokens].trailing.is_empty()
+                        && tokens
+                            .get(used + 1 + cents.tokens)
+                            .is_some_and(|token| token.key == "cents" || token.key == "cent")
+                });
+            if let Some(cents) = cents {
+                text = format!("{}.{:02}", text, cents.integer);
+                used += cents.tokens + 2;
+            }
+        }
+        return Some((text, used));
+    }
+    if !normalization.numbers {
+        return None;
+    }
+    if unit == Some("percent") {
+        return Some((format!("{}%", number.format()), used + 1));
+    }
+    (number.number_words > 0
+        && number.tokens >= number_word_run(tokens)
+        && (number.integer >= 10 || number.fraction.is_some() || number.number_words > 1))
+        .then(|| (number.format(), used))
+}
+
+// How many number words follow each other at the start of `tokens`. A run
+// that does not read as one number ("three thirty", "five five five one")
+// is left as words.
+fn number_word_run(tokens: &[WordToken]) -> usize {
+    tokens
+        .iter()
+        .enumerate()
+        .take_while(|(index, token)| {
+            (*index == 0 || tokens[index - 1].trailing.is_empty() && token.leading.is_empty())
+                && (small_number_word(&token.key).is_some()
+                    || token.key == "hundred"
+                    || scale_word(&token.key).is_some())
+        })
+        .count()
+}
+
+// Rewrites spoken numbers, amounts, dates, and times in segment text as
+// numerals (inverse text normalization). English only.
+fn normalize_segment_text(segments: &mut [TimestampedSegment], normalization: TextNormalization) {
+    for segment in segments.iter_mut() {
+        let raw = segment.text.split_whitespace().collect::<Vec<&str>>();
+        let tokens = raw
+            .iter()
+            .map(|token| WordToken::new(token))
+            .collect::<Vec<WordToken>>();
+        let mut normalized = Vec::with_capacity(raw.len());
+        let mut changed = false;
+        let mut index = 0;
+        while index < tokens.len() {
+            let rest = &tokens[index..];
+            let replacement = normalization
+                .dates
+                .then(|| parse_spoken_date(rest).or_else(|| parse_spoken_time(rest)))
+                .flatten()
+                .or_else(|| parse_spoken_amount(rest, normalization));
+            let Some((text, used)) = replacement else {
+                let skipped = number_word_run(rest).max(1);
+                normalized.extend(
+                    raw[index..index + skipped]
+                        .iter()
+                        .map(|token| token.to_string()),
+                );
+                index += skipped;
+                continue;
+            };
+            let last = &tokens[index + used - 1];
+            // The period of "p.m." is part of the word unless it also ends
+            // the segment.
+            let trailing =
+                if last.key.ends_with('m') && last.key.contains('.') && index + used < tokens.len()
+                {
+                    last.trailing.strip_prefix('.').unwrap_or(last.trailing)
+                } else {
+                    last.trailing
+                };
+            normalized.push(format!("{}{}{}", tokens[index].leading, text, trailing));
+            changed = true;
+            index += used;
+        }
+        if changed {
+            segment.text = normalized.join(" ");
+        }
+    }
+}
+
+fn label_tdrz_segments(
+    segments: Vec<TimestampedSegment>,
+    verbatim: bool,
+) -> (Vec<TimestampedSegment>, bool) {
+    if !segments
+        .iter()
+        .any(|segment| segment.text.contains(SPEAKER_TURN_MARKER))
+    {
+        return (segments, false);
+    }
+
+    let mut speaker_a_turn = true;
+    let mut turn_has_text = false;
+    let mut labeled: Vec<TimestampedSegment> = Vec::new();
+
+    for segment in &segments {
+        for (part_index, part) in segment.text.split(SPEAKER_TURN_MARKER).enumerate() {
+            if part_index > 0 && turn_has_text {
+                speaker_a_turn = !speaker_a_turn;
+                turn_has_text = false;
+            }
+
+            let cleaned = if verbatim {
+                part.trim().to_string()
+            } else {
+                sanitize_transcript_text(part)
+            };
+            if cleaned.is_empty() {
+                continue;
+            }
+
+            let speaker = if speaker_a_turn {
+                "Speaker A"
+            } else {
+                "Speaker B"
+            };
+            turn_has_text = true;
+
+            if let Some(last) = labeled.last_mut().filter(|_| !verbatim) {
+                if last.speaker == speaker {
+                    last.confidence = merged_confidence(last, &cleaned, segment.confidence);
+                    last.end_ms = last.end_ms.max(segment.end_ms);
+                    last.text = format!("{} {}", last.text, cleaned);
+                    continue;
+                }
+            }
+
+            labeled.push(TimestampedSegment {
+                speaker: speaker.to_string(),
+                start_ms: segment.start_ms,
+                end_ms: segment.end_ms,
+                text: cleaned,
+                confidence: segment.confidence,
+            });
+        }
+    }
+
+    if labeled.is_empty() {
+        return (segments, false);
+    }
+
+    (labeled, true)
+}
+
+const RESERVED_FRONTMATTER_KEYS: [&str; 28] = [
+    "title",
+    "date",
+    "client",
+    "note_type",
+    "source",
+    "transcript",
+    "speakers",
+    "tags",
+    "source_app",
+    "created_at",
+    "model",
+    "language",
+    "diarization_mode",
+    "duration_seconds",
+    "topics",
+    "sentiment",
+    "speech_stats",
+    "notes",
+    "part",
+    "parts",
+    "previous_part",
+    "next_part",
+    "previous_session",
+    "next_session",
+    "anonymized",
+    "summary",
+    "verbatim",
+    BODY_HASH_KEY,
+];
+
+fn validate_frontmatter_key(key: &str) -> Result<String, String> {
+    let trimmed = key.trim();
+    if trimmed.is_empty()
+        || !trimmed
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "Invalid frontmatter key ({}). Use letters, numbers, '-' or '_'.",
+            key
+        ));
+    }
+    if RESERVED_FRONTMATTER_KEYS.contains(&trimmed) {
+        return Err(format!(
+            "Frontmatter key '{}' is set by Echo Scribe and cannot be overridden.",
+            trimmed
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+// Settings fields come first; per-transcription overrides replace them, and an empty override
+// value removes the field. Invalid or reserved keys are skipped.
+fn merge_custom_frontmatter(
+    defaults: &BTreeMap<String, String>,
+    overrides: &BTreeMap<String, String>,
+) -> Vec<(String, String)> {
+    let mut merged = BTreeMap::new();
+    for (key, value) in defaults.iter().chain(overrides.iter()) {
+        let Ok(key) = validate_frontmatter_key(key) else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            merged.remove(&key);
+        } else {
+            merged.insert(key, value.to_string());
+        }
+    }
+    merged.into_iter().collect()
+}
+
+// Opening keys of a transcript's frontmatter. CoachNotes notes lead with the
+// client and carry the fields the CoachNotes app reads.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum FrontmatterHeader<'a> {
+    CoachNotes {
+        client: &'a str,
+        date: &'a str,
+        title: &'a str,
+        note_type: &'a str,
+        source: &'a str,
+        transcript: bool,
+        speakers: [&'a str; 2],
+        tags: [&'a str; 2],
+        #[serde(skip_serializing_if = "Option::is_none")]
+        previous_session: Option<&'a str>,
+    },
+    Standard {
+        title: &'a str,
+        date: &'a str,
+    },
+}
+
+// A saved transcript's frontmatter in the order the keys are written.
+#[derive(Serialize)]
+struct TranscriptFrontmatter<'a> {
+    #[serde(flatten)]
+    header: FrontmatterHeader<'a>,
+    source_app: &'a str,
+    created_at: &'a str,
+    model: &'a str,
+    language: &'a str,
+    diarization_mode: &'a str,
+    duration_seconds: u64,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    verbatim: bool,
+    // Whisper translated the speech from `language` into English.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    translated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parts: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_part: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_part: Option<&'a str>,
+    topics: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sentiment: Option<&'a SentimentSummary>,
+    speech_stats: &'a SpeechStatistics,
+    #[serde(flatten)]
+    custom_fields: BTreeMap<&'a str, &'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<&'a str>,
+    body_sha256: String,
+}
+
+// The metadata block under a same-day session's `## Part N` heading.
+#[derive(Serialize)]
+struct SessionPartFrontmatter<'a> {
+    title: &'a str,
+    created_at: &'a str,
+    model: &'a str,
+    language: &'a str,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    translated: bool,
+    diarization_mode: &'a str,
+    duration_seconds: u64,
+    word_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a str>,
+    #[serde(flatten)]
+    custom_fields: BTreeMap<&'a str, &'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<&'a str>,
+}
+
+fn custom_field_map(custom_fields: &[(String, String)]) -> BTreeMap<&str, &str> {
+    custom_fields
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect()
+}
+
+// Serializes frontmatter with a YAML library, so values with colons, quotes,
+// unicode or line breaks stay valid. The block ends with a newline.
+fn to_frontmatter_yaml<T: Serialize + ?Sized>(fields: &T) -> Result<String, String> {
+    serde_yaml::to_string(fields).map_err(|e| format!("Failed to encode frontmatter: {}", e))
+}
+
+fn build_markdown_transcript(
+    transcript: &str,
+    metadata: &MarkdownMetadata,
+) -> Result<String, String> {
+    let header = if metadata.coachnotes {
+        let (speaker_1, speaker_2) = metadata.speaker_labels.unwrap_or(("Coach", "Client"));
+        FrontmatterHeader::CoachNotes {
+            client: metadata.client.unwrap_or(""),
+            date: metadata.date,
+            title: metadata.title,
+            note_type: "transcript",
+            source: "coachnotes-voice-app",
+            transcript: true,
+            speakers: [speaker_1, speaker_2],
+            tags: ["transcript", "coaching-session"],
+            previous_session: metadata.previous_session,
+        }
+    } else {
+        FrontmatterHeader::Standard {
+            title: metadata.title,
+            date: metadata.date,
+        }
+    };
+
+    let notes_section = match metadata.notes {
+        Some(notes) if metadata.notes_section => format!("\n## Notes\n\n{}\n", notes),
+        _ => String::new(),
+    };
+
+    let session_link = match metadata.previous_session {
+        Some(previous) if metadata.coachnotes && metadata.inline_session_links => {
+            format!("[Previous session](<{}>)\n\n", previous)
+        }
+        _ => String::new(),
+    };
+    let prep_section = match metadata.session_prep {
+        Some(prep) => format!("\n{}\n\n{}\n", SESSION_PREP_HEADING, prep),
+        None => String::new(),
+    };
+    let body = format!(
+        "# Transcript\n\n{}{}\n{}{}",
+        session_link, transcript, notes_section, prep_section
+    );
+    let frontmatter = TranscriptFrontmatter {
+        header,
+        source_app: "Echo Scribe",
+        created_at: metadata.created_at,
+        model: metadata.model,
+        language: metadata.language,
+        diarization_mode: metadata.diarization_mode,
+        duration_seconds: metadata.duration_seconds,
+        verbatim: metadata.verbatim,
+        translated: metadata.translated,
+        part: metadata.part.map(|part| part.number),
+        parts: metadata.part.map(|part| part.count),
+        previous_part: metadata.part.and_then(|part| part.previous),
+        next_part: metadata.part.and_then(|part| part.next),
+        topics: metadata.topics,
+        summary: metadata.summary,
+        sentiment: metadata.sentiment,
+        speech_stats: metadata.speech_stats,
+        custom_fields: custom_field_map(metadata.custom_fields),
+        notes: metadata.notes,
+        body_sha256: body_sha256(&body),
+    };
+    Ok(format!(
+        "---\n{}---\n{}",
+        to_frontmatter_yaml(&frontmatter)?,
+        body
+    ))
+}
+
+// The earliest transcript already saved for this client and day. Split parts
+// and anonymized copies keep their own files and never receive sessions.
+fn same_day_transcript(planned_path: &Path, date: &str) -> Option<PathBuf> {
+    let prefix = format!("{}-transcript-", date);
+    let mut candidates = markdown_files_in(planned_path.parent()?)
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .map_or(false, |name| name.to_string_lossy().starts_with(&prefix))
+        })
+        .filter(|path| {
+            fs::read_to_string(path)
+                .is_ok_and(|markdown| is_whole_session(split_frontmatter(&markdown).0))
+        })
+        .collect::<Vec<PathBuf>>();
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+fn is_whole_session(frontmatter: &str) -> bool {
+    frontmatter_value(frontmatter, "source_app").as_deref() == Some("Echo Scribe")
+        && frontmatter_value(frontmatter, "transcript").as_deref() == Some("true")
+        && frontmatter_value(frontmatter, "anonymized").is_none()
+        && frontmatter_value(frontmatter, "part").is_none()
+}
+
+// The latest session saved for this client before `created_at`, found by
+// scanning the client folder. Parts and anonymized copies are skipped like
+// for same-day appends. Times are compared as instants, so sessions saved
+// under different timezone settings still sort correctly.
+fn previous_session_transcript(planned_path: &Path, created_at: &str) -> Option<PathBuf> {
+    let created_at = parse_iso8601(created_at)?;
+    markdown_files_in(planned_path.parent()?)
+        .into_iter()
+        .filter(|path| path != planned_path)
+        .filter_map(|path| {
+            let markdown = fs::read_to_string(&path).ok()?;
+            let (frontmatter, _) = split_frontmatter(&markdown);
+            let session_created_at = parse_iso8601(&frontmatter_value(frontmatter, "created_at")?)?;
+            (is_whole_session(frontmatter) && session_created_at < created_at)
+                .then_some((session_created_at, path))
+        })
+        .max()
+        .map(|(_, path)| path)
+}
+
+// Points the previous session's `next_session` at the note just saved.
+fn link_next_session(
+    app: &AppHandle,
+    previous_path: &Path,
+    next_path: &Path,
+) -> Result<(), String> {
+    let next_name = next_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let markdown = fs::read_to_string(previous_path).map_err(|e| {
+        format!(
+            "Failed to read previous session ({}): {}",
+            previous_path.display(),
+            e
+        )
+    })?;
+    let updated = with_updated_body_hash(
+        &markdown,
+        &with_frontmatter_value(&markdown, "next_session", &next_name)?,
+    )?;
+    replace_file(previous_path, updated.as_bytes()).map_err(|e| {
+        format!(
+            "Failed to link previous session ({}): {}",
+            previous_path.display(),
+            e
+        )
+    })?;
+    record_audit(app, AuditAction::Update, previous_path, None)
+}
+
+const SESSION_PREP_NOTE_TYPE: &str = "session_prep";
+const SESSION_PREP_HEADING: &str = "## Session prep";
+const DEFAULT_SESSION_TEMPLATE: &str =
+    "### Agenda\n\n- \n\n### Goals carried over\n\n{carried_goals}\n\n### Questions to ask\n\n- ";
+
+// Frontmatter of a note prepared ahead of a session. It is replaced by the
+// transcript's own frontmatter when the session is merged in.
+#[derive(Serialize)]
+struct SessionPrepFrontmatter<'a> {
+    client: &'a str,
+    date: &'a str,
+    title: &'a str,
+    note_type: &'a str,
+    source_app: &'a str,
+    prepared_at: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_session: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreparedSessionNote {
+    path: String,
+    previous_session: Option<String>,
+    carried_goals: usize,
+}
+
+// The note prepared for this client folder and day that has no transcript
+// yet, earliest first.
+fn pending_session_prep(client_dir: &Path, date: &str) -> Option<PathBuf> {
+    let mut candidates = markdown_files_in(client_dir)
+        .into_iter()
+        .filter(|path| {
+            fs::read_to_string(path).is_ok_and(|markdown| {
+                let (frontmatter, _) = split_frontmatter(&markdown);
+                frontmatter_value(frontmatter, "source_app").as_deref() == Some("Echo Scribe")
+                    && frontmatter_value(frontmatter, "note_type").as_deref()
+                        == Some(SESSION_PREP_NOTE_TYPE)
+                    && frontmatter_value(frontmatter, "date").as_deref() == Some(date)
+            })
+        })
+        .collect::<Vec<PathBuf>>();
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+// Checkboxes left open in the previous session note, then the commitments
+// spoken in it, as a checklist for the next session.
+fn carried_goals(previous: &SavedTranscript) -> Vec<String> {
+    let mut goals = previous
+        .body
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("- [ ] "))
+        .map(str::trim)
+        .filter(|goal| !goal.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<String>>();
+    for item in extract_action_items(&previous.body, &previous.speakers) {
+        if !goals.contains(&item) {
+            goals.push(item);
+        }
+    }
+    goals
+}
+
+// Fills `{client}`, `{date}`, `{previous_session}`, and `{carried_goals}`.
+fn fill_session_template(template: &str, fields: &[(&str, &str)]) -> String {
+    fields
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+        .trim()
+        .to_string()
+}
+
+// Creates today's note for `client` from their session template before the
+// session starts. The next CoachNotes transcription for that client on the
+// same day is saved into this note, keeping what was written in it under
+// "Session prep".
+#[tauri::command]
+async fn prepare_session_note(
+    app: AppHandle,
+    client: String,
+) -> Result<PreparedSessionNote, String> {
+    let settings = load_settings(&app)?;
+    let client = sanitize_non_empty(Some(client)).ok_or("Client name is required.")?;
+    let root = sanitize_non_empty(settings.coachnotes_root_dir.clone())
+        .ok_or("Choose a CoachNotes root folder first.")?;
+    let client_dir = PathBuf::from(root).join(&client);
+    require_unlocked(&app, Some(&client_dir))?;
+    if !client_dir.is_dir() {
+        return Err(format!(
+            "Client folder not found ({}).",
+            client_dir.display()
+        ));
+    }
+
+    let (now, _) = now_for_settings(&settings);
+    let date = format_date(now, &settings.date_format);
+    if let Some(existing) = pending_session_prep(&client_dir, &date) {
+        return Err(format!(
+            "A session note for {} is already prepared for today ({}).",
+            client,
+            existing.display()
+        ));
+    }
+
+    let created_at = format_iso8601(now);
+    let planned_path = client_dir.join(format!(
+        "{}-transcript-{}.md",
+        date,
+        format_time_compact(now)
+    ));
+    let previous_path = previous_session_transcript(&planned_path, &created_at);
+    let previous_name = previous_path.as_ref().and_then(|path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    });
+    let goals = previous_path
+        .as_deref()
+        .and_then(read_saved_transcript)
+        .map(|previous| carried_goals(&previous))
+        .unwrap_or_default();
+    let goal_list = if goals.is_empty() {
+        "- [ ] ".to_string()
+    } else {
+        goals
+            .iter()
+            .map(|goal| format!("- [ ] {}", goal))
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    let template = settings
+        .client_templates
+        .get(&client)
+        .map(String::as_str)
+        .unwrap_or(DEFAULT_SESSION_TEMPLATE);
+    let body = fill_session_template(
+        template,
+        &[
+            ("client", client.as_str()),
+            ("date", date.as_str()),
+            ("previous_session", previous_name.as_deref().unwrap_or("")),
+            ("carried_goals", goal_list.as_str()),
+        ],
+    );
+    let frontmatter = to_frontmatter_yaml(&SessionPrepFrontmatter {
+        client: &client,
+        date: &date,
+        title: "Session prep",
+        note_type: SESSION_PREP_NOTE_TYPE,
+        source_app: "Echo Scribe",
+        prepared_at: &created_at,
+        previous_session: previous_name.as_deref(),
+    })?;
+    let path = write_new_file(
+        &planned_path,
+        format!("---\n{}---\n{}\n", frontmatter, body).as_bytes(),
+    )
+    .map_err(|e| {
+        format!(
+            "Failed to write session note ({}): {}",
+            planned_path.display(),
+            e
+        )
+    })?;
+    record_audit(&app, AuditAction::Create, &path, None)?;
+
+    Ok(PreparedSessionNote {
+        path: path.to_string_lossy().to_string(),
+        previous_session: previous_name,
+        carried_goals: goals.len(),
+    })
+}
+
+// Appends a session to an existing day transcript as "Part N" (the original
+// session being Part 1), with its own metadata block under the heading.
+fn append_session_section(
+    existing: &str,
+    transcript: &str,
+    metadata: &MarkdownMetadata,
+) -> Result<(String, usize), String> {
+    let part_number = existing.matches("\n## Part ").count() + 2;
+    let fields = to_frontmatter_yaml(&SessionPartFrontmatter {
+        title: metadata.title,
+        created_at: metadata.created_at,
+        model: metadata.model,
+        language: metadata.language,
+        translated: metadata.translated,
+        diarization_mode: metadata.diarization_mode,
+        duration_seconds: metadata.duration_seconds,
+        word_count: metadata.speech_stats.word_count,
+        summary: metadata.summary,
+        custom_fields: custom_field_map(metadata.custom_fields),
+        notes: metadata.notes,
+    })?;
+
+    let notes_section = match metadata.notes {
+        Some(notes) if metadata.notes_section => format!("\n### Notes\n\n{}\n", notes),
+        _ => String::new(),
+    };
+
+    let markdown = format!(
+        "{}\n\n---\n\n## Part {}\n\n```yaml\n{}```\n\n{}\n{}",
+        existing.trim_end(),
+        part_number,
+        fields,
+        transcript,
+        notes_section
+    );
+    Ok((with_updated_body_hash(existing, &markdown)?, part_number))
+}
+
+fn current_engine_status(app: &AppHandle) -> EngineStatus {
+    app.state::<EngineState>()
+        .status
+        .lock()
+        .map(|status| status.clone())
+        .unwrap_or_default()
+}
+
+fn set_engine_status(app: &AppHandle, state: &str, model: &str, message: &str) -> EngineStatus {
+    let status = EngineStatus {
+        state: state.to_string(),
+        model: Some(model.to_string()),
+        message: message.to_string(),
+    };
+
+    if let Ok(mut guard) = app.state::<EngineState>().status.lock() {
+        *guard = status.clone();
+    }
+    let _ = app.emit("engine-status", status.clone());
+    status
+}
+
+async fn prewarm_selected_model(app: &AppHandle, model_id: &str) -> Result<EngineStatus, String> {
+    let model = validate_model(model_id)?;
+    let model_path = model_file_path(app, model.id)?;
+    if !model_path.exists() {
+        return Err(format!(
+            "Model '{}' is not downloaded yet. Use Setup to download it first.",
+            model.id
+        ));
+    }
+
+    set_engine_status(app, "warming", model.id, "Verifying model...");
+    let checksum = sha256_for_file(&model_path, |percent| {
+        set_engine_status(
+            app,
+            "warming",
+            model.id,
+            &format!("Verifying model ({}%)...", percent),
+        );
+    })
+    .await?;
+    if checksum != model.sha256 {
+        return Err(format!(
+            "Model '{}' failed checksum verification. Download it again from Setup.",
+            model.id
+        ));
+    }
+
+    set_engine_status(app, "warming", model.id, "Loading model...");
+    let temp_dir = echo_scribe_temp_dir()?;
+    let wav_path = temp_dir.join(format!("prewarm-{}.wav", unix_timestamp_secs()?));
+    let _cleanup = TempFileCleanup::new(vec![wav_path.clone()]);
+    fs::write(
+        &wav_path,
+        encode_pcm16_wav(&vec![0u8; ms_to_pcm_offset(1000)]),
+    )
+    .map_err(|e| {
+        format!(
+            "Failed to write warm-up audio file ({}): {}",
+            wav_path.display(),
+            e
+        )
+    })?;
+
+    let whisper_args = vec![
+        "-m".to_string(),
+        model_path.to_string_lossy().to_string(),
+        "-f".to_string(),
+        wav_path.to_string_lossy().to_string(),
+        "-np".to_string(),
+    ];
+    let whisper_output = run_whisper(app, &whisper_args, &mut |_: &str| {}).await?;
+    if !whisper_output.success {
+        return Err(format!(
+            "Whisper failed to load the model: {}",
+            process_output_detail(&whisper_output.stdout, &whisper_output.stderr)
+        ));
+    }
+
+    Ok(set_engine_status(app, "ready", model.id, "Model is warm."))
+}
+
+async fn prewarm_engine_with_status(app: &AppHandle) -> Result<EngineStatus, String> {
+    let settings = load_settings(app)?;
+    let model_id = match settings.selected_model.as_str() {
+        AUTO_MODEL => auto_model_for_language(&settings, "auto"),
+        model_id => model_id,
+    };
+    match prewarm_selected_model(app, model_id).await {
+        Ok(status) => Ok(status),
+        Err(error) => {
+            set_engine_status(app, "failed", model_id, &error);
+            Err(error)
+        }
+    }
+}
+
+fn build_setup_state(app: &AppHandle) -> Result<SetupState, String> {
+    let settings = load_settings(app)?;
+    let models_directory = models_dir(app)?;
+    let transcript_directory = resolve_transcript_dir(&settings);
+
+    let models = MODEL_CATALOG
+        .iter()
+        .map(|entry| {
+            let path = models_directory.join(format!("ggml-{}.bin", entry.id));
+            ModelState {
+                id: entry.id.to_string(),
+                label: entry.label.to_string(),
+                size_mb: entry.size_mb,
+                ram_mb: entry.ram_mb,
+                downloaded: path.exists(),
+                path: path.to_string_lossy().to_string(),
+            }
+        })
+        .collect::<Vec<ModelState>>();
+
+    let model_downloaded = |model_id: &str| {
+        models
+            .iter()
+            .find(|entry| entry.id == model_id)
+            .map(|entry| entry.downloaded)
+            .unwrap_or(false)
+    };
+    // With "auto" each session checks its own model, so one usable choice is
+    // enough to be ready here.
+    let selected_model_downloaded = if settings.selected_model == AUTO_MODEL {
+        settings
+            .language_models
+            .values()
+            .map(String::as_str)
+            .chain([auto_model_for_language(&settings, "auto")])
+            .any(model_downloaded)
+    } else {
+        model_downloaded(&settings.selected_model)
+    };
+
+    let diarization_capabilities = DiarizationCapabilities {
+        engines: DIARIZATION_ENGINES
+            .iter()
+            .map(|engine| DiarizationEngineState {
+                engine: *engine,
+                ready: engine.required_model.map_or(true, |model| {
+                    models
+                        .iter()
+                        .any(|entry| entry.id == model && entry.downloaded)
+                }),
+            })
+            .collect(),
+    };
+
+    let sidecar_ready = is_sidecar_available();
+    let hardware = detect_hardware();
+    let runtime_ready = if cfg!(debug_assertions) {
+        true
+    } else {
+        sidecar_ready
+    };
+
+    let app_locked = settings.app_lock_enabled && !is_app_unlocked(app);
+    let coachnotes_root_dir = sanitize_non_empty(settings.coachnotes_root_dir.clone());
+    let coachnotes_clients = match &coachnotes_root_dir {
+        Some(root) if !app_locked => {
+            list_coachnotes_clients_from_root(Path::new(root)).unwrap_or_default()
+        }
+        _ => Vec::new(),
+    };
+
+    Ok(SetupState {
+        selected_model: settings.selected_model,
+        transcript_dir: transcript_directory.to_string_lossy().to_string(),
+        transcript_format: "md".to_string(),
+        models_dir: models_directory.to_string_lossy().to_string(),
+        app_data_dir: app_data_dir(app)?.to_string_lossy().to_string(),
+        models,
+        // A missing model does not block transcribing when it will be
+        // downloaded on demand.
+        ready: (selected_model_downloaded
+            || (settings.auto_download_models && !settings.offline_mode))
+            && runtime_ready,
+        sidecar_ready,
+        ffmpeg_ready: ffmpeg_sidecar_path()
+            .map(|path| path.exists())
+            .unwrap_or(false),
+        coachnotes_enabled: settings.coachnotes_enabled,
+        coachnotes_root_dir,
+        coachnotes_clients,
+        coachnotes_client: sanitize_non_empty(settings.coachnotes_client),
+        diarization_mode: settings.diarization_mode,
+        diarization_capabilities,
+        recommended_model: recommend_model(&hardware).to_string(),
+        hardware,
+        prewarm_on_launch: settings.prewarm_on_launch,
+        engine_status: current_engine_status(app),
+        acceleration: build_acceleration_info(app)?,
+        locale: settings.locale,
+        supported_locales: SUPPORTED_LOCALES
+            .iter()
+            .map(|locale| locale.to_string())
+            .collect(),
+        timezone: settings.timezone,
+        date_format: settings.date_format,
+        custom_frontmatter: settings.custom_frontmatter,
+        auto_title: settings.auto_title,
+        title_in_filename: settings.title_in_filename,
+        auto_split_minutes: settings.auto_split_minutes,
+        rolling_transcription: settings.rolling_transcription,
+        max_recording_minutes: settings.max_recording_minutes,
+        max_audio_mb: settings.max_audio_mb,
+        fallback_to_smaller_model: settings.fallback_to_smaller_model,
+        auto_download_models: settings.auto_download_models,
+        language_models: settings.language_models.clone(),
+        extra_whisper_args: settings.extra_whisper_args.clone(),
+        ignored_whisper_args: settings.ignored_whisper_args.clone(),
+        spell_correction: settings.spell_correction,
+        spell_dictionary: settings.spell_dictionary.clone(),
+        text_normalization: settings.text_normalization,
+        client_prompts: settings.client_prompts.clone(),
+        client_templates: settings.client_templates.clone(),
+        client_languages: settings.client_languages.clone(),
+        duplicate_check: settings.duplicate_check.clone(),
+        notify_on_completion: settings.notify_on_completion,
+        completion_sound: settings.completion_sound.clone(),
+        completion_sound_min_seconds: settings.completion_sound_min_seconds,
+        battery_saver: settings.battery_saver,
+        power: power_state(),
+        pause_queue_on_battery: settings.pause_queue_on_battery,
+        deferred_window: settings.deferred_window.clone(),
+        update_channel: settings.update_channel.clone(),
+        keep_raw_whisper_output: settings.keep_raw_whisper_output,
+        raw_output_dir: settings.raw_output_dir,
+        coachnotes_append_same_day: settings.coachnotes_append_same_day,
+        verify_models_in_background: settings.verify_models_in_background,
+        redownload_corrupted_models: settings.redownload_corrupted_models,
+        download_limit_kbps: settings.download_limit_kbps,
+        zoom_recordings_dir: settings.zoom_recordings_dir,
+        zoom_client_map: settings.zoom_client_map,
+        client_detection_enabled: settings.client_detection_enabled,
+        client_detection_calendar: settings.client_detection_calendar,
+        client_detection_map: settings.client_detection_map,
+        recording_presets: settings.recording_presets,
+        active_recording_preset: settings.active_recording_preset,
+        workspaces: workspace_names(&settings.workspaces, &settings.active_workspace),
+        active_workspace: settings.active_workspace,
+        recorder_volume_patterns: settings.recorder_volume_patterns,
+        recorder_move_originals: settings.recorder_move_originals,
+        recorder_imported_until: settings.recorder_imported_until,
+        app_lock_enabled: settings.app_lock_enabled,
+        app_lock_touch_id: settings.app_lock_touch_id,
+        app_locked,
+        touch_id_available: cfg!(target_os = "macos"),
+        offline_mode: settings.offline_mode,
+        verbatim: settings.verbatim,
+        timestamp_interval_seconds: settings.timestamp_interval_seconds,
+        share_title_template: settings.share_title_template,
+        share_tags: settings.share_tags,
+        event_bridge_enabled: settings.event_bridge_enabled,
+        event_bridge_port: settings.event_bridge_port,
+        webhooks: settings.webhooks,
+        drive_client_id: settings.drive_client_id,
+        drive_folder_name: settings.drive_folder_name,
+        drive_as_google_doc: settings.drive_as_google_doc,
+        drive_connected: settings.drive_connected,
+        huggingface_token_set: settings.huggingface_token_set,
+        coachnotes_inline_session_links: settings.coachnotes_inline_session_links,
+        markdown_escape: settings.markdown_escape,
+    })
+}
+
+#[tauri::command]
+async fn get_setup_state(app: AppHandle) -> Result<SetupState, String> {
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn get_acceleration_info(app: AppHandle) -> Result<AccelerationInfo, String> {
+    build_acceleration_info(&app)
+}
+
+#[tauri::command]
+async fn set_selected_model(app: AppHandle, model: String) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    validate_model_choice(&model)?;
+
+    let mut settings = load_settings(&app)?;
+    settings.selected_model = model;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_transcript_directory(app: AppHandle, directory: String) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let directory = directory.trim();
+    if directory.is_empty() {
+        return Err("Directory path cannot be empty.".to_string());
+    }
+
+    let directory_path = PathBuf::from(directory);
+    fs::create_dir_all(&directory_path).map_err(|e| {
+        format!(
+            "Could not create transcript directory ({}): {}",
+            directory_path.display(),
+            e
+        )
+    })?;
+
+    let mut settings = load_settings(&app)?;
+    settings.transcript_dir = Some(directory_path.to_string_lossy().to_string());
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+const DEFAULT_WORKSPACE: &str = "Default";
+const MAX_WORKSPACES: usize = 20;
+const MAX_WORKSPACE_NAME_CHARS: usize = 60;
+
+// A named output setup, such as "Coaching" or "Research interviews": where
+// transcripts go, the CoachNotes structure, the templates and extra
+// frontmatter used there, and how numbers are written.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Workspace {
+    name: String,
+    #[serde(default)]
+    transcript_dir: Option<String>,
+    #[serde(default)]
+    coachnotes_enabled: bool,
+    #[serde(default)]
+    coachnotes_root_dir: Option<String>,
+    #[serde(default)]
+    coachnotes_client: Option<String>,
+    #[serde(default)]
+    client_templates: BTreeMap<String, String>,
+    #[serde(default)]
+    custom_frontmatter: BTreeMap<String, String>,
+    #[serde(default)]
+    text_normalization: TextNormalization,
+}
+
+fn validate_workspace_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_WORKSPACE_NAME_CHARS {
+        return Err(format!(
+            "Workspace name must be 1 to {} characters.",
+            MAX_WORKSPACE_NAME_CHARS
+        ));
+    }
+    Ok(name.to_string())
+}
+
+// The active workspace first, then the rest in the order they were added.
+fn workspace_names(workspaces: &[Workspace], active: &str) -> Vec<String> {
+    let mut names = vec![active.to_string()];
+    names.extend(
+        workspaces
+            .iter()
+            .filter(|workspace| !workspace.name.eq_ignore_ascii_case(active))
+            .map(|workspace| workspace.name.clone()),
+    );
+    names
+}
+
+fn find_workspace<'a>(settings: &'a AppSettings, name: &str) -> Option<&'a Workspace> {
+    settings
+        .workspaces
+        .iter()
+        .find(|workspace| workspace.name.eq_ignore_ascii_case(name.trim()))
+}
+
+fn current_workspace(settings: &AppSettings) -> Workspace {
+    Workspace {
+        name: settings.active_workspace.clone(),
+        transcript_dir: settings.transcript_dir.clone(),
+        coachnotes_enabled: settings.coachnotes_enabled,
+        coachnotes_root_dir: settings.coachnotes_root_dir.clone(),
+        coachnotes_client: settings.coachnotes_client.clone(),
+        client_templates: settings.client_templates.clone(),
+        custom_frontmatter: settings.custom_frontmatter.clone(),
+        text_normalization: settings.text_normalization,
+    }
+}
+
+fn apply_workspace(settings: &mut AppSettings, workspace: &Workspace) {
+    settings.active_workspace = workspace.name.clone();
+    settings.transcript_dir = workspace.transcript_dir.clone();
+    settings.coachnotes_enabled = workspace.coachnotes_enabled;
+    settings.coachnotes_root_dir = workspace.coachnotes_root_dir.clone();
+    settings.coachnotes_client = workspace.coachnotes_client.clone();
+    settings.client_templates = workspace.client_templates.clone();
+    settings.custom_frontmatter = workspace.custom_frontmatter.clone();
+    settings.text_normalization = workspace.text_normalization;
+}
+
+// Writes the active workspace's current values back into `workspaces`.
+fn store_current_workspace(settings: &mut AppSettings) {
+    let current = current_workspace(settings);
+    match settings
+        .workspaces
+        .iter_mut()
+        .find(|workspace| workspace.name.eq_ignore_ascii_case(&current.name))
+    {
+        Some(existing) => *existing = current,
+        None => settings.workspaces.push(current),
+    }
+}
+
+// Points a job's copy of the settings at the workspace it asked for. Only
+// this job sees the change; the saved settings stay on the active workspace.
+fn use_job_workspace(
+    settings: &mut AppSettings,
+    options: &TranscriptionOptions,
+) -> Result<(), String> {
+    let Some(name) = sanitize_non_empty(options.workspace.clone()) else {
+        return Ok(());
+    };
+    if name.eq_ignore_ascii_case(&settings.active_workspace) {
+        return Ok(());
+    }
+    let workspace = find_workspace(settings, &name)
+        .cloned()
+        .ok_or_else(|| format!("No workspace named '{}'.", name))?;
+    apply_workspace(settings, &workspace);
+    Ok(())
+}
+
+// Creates an empty workspace that saves to its own folder under the default
+// transcript location, and switches to it.
+#[tauri::command]
+async fn create_workspace(app: AppHandle, name: String) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let name = validate_workspace_name(&name)?;
+    let mut settings = load_settings(&app)?;
+    if name.eq_ignore_ascii_case(&settings.active_workspace)
+        || find_workspace(&settings, &name).is_some()
+    {
+        return Err(format!("A workspace named '{}' already exists.", name));
+    }
+    if workspace_names(&settings.workspaces, &settings.active_workspace).len() >= MAX_WORKSPACES {
+        return Err(format!(
+            "Keep at most {} workspaces. Delete one first.",
+            MAX_WORKSPACES
+        ));
+    }
+    let folder = match sanitize_filename_component(&name) {
+        folder if folder.is_empty() => "workspace".to_string(),
+        folder => folder,
+    };
+    let directory = default_transcript_dir().join(folder);
+    fs::create_dir_all(&directory).map_err(|e| {
+        format!(
+            "Could not create workspace folder ({}): {}",
+            directory.display(),
+            e
+        )
+    })?;
+
+    store_current_workspace(&mut settings);
+    let workspace = Workspace {
+        name,
+        transcript_dir: Some(directory.to_string_lossy().to_string()),
+        ..Workspace::default()
+    };
+    settings.workspaces.push(workspace.clone());
+    apply_workspace(&mut settings, &workspace);
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn switch_workspace(app: AppHandle, name: String) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    if name.trim().eq_ignore_ascii_case(&settings.active_workspace) {
+        return build_setup_state(&app);
+    }
+    let workspace = find_workspace(&settings, &name)
+        .cloned()
+        .ok_or_else(|| format!("No workspace named '{}'.", name.trim()))?;
+    store_current_workspace(&mut settings);
+    apply_workspace(&mut settings, &workspace);
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+// Forgets the workspace's settings; transcripts already saved there are kept.
+// Deleting the active workspace switches to the first remaining one.
+#[tauri::command]
+async fn delete_workspace(app: AppHandle, name: String) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    let name = name.trim();
+    settings
+        .workspaces
+        .retain(|workspace| !workspace.name.eq_ignore_ascii_case(name));
+    if name.eq_ignore_ascii_case(&settings.active_workspace) {
+        let next = settings
+            .workspaces
+            .first()
+            .cloned()
+            .ok_or("Create another workspace before deleting the only one.")?;
+        apply_workspace(&mut settings, &next);
+    }
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+const SAVED_RECORDING_SUFFIXES: [&str; 3] =
+    ["-recording.wav", "-coach-mic.wav", "-client-system.wav"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptMove {
+    from: String,
+    to: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TranscriptMigrationResult {
+    dry_run: bool,
+    moves: Vec<TranscriptMove>,
+    // Files left in place because the destination already has one by that name.
+    conflicts: Vec<String>,
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptMigrationProgress {
+    done: usize,
+    total: usize,
+    path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RenameManifest {
+    created_at: String,
+    renames: Vec<TranscriptMove>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TranscriptRenameResult {
+    dry_run: bool,
+    renames: Vec<TranscriptMove>,
+    manifest_path: Option<String>,
+    errors: Vec<String>,
+}
+
+// The name a saved transcript gets under the current filename settings:
+// `<date>-transcript-<time>` in a CoachNotes client folder, otherwise
+// `transcript-<unix time>`, plus the title slug and part number.
+fn current_transcript_stem(settings: &AppSettings, transcript: &SavedTranscript) -> Option<String> {
+    let created_at = parse_iso8601(&transcript.created_at)?;
+    let root = sanitize_non_empty(settings.coachnotes_root_dir.clone());
+    let in_client_folder = root.map_or(false, |root| {
+        transcript.path.parent().and_then(Path::parent) == Some(Path::new(&root))
+    });
+    let base = if in_client_folder {
+        format!(
+            "{}-transcript-{}",
+            format_date(created_at, &settings.date_format),
+            format_time_compact(created_at)
+        )
+    } else {
+        // Standard names carry the time the job started, a little before
+        // `created_at`, so an existing one is kept.
+        let existing = transcript
+            .path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.strip_prefix("transcript-"))
+            .map(|rest| {
+                rest.chars()
+                    .take_while(char::is_ascii_digit)
+                    .collect::<String>()
+            })
+            .filter(|digits| !digits.is_empty());
+        format!(
+            "transcript-{}",
+            existing.unwrap_or_else(|| created_at.unix_timestamp().to_string())
+        )
+    };
+    let title_suffix = if settings.title_in_filename {
+        title_filename_suffix(&transcript.title)
+    } else {
+        String::new()
+    };
+    let part = fs::read_to_string(&transcript.path)
+        .ok()
+        .and_then(|markdown| frontmatter_value(split_frontmatter(&markdown).0, "part"))
+        .map(|part| format!("-part-{}", part))
+        .unwrap_or_default();
+    Some(format!("{}{}{}", base, title_suffix, part))
+}
+
+// Renames for every transcript whose name no longer matches the settings,
+// each followed by the files named after it (metadata, whisper output,
+// exports). A file belongs to the transcript with the longest matching stem,
+// so `name-2.json` follows `name-2.md` rather than `name.md`.
+fn plan_transcript_renames(settings: &AppSettings) -> Vec<TranscriptMove> {
+    let transcripts = scan_saved_transcripts(settings);
+    let mut claimed = HashSet::new();
+    let mut renames = Vec::new();
+    for transcript in &transcripts {
+        let (Some(dir), Some(old_stem), Some(new_stem)) = (
+            transcript.path.parent(),
+            transcript
+                .path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string()),
+            current_transcript_stem(settings, transcript),
+        ) else {
+            continue;
+        };
+        let Some(new_path) = (1..=MAX_COLLISION_SUFFIX)
+            .map(|attempt| numbered_path(&dir.join(format!("{}.md", new_stem)), attempt))
+            .find(|candidate| {
+                candidate == &transcript.path
+                    || (!candidate.exists() && !claimed.contains(candidate))
+            })
+        else {
+            continue;
+        };
+        if new_path == transcript.path {
+            continue;
+        }
+        let new_stem = new_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or(new_stem);
+
+        let sibling_stems = transcripts
+            .iter()
+            .filter(|other| other.path.parent() == Some(dir))
+            .filter_map(|other| other.path.file_stem())
+            .map(|stem| stem.to_string_lossy().to_string())
+            .collect::<Vec<String>>();
+        let owned_by = |name: &str| {
+            sibling_stems
+                .iter()
+                .filter(|stem| {
+                    name.starts_with(&format!("{}.", stem))
+                        || name.starts_with(&format!("{}-", stem))
+                })
+                .max_by_key(|stem| stem.len())
+                .cloned()
+        };
+        let mut companions = fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.is_file() && path != &transcript.path)
+                    .collect::<Vec<PathBuf>>()
+            })
+            .unwrap_or_default();
+        companions.sort();
+
+        claimed.insert(new_path.clone());
+        renames.push(TranscriptMove {
+            from: transcript.path.to_string_lossy().to_string(),
+            to: new_path.to_string_lossy().to_string(),
+        });
+        for companion in companions {
+            let name = companion
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if owned_by(&name).as_deref() != Some(old_stem.as_str()) {
+                continue;
+            }
+            let target = dir.join(format!("{}{}", new_stem, &name[old_stem.len()..]));
+            if target.exists() || claimed.contains(&target) {
+                continue;
+            }
+            claimed.insert(target.clone());
+            renames.push(TranscriptMove {
+                from: companion.to_string_lossy().to_string(),
+                to: target.to_string_lossy().to_string(),
+            });
+        }
+    }
+    renames
+}
+
+// Renames files and then fixes what points at them by name: part links in
+// frontmatter and `_index.md` links (both by file name), and the transcript
+// path recorded in JSON metadata. Returns the renames that succeeded.
+fn apply_transcript_renames(
+    renames: &[TranscriptMove],
+    errors: &mut Vec<String>,
+) -> Vec<TranscriptMove> {
+    let mut applied = Vec::new();
+    for rename in renames {
+        let (from, to) = (Path::new(&rename.from), Path::new(&rename.to));
+        let result = if to.exists() {
+            Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "destination already exists",
+            ))
+        } else {
+            fs::rename(from, to)
+        };
+        match result {
+            Ok(()) => applied.push(rename.clone()),
+            Err(e) => errors.push(format!(
+                "Failed to rename transcript file ({}): {}",
+                from.display(),
+                e
+            )),
+        }
+    }
+
+    let renamed_notes = applied
+        .iter()
+        .filter(|rename| rename.from.ends_with(".md"))
+        .collect::<Vec<&TranscriptMove>>();
+    let dirs = applied
+        .iter()
+        .filter_map(|rename| Path::new(&rename.to).parent().map(Path::to_path_buf))
+        .collect::<HashSet<PathBuf>>();
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+            let is_json = path
+                .extension()
+                .map_or(false, |extension| extension == "json");
+            let is_markdown = path
+                .extension()
+                .map_or(false, |extension| extension == "md");
+            if !is_json && !is_markdown {
+                continue;
+            }
+            let Ok(original) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let mut text = original.clone();
+            for rename in &renamed_notes {
+                if is_json {
+                    if let (Ok(from), Ok(to)) = (
+                        serde_json::to_string(&rename.from),
+                        serde_json::to_string(&rename.to),
+                    ) {
+                        text = text.replace(&from, &to);
+                    }
+                    continue;
+                }
+                let file_name = |path: &str| {
+                    Path::new(path)
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                };
+                let (from, to) = (file_name(&rename.from), file_name(&rename.to));
+                // Links are quoted in older notes and written by serde_yaml in
+                // newer ones.
+                let yaml_scalar =
+                    |value: &str| to_frontmatter_yaml(value).map(|yaml| format!(": {}", yaml));
+                if let (Ok(from_scalar), Ok(to_scalar)) = (yaml_scalar(&from), yaml_scalar(&to)) {
+                    text = text.replace(&from_scalar, &to_scalar);
+                }
+                text = text
+                    .replace(&yaml_quote(&from), &yaml_quote(&to))
+                    .replace(&format!("<{}>", from), &format!("<{}>", to));
+            }
+            if text != original {
+                if is_markdown {
+                    match with_updated_body_hash(&original, &text) {
+                        Ok(hashed) => text = hashed,
+                        Err(e) => {
+                            errors.push(format!(
+                                "Failed to update links ({}): {}",
+                                path.display(),
+                                e
+                            ));
+                            continue;
+                        }
+                    }
+                }
+                if let Err(e) = fs::write(&path, text) {
+                    errors.push(format!(
+                        "Failed to update links ({}): {}",
+                        path.display(),
+                        e
+                    ));
+                }
+            }
+        }
+    }
+    applied
+}
+
+fn rename_manifests_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app_data_dir(app)?.join("rename-manifests"))
+}
+
+// Renames saved transcripts in place to match the current filename settings
+// (CoachNotes date format, title in filename), reading date, time and title
+// from each note's frontmatter. Names already taken get a numbered suffix.
+// The renames are recorded in a manifest that `undo_transcript_rename` replays
+// backwards.
+#[tauri::command]
+async fn rename_transcripts(
+    app: AppHandle,
+    dry_run: bool,
+) -> Result<TranscriptRenameResult, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    let renames = plan_transcript_renames(&settings);
+    if dry_run || renames.is_empty() {
+        return Ok(TranscriptRenameResult {
+            dry_run,
+            renames,
+            manifest_path: None,
+            errors: Vec::new(),
+        });
+    }
+
+    let (now, _) = now_for_settings(&settings);
+    let dir = rename_manifests_dir(&app)?;
+    fs::create_dir_all(&dir).map_err(|e| {
+        format!(
+            "Failed to create rename manifest folder ({}): {}",
+            dir.display(),
+            e
+        )
+    })?;
+    let manifest_path = dir.join(format!("rename-{}.json", unix_timestamp_millis()));
+
+    let mut errors = Vec::new();
+    let applied = apply_transcript_renames(&renames, &mut errors);
+    audit_moves(&app, &applied, &mut errors);
+    let manifest = RenameManifest {
+        created_at: format_iso8601(now),
+        renames: applied.clone(),
+    };
+    let json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to encode rename manifest: {}", e))?;
+    fs::write(&manifest_path, json).map_err(|e| {
+        format!(
+            "Failed to write rename manifest ({}): {}",
+            manifest_path.display(),
+            e
+        )
+    })?;
+
+    Ok(TranscriptRenameResult {
+        dry_run,
+        renames: applied,
+        manifest_path: Some(manifest_path.to_string_lossy().to_string()),
+        errors,
+    })
+}
+
+fn audit_moves(app: &AppHandle, moves: &[TranscriptMove], errors: &mut Vec<String>) {
+    for planned in moves {
+        let (from, to) = (Path::new(&planned.from), Path::new(&planned.to));
+        if let Err(error) = record_audit(app, AuditAction::Move, from, Some(to)) {
+            errors.push(error);
+        }
+    }
+}
+
+#[tauri::command]
+async fn undo_transcript_rename(
+    app: AppHandle,
+    manifest_path: String,
+) -> Result<TranscriptRenameResult, String> {
+    require_unlocked(&app, None)?;
+    let path = PathBuf::from(&manifest_path);
+    let raw = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read rename manifest ({}): {}", path.display(), e))?;
+    let manifest: RenameManifest =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid rename manifest: {}", e))?;
+
+    let reversed = manifest
+        .renames
+        .iter()
+        .rev()
+        .map(|rename| TranscriptMove {
+            from: rename.to.clone(),
+            to: rename.from.clone(),
+        })
+        .collect::<Vec<TranscriptMove>>();
+    let mut errors = Vec::new();
+    let applied = apply_transcript_renames(&reversed, &mut errors);
+    audit_moves(&app, &applied, &mut errors);
+    if errors.is_empty() {
+        let _ = fs::remove_file(&path);
+    }
+
+    Ok(TranscriptRenameResult {
+        dry_run: false,
+        renames: applied,
+        manifest_path: Some(manifest_path),
+        errors,
+    })
+}
+
+// Files in `dir` that belong to Echo Scribe: its transcripts, anything named
+// after one (metadata, whisper output, exports), saved recordings and the
+// client index. Other files in the folder are left alone.
+fn transcript_files_in(dir: &Path) -> Vec<PathBuf> {
+    let stems = markdown_files_in(dir)
+        .into_iter()
+        .filter(|path| read_saved_transcript(path).is_some())
+        .filter_map(|path| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .collect::<Vec<String>>();
+    if stems.is_empty() {
+        return Vec::new();
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            name == CLIENT_INDEX_FILE
+                || stems.iter().any(|stem| {
+                    name.starts_with(&format!("{}.", stem))
+                        || name.starts_with(&format!("{}-", stem))
+                })
+                || (name.contains("-transcript-")
+                    && SAVED_RECORDING_SUFFIXES
+                        .iter()
+                        .any(|suffix| name.ends_with(suffix)))
+        })
+        .collect::<Vec<PathBuf>>();
+    files.sort();
+    files
+}
+
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    fs::copy(from, to)?;
+    fs::remove_file(from)
+}
+
+// Folds a moved client index into the one already at the destination,
+// dropping its `# Client` heading so the entries read as one list.
+fn merge_client_index(from: &Path, to: &Path) -> io::Result<()> {
+    let text = fs::read_to_string(from)?;
+    let entries = match text.strip_prefix("# ") {
+        Some(rest) => rest.split_once('\n').map_or("", |(_, entries)| entries),
+        None => text.as_str(),
+    };
+    fs::OpenOptions::new()
+        .append(true)
+        .open(to)
+        .and_then(|mut file| file.write_all(entries.as_bytes()))?;
+    fs::remove_file(from)
+}
+
+// Moves transcripts and their companion files from `old_dir` to `new_dir`
+// after the transcript folder or CoachNotes root changes. First-level folders
+// (CoachNotes clients) keep their names, and each client index moves with its
+// transcripts so the links inside it still resolve. Existing files at the
+// destination are never replaced. With `dry_run` nothing is touched and the
+// planned moves are returned.
+#[tauri::command]
+async fn migrate_transcripts(
+    app: AppHandle,
+    old_dir: String,
+    new_dir: String,
+    dry_run: bool,
+) -> Result<TranscriptMigrationResult, String> {
+    require_unlocked(&app, None)?;
+    let old_dir = PathBuf::from(old_dir.trim());
+    let new_dir = PathBuf::from(new_dir.trim());
+    if !old_dir.is_dir() {
+        return Err(format!(
+            "Transcript folder does not exist: {}",
+            old_dir.display()
+        ));
+    }
+    let same_dir = is_within(&new_dir, &old_dir) && is_within(&old_dir, &new_dir);
+    if new_dir.as_os_str().is_empty() || same_dir {
+        return Err("Choose two different folders to migrate between.".to_string());
+    }
+
+    let mut dirs = vec![(old_dir.clone(), new_dir.clone())];
+    for client in list_coachnotes_clients_from_root(&old_dir).unwrap_or_default() {
+        let from = old_dir.join(&client);
+        // A new folder created inside the old one is the destination, not a client.
+        if !is_within(&new_dir, &from) {
+            dirs.push((from, new_dir.join(&client)));
+        }
+    }
+
+    let mut result = TranscriptMigrationResult {
+        dry_run,
+        moves: Vec::new(),
+        conflicts: Vec::new(),
+        errors: Vec::new(),
+    };
+    for (from_dir, to_dir) in &dirs {
+        for from in transcript_files_in(from_dir) {
+            let Some(name) = from.file_name() else {
+                continue;
+            };
+            let to = to_dir.join(name);
+            if to.exists() && name != CLIENT_INDEX_FILE {
+                result.conflicts.push(from.to_string_lossy().to_string());
+                continue;
+            }
+            result.moves.push(TranscriptMove {
+                from: from.to_string_lossy().to_string(),
+                to: to.to_string_lossy().to_string(),
+            });
+        }
+    }
+    if dry_run {
+        return Ok(result);
+    }
+
+    let total = result.moves.len();
+    for (done, planned) in result.moves.iter().enumerate() {
+        let (from, to) = (Path::new(&planned.from), Path::new(&planned.to));
+        let moved = if !to.exists() {
+            move_file(from, to)
+        } else if from
+            .file_name()
+            .map_or(false, |name| name == CLIENT_INDEX_FILE)
+        {
+            merge_client_index(from, to)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "destination already exists",
+            ))
+        };
+        match moved {
+            Ok(()) => {
+                if let Err(error) = record_audit(&app, AuditAction::Move, from, Some(to)) {
+                    result.errors.push(error);
+                }
+            }
+            Err(e) => result.errors.push(format!(
+                "Failed to move transcript file ({}): {}",
+                from.display(),
+                e
+            )),
+        }
+        let _ = app.emit(
+            "transcript-migration-progress",
+            TranscriptMigrationProgress {
+                done: done + 1,
+                total,
+                path: planned.to.clone(),
+            },
+        );
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+async fn set_diarization_mode(app: AppHandle, mode: String) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.diarization_mode = validate_diarization_mode(&mode).to_string();
+    settings.diarization_mode_configured = true;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_prewarm_on_launch(app: AppHandle, enabled: bool) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.prewarm_on_launch = enabled;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_fallback_to_smaller_model(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.fallback_to_smaller_model = enabled;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_auto_download_models(app: AppHandle, enabled: bool) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.auto_download_models = enabled;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_notify_on_completion(app: AppHandle, enabled: bool) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.notify_on_completion = enabled;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_verify_models_in_background(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.verify_models_in_background = enabled;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_redownload_corrupted_models(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.redownload_corrupted_models = enabled;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_battery_saver(app: AppHandle, enabled: bool) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.battery_saver = enabled;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_raw_output_preferences(
+    app: AppHandle,
+    keep: bool,
+    directory: Option<String>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let directory = sanitize_non_empty(directory);
+    if let Some(directory) = &directory {
+        let directory_path = PathBuf::from(directory);
+        fs::create_dir_all(&directory_path).map_err(|e| {
+            format!(
+                "Could not create raw output folder ({}): {}",
+                directory_path.display(),
+                e
+            )
+        })?;
+    }
+
+    let mut settings = load_settings(&app)?;
+    settings.keep_raw_whisper_output = keep;
+    settings.raw_output_dir = directory;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_pause_queue_on_battery(app: AppHandle, enabled: bool) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.pause_queue_on_battery = enabled;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_deferred_window(
+    app: AppHandle,
+    window: Option<DeferredWindow>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let window = window.map(validate_deferred_window).transpose()?;
+    let mut settings = load_settings(&app)?;
+    settings.deferred_window = window;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_update_channel(app: AppHandle, channel: String) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    if !UPDATE_CHANNELS.contains(&channel.as_str()) {
+        return Err(format!(
+            "Unsupported update channel '{}'. Valid values: {}",
+            channel,
+            UPDATE_CHANNELS.join(", ")
+        ));
+    }
+    let mut settings = load_settings(&app)?;
+    settings.update_channel = channel;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_completion_sound(
+    app: AppHandle,
+    sound: Option<String>,
+    min_seconds: u32,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.completion_sound = validate_completion_sound(sound)?;
+    settings.completion_sound_min_seconds = min_seconds;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_extra_whisper_args(app: AppHandle, args: Vec<String>) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let extra_whisper_args = validate_extra_whisper_args(&args)?;
+
+    let mut settings = load_settings(&app)?;
+    settings.extra_whisper_args = extra_whisper_args;
+    settings.ignored_whisper_args.clear();
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_spell_correction(
+    app: AppHandle,
+    enabled: bool,
+    dictionary: Vec<String>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let dictionary = normalize_spell_dictionary(&dictionary);
+    if dictionary.len() > MAX_SPELL_DICTIONARY_TERMS {
+        return Err(format!(
+            "Keep the spelling dictionary to {} terms or fewer.",
+            MAX_SPELL_DICTIONARY_TERMS
+        ));
+    }
+
+    let mut settings = load_settings(&app)?;
+    settings.spell_correction = enabled;
+    settings.spell_dictionary = dictionary;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+// Applies to the active workspace.
+#[tauri::command]
+async fn set_text_normalization(
+    app: AppHandle,
+    normalization: TextNormalization,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.text_normalization = normalization;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+// whisper.cpp keeps at most 224 prompt tokens; longer prompts are cut off.
+const MAX_CLIENT_PROMPT_CHARS: usize = 800;
+
+#[tauri::command]
+async fn set_client_prompt(
+    app: AppHandle,
+    client: String,
+    prompt: String,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let client = sanitize_non_empty(Some(client)).ok_or("Client name is required.")?;
+    let prompt = prompt.trim();
+    if prompt.chars().count() > MAX_CLIENT_PROMPT_CHARS {
+        return Err(format!(
+            "Client prompt is too long ({} characters). Keep it under {}.",
+            prompt.chars().count(),
+            MAX_CLIENT_PROMPT_CHARS
+        ));
+    }
+
+    let mut settings = load_settings(&app)?;
+    if prompt.is_empty() {
+        settings.client_prompts.remove(&client);
+    } else {
+        settings.client_prompts.insert(client, prompt.to_string());
+    }
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+// Language a client's sessions are transcribed in, overriding the one picked
+// in the recorder, and whether whisper translates them into English.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientLanguage {
+    language: String,
+    #[serde(default)]
+    translate: bool,
+}
+
+// No language clears the client's override.
+#[tauri::command]
+async fn set_duplicate_check(app: AppHandle, mode: String) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    if !DUPLICATE_CHECK_MODES.contains(&mode.as_str()) {
+        return Err(format!(
+            "Unsupported duplicate check '{}'. Valid values: {}",
+            mode,
+            DUPLICATE_CHECK_MODES.join(", ")
+        ));
+    }
+    let mut settings = load_settings(&app)?;
+    settings.duplicate_check = mode;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_client_language(
+    app: AppHandle,
+    client: String,
+    language: Option<String>,
+    translate: bool,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let client = sanitize_non_empty(Some(client)).ok_or("Client name is required.")?;
+    let language = sanitize_non_empty(language);
+    if let Some(language) = &language {
+        validate_language(language)?;
+    }
+
+    let mut settings = load_settings(&app)?;
+    match language {
+        Some(language) => {
+            settings.client_languages.insert(
+                client,
+                ClientLanguage {
+                    language,
+                    translate,
+                },
+            );
+        }
+        None => {
+            settings.client_languages.remove(&client);
+        }
+    }
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+const MAX_CLIENT_TEMPLATE_CHARS: usize = 8000;
+
+// An empty template goes back to the default one.
+#[tauri::command]
+async fn set_client_template(
+    app: AppHandle,
+    client: String,
+    template: String,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let client = sanitize_non_empty(Some(client)).ok_or("Client name is required.")?;
+    let template = template.trim();
+    if template.chars().count() > MAX_CLIENT_TEMPLATE_CHARS {
+        return Err(format!(
+            "Session template is too long ({} characters). Keep it under {}.",
+            template.chars().count(),
+            MAX_CLIENT_TEMPLATE_CHARS
+        ));
+    }
+
+    let mut settings = load_settings(&app)?;
+    if template.is_empty() {
+        settings.client_templates.remove(&client);
+    } else {
+        settings
+            .client_templates
+            .insert(client, template.to_string());
+    }
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+fn validate_language_model(language: &str, model: &str) -> Result<(), String> {
+    validate_language(language)?;
+    let model = validate_model(model)?;
+    if is_english_only_model(model.id) && language != "en" {
+        return Err(format!(
+            "Model '{}' only transcribes English and cannot be preferred for '{}'.",
+            model.id, language
+        ));
+    }
+    Ok(())
+}
+
+// A missing or empty model clears the language's preference.
+#[tauri::command]
+async fn set_language_model(
+    app: AppHandle,
+    language: String,
+    model: Option<String>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let language = language.trim().to_string();
+    validate_language(&language)?;
+
+    let mut settings = load_settings(&app)?;
+    match sanitize_non_empty(model) {
+        Some(model) => {
+            validate_language_model(&language, &model)?;
+            settings.language_models.insert(language, model);
+        }
+        None => {
+            settings.language_models.remove(&language);
+        }
+    }
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_locale(app: AppHandle, locale: String) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.locale = validate_locale(&locale).to_string();
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_date_preferences(
+    app: AppHandle,
+    timezone: Option<String>,
+    date_format: String,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let timezone = sanitize_non_empty(timezone);
+    if let Some(value) = &timezone {
+        if parse_utc_offset(value).is_none() {
+            return Err(format!(
+                "Invalid timezone offset ({}). Use UTC or an offset like +02:00.",
+                value
+            ));
+        }
+    }
+
+    let mut settings = load_settings(&app)?;
+    settings.timezone = timezone;
+    settings.date_format = validate_date_format(&date_format).to_string();
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_custom_frontmatter(
+    app: AppHandle,
+    fields: BTreeMap<String, String>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut custom_frontmatter = BTreeMap::new();
+    for (key, value) in fields {
+        let key = validate_frontmatter_key(&key)?;
+        let value = value.trim();
+        if !value.is_empty() {
+            custom_frontmatter.insert(key, value.to_string());
+        }
+    }
+
+    let mut settings = load_settings(&app)?;
+    settings.custom_frontmatter = custom_frontmatter;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_title_preferences(
+    app: AppHandle,
+    auto_title: bool,
+    title_in_filename: bool,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.auto_title = auto_title;
+    settings.title_in_filename = title_in_filename;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_auto_split_minutes(
+    app: AppHandle,
+    minutes: Option<u32>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.auto_split_minutes = validate_auto_split_minutes(minutes)?;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_timestamp_interval(
+    app: AppHandle,
+    seconds: Option<u32>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.timestamp_interval_seconds = validate_timestamp_interval(seconds)?;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_markdown_escape(app: AppHandle, level: MarkdownEscape) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.markdown_escape = level;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_input_limits(
+    app: AppHandle,
+    max_recording_minutes: u32,
+    max_audio_mb: u32,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.max_recording_minutes = max_recording_minutes;
+    settings.max_audio_mb = max_audio_mb;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+// `kbps` is in kilobytes per second; 0 removes the cap.
+#[tauri::command]
+async fn set_download_limit(app: AppHandle, kbps: u32) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.download_limit_kbps = kbps;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn prewarm_engine(app: AppHandle) -> Result<EngineStatus, String> {
+    prewarm_engine_with_status(&app).await
+}
+
+#[tauri::command]
+async fn get_coachnotes_clients(app: AppHandle, root_dir: String) -> Result<Vec<String>, String> {
+    require_unlocked(&app, None)?;
+    let trimmed = root_dir.trim();
+    if trimmed.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    list_coachnotes_clients_from_root(Path::new(trimmed))
+}
+
+#[derive(Debug, Serialize)]
+pub struct CoachnotesHealthReport {
+    root_dir: String,
+    exists: bool,
+    writable: bool,
+    // The root sits inside the standard transcript folder or contains it, so
+    // standard transcripts would show up as client folders (or the reverse).
+    overlaps_transcript_dir: bool,
+    deleted_notes_exists: bool,
+    clients: Vec<String>,
+    unreadable_clients: Vec<String>,
+    problems: Vec<String>,
+}
+
+fn is_dir_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".echo-scribe-write-check-{}", std::process::id()));
+    let created = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .is_ok();
+    if created {
+        let _ = fs::remove_file(&probe);
+    }
+    created
+}
+
+fn is_within(path: &Path, dir: &Path) -> bool {
+    match (path.canonicalize(), dir.canonicalize()) {
+        (Ok(path), Ok(dir)) => path.starts_with(dir),
+        _ => false,
+    }
+}
+
+fn paths_overlap(a: &Path, b: &Path) -> bool {
+    is_within(a, b) || is_within(b, a)
+}
+
+// Health check for the setup screen. `root_dir` defaults to the saved root.
+#[tauri::command]
+async fn validate_coachnotes_root(
+    app: AppHandle,
+    root_dir: Option<String>,
+) -> Result<CoachnotesHealthReport, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    let root_dir = sanitize_non_empty(root_dir)
+        .or_else(|| settings.coachnotes_root_dir.clone())
+        .ok_or_else(|| "No CoachNotes root folder is set.".to_string())?;
+    let root = PathBuf::from(&root_dir);
+    let mut report = CoachnotesHealthReport {
+        root_dir,
+        exists: root.is_dir(),
+        writable: false,
+        overlaps_transcript_dir: false,
+        deleted_notes_exists: false,
+        clients: Vec::new(),
+        unreadable_clients: Vec::new(),
+        problems: Vec::new(),
+    };
+    if !report.exists {
+        report.problems.push(format!(
+            "CoachNotes root does not exist: {}",
+            root.display()
+        ));
+        return Ok(report);
+    }
+
+    report.writable = is_dir_writable(&root);
+    if !report.writable {
+        report.problems.push(format!(
+            "Echo Scribe cannot create files in {}.",
+            root.display()
+        ));
+    }
+
+    let transcript_dir = resolve_transcript_dir(&settings);
+    report.overlaps_transcript_dir = paths_overlap(&root, &transcript_dir);
+    if report.overlaps_transcript_dir {
+        report.problems.push(format!(
+            "The CoachNotes root and the standard transcript folder ({}) are nested inside each other. Pick separate folders.",
+            transcript_dir.display()
+        ));
+    }
+
+    report.deleted_notes_exists = root.join(COACHNOTES_DELETED_DIR).is_dir();
+    match list_coachnotes_clients_from_root(&root) {
+        Ok(clients) => report.clients = clients,
+        Err(error) => report.problems.push(error),
+    }
+    report.unreadable_clients = report
+        .clients
+        .iter()
+        .filter(|client| fs::read_dir(root.join(client)).is_err())
+        .cloned()
+        .collect();
+    if !report.unreadable_clients.is_empty() {
+        report.problems.push(format!(
+            "These client folders cannot be read: {}",
+            report.unreadable_clients.join(", ")
+        ));
+    }
+
+    Ok(report)
+}
+
+// APFS marks iCloud and File Provider files whose contents are still in the
+// cloud with this flag (`SF_DATALESS` in sys/stat.h).
+#[cfg(target_os = "macos")]
+const SF_DATALESS: u32 = 0x4000_0000;
+
+// FILE_ATTRIBUTE_OFFLINE, FILE_ATTRIBUTE_RECALL_ON_OPEN and
+// FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: OneDrive, Dropbox and iCloud set
+// these on online-only files.
+#[cfg(windows)]
+const WINDOWS_PLACEHOLDER_ATTRIBUTES: u32 = 0x0000_1000 | 0x0004_0000 | 0x0040_0000;
+
+// Folder names the sync clients use for their roots, by provider.
+const SYNC_FOLDER_MARKERS: [(&str, &str); 6] = [
+    ("Mobile Documents", "iCloud Drive"),
+    ("iCloudDrive", "iCloud Drive"),
+    ("Dropbox", "Dropbox"),
+    ("OneDrive", "OneDrive"),
+    ("GoogleDrive", "Google Drive"),
+    ("Google Drive", "Google Drive"),
+];
+
+// The sync service that owns `path`, judged from its folder names. On macOS
+// third-party clients live under ~/Library/CloudStorage/<Provider>-<account>.
+fn sync_provider(path: &Path) -> Option<&'static str> {
+    path.components().find_map(|component| {
+        let name = component.as_os_str().to_string_lossy();
+        SYNC_FOLDER_MARKERS
+            .iter()
+            .find(|(marker, _)| name.starts_with(marker))
+            .map(|(_, provider)| *provider)
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn is_dataless(metadata: &fs::Metadata) -> bool {
+    use std::os::macos::fs::MetadataExt;
+    metadata.st_flags() & SF_DATALESS != 0
+}
+
+#[cfg(windows)]
+fn is_dataless(metadata: &fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    metadata.file_attributes() & WINDOWS_PLACEHOLDER_ATTRIBUTES != 0
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn is_dataless(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+// Older iCloud Drive builds replace an evicted `name.md` with a hidden
+// `.name.md.icloud` stub; returns `name.md` for such stubs.
+fn icloud_stub_target(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_string_lossy();
+    name.strip_prefix('.')?
+        .strip_suffix(".icloud")
+        .filter(|target| !target.is_empty())
+        .map(str::to_string)
+}
+
+// A file whose contents have not been downloaded yet: reading it either
+// blocks on the network or fails with an unhelpful IO error.
+fn is_sync_placeholder(path: &Path) -> bool {
+    icloud_stub_target(path).is_some()
+        || fs::symlink_metadata(path).is_ok_and(|metadata| is_dataless(&metadata))
+}
+
+// The file a sync conflict copy was split from. Dropbox ("name (Dana's
+// conflicted copy 2024-05-01).md") and Syncthing ("name.sync-conflict-...")
+// name theirs unambiguously; iCloud ("name 2.md") and Google Drive
+// ("name (1).md") only add a number, so those count only inside a synced
+// folder and when the original is still next to them.
+fn sync_conflict_original(path: &Path, synced: bool) -> Option<PathBuf> {
+    let stem = path.file_stem()?.to_string_lossy().to_string();
+    let extension = path
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+    let sibling =
+        |original_stem: &str| path.with_file_name(format!("{}{}", original_stem, extension));
+
+    if let Some(index) = stem.find("conflicted copy") {
+        let original_stem = stem[..index].rsplit_once(" (")?.0;
+        return Some(sibling(original_stem));
+    }
+    if let Some((original_stem, _)) = stem.split_once(".sync-conflict-") {
+        return Some(sibling(original_stem));
+    }
+    if !synced {
+        return None;
+    }
+
+    let numbered = stem
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" ("))
+        .or_else(|| stem.rsplit_once(' '))
+        .filter(|(_, number)| {
+            !number.is_empty() && number.len() <= 2 && number.chars().all(|c| c.is_ascii_digit())
+        })?;
+    let original = sibling(numbered.0);
+    original.is_file().then_some(original)
+}
+
+// Placeholders and conflict copies directly inside `dir`, sorted by path.
+fn sync_issues_in(dir: &Path) -> (Vec<PathBuf>, Vec<(PathBuf, PathBuf)>) {
+    let synced = sync_provider(dir).is_some();
+    let mut files = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| !path.is_dir())
+                .collect::<Vec<PathBuf>>()
+        })
+        .unwrap_or_default();
+    files.sort();
+
+    let placeholders = files
+        .iter()
+        .filter(|path| is_sync_placeholder(path))
+        .cloned()
+        .collect();
+    let conflicts = files
+        .iter()
+        .filter_map(|path| {
+            sync_conflict_original(path, synced).map(|original| (path.clone(), original))
+        })
+        .collect();
+    (placeholders, conflicts)
+}
+
+fn display_file_name(path: &Path) -> String {
+    icloud_stub_target(path).unwrap_or_else(|| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    })
+}
+
+// Warnings for a job saving into `dir`: same-day appends and previous-session
+// links skip files that are not downloaded, and conflict copies are never
+// picked up, so the user should know they exist.
+fn sync_warnings(dir: &Path) -> Vec<String> {
+    let (placeholders, conflicts) = sync_issues_in(dir);
+    let provider = sync_provider(dir).unwrap_or("cloud storage");
+    let mut warnings = Vec::new();
+    if !placeholders.is_empty() {
+        warnings.push(format!(
+            "{} file{} in {} {} not downloaded from {} yet and {} skipped: {}",
+            placeholders.len(),
+            if placeholders.len() == 1 { "" } else { "s" },
+            dir.display(),
+            if placeholders.len() == 1 { "is" } else { "are" },
+            provider,
+            if placeholders.len() == 1 {
+                "was"
+            } else {
+                "were"
+            },
+            placeholders
+                .iter()
+                .map(|path| display_file_name(path))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+    for (conflict, original) in conflicts {
+        warnings.push(format!(
+            "Sync conflict copy in {}: {} (conflicts with {}). Merge it by hand and delete the copy.",
+            dir.display(),
+            display_file_name(&conflict),
+            display_file_name(&original)
+        ));
+    }
+    warnings
+}
+
+// Read failures on a cloud placeholder say what to do about it instead of
+// passing on the sync client's generic IO error.
+fn read_failure(what: &str, path: &Path, error: std::io::Error) -> String {
+    if is_sync_placeholder(path) {
+        format!(
+            "The {} ({}) is an online-only {} file that could not be downloaded. Make it available offline and try again.",
+            what,
+            path.display(),
+            sync_provider(path).unwrap_or("cloud storage")
+        )
+    } else {
+        format!("Failed to read {} ({}): {}", what, path.display(), error)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncFolder {
+    path: String,
+    provider: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncConflict {
+    path: String,
+    original: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncHealthReport {
+    // The standard transcript folder and the CoachNotes root, when set.
+    folders: Vec<SyncFolder>,
+    placeholders: Vec<String>,
+    conflicts: Vec<SyncConflict>,
+    problems: Vec<String>,
+}
+
+// Looks for online-only files and conflict copies in the transcript folder
+// and every CoachNotes client folder.
+#[tauri::command]
+async fn check_sync_health(app: AppHandle) -> Result<SyncHealthReport, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    let mut roots = vec![resolve_transcript_dir(&settings)];
+    let mut dirs = roots.clone();
+    if let Some(root) = sanitize_non_empty(settings.coachnotes_root_dir.clone()) {
+        let root = PathBuf::from(root);
+        dirs.extend(
+            list_coachnotes_clients_from_root(&root)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|client| root.join(client)),
+        );
+        dirs.push(root.clone());
+        roots.push(root);
+    }
+
+    let mut report = SyncHealthReport {
+        folders: roots
+            .iter()
+            .map(|root| SyncFolder {
+                path: root.display().to_string(),
+                provider: sync_provider(root).map(str::to_string),
+            })
+            .collect(),
+        placeholders: Vec::new(),
+        conflicts: Vec::new(),
+        problems: Vec::new(),
+    };
+    for dir in &dirs {
+        let (placeholders, conflicts) = sync_issues_in(dir);
+        report
+            .placeholders
+            .extend(placeholders.iter().map(|path| path.display().to_string()));
+        report.conflicts.extend(
+            conflicts
+                .into_iter()
+                .map(|(conflict, original)| SyncConflict {
+                    path: conflict.display().to_string(),
+                    original: original.display().to_string(),
+                }),
+        );
+    }
+
+    if !report.placeholders.is_empty() {
+        report.problems.push(format!(
+            "{} file{} not downloaded yet. Mark the folders as available offline so searches and same-day appends see them.",
+            report.placeholders.len(),
+            if report.placeholders.len() == 1 { " is" } else { "s are" }
+        ));
+    }
+    if !report.conflicts.is_empty() {
+        report.problems.push(format!(
+            "{} sync conflict cop{} found. Echo Scribe ignores them; merge each into its original and delete the copy.",
+            report.conflicts.len(),
+            if report.conflicts.len() == 1 { "y" } else { "ies" }
+        ));
+    }
+
+    Ok(report)
+}
+
+#[tauri::command]
+async fn set_coachnotes_settings(
+    app: AppHandle,
+    input: CoachNotesSettingsInput,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+
+    let root = sanitize_non_empty(input.root_dir);
+    if let Some(root_dir) = &root {
+        fs::create_dir_all(root_dir).map_err(|e| {
+            format!(
+                "Failed to ensure CoachNotes root exists ({}): {}",
+                root_dir, e
+            )
+        })?;
+    }
+
+    settings.coachnotes_enabled = input.enabled;
+    settings.coachnotes_root_dir = root;
+    settings.coachnotes_client = sanitize_non_empty(input.client);
+    settings.coachnotes_append_same_day = input.append_same_day;
+    settings.coachnotes_inline_session_links = input.inline_session_links;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+const UPDATE_CHANNELS: [&str; 2] = ["stable", "beta"];
+// `latest.json` manifests published by the release workflow. Stable follows
+// the newest non-prerelease; the rolling `beta` release also carries every
+// prerelease.
+const STABLE_UPDATE_ENDPOINT: &str =
+    "https://github.com/AdamPallus/echo-scribe-app/releases/latest/download/latest.json";
+const BETA_UPDATE_ENDPOINT: &str =
+    "https://github.com/AdamPallus/echo-scribe-app/releases/download/beta/latest.json";
+
+fn validate_update_channel(channel: &str) -> &'static str {
+    match channel {
+        "beta" => "beta",
+        _ => "stable",
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateInfo {
+    channel: String,
+    current_version: String,
+    available: bool,
+    version: Option<String>,
+    notes: Option<String>,
+    date: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct UpdateDownloadProgress {
+    downloaded_bytes: u64,
+    total_bytes: Option<u64>,
+    percent: u8,
+}
+
+// Builds without a signing key in `plugins.updater.pubkey` cannot verify a
+// release, so they do not look for one.
+fn updater_configured(app: &AppHandle) -> bool {
+    app.config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|updater| updater.get("pubkey"))
+        .and_then(serde_json::Value::as_str)
+        .is_some_and(|pubkey| !pubkey.trim().is_empty())
+}
+
+async fn check_update_channel(
+    app: &AppHandle,
+) -> Result<(String, Option<tauri_plugin_updater::Update>), String> {
+    if !updater_configured(app) {
+        return Err(
+            "Updates are not configured in this build: it has no updater signing key.".to_string(),
+        );
+    }
+    let settings = load_settings(app)?;
+    ensure_network_allowed(&settings)?;
+    let channel = validate_update_channel(&settings.update_channel).to_string();
+    let endpoint = if channel == "beta" {
+        BETA_UPDATE_ENDPOINT
+    } else {
+        STABLE_UPDATE_ENDPOINT
+    };
+    let endpoint = tauri::Url::parse(endpoint)
+        .map_err(|e| format!("Invalid update endpoint ({}): {}", endpoint, e))?;
+    let update = app
+        .updater_builder()
+        .endpoints(vec![endpoint])
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("Failed to initialize updater: {}", e))?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+    Ok((channel, update))
+}
+
+#[tauri::command]
+async fn check_for_updates(app: AppHandle) -> Result<UpdateInfo, String> {
+    let (channel, update) = check_update_channel(&app).await?;
+    Ok(UpdateInfo {
+        channel,
+        current_version: app.package_info().version.to_string(),
+        available: update.is_some(),
+        version: update.as_ref().map(|update| update.version.clone()),
+        notes: update.as_ref().and_then(|update| update.body.clone()),
+        date: update
+            .as_ref()
+            .and_then(|update| update.date)
+            .map(format_iso8601),
+    })
+}
+
+// Downloads and installs the newest release on the selected channel, emitting
+// `update-download-progress`, then restarts into the new version.
+#[tauri::command]
+async fn install_update(app: AppHandle) -> Result<(), String> {
+    let (_, update) = check_update_channel(&app).await?;
+    let Some(update) = update else {
+        return Err("Echo Scribe is already up to date.".to_string());
+    };
+
+    let mut downloaded_bytes = 0u64;
+    update
+        .download_and_install(
+            |chunk_length, total_bytes| {
+                downloaded_bytes += chunk_length as u64;
+                let percent = total_bytes
+                    .filter(|total| *total > 0)
+                    .map(|total| ((downloaded_bytes * 100) / total).min(100) as u8)
+                    .unwrap_or(0);
+                let _ = app.emit(
+                    "update-download-progress",
+                    UpdateDownloadProgress {
+                        downloaded_bytes,
+                        total_bytes,
+                        percent,
+                    },
+                );
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| format!("Failed to install update: {}", e))?;
+
+    app.restart()
+}
+
+// Language codes accepted by whisper.cpp (`-l`), in whisper's own order.
+const WHISPER_LANGUAGES: [(&str, &str); 100] = [
+    ("en", "English"),
+    ("zh", "Chinese"),
+    ("de", "German"),
+    ("es", "Spanish"),
+    ("ru", "Russian"),
+    ("ko", "Korean"),
+    ("fr", "French"),
+    ("ja", "Japanese"),
+    ("pt", "Portuguese"),
+    ("tr", "Turkish"),
+    ("pl", "Polish"),
+    ("ca", "Catalan"),
+    ("nl", "Dutch"),
+    ("ar", "Arabic"),
+    ("sv", "Swedish"),
+    ("it", "Italian"),
+    ("id", "Indonesian"),
+    ("hi", "Hindi"),
+    ("fi", "Finnish"),
+    ("vi", "Vietnamese"),
+    ("he", "Hebrew"),
+    ("uk", "Ukrainian"),
+    ("el", "Greek"),
+    ("ms", "Malay"),
+    ("cs", "Czech"),
+    ("ro", "Romanian"),
+    ("da", "Danish"),
+    ("hu", "Hungarian"),
+    ("ta", "Tamil"),
+    ("no", "Norwegian"),
+    ("th", "Thai"),
+    ("ur", "Urdu"),
+    ("hr", "Croatian"),
+    ("bg", "Bulgarian"),
+    ("lt", "Lithuanian"),
+    ("la", "Latin"),
+    ("mi", "Maori"),
+    ("ml", "Malayalam"),
+    ("cy", "Welsh"),
+    ("sk", "Slovak"),
+    ("te", "Telugu"),
+    ("fa", "Persian"),
+    ("lv", "Latvian"),
+    ("bn", "Bengali"),
+    ("sr", "Serbian"),
+    ("az", "Azerbaijani"),
+    ("sl", "Slovenian"),
+    ("kn", "Kannada"),
+    ("et", "Estonian"),
+    ("mk", "Macedonian"),
+    ("br", "Breton"),
+    ("eu", "Basque"),
+    ("is", "Icelandic"),
+    ("hy", "Armenian"),
+    ("ne", "Nepali"),
+    ("mn", "Mongolian"),
+    ("bs", "Bosnian"),
+    ("kk", "Kazakh"),
+    ("sq", "Albanian"),
+    ("sw", "Swahili"),
+    ("gl", "Galician"),
+    ("mr", "Marathi"),
+    ("pa", "Punjabi"),
+    ("si", "Sinhala"),
+    ("km", "Khmer"),
+    ("sn", "Shona"),
+    ("yo", "Yoruba"),
+    ("so", "Somali"),
+    ("af", "Afrikaans"),
+    ("oc", "Occitan"),
+    ("ka", "Georgian"),
+    ("be", "Belarusian"),
+    ("tg", "Tajik"),
+    ("sd", "Sindhi"),
+    ("gu", "Gujarati"),
+    ("am", "Amharic"),
+    ("yi", "Yiddish"),
+    ("lo", "Lao"),
+    ("uz", "Uzbek"),
+    ("fo", "Faroese"),
+    ("ht", "Haitian Creole"),
+    ("ps", "Pashto"),
+    ("tk", "Turkmen"),
+    ("nn", "Nynorsk"),
+    ("mt", "Maltese"),
+    ("sa", "Sanskrit"),
+    ("lb", "Luxembourgish"),
+    ("my", "Myanmar"),
+    ("bo", "Tibetan"),
+    ("tl", "Tagalog"),
+    ("mg", "Malagasy"),
+    ("as", "Assamese"),
+    ("tt", "Tatar"),
+    ("haw", "Hawaiian"),
+    ("ln", "Lingala"),
+    ("ha", "Hausa"),
+    ("ba", "Bashkir"),
+    ("jw", "Javanese"),
+    ("su", "Sundanese"),
+    ("yue", "Cantonese"),
+];
+
+#[derive(Debug, Serialize)]
+pub struct SupportedLanguage {
+    code: String,
+    name: String,
+    // `.en` models only transcribe English.
+    compatible: bool,
+}
+
+fn is_english_only_model(model_id: &str) -> bool {
+    model_id.contains(".en")
+}
+
+fn validate_language(language: &str) -> Result<(), String> {
+    if language == "auto" || WHISPER_LANGUAGES.iter().any(|(code, _)| *code == language) {
+        return Ok(());
+    }
+    Err(format!(
+        "Unsupported language '{}'. Use 'auto' or a whisper language code such as 'en' or 'de'.",
+        language
+    ))
+}
+
+#[tauri::command]
+async fn get_supported_languages(
+    app: AppHandle,
+    model: Option<String>,
+) -> Result<Vec<SupportedLanguage>, String> {
+    let model_id = match model {
+        Some(model) => model,
+        None => load_settings(&app)?.selected_model,
+    };
+    // "auto" never prefers an English-only model outside English.
+    let english_only =
+        model_id != AUTO_MODEL && is_english_only_model(validate_model(&model_id)?.id);
+
+    let mut languages = vec![SupportedLanguage {
+        code: "auto".to_string(),
+        name: "Auto-detect".to_string(),
+        compatible: !english_only,
+    }];
+    languages.extend(
+        WHISPER_LANGUAGES
+            .iter()
+            .map(|(code, name)| SupportedLanguage {
+                code: code.to_string(),
+                name: name.to_string(),
+                compatible: !english_only || *code == "en",
+            }),
+    );
+    Ok(languages)
+}
+
+// With `offline_mode` on the backend makes no outbound connections. Anything
+// that would reach the network (model downloads, update checks, and any
+// future integration) has to pass this check first, and HTTP requests are
+// made only through `http_client`.
+fn ensure_network_allowed(settings: &AppSettings) -> Result<(), String> {
+    if settings.offline_mode {
+        return Err(format!(
+            "[offline_mode] {}",
+            localize(&settings.locale, Message::OfflineModeBlocked)
+        ));
+    }
+    Ok(())
+}
+
+fn http_client(settings: &AppSettings) -> Result<reqwest::Client, String> {
+    ensure_network_allowed(settings)?;
+    reqwest::Client::builder()
+        .build()
+        .map_err(|e| format!("Failed to initialize HTTP client: {}", e))
+}
+
+#[tauri::command]
+async fn set_verbatim(app: AppHandle, enabled: bool) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.verbatim = enabled;
+    save_settings(&app, &settings)?;
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_offline_mode(app: AppHandle, enabled: bool) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.offline_mode = enabled;
+    save_settings(&app, &settings)?;
+    build_setup_state(&app)
+}
+
+// Gated and private models on Hugging Face need an access token. It is sent
+// only to huggingface.co; reqwest drops it when a download redirects to the
+// CDN.
+const HUGGINGFACE_TOKEN_SECRET: &str = "huggingface-token";
+const HUGGINGFACE_HOST: &str = "huggingface.co";
+
+fn is_huggingface_url(url: &str) -> bool {
+    tauri::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .is_some_and(|host| {
+            host == HUGGINGFACE_HOST || host.ends_with(&format!(".{}", HUGGINGFACE_HOST))
+        })
+}
+
+// A GET request for `url`, with the Hugging Face token attached when one is
+// saved and the URL is on huggingface.co.
+fn model_request(
+    app: &AppHandle,
+    settings: &AppSettings,
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<reqwest::RequestBuilder, String> {
+    let request = client.get(url);
+    if !settings.huggingface_token_set || !is_huggingface_url(url) {
+        return Ok(request);
+    }
+    Ok(match read_secret(app, HUGGINGFACE_TOKEN_SECRET)? {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    })
+}
+
+// An empty token removes the saved one.
+#[tauri::command]
+async fn set_huggingface_token(app: AppHandle, token: String) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let token = token.trim();
+    if token.chars().any(char::is_whitespace) {
+        return Err("A Hugging Face token cannot contain spaces.".to_string());
+    }
+    if token.is_empty() {
+        delete_secret_entry(&app, HUGGINGFACE_TOKEN_SECRET)?;
+    } else {
+        secret_entry(&app, HUGGINGFACE_TOKEN_SECRET)?
+            .set_password(token)
+            .map_err(|e| format!("Failed to save the Hugging Face token: {}", e))?;
+    }
+
+    let mut settings = load_settings(&app)?;
+    settings.huggingface_token_set = !token.is_empty();
+    save_settings(&app, &settings)?;
+    build_setup_state(&app)
+}
+
+fn model_file_exists_error(locale: &str, model: &str, path: &Path) -> String {
+    let path = path.to_string_lossy();
+    localize_with(
+        locale,
+        Message::ModelFileExists,
+        &[("model", model), ("path", path.as_ref())],
+    )
+}
+
+#[tauri::command]
+async fn download_model(
+    app: AppHandle,
+    options: ModelDownloadOptions,
+) -> Result<ModelDownloadResult, String> {
+    require_unlocked(&app, None)?;
+    let model = validate_model(&options.model)?;
+    let settings = load_settings(&app).unwrap_or_default();
+    let locale = settings.locale.clone();
+
+    let model_dir = models_dir(&app)?;
+    fs::create_dir_all(&model_dir).map_err(|e| {
+        format!(
+            "Failed to create models directory ({}): {}",
+            model_dir.display(),
+            e
+        )
+    })?;
+
+    let target_path = model_dir.join(format!("ggml-{}.bin", model.id));
+    let temp_path = target_path.with_extension("bin.part");
+
+    let expected_checksum = model.sha256;
+
+    if target_path.exists() {
+        let existing_checksum = sha256_for_file(&target_path, |percent| {
+            emit_model_download_progress(
+                &app,
+                model.id,
+                percent.max(1),
+                0,
+                None,
+                &localize_with(
+                    &locale,
+                    Message::VerifyingExistingModel,
+                    &[("percent", &percent.to_string())],
+                ),
+            );
+        })
+        .await?;
+        if existing_checksum == expected_checksum {
+            emit_model_download_progress(
+                &app,
+                model.id,
+                100,
+                0,
+                None,
+                &localize(&locale, Message::ModelAlreadyDownloaded),
+            );
+            return Ok(ModelDownloadResult {
+                model: model.id.to_string(),
+                path: target_path.to_string_lossy().to_string(),
+            });
+        }
+        if !options.overwrite {
+            return Err(model_file_exists_error(&locale, model.id, &target_path));
+        }
+        let _ = fs::remove_file(&target_path);
+    }
+
+    let client = http_client(&settings)?;
+    let _ = fs::remove_file(&temp_path);
+    emit_model_download_progress(
+        &app,
+        model.id,
+        2,
+        0,
+        None,
+        &localize(&locale, Message::StartingDownload),
+    );
+
+    let response = model_request(&app, &settings, &client, model.url)?
+        .send()
+        .await
+        .map_err(|e| format!("Model download failed: {}", e))?;
+
+    let status = response.status();
+    if matches!(status.as_u16(), 401 | 403) && is_huggingface_url(model.url) {
+        return Err(format!(
+            "Model download failed with HTTP status {}. The {} model is gated or private on Hugging Face: accept its terms on huggingface.co and check the access token under Models.",
+            status, model.id
+        ));
+    }
+    if !status.is_success() {
+        return Err(format!("Model download failed with HTTP status {}", status));
+    }
+
+    let total_bytes = response.content_length();
+    let mut stream = response.bytes_stream();
+    let mut file = tokio::fs::File::create(&temp_path)
+        .await
+        .map_err(|e| format!("Failed to create temp model file: {}", e))?;
+
+    let mut hasher = Sha256::new();
+    let mut downloaded_bytes: u64 = 0;
+    let started = Instant::now();
+
+    while let Some(next) = stream.next().await {
+        let chunk = next.map_err(|e| format!("Download stream failed: {}", e))?;
+
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Failed to write model file: {}", e))?;
+
+        hasher.update(&chunk);
+        downloaded_bytes += chunk.len() as u64;
+
+        let percent = total_bytes
+            .map(|total| ((downloaded_bytes.saturating_mul(100)) / total.max(1)) as u32)
+            .unwrap_or(0)
+            .min(99);
+
+        emit_model_download_progress(
+            &app,
+            model.id,
+            percent.max(2),
+            downloaded_bytes,
+            total_bytes,
+            &localize(&locale, Message::DownloadingModel),
+        );
+
+        // Reading no faster than the cap lets TCP flow control slow the
+        // sender down too, so the limit holds for the whole connection.
+        if settings.download_limit_kbps > 0 {
+            let allowed = Duration::from_secs_f64(
+                downloaded_bytes as f64 / (f64::from(settings.download_limit_kbps) * 1024.0),
+            );
+            if let Some(wait) = allowed.checked_sub(started.elapsed()) {
+                tokio::time::sleep(wait).await;
+            }
+        }
+    }
+
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to flush model file: {}", e))?;
+
+    let actual_checksum = format!("{:x}", hasher.finalize());
+
+    if actual_checksum != expected_checksum {
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!(
+            "Checksum mismatch for {} model. Expected {}, got {}.",
+            model.id, expected_checksum, actual_checksum
+        ));
+    }
+
+    // Another download may have finished while this one was running.
+    if target_path.exists() {
+        if !options.overwrite {
+            let _ = fs::remove_file(&temp_path);
+            return Err(model_file_exists_error(&locale, model.id, &target_path));
+        }
+        let _ = fs::remove_file(&target_path);
+    }
+
+    tokio::fs::rename(&temp_path, &target_path)
+        .await
+        .map_err(|e| format!("Failed to finalize model file: {}", e))?;
+
+    emit_model_download_progress(
+        &app,
+        model.id,
+        100,
+        downloaded_bytes,
+        total_bytes,
+        &localize(&locale, Message::ModelDownloadComplete),
+    );
+    notify_completion(
+        &app,
+        Message::NotifyModelDownloaded,
+        &[("model", model.id)],
+        &target_path.to_string_lossy(),
+    );
+    if settings.verify_models_in_background {
+        spawn_model_reverification(app.clone(), model, target_path.clone(), locale);
+    }
+
+    Ok(ModelDownloadResult {
+        model: model.id.to_string(),
+        path: target_path.to_string_lossy().to_string(),
+    })
+}
+
+// The download is finalized from the checksum of the bytes as they streamed
+// in; this re-reads the file once it is on disk to catch corruption in the
+// write itself, and reports through `model-verified`.
+fn spawn_model_reverification(
+    app: AppHandle,
+    model: &'static ModelCatalogEntry,
+    path: PathBuf,
+    locale: String,
+) {
+    tauri::async_runtime::spawn(async move {
+        let (verified, message) = match sha256_for_file(&path, |_| {}).await {
+            Ok(checksum) if checksum == model.sha256 => (
+                true,
+                localize_with(
+                    &locale,
+                    Message::ModelVerifiedOnDisk,
+                    &[("model", model.id)],
+                ),
+            ),
+            Ok(_) => {
+                report_corrupted_model(&app, model, &path);
+                (
+                    false,
+                    localize_with(&locale, Message::ModelCorruptOnDisk, &[("model", model.id)]),
+                )
+            }
+            Err(error) => (false, error),
+        };
+        let _ = app.emit(
+            "model-verified",
+            ModelVerifiedPayload {
+                model: model.id.to_string(),
+                path: path.to_string_lossy().to_string(),
+                verified,
+                message,
+            },
+        );
+    });
+}
+
+// Announces a model whose file no longer matches its checksum through
+// `model-corrupted`. With `redownload_corrupted_models` on it is replaced
+// right away; otherwise the frontend offers the download.
+fn report_corrupted_model(app: &AppHandle, model: &'static ModelCatalogEntry, path: &Path) {
+    let settings = load_settings(app).unwrap_or_default();
+    let redownloading = settings.redownload_corrupted_models && !settings.offline_mode;
+    let message = localize_with(
+        &settings.locale,
+        if redownloading {
+            Message::ModelCorruptRedownloading
+        } else {
+            Message::ModelCorruptOnDisk
+        },
+        &[("model", model.id)],
+    );
+    let _ = app.emit(
+        "model-corrupted",
+        ModelCorruptedPayload {
+            model: model.id.to_string(),
+            path: path.to_string_lossy().to_string(),
+            redownloading,
+            message,
+        },
+    );
+
+    if redownloading {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let options = ModelDownloadOptions {
+                model: model.id.to_string(),
+                overwrite: true,
+            };
+            if let Err(error) = download_model(app.clone(), options).await {
+                emit_model_download_progress(&app, model.id, 0, 0, None, &error);
+            }
+        });
+    }
+}
+
+// Re-hashes the model behind a failed job, in the background so the failure
+// is reported without waiting on it.
+fn check_model_after_failure(app: &AppHandle, model_id: &str) {
+    let Some(model) = find_model(model_id) else {
+        return;
+    };
+    let Ok(path) = model_file_path(app, model.id) else {
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if !path.exists() {
+            return;
+        }
+        if let Ok(checksum) = sha256_for_file(&path, |_| {}).await {
+            if checksum != model.sha256 {
+                report_corrupted_model(&app, model, &path);
+            }
+        }
+    });
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModelVerification {
+    model: String,
+    path: String,
+    verified: bool,
+}
+
+#[tauri::command]
+async fn verify_models(app: AppHandle) -> Result<Vec<ModelVerification>, String> {
+    require_unlocked(&app, None)?;
+    let locale = load_settings(&app)
+        .map(|settings| settings.locale)
+        .unwrap_or_default();
+    let mut results = Vec::new();
+    for model in MODEL_CATALOG.iter() {
+        let path = model_file_path(&app, model.id)?;
+        if !path.exists() {
+            continue;
+        }
+
+        let checksum = sha256_for_file(&path, |percent| {
+            emit_model_download_progress(
+                &app,
+                model.id,
+                percent,
+                0,
+                None,
+                &localize_with(
+                    &locale,
+                    Message::VerifyingExistingModel,
+                    &[("percent", &percent.to_string())],
+                ),
+            );
+        })
+        .await?;
+        let verified = checksum == model.sha256;
+        if !verified {
+            report_corrupted_model(&app, model, &path);
+        }
+        results.push(ModelVerification {
+            model: model.id.to_string(),
+            path: path.to_string_lossy().to_string(),
+            verified,
+        });
+    }
+    Ok(results)
+}
+
+fn benchmark_sample_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let sample_path = app
+        .path()
+        .resolve(
+            BENCHMARK_SAMPLE_RESOURCE,
+            tauri::path::BaseDirectory::Resource,
+        )
+        .map_err(|e| format!("Failed to locate the benchmark sample: {}", e))?;
+    if !sample_path.is_file() {
+        return Err(format!(
+            "The benchmark sample is only bundled with the macOS app ({}).",
+            sample_path.display()
+        ));
+    }
+    Ok(sample_path)
+}
+
+// Resident memory of a running process in KiB, from `ps`. `None` where `ps`
+// is unavailable (Windows) or the process has already exited.
+async fn resident_memory_kib(pid: u32) -> Option<u64> {
+    let output = tokio::process::Command::new("ps")
+        .args(["-o", "rss=", "-p", &pid.to_string()])
+        .output()
+        .await
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+// Runs the whisper sidecar to completion, timing it and sampling its resident
+// memory every `BENCHMARK_MEMORY_POLL` for the peak. Nothing here blocks the
+// async runtime.
+async fn run_whisper_measured(
+    app: &AppHandle,
+    args: &[String],
+) -> Result<(Duration, Option<u64>), String> {
+    let command = app
+        .shell()
+        .sidecar("whisper-cli")
+        .map_err(|e| format!("Whisper sidecar is unavailable: {}", e))?;
+    let started = Instant::now();
+    let (mut events, child) = command
+        .args(args)
+        .spawn()
+        .map_err(|e| format!("Failed to run whisper: {}", e))?;
+    let pid = child.pid();
+    let mut poll = tokio::time::interval(BENCHMARK_MEMORY_POLL);
+    let mut peak_kib: Option<u64> = None;
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+
+    let status = loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Some(CommandEvent::Stdout(line)) => {
+                    stdout.extend_from_slice(&line);
+                    stdout.push(b'\n');
+                }
+                Some(CommandEvent::Stderr(line)) => {
+                    stderr.extend_from_slice(&line);
+                    stderr.push(b'\n');
+                }
+                Some(CommandEvent::Error(error)) => return Err(format!("Whisper failed: {}", error)),
+                Some(CommandEvent::Terminated(payload)) => break payload,
+                Some(_) => {}
+                None => return Err("Whisper exited without a status".to_string()),
+            },
+            _ = poll.tick() => {
+                if let Some(kib) = resident_memory_kib(pid).await {
+                    peak_kib = Some(peak_kib.map_or(kib, |peak| peak.max(kib)));
+                }
+            }
+        }
+    };
+    let elapsed = started.elapsed();
+
+    if status.code != Some(0) {
+        return Err(format!(
+            "Whisper failed: {}",
+            process_output_detail(&stdout, &stderr)
+        ));
+    }
+
+    Ok((elapsed, peak_kib.map(|kib| kib * 1024)))
+}
+
+#[tauri::command]
+async fn run_benchmark(
+    app: AppHandle,
+    models: Vec<String>,
+) -> Result<Vec<BenchmarkResult>, String> {
+    let model_ids = if models.is_empty() {
+        MODEL_CATALOG
+            .iter()
+            .filter(|entry| {
+                model_file_path(&app, entry.id)
+                    .map(|path| path.exists())
+                    .unwrap_or(false)
+            })
+            .map(|entry| entry.id.to_string())
+            .collect::<Vec<String>>()
+    } else {
+        models
+    };
+
+    if model_ids.is_empty() {
+        return Err("No downloaded models to benchmark. Download a model first.".to_string());
+    }
+
+    let sample_path = benchmark_sample_path(&app)?;
+    let sample = fs::read(&sample_path).map_err(|e| {
+        format!(
+            "Failed to read benchmark sample ({}): {}",
+            sample_path.display(),
+            e
+        )
+    })?;
+    let audio_seconds = estimate_duration_ms(&sample) as f64 / 1000.0;
+    let mut results = Vec::new();
+
+    for (index, model_id) in model_ids.iter().enumerate() {
+        let model = validate_model(model_id)?;
+        let model_path = model_file_path(&app, model.id)?;
+        let mut result = BenchmarkResult {
+            model: model.id.to_string(),
+            audio_seconds,
+            wall_seconds: 0.0,
+            real_time_factor: 0.0,
+            peak_memory_mb: None,
+            error: None,
+        };
+
+        if !model_path.exists() {
+            result.error = Some("Model is not downloaded.".to_string());
+            results.push(result);
+            continue;
+        }
+
+        let _ = app.emit(
+            "benchmark-progress",
+            ProgressPayload {
+                percent: ((index * 100) / model_ids.len()) as u32,
+                message: format!("Benchmarking {} model...", model.id),
+            },
+        );
+
+        let args = vec![
+            "-m".to_string(),
+            model_path.to_string_lossy().to_string(),
+            "-f".to_string(),
+            sample_path.to_string_lossy().to_string(),
+            "-l".to_string(),
+            "en".to_string(),
+            "-np".to_string(),
+        ];
+
+        match run_whisper_measured(&app, &args).await {
+            Ok((elapsed, peak_memory_bytes)) => {
+                result.wall_seconds = round_to(elapsed.as_secs_f64(), 2);
+                result.real_time_factor = if audio_seconds > 0.0 {
+                    round_to(elapsed.as_secs_f64() / audio_seconds, 3)
+                } else {
+                    0.0
+                };
+                result.peak_memory_mb = peak_memory_bytes.map(|bytes| bytes / (1024 * 1024));
+            }
+            Err(error) => result.error = Some(error),
+        }
+
+        results.push(result);
+    }
+
+    let _ = app.emit(
+        "benchmark-progress",
+        ProgressPayload {
+            percent: 100,
+            message: "Benchmark complete.".to_string(),
+        },
+    );
+
+    Ok(results)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EditOp {
+    Equal(usize, usize),
+    Substitute(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+// Edit costs of turning `a` into every prefix of `b`, one row of the
+// Levenshtein table in O(len(b)) memory.
+fn edit_distance_row<T: PartialEq>(a: &[T], b: &[T]) -> Vec<usize> {
+    let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_item) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_item) in b.iter().enumerate() {
+            current[j + 1] = (previous[j] + usize::from(a_item != b_item))
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous
+}
+
+fn edit_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    edit_distance_row(a, b)[b.len()]
+}
+
+const FULL_ALIGNMENT_CELLS: usize = 4096;
+
+// Small inputs: the full table with a backtrace.
+fn align_full<T: PartialEq>(a: &[T], b: &[T], offset: (usize, usize), ops: &mut Vec<EditOp>) {
+    let width = b.len() + 1;
+    let mut table = vec![0usize; (a.len() + 1) * width];
+    for i in 0..=a.len() {
+        for j in 0..=b.len() {
+            table[i * width + j] = if i == 0 {
+                j
+            } else if j == 0 {
+                i
+            } else {
+                (table[(i - 1) * width + j - 1] + usize::from(a[i - 1] != b[j - 1]))
+                    .min(table[(i - 1) * width + j] + 1)
+                    .min(table[i * width + j - 1] + 1)
+            };
+        }
+    }
+    let (mut i, mut j) = (a.len(), b.len());
+    let start = ops.len();
+    while i > 0 || j > 0 {
+        let cost = table[i * width + j];
+        if i > 0
+            && j > 0
+            && cost == table[(i - 1) * width + j - 1] + usize::from(a[i - 1] != b[j - 1])
+        {
+            i -= 1;
+            j -= 1;
+            ops.push(if a[i] == b[j] {
+                EditOp::Equal(offset.0 + i, offset.1 + j)
+            } else {
+                EditOp::Substitute(offset.0 + i, offset.1 + j)
+            });
+        } else if i > 0 && cost == table[(i - 1) * width + j] + 1 {
+            i -= 1;
+            ops.push(EditOp::Delete(offset.0 + i));
+        } else {
+            j -= 1;
+            ops.push(EditOp::Insert(offset.1 + j));
+        }
+    }
+    ops[start..].reverse();
+}
+
+// Hirschberg's split keeps memory linear, so hour-long transcripts align
+// without a table of every word pair.
+fn align_range<T: PartialEq>(a: &[T], b: &[T], offset: (usize, usize), ops: &mut Vec<EditOp>) {
+    if a.len() <= 1 || (a.len() + 1) * (b.len() + 1) <= FULL_ALIGNMENT_CELLS {
+        align_full(a, b, offset, ops);
+        return;
+    }
+    let middle = a.len() / 2;
+    let left = edit_distance_row(&a[..middle], b);
+    let a_tail = a[middle..].iter().rev().collect::<Vec<&T>>();
+    let b_reversed = b.iter().rev().collect::<Vec<&T>>();
+    let right = edit_distance_row(&a_tail, &b_reversed);
+    let split = (0..=b.len())
+        .min_by_key(|&j| left[j] + right[b.len() - j])
+        .unwrap_or(0);
+    align_range(&a[..middle], &b[..split], offset, ops);
+    align_range(
+        &a[middle..],
+        &b[split..],
+        (offset.0 + middle, offset.1 + split),
+        ops,
+    );
+}
+
+fn align_tokens<T: PartialEq>(a: &[T], b: &[T]) -> Vec<EditOp> {
+    let mut ops = Vec::new();
+    align_range(a, b, (0, 0), &mut ops);
+    ops
+}
+
+// Lowercase with surrounding punctuation removed, so "Okay," matches "okay".
+fn comparison_key(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiffSpan {
+    // "equal", "substitute", "delete" (only in the first text) or "insert"
+    // (only in the second).
+    op: &'static str,
+    from: String,
+    to: String,
+}
+
+// Word-level alignment of two texts, with runs of the same operation joined
+// into one span. Words compare by `comparison_key` but keep their spelling.
+fn word_diff(from: &str, to: &str) -> Vec<DiffSpan> {
+    let tokenize = |text: &str| {
+        text.split_whitespace()
+            .map(|word| (comparison_key(word), word.to_string()))
+            .filter(|(key, _)| !key.is_empty())
+            .collect::<Vec<(String, String)>>()
+    };
+    let (from, to) = (tokenize(from), tokenize(to));
+    let from_keys = from.iter().map(|(key, _)| key).collect::<Vec<&String>>();
+    let to_keys = to.iter().map(|(key, _)| key).collect::<Vec<&String>>();
+
+    let mut spans: Vec<DiffSpan> = Vec::new();
+    for op in align_tokens(&from_keys, &to_keys) {
+        let (name, from_word, to_word) = match op {
+            EditOp::Equal(i, j) => ("equal", Some(&from[i].1), Some(&to[j].1)),
+            EditOp::Substitute(i, j) => ("substitute", Some(&from[i].1), Some(&to[j].1)),
+            EditOp::Delete(i) => ("delete", Some(&from[i].1), None),
+            EditOp::Insert(j) => ("insert", None, Some(&to[j].1)),
+        };
+        if spans.last().map_or(true, |span| span.op != name) {
+            spans.push(DiffSpan {
+                op: name,
+                from: String::new(),
+                to: String::new(),
+            });
+        }
+        let Some(span) = spans.last_mut() else {
+            continue;
+        };
+        for (text, word) in [(&mut span.from, from_word), (&mut span.to, to_word)] {
+            if let Some(word) = word {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(word);
+            }
+        }
+    }
+    spans
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComparedTranscript {
+    model: String,
+    transcript: String,
+    word_count: usize,
+    wall_seconds: f64,
+    real_time_factor: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModelComparison {
+    audio_seconds: f64,
+    transcripts: Vec<ComparedTranscript>,
+    // From the first model's transcript to the second's.
+    diff: Vec<DiffSpan>,
+    changed_words: usize,
+    warnings: Vec<String>,
+}
+
+// Transcribes the same audio with two models, one after the other and in
+// the job queue's slot, so the timings are not skewed by a running job.
+#[tauri::command]
+async fn compare_models(
+    app: AppHandle,
+    audio: WaveformSource,
+    models: Vec<String>,
+    language: Option<String>,
+) -> Result<ModelComparison, String> {
+    let [first, second] = models.as_slice() else {
+        return Err("Pick exactly two models to compare.".to_string());
+    };
+    if first == second {
+        return Err("Pick two different models to compare.".to_string());
+    }
+    let models = [validate_model(first)?, validate_model(second)?];
+    let settings = load_settings(&app)?;
+    let locale = settings.locale.as_str();
+    let wav_data = match audio {
+        WaveformSource::Path(path) => {
+            let path = PathBuf::from(path);
+            require_unlocked(&app, Some(&path))?;
+            fs::read(&path)
+                .map_err(|e| format!("Failed to read audio file ({}): {}", path.display(), e))?
+        }
+        WaveformSource::Bytes(bytes) => bytes,
+    };
+    let info = validate_wav(
+        &wav_data,
+        AudioTrack::Recording,
+        MIN_AUDIO_DURATION_MS,
+        locale,
+    )?;
+    let (wav_data, warning) = convert_for_whisper(&wav_data, info, locale)?;
+    let mut warnings = warning.into_iter().collect::<Vec<String>>();
+    let audio_seconds = estimate_duration_ms(&wav_data) as f64 / 1000.0;
+    let language = sanitize_non_empty(language).unwrap_or_else(|| "auto".to_string());
+    let timestamp = unix_timestamp_secs()?;
+
+    let _slot = app.state::<TranscriptionQueue>().slot.lock().await;
+    let mut transcripts = Vec::new();
+    for (index, model) in models.iter().enumerate() {
+        let model_path = model_file_path(&app, model.id)?;
+        if !model_path.exists() {
+            return Err(localize_with(
+                locale,
+                Message::ModelNotDownloaded,
+                &[("model", model.id)],
+            ));
+        }
+        let job = WhisperJob {
+            app: &app,
+            model_path: &model_path,
+            language: &language,
+            speaker_mode: "none",
+            preprocessing: AudioPreprocessing::default(),
+            locale,
+            prompt: None,
+            raw_output_dir: None,
+            partial: None,
+            captions: None,
+            verbatim: false,
+            translate: false,
+        };
+        let started = Instant::now();
+        let (segments, _) = transcribe_part_segments(
+            &job,
+            PartAudio {
+                primary: &JobTrack::Memory(Cow::Borrowed(&wav_data)),
+                microphone: &JobTrack::Memory(Cow::Borrowed(&[])),
+                system: &JobTrack::Memory(Cow::Borrowed(&[])),
+                system_offset_ms: 0,
+            },
+            &format!("compare-{}-{}", timestamp, model.id),
+            (50 * index as u32, 50 * (index as u32 + 1)),
+            &mut warnings,
+        )
+        .await?;
+        let elapsed = started.elapsed().as_secs_f64();
+        let transcript = render_segments(&segments);
+        transcripts.push(ComparedTranscript {
+            model: model.id.to_string(),
+            word_count: transcript.split_whitespace().count(),
+            transcript,
+            wall_seconds: round_to(elapsed, 2),
+            real_time_factor: if audio_seconds > 0.0 {
+                round_to(elapsed / audio_seconds, 3)
+            } else {
+                0.0
+            },
+        });
+    }
+
+    let diff = word_diff(&transcripts[0].transcript, &transcripts[1].transcript);
+    let changed_words = diff
+        .iter()
+        .filter(|span| span.op != "equal")
+        .map(|span| {
+            span.from
+                .split_whitespace()
+                .count()
+                .max(span.to.split_whitespace().count())
+        })
+        .sum();
+    let mut seen_warnings = HashSet::new();
+    warnings.retain(|warning| seen_warnings.insert(warning.clone()));
+    emit_progress(&app, 100, &localize(locale, Message::TranscriptionComplete));
+
+    Ok(ModelComparison {
+        audio_seconds: round_to(audio_seconds, 2),
+        transcripts,
+        diff,
+        changed_words,
+        warnings,
+    })
+}
+
+// The spoken words of a transcript or reference file: frontmatter, headings,
+// and speaker labels are dropped so only the text is scored.
+fn evaluation_text(path: &Path) -> Result<String, String> {
+    let raw = fs::read_to_string(path).map_err(|e| read_failure("transcript", path, e))?;
+    let (frontmatter, body) = split_frontmatter(&raw);
+    let mut labels = frontmatter_list(frontmatter, "speakers");
+    labels.extend(DEFAULT_SPEAKER_LABELS.iter().map(|label| label.to_string()));
+    Ok(body
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && *line != "---")
+        .map(|line| {
+            labels
+                .iter()
+                .find_map(|label| line.strip_prefix(label.as_str())?.strip_prefix(':'))
+                .unwrap_or(line)
+        })
+        .collect::<Vec<&str>>()
+        .join(" "))
+}
+
+#[derive(Debug, Serialize)]
+pub struct TranscriptEvaluation {
+    reference_words: usize,
+    hypothesis_words: usize,
+    substitutions: usize,
+    deletions: usize,
+    insertions: usize,
+    word_error_rate: f64,
+    character_error_rate: f64,
+    // From the reference to the hypothesis: `delete` spans are words the
+    // hypothesis missed, `insert` spans words it added.
+    diff: Vec<DiffSpan>,
+}
+
+fn evaluate_texts(reference: &str, hypothesis: &str) -> TranscriptEvaluation {
+    let diff = word_diff(reference, hypothesis);
+    let words = |text: &str| text.split_whitespace().count();
+    let count = |op: &str, side: fn(&DiffSpan) -> &str| {
+        diff.iter()
+            .filter(|span| span.op == op)
+            .map(|span| words(side(span)))
+            .sum::<usize>()
+    };
+    let substitutions = count("substitute", |span| span.from.as_str());
+    let deletions = count("delete", |span| span.from.as_str());
+    let insertions = count("insert", |span| span.to.as_str());
+
+    // Characters compare on the same normalized words, single-spaced.
+    let normalize = |text: &str| {
+        text.split_whitespace()
+            .map(comparison_key)
+            .filter(|key| !key.is_empty())
+            .collect::<Vec<String>>()
+            .join(" ")
+            .chars()
+            .collect::<Vec<char>>()
+    };
+    let (reference_chars, hypothesis_chars) = (normalize(reference), normalize(hypothesis));
+    let rate = |errors: usize, total: usize| {
+        if total == 0 {
+            0.0
+        } else {
+            round_to(errors as f64 / total as f64, 4)
+        }
+    };
+    let reference_words = diff.iter().map(|span| words(&span.from)).sum::<usize>();
+
+    TranscriptEvaluation {
+        reference_words,
+        hypothesis_words: diff.iter().map(|span| words(&span.to)).sum(),
+        substitutions,
+        deletions,
+        insertions,
+        word_error_rate: rate(substitutions + deletions + insertions, reference_words),
+        character_error_rate: rate(
+            edit_distance(&reference_chars, &hypothesis_chars),
+            reference_chars.len(),
+        ),
+        diff,
+    }
+}
+
+// Scores a transcript against a hand-corrected reference with word and
+// character error rates (substitutions + deletions + insertions over the
+// reference length). Case and punctuation around words are ignored.
+#[tauri::command]
+async fn evaluate_transcript(
+    app: AppHandle,
+    hypothesis_path: String,
+    reference_path: String,
+) -> Result<TranscriptEvaluation, String> {
+    require_unlocked(&app, Some(Path::new(&hypothesis_path)))?;
+    require_unlocked(&app, Some(Path::new(&reference_path)))?;
+    let hypothesis = evaluation_text(Path::new(&hypothesis_path))?;
+    let reference = evaluation_text(Path::new(&reference_path))?;
+    if reference.split_whitespace().next().is_none() {
+        return Err(format!(
+            "Reference transcript has no text: {}",
+            reference_path
+        ));
+    }
+    tauri::async_runtime::spawn_blocking(move || evaluate_texts(&reference, &hypothesis))
+        .await
+        .map_err(|e| format!("Evaluation failed: {}", e))
+}
+
+#[tauri::command]
+async fn start_system_audio_recording(
+    app: AppHandle,
+    state: State<'_, SystemAudioCaptureState>,
+    preset: Option<String>,
+) -> Result<(), String> {
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (state, preset);
+        return Err("System audio capture is only supported on macOS.".to_string());
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let mut guard = state
+            .session
+            .lock()
+            .map_err(|_| "Failed to lock system audio state.".to_string())?;
+
+        if guard.is_some() {
+            return Err("System audio capture is already running.".to_string());
+        }
+
+        let timestamp = unix_timestamp_secs()?;
+        let temp_dir = std::env::temp_dir().join("echo-scribe");
+        fs::create_dir_all(&temp_dir)
+            .map_err(|e| format!("Failed to create temporary directory: {}", e))?;
+        let output_path = temp_dir.join(format!("system-audio-{}.wav", timestamp));
+
+        // The preset's format applies to the loopback capture as well as the
+        // microphone; without one the sidecar records 16 kHz mono.
+        let (sample_rate, channels) = match sanitize_non_empty(preset) {
+            Some(name) => {
+                let settings = load_settings(&app)?;
+                let preset = find_recording_preset(&settings, &name)
+                    .ok_or_else(|| format!("No recording preset named '{}'.", name))?;
+                (preset.sample_rate, preset.channels)
+            }
+            None => (default_preset_sample_rate(), default_preset_channels()),
+        };
+
+        let sidecar_path = resolve_system_audio_capture_sidecar_path(&app)?;
+        let mut command = StdCommand::new(&sidecar_path);
+        command
+            .arg("record")
+            .arg("--output")
+            .arg(&output_path)
+            .arg("--sample-rate")
+            .arg(sample_rate.to_string())
+            .arg("--channels")
+            .arg(channels.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let child = command.spawn().map_err(|e| {
+            format!(
+                "Failed to start system audio capture binary ({}): {}",
+                sidecar_path.display(),
+                e
+            )
+        })?;
+
+        *guard = Some(SystemAudioCaptureSession { child, output_path });
+        Ok(())
+    }
+}
+
+const RECORDING_PRESET_CAPTURE_MODES: [&str; 3] = ["microphone", "system", "both"];
+const RECORDING_PRESET_SAMPLE_RATES: [u32; 4] = [16_000, 24_000, 44_100, 48_000];
+const MAX_RECORDING_PRESETS: usize = 20;
+const MAX_RECORDING_PRESET_NAME_CHARS: usize = 60;
+const MAX_AUTO_STOP_SILENCE_SECONDS: u32 = 60 * 60;
+
+fn default_preset_sample_rate() -> u32 {
+    WAV_SAMPLE_RATE
+}
+
+fn default_preset_channels() -> u16 {
+    1
+}
+
+// A named capture setup, such as "In-person session" or "Zoom call".
+// `capture_mode` turns system audio loopback on (`system`, `both`) or off
+// (`microphone`). The auto-stop rules end a recording after a set length or
+// after a stretch of microphone silence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingPreset {
+    name: String,
+    capture_mode: String,
+    // Browser media device id of the microphone; the default input when unset.
+    #[serde(default)]
+    input_device_id: Option<String>,
+    #[serde(default = "default_preset_sample_rate")]
+    sample_rate: u32,
+    #[serde(default = "default_preset_channels")]
+    channels: u16,
+    #[serde(default)]
+    auto_stop_minutes: Option<u32>,
+    #[serde(default)]
+    auto_stop_silence_seconds: Option<u32>,
+}
+
+fn validate_recording_preset(preset: RecordingPreset) -> Result<RecordingPreset, String> {
+    let name = preset.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_RECORDING_PRESET_NAME_CHARS {
+        return Err(format!(
+            "Preset name must be 1 to {} characters.",
+            MAX_RECORDING_PRESET_NAME_CHARS
+        ));
+    }
+    if !RECORDING_PRESET_CAPTURE_MODES.contains(&preset.capture_mode.as_str()) {
+        return Err(format!(
+            "Unsupported capture mode '{}'. Valid values: {}",
+            preset.capture_mode,
+            RECORDING_PRESET_CAPTURE_MODES.join(", ")
+        ));
+    }
+    if !RECORDING_PRESET_SAMPLE_RATES.contains(&preset.sample_rate) {
+        return Err(format!(
+            "Unsupported sample rate {} Hz. Valid values: {}",
+            preset.sample_rate,
+            RECORDING_PRESET_SAMPLE_RATES
+                .iter()
+                .map(|rate| rate.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+    if !(1..=2).contains(&preset.channels) {
+        return Err("Presets record 1 or 2 channels.".to_string());
+    }
+    if preset.auto_stop_minutes == Some(0) {
+        return Err("Auto-stop length must be at least 1 minute.".to_string());
+    }
+    if let Some(seconds) = preset.auto_stop_silence_seconds {
+        if !(10..=MAX_AUTO_STOP_SILENCE_SECONDS).contains(&seconds) {
+            return Err(format!(
+                "Silence auto-stop must be between 10 and {} seconds.",
+                MAX_AUTO_STOP_SILENCE_SECONDS
+            ));
+        }
+    }
+
+    Ok(RecordingPreset {
+        name,
+        input_device_id: sanitize_non_empty(preset.input_device_id),
+        ..preset
+    })
+}
+
+fn find_recording_preset<'a>(settings: &'a AppSettings, name: &str) -> Option<&'a RecordingPreset> {
+    settings
+        .recording_presets
+        .iter()
+        .find(|preset| preset.name.eq_ignore_ascii_case(name.trim()))
+}
+
+// Adds the preset, or replaces the one with the same name, and makes it the
+// active preset.
+#[tauri::command]
+async fn save_recording_preset(
+    app: AppHandle,
+    preset: RecordingPreset,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let preset = validate_recording_preset(preset)?;
+    let mut settings = load_settings(&app)?;
+    match settings
+        .recording_presets
+        .iter_mut()
+        .find(|existing| existing.name.eq_ignore_ascii_case(&preset.name))
+    {
+        Some(existing) => *existing = preset.clone(),
+        None if settings.recording_presets.len() >= MAX_RECORDING_PRESETS => {
+            return Err(format!(
+                "Keep at most {} recording presets. Delete one first.",
+                MAX_RECORDING_PRESETS
+            ));
+        }
+        None => settings.recording_presets.push(preset.clone()),
+    }
+    settings.active_recording_preset = Some(preset.name);
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn delete_recording_preset(app: AppHandle, name: String) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings
+        .recording_presets
+        .retain(|preset| !preset.name.eq_ignore_ascii_case(name.trim()));
+    if settings
+        .active_recording_preset
+        .as_ref()
+        .is_some_and(|active| active.eq_ignore_ascii_case(name.trim()))
+    {
+        settings.active_recording_preset = None;
+    }
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+// `None` goes back to choosing the capture source by hand.
+#[tauri::command]
+async fn set_active_recording_preset(
+    app: AppHandle,
+    name: Option<String>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.active_recording_preset = match sanitize_non_empty(name) {
+        Some(name) => Some(
+            find_recording_preset(&settings, &name)
+                .ok_or_else(|| format!("No recording preset named '{}'.", name))?
+                .name
+                .clone(),
+        ),
+        None => None,
+    };
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn stop_system_audio_recording(
+    state: State<'_, SystemAudioCaptureState>,
+) -> Result<SystemAudioCaptureResult, String> {
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = state;
+        return Err("System audio capture is only supported on macOS.".to_string());
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let mut session = {
+            let mut guard = state
+                .session
+                .lock()
+                .map_err(|_| "Failed to lock system audio state.".to_string())?;
+
+            guard
+                .take()
+                .ok_or_else(|| "System audio capture is not currently running.".to_string())?
+        };
+        let _cleanup = TempFileCleanup::new(vec![session.output_path.clone()]);
+
+        let _ = session.child.stdin.take();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let status = loop {
+            if let Some(status) = session
+                .child
+                .try_wait()
+                .map_err(|e| format!("Failed to wait for system audio capture process: {}", e))?
+            {
+                break status;
+            }
+
+            if Instant::now() >= deadline {
+                let _ = session.child.kill();
+                let _ = session.child.wait();
+                return Err(
+                    "Timed out while stopping system audio capture. Please try again.".to_string(),
+                );
+            }
+
+            std::thread::sleep(Duration::from_millis(50));
+        };
+
+        let mut stdout = Vec::new();
+        if let Some(mut stdout_reader) = session.child.stdout.take() {
+            let _ = stdout_reader.read_to_end(&mut stdout);
+        }
+
+        let mut stderr = Vec::new();
+        if let Some(mut stderr_reader) = session.child.stderr.take() {
+            let _ = stderr_reader.read_to_end(&mut stderr);
+        }
+
+        if !status.success() {
+            let stderr_text = String::from_utf8_lossy(&stderr).trim().to_string();
+            if stderr_text.is_empty() {
+                return Err("System audio capture exited with an error.".to_string());
+            }
+            return Err(format!("System audio capture failed: {}", stderr_text));
+        }
+
+        let wav_data = fs::read(&session.output_path).map_err(|e| {
+            format!(
+                "Failed to read captured system audio file ({}): {}",
+                session.output_path.display(),
+                e
+            )
+        })?;
+
+        if wav_pcm_data(&wav_data).is_empty() {
+            return Err(
+                "No shared audio was captured. Start playback first, then record again."
+                    .to_string(),
+            );
+        }
+
+        let metadata = if stdout.is_empty() {
+            SystemAudioCaptureMetadata {
+                first_audio_wall_time_ms: 0,
+            }
+        } else {
+            serde_json::from_slice::<SystemAudioCaptureMetadata>(&stdout).map_err(|error| {
+                format!("System audio capture returned invalid metadata: {}", error)
+            })?
+        };
+
+        Ok(SystemAudioCaptureResult {
+            audio_data: wav_data,
+            first_audio_wall_time_ms: metadata.first_audio_wall_time_ms,
+        })
+    }
+}
+
+const AUDIO_UPLOAD_ID_HEADER: &str = "x-upload-id";
+const AUDIO_UPLOAD_TOTAL_HEADER: &str = "x-upload-total";
+// Share of the progress bar spent receiving audio and checking its format,
+// before whisper's own range starts.
+const UPLOAD_PROGRESS_RANGE: (u32, u32) = (0, 3);
+const CHECKING_AUDIO_PROGRESS: u32 = 4;
+const TEMP_AUDIO_WRITE_CHUNK_BYTES: usize = 4 * 1024 * 1024;
+
+fn upload_file_path(state: &AudioUploadState, upload_id: &str) -> Result<PathBuf, String> {
+    state
+        .uploads
+        .lock()
+        .map_err(|_| "Failed to lock audio upload state.".to_string())?
+        .get(upload_id)
+        .cloned()
+        .ok_or_else(|| format!("Unknown audio upload: {}", upload_id))
+}
+
+fn take_uploaded_audio(state: &AudioUploadState, upload_id: &str) -> Result<PathBuf, String> {
+    state
+        .uploads
+        .lock()
+        .map_err(|_| "Failed to lock audio upload state.".to_string())?
+        .remove(upload_id)
+        .ok_or_else(|| format!("Unknown audio upload: {}", upload_id))
+}
+
+// Hands the staged uploads to the job, which reads them where they are. The
+// files are deleted when the returned guard drops, once the job is done.
+fn resolve_audio_uploads(
+    app: &AppHandle,
+    options: &mut TranscriptionOptions,
+) -> Result<TempFileCleanup, String> {
+    let state = app.state::<AudioUploadState>();
+    let mut cleanup = TempFileCleanup::new(Vec::new());
+    for (upload_id, audio_path) in [
+        (&options.audio_upload_id, &mut options.audio_path),
+        (
+            &options.microphone_upload_id,
+            &mut options.microphone_audio_path,
+        ),
+        (&options.system_upload_id, &mut options.system_audio_path),
+    ] {
+        if let Some(upload_id) = upload_id {
+            let path = take_uploaded_audio(&state, upload_id)?;
+            cleanup.paths.push(path.clone());
+            *audio_path = Some(path);
+        }
+    }
+    Ok(cleanup)
+}
+
+// Points the options at staged uploads without releasing them, so the
+// transcription that follows a plan still finds its audio.
+fn peek_audio_uploads(app: &AppHandle, options: &mut TranscriptionOptions) -> Result<(), String> {
+    let state = app.state::<AudioUploadState>();
+    for (upload_id, audio_path) in [
+        (&options.audio_upload_id, &mut options.audio_path),
+        (
+            &options.microphone_upload_id,
+            &mut options.microphone_audio_path,
+        ),
+        (&options.system_upload_id, &mut options.system_audio_path),
+    ] {
+        if let Some(upload_id) = upload_id {
+            *audio_path = Some(upload_file_path(&state, upload_id)?);
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn start_audio_upload(state: State<'_, AudioUploadState>) -> Result<String, String> {
+    let upload_id = format!(
+        "{}-{}",
+        unix_timestamp_secs()?,
+        state.next_id.fetch_add(1, Ordering::Relaxed)
+    );
+    let path = echo_scribe_temp_dir()?.join(format!("upload-{}.wav", upload_id));
+    fs::File::create(&path)
+        .map_err(|e| format!("Failed to create upload file ({}): {}", path.display(), e))?;
+
+    state
+        .uploads
+        .lock()
+        .map_err(|_| "Failed to lock audio upload state.".to_string())?
+        .insert(upload_id.clone(), path);
+    Ok(upload_id)
+}
+
+#[tauri::command]
+async fn append_audio_chunk(
+    app: AppHandle,
+    state: State<'_, AudioUploadState>,
+    request: tauri::ipc::Request<'_>,
+) -> Result<u64, String> {
+    let tauri::ipc::InvokeBody::Raw(chunk) = request.body() else {
+        return Err("Audio chunks must be sent as raw bytes.".to_string());
+    };
+    let upload_id = request
+        .headers()
+        .get(AUDIO_UPLOAD_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| "Missing audio upload id header.".to_string())?;
+    let path = upload_file_path(&state, upload_id)?;
+
+    let mut file = fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open upload file ({}): {}", path.display(), e))?;
+    file.write_all(chunk)
+        .map_err(|e| format!("Failed to write upload file ({}): {}", path.display(), e))?;
+
+    let received = file
+        .metadata()
+        .map(|metadata| metadata.len())
+        .map_err(|e| format!("Failed to read upload file ({}): {}", path.display(), e))?;
+    let total = request
+        .headers()
+        .get(AUDIO_UPLOAD_TOTAL_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|total| *total > 0);
+    if let Some(total) = total {
+        let locale = load_settings(&app)
+            .map(|settings| settings.locale)
+            .unwrap_or_default();
+        let to_mb = |bytes: u64| format!("{:.1}", bytes as f64 / 1_048_576.0);
+        emit_progress(
+            &app,
+            scaled_progress(
+                UPLOAD_PROGRESS_RANGE,
+                (received.min(total) * 100 / total) as u32,
+            ),
+            &localize_with(
+                &locale,
+                Message::ReceivingAudio,
+                &[("received", &to_mb(received)), ("total", &to_mb(total))],
+            ),
+        );
+    }
+    Ok(received)
+}
+
+#[tauri::command]
+async fn discard_audio_upload(
+    state: State<'_, AudioUploadState>,
+    upload_id: String,
+) -> Result<(), String> {
+    let path = state
+        .uploads
+        .lock()
+        .map_err(|_| "Failed to lock audio upload state.".to_string())?
+        .remove(&upload_id);
+    if let Some(path) = path {
+        let _ = fs::remove_file(path);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn transcribe_recording(
+    app: AppHandle,
+    mut options: TranscriptionOptions,
+) -> Result<TranscriptionResult, String> {
+    apply_client_language(&load_settings(&app)?, &mut options);
+    run_transcription_job(&app, options, None).await
+}
+
+#[derive(Debug, Serialize)]
+pub struct TranscriptionPlan {
+    save_paths: Vec<String>,
+    model_used: String,
+    speaker_mode_used: String,
+    duration_seconds: u64,
+    part_count: usize,
+    warnings: Vec<String>,
+}
+
+// Dry run of `transcribe_recording`: resolves the destination, model and
+// speaker mode the job would use, and the warnings it would raise, without
+// running whisper or consuming the uploaded audio.
+#[tauri::command]
+async fn plan_transcription(
+    app: AppHandle,
+    options: TranscriptionOptions,
+) -> Result<TranscriptionPlan, String> {
+    let mut options = options;
+    peek_audio_uploads(&app, &mut options)?;
+    let mut settings = load_settings(&app)?;
+    use_job_workspace(&mut settings, &options)?;
+    resolve_auto_model(&settings, &mut options);
+    let audio = prepare_job_audio(&settings, &options)?;
+    let has_dual_source_audio = !audio.microphone.is_empty() && !audio.system.is_empty();
+    let setup = resolve_job_setup(&app, &settings, &options, has_dual_source_audio)?;
+    let mut warnings = audio.warnings;
+    warnings.extend(setup.warnings);
+
+    let part_count = match settings.auto_split_minutes {
+        Some(minutes) => {
+            plan_part_starts(&audio.primary.bytes()?, u64::from(minutes) * 60_000).len()
+        }
+        None => 1,
+    };
+    let save_paths = planned_transcript_paths(&settings, &options, part_count, &mut warnings)?;
+    let mut seen_warnings = HashSet::new();
+    warnings.retain(|warning| seen_warnings.insert(warning.clone()));
+
+    let duration_seconds = [&audio.primary, &audio.microphone, &audio.system]
+        .into_iter()
+        .map(|track| track.duration_ms() / 1000)
+        .max()
+        .unwrap_or(0);
+
+    Ok(TranscriptionPlan {
+        save_paths: save_paths
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect(),
+        model_used: setup.model_used.id.to_string(),
+        speaker_mode_used: setup.speaker_mode,
+        duration_seconds,
+        part_count,
+        warnings,
+    })
+}
+
+const DROPPED_AUDIO_EXTENSIONS: [&str; 9] = [
+    "wav", "mp3", "m4a", "aac", "flac", "ogg", "opus", "webm", "mp4",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDropEvent {
+    source_path: String,
+    job_id: Option<String>,
+    planned_path: Option<String>,
+    error: Option<String>,
+}
+
+fn validate_dropped_audio(path: &Path) -> Result<(), String> {
+    if !path.is_file() {
+        return Err(format!("Dropped item is not a file ({})", path.display()));
+    }
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase())
+        .unwrap_or_default();
+    if !DROPPED_AUDIO_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(format!(
+            "Unsupported audio format ({}). Supported: {}",
+            path.display(),
+            DROPPED_AUDIO_EXTENSIONS.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+// Where a job's transcript goes: the selected client's CoachNotes folder when
+// that mode is fully configured, otherwise the standard transcript folder.
+// Nothing is created here. `name_parts` is (date, compact time, unix timestamp).
+fn transcript_destination(
+    settings: &AppSettings,
+    options: &TranscriptionOptions,
+    name_parts: (&str, &str, u64),
+    title_suffix: &str,
+    warnings: &mut Vec<String>,
+) -> PathBuf {
+    let (date, time_compact, timestamp) = name_parts;
+    if validate_output_mode(&options.output_mode) == "coachnotes" && settings.coachnotes_enabled {
+        let root = sanitize_non_empty(settings.coachnotes_root_dir.clone());
+        let client = sanitize_non_empty(options.client.clone())
+            .or_else(|| sanitize_non_empty(settings.coachnotes_client.clone()));
+        match (root, client) {
+            (Some(root), Some(client)) => {
+                return PathBuf::from(root).join(client).join(format!(
+                    "{}-transcript-{}{}.md",
+                    date, time_compact, title_suffix
+                ));
+            }
+            _ => warnings.push(localize(&settings.locale, Message::CoachnotesIncomplete)),
+        }
+    }
+    resolve_transcript_dir(settings).join(format!("transcript-{}{}.md", timestamp, title_suffix))
+}
+
+fn first_free_path(path: &Path) -> PathBuf {
+    (1..=MAX_COLLISION_SUFFIX)
+        .map(|attempt| numbered_path(path, attempt))
+        .find(|candidate| !candidate.exists())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+// The files a job would write if it were saved now, collision suffixes
+// included. An automatic title is only known once whisper has run, so the
+// filename carries a title only when the session title is set.
+fn planned_transcript_paths(
+    settings: &AppSettings,
+    options: &TranscriptionOptions,
+    part_count: usize,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<PathBuf>, String> {
+    if !options.save_markdown {
+        return Ok(Vec::new());
+    }
+
+    let (now, _) = now_for_settings(settings);
+    let title_suffix = match sanitize_non_empty(options.session_title.clone()) {
+        Some(title) if settings.title_in_filename => title_filename_suffix(&title),
+        _ => String::new(),
+    };
+    let date = format_date(now, &settings.date_format);
+    let path = transcript_destination(
+        settings,
+        options,
+        (&date, &format_time_compact(now), unix_timestamp_secs()?),
+        &title_suffix,
+        warnings,
+    );
+    let append_target = (validate_output_mode(&options.output_mode) == "coachnotes"
+        && settings.coachnotes_enabled
+        && settings.coachnotes_append_same_day)
+        .then(|| same_day_transcript(&path, &date))
+        .flatten();
+    let prepared_note = (validate_output_mode(&options.output_mode) == "coachnotes"
+        && settings.coachnotes_enabled)
+        .then(|| {
+            path.parent()
+                .and_then(|dir| pending_session_prep(dir, &date))
+        })
+        .flatten();
+    Ok(if part_count > 1 {
+        part_paths_for(&path, part_count, options.overwrite)
+    } else if let Some(day_path) = append_target {
+        vec![day_path]
+    } else if let Some(prepared_path) = prepared_note {
+        vec![prepared_path]
+    } else if options.overwrite {
+        vec![path]
+    } else {
+        vec![first_free_path(&path)]
+    })
+}
+
+// Options for a transcription started outside the recorder. They follow the
+// saved settings; source-aware mode needs separate channels, so it drops to
+// plain transcription.
+fn default_transcription_options(
+    settings: &AppSettings,
+    audio_data: Vec<u8>,
+) -> TranscriptionOptions {
+    let diarization_mode = match validate_diarization_mode(&settings.diarization_mode) {
+        "source_aware_2speaker" => "none",
+        mode => mode,
+    };
+    let language = if diarization_mode == "tdrz_2speaker" {
+        "en"
+    } else {
+        "auto"
+    };
+    TranscriptionOptions {
+        audio_data,
+        model: settings.selected_model.clone(),
+        language: language.to_string(),
+        save_markdown: true,
+        output_mode: if settings.coachnotes_enabled {
+            "coachnotes".to_string()
+        } else {
+            "standard".to_string()
+        },
+        client: settings.coachnotes_client.clone(),
+        diarization_mode: diarization_mode.to_string(),
+        verbatim: settings.verbatim,
+        background: true,
+        workspace: Some(settings.active_workspace.clone()),
+        ..TranscriptionOptions::default()
+    }
+}
+
+// Per-file details an importer knows better than the saved settings.
+#[derive(Debug, Default)]
+struct AudioImport {
+    client: Option<String>,
+    session_title: Option<String>,
+}
+
+async fn enqueue_audio_file(
+    app: &AppHandle,
+    path: &Path,
+    import: AudioImport,
+) -> Result<FileDropEvent, String> {
+    validate_dropped_audio(path)?;
+    let settings = load_settings(app)?;
+    let job_id = format!(
+        "drop-{}-{}",
+        unix_timestamp_millis(),
+        app.state::<TranscriptionQueue>()
+            .next_id
+            .fetch_add(1, Ordering::Relaxed)
+    );
+
+    let wav_path = echo_scribe_temp_dir()?.join(format!("{}.wav", job_id));
+    let spec = AudioOutputSpec {
+        path: wav_path.to_string_lossy().to_string(),
+        sample_rate: Some(16_000),
+        channels: Some(1),
+        codec: Some("pcm_s16le".to_string()),
+        bitrate_kbps: None,
+        start_ms: None,
+        end_ms: None,
+    };
+    let converted = convert_audio_file(app, path, &spec).await;
+    let audio_data = converted.and_then(|wav_path| {
+        fs::read(&wav_path).map_err(|e| {
+            format!(
+                "Failed to read converted audio ({}): {}",
+                wav_path.display(),
+                e
+            )
+        })
+    });
+    let _ = fs::remove_file(&wav_path);
+
+    let mut options = default_transcription_options(&settings, audio_data?);
+    options.job_id = Some(job_id.clone());
+    if import.client.is_some() {
+        options.client = import.client;
+    }
+    options.session_title = import.session_title;
+    apply_client_language(&settings, &mut options);
+    let event = FileDropEvent {
+        source_path: path.to_string_lossy().to_string(),
+        job_id: Some(job_id),
+        planned_path: planned_transcript_paths(&settings, &options, 1, &mut Vec::new())?
+            .first()
+            .map(|path| path.to_string_lossy().to_string()),
+        error: None,
+    };
+    let _ = app.emit("file-drop-queued", event.clone());
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = run_transcription_job(&app, options, None).await;
+    });
+    Ok(event)
+}
+
+// Files dropped on the window or opened through the dock icon. Each one is
+// validated and queued on its own; progress then follows the usual job events.
+fn handle_opened_files(app: &AppHandle, paths: Vec<PathBuf>) {
+    for path in paths {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(error) = enqueue_audio_file(&app, &path, AudioImport::default()).await {
+                let _ = app.emit(
+                    "file-drop-rejected",
+                    FileDropEvent {
+                        source_path: path.to_string_lossy().to_string(),
+                        job_id: None,
+                        planned_path: None,
+                        error: Some(error),
+                    },
+                );
+            }
+        });
+    }
+}
+
+const ZOOM_AUDIO_SUBDIR: &str = "Audio Record";
+
+#[derive(Debug, Serialize)]
+pub struct ZoomMeeting {
+    folder: String,
+    topic: String,
+    date: String,
+    time: String,
+    client: Option<String>,
+    audio_files: Vec<String>,
+}
+
+fn zoom_recordings_dir(settings: &AppSettings) -> Option<PathBuf> {
+    sanitize_non_empty(settings.zoom_recordings_dir.clone())
+        .map(PathBuf::from)
+        .or_else(|| dirs::document_dir().map(|documents| documents.join("Zoom")))
+}
+
+// Zoom names each local recording folder "YYYY-MM-DD HH.MM.SS Topic".
+fn parse_zoom_folder_name(name: &str) -> Option<(String, String, String)> {
+    let date = name.get(..10)?;
+    let time = name.get(11..19)?;
+    if !is_iso_date(date) || name.as_bytes().get(10) != Some(&b' ') {
+        return None;
+    }
+    let clock = time.split('.').collect::<Vec<&str>>();
+    if clock.len() != 3
+        || clock
+            .iter()
+            .any(|part| part.len() != 2 || part.parse::<u8>().is_err())
+    {
+        return None;
+    }
+    let topic = name.get(19..).unwrap_or_default().trim();
+    Some((
+        date.to_string(),
+        clock.join(":"),
+        (!topic.is_empty())
+            .then(|| topic.to_string())
+            .unwrap_or_else(|| "Zoom Meeting".to_string()),
+    ))
+}
+
+fn zoom_topic_client(settings: &AppSettings, topic: &str) -> Option<String> {
+    title_map_client(&settings.zoom_client_map, topic).map(|(_, client)| client)
+}
+
+// Mapping keys match anywhere in the title, ignoring case; the longest match
+// wins so "Acme Weekly" can override a broader "Acme". Returns the matched
+// key and its client.
+fn title_map_client(map: &BTreeMap<String, String>, title: &str) -> Option<(String, String)> {
+    let title = title.to_lowercase();
+    map.iter()
+        .filter(|(pattern, _)| {
+            !pattern.trim().is_empty() && title.contains(&pattern.trim().to_lowercase())
+        })
+        .max_by_key(|(pattern, _)| pattern.trim().len())
+        .map(|(pattern, client)| (pattern.trim().to_string(), client.clone()))
+}
+
+// The mixed recording first, then any per-participant files Zoom wrote to
+// "Audio Record". Video files are listed too; ffmpeg takes their audio.
+fn zoom_audio_files(folder: &Path) -> Vec<PathBuf> {
+    let is_audio = |path: &PathBuf| {
+        path.is_file()
+            && path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .map_or(false, |extension| {
+                    DROPPED_AUDIO_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+                })
+    };
+    let mut files = Vec::new();
+    for dir in [folder.to_path_buf(), folder.join(ZOOM_AUDIO_SUBDIR)] {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut found = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(is_audio)
+            .collect::<Vec<PathBuf>>();
+        found.sort_by_key(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            (!name.starts_with("audio_only"), name)
+        });
+        files.extend(found);
+    }
+    files
+}
+
+// The meeting folder of a Zoom recording file, whether it sits in the
+// folder itself or in its "Audio Record" subfolder.
+fn zoom_meeting_folder(path: &Path) -> Option<(PathBuf, (String, String, String))> {
+    path.ancestors().skip(1).take(2).find_map(|folder| {
+        let name = folder.file_name()?.to_string_lossy().to_string();
+        parse_zoom_folder_name(&name).map(|parsed| (folder.to_path_buf(), parsed))
+    })
+}
+
+#[tauri::command]
+async fn list_zoom_recordings(app: AppHandle) -> Result<Vec<ZoomMeeting>, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    let Some(root) = zoom_recordings_dir(&settings) else {
+        return Ok(Vec::new());
+    };
+    let Ok(entries) = fs::read_dir(&root) else {
+        return Ok(Vec::new());
+    };
+
+    let mut meetings = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|folder| {
+            let name = folder.file_name()?.to_string_lossy().to_string();
+            let (date, time, topic) = parse_zoom_folder_name(&name)?;
+            let audio_files = zoom_audio_files(&folder);
+            if audio_files.is_empty() {
+                return None;
+            }
+            Some(ZoomMeeting {
+                folder: folder.to_string_lossy().to_string(),
+                client: zoom_topic_client(&settings, &topic),
+                topic,
+                date,
+                time,
+                audio_files: audio_files
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect(),
+            })
+        })
+        .collect::<Vec<ZoomMeeting>>();
+    meetings.sort_by(|a, b| (&b.date, &b.time).cmp(&(&a.date, &a.time)));
+    Ok(meetings)
+}
+
+// Queues each selected file like a dropped file, titled with the meeting topic
+// and filed under the client its topic maps to.
+#[tauri::command]
+async fn import_zoom_recordings(
+    app: AppHandle,
+    files: Vec<String>,
+) -> Result<Vec<FileDropEvent>, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    let mut events = Vec::new();
+    for file in files {
+        let path = PathBuf::from(&file);
+        let import = match zoom_meeting_folder(&path) {
+            Some((_, (_, _, topic))) => AudioImport {
+                client: zoom_topic_client(&settings, &topic),
+                session_title: Some(topic),
+            },
+            None => AudioImport::default(),
+        };
+        let event = match enqueue_audio_file(&app, &path, import).await {
+            Ok(event) => event,
+            Err(error) => FileDropEvent {
+                source_path: file,
+                job_id: None,
+                planned_path: None,
+                error: Some(error),
+            },
+        };
+        events.push(event);
+    }
+    Ok(events)
+}
+
+#[tauri::command]
+async fn set_client_detection(
+    app: AppHandle,
+    enabled: bool,
+    use_calendar: bool,
+    client_map: BTreeMap<String, String>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.client_detection_enabled = enabled;
+    settings.client_detection_calendar = use_calendar;
+    settings.client_detection_map = client_map
+        .into_iter()
+        .filter_map(|(title, client)| {
+            let title = title.trim();
+            let client = client.trim();
+            (!title.is_empty() && !client.is_empty())
+                .then(|| (title.to_string(), client.to_string()))
+        })
+        .collect();
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+// Shorter client folder names ("Al", "Jo") would match inside unrelated
+// words of a title.
+const MIN_DETECTED_CLIENT_CHARS: usize = 3;
+// Calendar can be slow to answer, and a pending automation prompt never
+// does; detection gives up rather than hold the recording back.
+const CONTEXT_SCRIPT_TIMEOUT_SECS: u64 = 5;
+
+// Frontmost app name and, when it has one, its front window title. Reading
+// window titles needs Accessibility access for Echo Scribe.
+const FRONT_WINDOW_SCRIPT: &str = r#"tell application "System Events"
+    set frontApp to first application process whose frontmost is true
+    set appName to name of frontApp
+    try
+        return appName & linefeed & (name of front window of frontApp)
+    on error
+        return appName
+    end try
+end tell"#;
+
+// Titles of events under way or starting in the next ten minutes, one per
+// line, so a session started a little early still finds its event.
+const CALENDAR_EVENTS_SCRIPT: &str = r#"set now to current date
+set soon to now + (10 * minutes)
+set titles to {}
+tell application "Calendar"
+    repeat with eventCalendar in calendars
+        set titles to titles & (summary of every event of eventCalendar whose start date is less than or equal to soon and end date is greater than or equal to now)
+    end repeat
+end tell
+set AppleScript's text item delimiters to linefeed
+return titles as text"#;
+
+#[derive(Debug, Serialize)]
+pub struct ClientGuess {
+    client: String,
+    // calendar or window.
+    source: &'static str,
+    // The event or window title the client was found in.
+    context: String,
+    // The `client_detection_map` entry that matched, if it was not the
+    // client's own name.
+    pattern: Option<String>,
+}
+
+// Output of an AppleScript, or nothing when it fails, is denied automation
+// access, or times out. Other platforms have no equivalent yet.
+async fn run_context_script(script: &str) -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        let mut command = tokio::process::Command::new("osascript");
+        command.args(["-e", script]).kill_on_drop(true);
+        let output = tokio::time::timeout(
+            Duration::from_secs(CONTEXT_SCRIPT_TIMEOUT_SECS),
+            command.output(),
+        )
+        .await
+        .ok()?
+        .ok()?;
+        let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !text.is_empty()).then_some(text)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = script;
+        None
+    }
+}
+
+// A mapped title first, then a client folder name appearing in the title.
+// Only clients with a folder are returned, since those are the ones that can
+// be selected.
+fn client_from_title(
+    settings: &AppSettings,
+    clients: &[String],
+    title: &str,
+) -> Option<(String, Option<String>)> {
+    if let Some((pattern, client)) = title_map_client(&settings.client_detection_map, title) {
+        if let Some(known) = clients
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(&client))
+        {
+            return Some((known.clone(), Some(pattern)));
+        }
+    }
+    let lowered = title.to_lowercase();
+    clients
+        .iter()
+        .filter(|client| {
+            client.chars().count() >= MIN_DETECTED_CLIENT_CHARS
+                && lowered.contains(&client.to_lowercase())
+        })
+        .max_by_key(|client| client.len())
+        .map(|client| (client.clone(), None))
+}
+
+// Guesses the CoachNotes client for a session about to start from the
+// current calendar event (when enabled) and then the frontmost window title.
+// Returns nothing when detection is off or nothing matched; the guess is for
+// the user to confirm, never applied here.
+#[tauri::command]
+async fn detect_client(app: AppHandle) -> Result<Option<ClientGuess>, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    if !settings.client_detection_enabled {
+        return Ok(None);
+    }
+    let Some(root) = sanitize_non_empty(settings.coachnotes_root_dir.clone()) else {
+        return Ok(None);
+    };
+    let clients = list_coachnotes_clients_from_root(Path::new(&root)).unwrap_or_default();
+    if clients.is_empty() {
+        return Ok(None);
+    }
+
+    let mut contexts = Vec::new();
+    if settings.client_detection_calendar {
+        if let Some(titles) = run_context_script(CALENDAR_EVENTS_SCRIPT).await {
+            contexts.extend(
+                titles
+                    .lines()
+                    .map(|title| ("calendar", title.trim().to_string())),
+            );
+        }
+    }
+    if let Some(window) = run_context_script(FRONT_WINDOW_SCRIPT).await {
+        let title = window
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<&str>>()
+            .join(" - ");
+        contexts.push(("window", title));
+    }
+
+    Ok(contexts.into_iter().find_map(|(source, context)| {
+        client_from_title(&settings, &clients, &context).map(|(client, pattern)| ClientGuess {
+            client,
+            source,
+            context,
+            pattern,
+        })
+    }))
+}
+
+#[tauri::command]
+async fn set_zoom_settings(
+    app: AppHandle,
+    directory: Option<String>,
+    client_map: BTreeMap<String, String>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.zoom_recordings_dir = sanitize_non_empty(directory);
+    settings.zoom_client_map = client_map
+        .into_iter()
+        .filter_map(|(topic, client)| {
+            let topic = topic.trim();
+            let client = client.trim();
+            (!topic.is_empty() && !client.is_empty())
+                .then(|| (topic.to_string(), client.to_string()))
+        })
+        .collect();
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+const RECORDER_WATCH_INTERVAL_SECS: u64 = 5;
+const RECORDER_SCAN_DEPTH: usize = 4;
+const RECORDER_IMPORT_DIR: &str = "Recorder Imports";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecorderFile {
+    path: String,
+    size_bytes: u64,
+    modified_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecorderVolume {
+    name: String,
+    path: String,
+    files: Vec<RecorderFile>,
+}
+
+// Where removable volumes mount on this platform.
+fn removable_volume_roots() -> Vec<PathBuf> {
+    if cfg!(target_os = "macos") {
+        return vec![PathBuf::from("/Volumes")];
+    }
+    let mut roots = vec![PathBuf::from("/media")];
+    if let Ok(user) = std::env::var("USER") {
+        roots.push(PathBuf::from("/media").join(&user));
+        roots.push(PathBuf::from("/run/media").join(&user));
+    }
+    roots
+}
+
+// Case-insensitive match where `*` stands for any run of characters.
+fn matches_volume_pattern(name: &str, pattern: &str) -> bool {
+    let name = name.to_lowercase();
+    let pattern = pattern.trim().to_lowercase();
+    let pieces = pattern.split('*').collect::<Vec<&str>>();
+    if pieces.len() == 1 {
+        return name == pattern;
+    }
+    let (first, last) = (pieces[0], pieces[pieces.len() - 1]);
+    if name.len() < first.len() + last.len() || !name.starts_with(first) || !name.ends_with(last) {
+        return false;
+    }
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for piece in &pieces[1..pieces.len() - 1] {
+        match rest.find(piece) {
+            Some(index) => rest = &rest[index + piece.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+fn mounted_recorder_volumes(settings: &AppSettings) -> Vec<(String, PathBuf)> {
+    let mut volumes = Vec::new();
+    for root in removable_volume_roots() {
+        let Ok(entries) = fs::read_dir(&root) else {
+            continue;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if path.is_dir()
+                && settings
+                    .recorder_volume_patterns
+                    .iter()
+                    .any(|pattern| matches_volume_pattern(&name, pattern))
+                && !volumes.iter().any(|(known, _)| known == &name)
+            {
+                volumes.push((name, path));
+            }
+        }
+    }
+    volumes
+}
+
+fn file_modified_secs(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_secs())
+}
+
+// Audio files on a recorder volume newer than its last import. Recorders nest
+// takes in a few levels of folders (`FOLDER01`, `MUSIC/...`), so the scan goes
+// a little deep but skips hidden system folders.
+fn new_recorder_files(settings: &AppSettings, name: &str, root: &Path) -> Vec<RecorderFile> {
+    let imported_until = settings
+        .recorder_imported_until
+        .get(name)
+        .copied()
+        .unwrap_or(0);
+    let mut files = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                if depth < RECORDER_SCAN_DEPTH {
+                    pending.push((path, depth + 1));
+                }
+                continue;
+            }
+            let modified_at = file_modified_secs(&metadata);
+            if modified_at > imported_until && validate_dropped_audio(&path).is_ok() {
+                files.push(RecorderFile {
+                    path: path.to_string_lossy().to_string(),
+                    size_bytes: metadata.len(),
+                    modified_at,
+                });
+            }
+        }
+    }
+    files.sort_by(|a, b| (a.modified_at, &a.path).cmp(&(b.modified_at, &b.path)));
+    files
+}
+
+fn scan_recorder_volumes(settings: &AppSettings) -> Vec<RecorderVolume> {
+    mounted_recorder_volumes(settings)
+        .into_iter()
+        .map(|(name, path)| RecorderVolume {
+            files: new_recorder_files(settings, &name, &path),
+            path: path.to_string_lossy().to_string(),
+            name,
+        })
+        .collect()
+}
+
+// Emits `recorder-mounted` when a matching volume with new audio appears, so
+// the UI can offer the import. Each volume is announced once per mount.
+async fn watch_recorder_volumes(app: AppHandle) {
+    let mut announced = Vec::<String>::new();
+    loop {
+        tokio::time::sleep(Duration::from_secs(RECORDER_WATCH_INTERVAL_SECS)).await;
+        let Ok(settings) = load_settings(&app) else {
+            continue;
+        };
+        let mounted = mounted_recorder_volumes(&settings);
+        announced.retain(|name| mounted.iter().any(|(mounted, _)| mounted == name));
+        for (name, path) in mounted {
+            if announced.contains(&name) {
+                continue;
+            }
+            announced.push(name.clone());
+            let files = new_recorder_files(&settings, &name, &path);
+            if !files.is_empty() {
+                let _ = app.emit(
+                    "recorder-mounted",
+                    RecorderVolume {
+                        name,
+                        path: path.to_string_lossy().to_string(),
+                        files,
+                    },
+                );
+            }
+        }
+    }
+}
+
+#[tauri::command]
+async fn list_recorder_files(app: AppHandle) -> Result<Vec<RecorderVolume>, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    Ok(scan_recorder_volumes(&settings))
+}
+
+// Copies every new file off the volume into the transcript folder, queues the
+// copies, and remembers the newest file so the next import starts after it.
+// Originals are removed only once their copy is safely written.
+#[tauri::command]
+async fn import_recorder_files(
+    app: AppHandle,
+    volume: String,
+) -> Result<Vec<FileDropEvent>, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    let recorder = scan_recorder_volumes(&settings)
+        .into_iter()
+        .find(|recorder| recorder.name == volume)
+        .ok_or_else(|| format!("Recorder volume {} is not mounted", volume))?;
+    let import_dir = resolve_transcript_dir(&settings)
+        .join(RECORDER_IMPORT_DIR)
+        .join(&recorder.name);
+    fs::create_dir_all(&import_dir).map_err(|e| {
+        format!(
+            "Failed to create import folder ({}): {}",
+            import_dir.display(),
+            e
+        )
+    })?;
+
+    let mut events = Vec::new();
+    let mut imported_until = settings
+        .recorder_imported_until
+        .get(&recorder.name)
+        .copied()
+        .unwrap_or(0);
+    for file in recorder.files {
+        let source = PathBuf::from(&file.path);
+        let file_name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let copy = (1..=MAX_COLLISION_SUFFIX)
+            .map(|attempt| numbered_path(&import_dir.join(&file_name), attempt))
+            .find(|candidate| !candidate.exists())
+            .ok_or_else(|| {
+                format!(
+                    "No free file name for {} in {}",
+                    file_name,
+                    import_dir.display()
+                )
+            });
+        let queued = match copy.and_then(|copy| {
+            fs::copy(&source, &copy)
+                .map(|_| copy)
+                .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))
+        }) {
+            Ok(copy) => {
+                if settings.recorder_move_originals {
+                    let _ = fs::remove_file(&source);
+                }
+                imported_until = imported_until.max(file.modified_at);
+                enqueue_audio_file(&app, &copy, AudioImport::default()).await
+            }
+            Err(error) => Err(error),
+        };
+        events.push(queued.unwrap_or_else(|error| FileDropEvent {
+            source_path: file.path,
+            job_id: None,
+            planned_path: None,
+            error: Some(error),
+        }));
+    }
+
+    let mut settings = load_settings(&app)?;
+    settings
+        .recorder_imported_until
+        .insert(recorder.name, imported_until);
+    save_settings(&app, &settings)?;
+    Ok(events)
+}
+
+#[tauri::command]
+async fn set_recorder_settings(
+    app: AppHandle,
+    patterns: Vec<String>,
+    move_originals: bool,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.recorder_volume_patterns = patterns
+        .iter()
+        .map(|pattern| pattern.trim().to_string())
+        .filter(|pattern| !pattern.is_empty())
+        .collect();
+    settings.recorder_move_originals = move_originals;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn set_share_templates(
+    app: AppHandle,
+    title_template: String,
+    tags: Vec<String>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.share_title_template =
+        sanitize_non_empty(Some(title_template)).unwrap_or_else(default_share_title_template);
+    settings.share_tags = tags
+        .iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+// Events mirrored to WebSocket clients of the event bridge.
+const BRIDGED_EVENTS: [&str; 8] = [
+    "progress",
+    "job-queued",
+    "job-started",
+    "job-paused",
+    "job-resumed",
+    "job-finished",
+    "job-failed",
+    CAPTIONS_EVENT,
+];
+
+// Connected bridge clients each subscribe to `sender`; `server` is the
+// accept loop while the bridge is on.
+struct EventBridgeState {
+    sender: tokio::sync::broadcast::Sender<String>,
+    server: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+impl Default for EventBridgeState {
+    fn default() -> Self {
+        Self {
+            sender: tokio::sync::broadcast::channel(256).0,
+            server: Mutex::new(None),
+        }
+    }
+}
+
+// Forwards every bridged event as `{"event": <name>, "payload": <payload>}`.
+fn forward_bridged_events(app: &AppHandle) {
+    for name in BRIDGED_EVENTS {
+        let sender = app.state::<EventBridgeState>().sender.clone();
+        app.listen_any(name, move |event| {
+            if sender.receiver_count() > 0 {
+                let _ = sender.send(format!(
+                    "{{\"event\":\"{}\",\"payload\":{}}}",
+                    name,
+                    event.payload()
+                ));
+            }
+        });
+    }
+}
+
+const EVENT_BRIDGE_TOKEN_SECRET: &str = "event-bridge-token";
+
+// The token lives in the credential store and is created the first time the
+// bridge starts.
+fn event_bridge_token(app: &AppHandle) -> Result<String, String> {
+    if let Some(token) = read_secret(app, EVENT_BRIDGE_TOKEN_SECRET)? {
+        return Ok(token);
+    }
+    let token = random_url_token(32)?;
+    secret_entry(app, EVENT_BRIDGE_TOKEN_SECRET)?
+        .set_password(&token)
+        .map_err(|e| format!("Failed to save the event bridge token: {}", e))?;
+    Ok(token)
+}
+
+// Stops the running bridge, if any, and starts it again on the configured
+// port when it is enabled. Only loopback connections are accepted, and each
+// must pass the bridge token as `?token=`.
+async fn restart_event_bridge(app: &AppHandle, settings: &AppSettings) -> Result<(), String> {
+    let state = app.state::<EventBridgeState>();
+    if let Some(server) = state
+        .server
+        .lock()
+        .map_err(|_| "Event bridge state is unavailable.".to_string())?
+        .take()
+    {
+        server.abort();
+    }
+    if !settings.event_bridge_enabled {
+        return Ok(());
+    }
+    let token = event_bridge_token(app)?;
+
+    let port = settings.event_bridge_port;
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| format!("Failed to start the event bridge on port {}: {}", port, e))?;
+    let sender = state.sender.clone();
+    let server = tauri::async_runtime::spawn(async move {
+        // Dropping the set when the bridge stops disconnects every client.
+        let mut clients = tokio::task::JoinSet::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            while clients.try_join_next().is_some() {}
+            clients.spawn(serve_event_bridge_client(
+                stream,
+                sender.subscribe(),
+                token.clone(),
+            ));
+        }
+    });
+    *state
+        .server
+        .lock()
+        .map_err(|_| "Event bridge state is unavailable.".to_string())? = Some(server);
+
+    Ok(())
+}
+
+async fn serve_event_bridge_client(
+    stream: tokio::net::TcpStream,
+    mut events: tokio::sync::broadcast::Receiver<String>,
+    token: String,
+) {
+    use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+    use tokio_tungstenite::tungstenite::http::StatusCode;
+    use tokio_tungstenite::tungstenite::Message as SocketMessage;
+
+    let expected = format!("token={}", token);
+    let check_token = |request: &Request, response: Response| {
+        let authorized = request
+            .uri()
+            .query()
+            .is_some_and(|query| query.split('&').any(|pair| pair == expected));
+        if authorized {
+            return Ok(response);
+        }
+        let mut rejection = ErrorResponse::new(Some("Invalid event bridge token".to_string()));
+        *rejection.status_mut() = StatusCode::UNAUTHORIZED;
+        Err(rejection)
+    };
+    let Ok(mut socket) = tokio_tungstenite::accept_hdr_async(stream, check_token).await else {
+        return;
+    };
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(message) => {
+                    if socket.send(SocketMessage::Text(message)).await.is_err() {
+                        return;
+                    }
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+            },
+            incoming = socket.next() => match incoming {
+                Some(Ok(SocketMessage::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[tauri::command]
+async fn set_event_bridge(
+    app: AppHandle,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    if let Some(port) = port {
+        if port < 1024 {
+            return Err("Event bridge port must be between 1024 and 65535.".to_string());
+        }
+        settings.event_bridge_port = port;
+    }
+    settings.event_bridge_enabled = enabled;
+    save_settings(&app, &settings)?;
+    restart_event_bridge(&app, &settings).await?;
+
+    build_setup_state(&app)
+}
+
+// The connection URL, token included. Only shown when the user asks for it.
+#[tauri::command]
+async fn reveal_event_bridge_url(app: AppHandle) -> Result<String, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    if !settings.event_bridge_enabled {
+        return Err("The event bridge is off.".to_string());
+    }
+    Ok(format!(
+        "ws://127.0.0.1:{}/?token={}",
+        settings.event_bridge_port,
+        event_bridge_token(&app)?
+    ))
+}
+
+const SHARE_TARGETS: [&str; 2] = ["bear", "logseq"];
+
+// Fills `{title}`, `{date}`, `{client}`, and `{model}` from the transcript.
+fn fill_share_template(template: &str, transcript: &SavedTranscript) -> String {
+    [
+        ("title", transcript.title.as_str()),
+        ("date", transcript.date.as_str()),
+        ("client", transcript.client.as_deref().unwrap_or_default()),
+        ("model", transcript.model.as_str()),
+    ]
+    .iter()
+    .fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+    .trim()
+    .to_string()
+}
+
+// Bear tags may contain spaces but not commas; Logseq tags become `#[[...]]`
+// when they do have spaces.
+fn share_url(target: &str, title: &str, tags: &[String], text: &str) -> Result<tauri::Url, String> {
+    let mut url = match target {
+        "bear" => tauri::Url::parse("bear://x-callback-url/create"),
+        _ => tauri::Url::parse("logseq://x-callback-url/quickCapture"),
+    }
+    .map_err(|e| format!("Failed to build share URL: {}", e))?;
+    match target {
+        "bear" => {
+            url.query_pairs_mut()
+                .append_pair("title", title)
+                .append_pair("text", text)
+                .append_pair("tags", &tags.join(","))
+                .append_pair("open_note", "yes");
+        }
+        _ => {
+            let tags = tags
+                .iter()
+                .map(|tag| {
+                    if tag.contains(char::is_whitespace) {
+                        format!("#[[{}]]", tag)
+                    } else {
+                        format!("#{}", tag)
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(" ");
+            let content = format!("[[{}]] {}\n{}", title, tags, text);
+            url.query_pairs_mut()
+                .append_pair("title", title)
+                .append_pair("content", content.trim());
+        }
+    }
+    Ok(url)
+}
+
+// Hands a saved transcript to another notes app through its URL scheme,
+// titled and tagged from the share templates in settings.
+#[tauri::command]
+async fn share_to(app_handle: AppHandle, app: String, path: String) -> Result<(), String> {
+    let target = app.trim().to_lowercase();
+    if !SHARE_TARGETS.contains(&target.as_str()) {
+        return Err(format!(
+            "Unsupported share target '{}'. Valid values: {}",
+            app,
+            SHARE_TARGETS.join(", ")
+        ));
+    }
+    let path = PathBuf::from(path);
+    require_unlocked(&app_handle, Some(&path))?;
+    let transcript = read_saved_transcript(&path).ok_or_else(|| {
+        format!(
+            "Not an Echo Scribe transcript ({}). Only saved transcripts can be shared.",
+            path.display()
+        )
+    })?;
+
+    let settings = load_settings(&app_handle)?;
+    let title = fill_share_template(&settings.share_title_template, &transcript);
+    let tags = settings
+        .share_tags
+        .iter()
+        .map(|tag| fill_share_template(tag, &transcript).replace(',', " "))
+        .filter(|tag| !tag.is_empty())
+        .collect::<Vec<String>>();
+    let url = share_url(&target, &title, &tags, transcript_text(&transcript.body))?;
+    tauri_plugin_opener::open_url(url.as_str(), None::<&str>)
+        .map_err(|e| format!("Failed to open {}: {}", app, e))?;
+    record_audit(&app_handle, AuditAction::Export, &path, None)?;
+
+    Ok(())
+}
+
+// Google Drive export. Echo Scribe signs in with the user's own OAuth client
+// (a "Desktop app" client from Google Cloud Console) through the loopback
+// redirect flow with PKCE. The `drive.file` scope only reaches files the app
+// created, so uploads go to a folder it creates and remembers by id.
+const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const DRIVE_FILES_URL: &str = "https://www.googleapis.com/drive/v3/files";
+const DRIVE_UPLOAD_URL: &str = "https://www.googleapis.com/upload/drive/v3/files";
+const DRIVE_SCOPE: &str = "https://www.googleapis.com/auth/drive.file";
+const DRIVE_FOLDER_MIME: &str = "application/vnd.google-apps.folder";
+const GOOGLE_DOC_MIME: &str = "application/vnd.google-apps.document";
+const DRIVE_REFRESH_TOKEN_SECRET: &str = "google-drive-refresh-token";
+const DRIVE_CLIENT_SECRET_SECRET: &str = "google-drive-client-secret";
+const DRIVE_SIGN_IN_TIMEOUT_SECS: u64 = 300;
+const DRIVE_REDIRECT_MAX_BYTES: usize = 8 * 1024;
+
+fn default_drive_folder_name() -> String {
+    "Echo Scribe Transcripts".to_string()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DriveUpload {
+    path: String,
+    file_id: String,
+    web_view_link: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DriveUploadFailure {
+    path: String,
+    error: String,
+}
+
+#[derive(Deserialize)]
+struct GoogleTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DriveFile {
+    id: String,
+    #[serde(default, rename = "webViewLink")]
+    web_view_link: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DriveFileList {
+    #[serde(default)]
+    files: Vec<DriveFile>,
+}
+
+fn base64_url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (index, byte)| {
+            value | (u32::from(*byte) << (16 - 8 * index))
+        });
+        for index in 0..=chunk.len() {
+            encoded.push(char::from(
+                ALPHABET[((value >> (18 - 6 * index)) & 63) as usize],
+            ));
+        }
+    }
+    encoded
+}
+
+// `bytes` of system randomness, base64url-encoded. Used for anything a caller
+// must not be able to guess: OAuth verifiers, tokens, salts.
+fn random_url_token(bytes: usize) -> Result<String, String> {
+    let mut buffer = vec![0u8; bytes];
+    ring::rand::SecureRandom::fill(&ring::rand::SystemRandom::new(), &mut buffer)
+        .map_err(|_| "The system random number generator is unavailable.".to_string())?;
+    Ok(base64_url(&buffer))
+}
+
+async fn read_google_json<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+    action: &str,
+) -> Result<T, String> {
+    let status = response.status();
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to {}: {}", action, e))?;
+    if !status.is_success() {
+        return Err(format!(
+            "Failed to {} (HTTP {}): {}",
+            action,
+            status,
+            String::from_utf8_lossy(&body).trim()
+        ));
+    }
+    serde_json::from_slice(&body).map_err(|e| format!("Failed to {}: {}", action, e))
+}
+
+fn drive_client_id(settings: &AppSettings) -> Result<String, String> {
+    sanitize_non_empty(settings.drive_client_id.clone())
+        .ok_or_else(|| "Enter a Google OAuth client ID for Drive export first.".to_string())
+}
+
+// Waits for the browser to come back to the loopback redirect and returns
+// the authorization code.
+async fn receive_oauth_redirect(
+    listener: tokio::net::TcpListener,
+    expected_state: &str,
+) -> Result<String, String> {
+    loop {
+        let (mut stream, _) = listener
+            .accept()
+            .await
+            .map_err(|e| format!("Google sign-in failed: {}", e))?;
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while !request.windows(4).any(|window| window == b"\r\n\r\n")
+            && request.len() < DRIVE_REDIRECT_MAX_BYTES
+        {
+            match stream.read(&mut buffer).await {
+                Ok(0) | Err(_) => break,
+                Ok(read) => request.extend_from_slice(&buffer[..read]),
+            }
+        }
+        let request = String::from_utf8_lossy(&request);
+        let target = request
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .unwrap_or("/");
+        let Ok(url) = tauri::Url::parse(&format!("http://127.0.0.1{}", target)) else {
+            continue;
+        };
+        let params = url
+            .query_pairs()
+            .into_owned()
+            .collect::<HashMap<String, String>>();
+        if !params.contains_key("code") && !params.contains_key("error") {
+            // The browser asking for a favicon, for example.
+            let _ = stream
+                .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                .await;
+            continue;
+        }
+
+        let page = "<html><body><p>Echo Scribe received the Google sign-in. You can close this tab.</p></body></html>";
+        let _ = stream
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    page.len(),
+                    page
+                )
+                .as_bytes(),
+            )
+            .await;
+        if let Some(error) = params.get("error") {
+            return Err(format!("Google sign-in was not completed: {}", error));
+        }
+        if params.get("state").map(String::as_str) != Some(expected_state) {
+            return Err("Google sign-in returned an unexpected state. Try again.".to_string());
+        }
+        return params
+            .get("code")
+            .cloned()
+            .ok_or_else(|| "Google sign-in returned no authorization code.".to_string());
+    }
+}
+
+// Exchanges the saved refresh token for a short-lived access token.
+async fn drive_access_token(
+    app: &AppHandle,
+    settings: &AppSettings,
+    client: &reqwest::Client,
+) -> Result<String, String> {
+    let client_id = drive_client_id(settings)?;
+    let refresh_token = read_secret(app, DRIVE_REFRESH_TOKEN_SECRET)?
+        .ok_or("Connect Google Drive in Settings first.")?;
+    let client_secret = read_secret(app, DRIVE_CLIENT_SECRET_SECRET)?.unwrap_or_default();
+    let response = client
+        .post(GOOGLE_TOKEN_URL)
+        .form(&[
+            ("client_id", client_id.as_str()),
+            ("client_secret", client_secret.as_str()),
+            ("refresh_token", refresh_token.as_str()),
+            ("grant_type", "refresh_token"),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to refresh Google access: {}", e))?;
+    let tokens: GoogleTokenResponse = read_google_json(response, "refresh Google access").await?;
+    Ok(tokens.access_token)
+}
+
+// The id of the upload folder: the remembered one if it still exists,
+// otherwise one found or created by name at the top of My Drive.
+async fn drive_folder_id(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    access_token: &str,
+) -> Result<String, String> {
+    let settings = load_settings(app)?;
+    if let Some(folder_id) = sanitize_non_empty(settings.drive_folder_id.clone()) {
+        let response = client
+            .get(format!("{}/{}", DRIVE_FILES_URL, folder_id))
+            .bearer_auth(access_token)
+            .query(&[("fields", "id,trashed")])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to look up the Drive folder: {}", e))?;
+        if let Ok(folder) =
+            read_google_json::<serde_json::Value>(response, "look up the Drive folder").await
+        {
+            if folder.get("trashed").and_then(serde_json::Value::as_bool) != Some(true) {
+                return Ok(folder_id);
+            }
+        }
+    }
+
+    let name = sanitize_non_empty(Some(settings.drive_folder_name.clone()))
+        .unwrap_or_else(default_drive_folder_name);
+    let query = format!(
+        "name = '{}' and mimeType = '{}' and trashed = false",
+        name.replace('\\', "\\\\").replace('\'', "\\'"),
+        DRIVE_FOLDER_MIME
+    );
+    let response = client
+        .get(DRIVE_FILES_URL)
+        .bearer_auth(access_token)
+        .query(&[("q", query.as_str()), ("fields", "files(id)")])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to look up the Drive folder: {}", e))?;
+    let existing: DriveFileList = read_google_json(response, "look up the Drive folder").await?;
+    let folder_id = match existing.files.into_iter().next() {
+        Some(folder) => folder.id,
+        None => {
+            let metadata = serde_json::json!({ "name": name, "mimeType": DRIVE_FOLDER_MIME });
+            let response = client
+                .post(DRIVE_FILES_URL)
+                .bearer_auth(access_token)
+                .query(&[("fields", "id")])
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(metadata.to_string())
+                .send()
+                .await
+                .map_err(|e| format!("Failed to create the Drive folder: {}", e))?;
+            read_google_json::<DriveFile>(response, "create the Drive folder")
+                .await?
+                .id
+        }
+    };
+
+    // Settings may have changed during the requests above, so only the folder
+    // id is written over the current copy.
+    let mut settings = load_settings(app)?;
+    settings.drive_folder_id = Some(folder_id.clone());
+    save_settings(app, &settings)?;
+    Ok(folder_id)
+}
+
+// Uploads a transcript to the Drive folder, as Markdown or converted to a
+// Google Doc, in one multipart request.
+async fn upload_to_drive(app: &AppHandle, path: &Path) -> Result<DriveUpload, String> {
+    let settings = load_settings(app)?;
+    let client = http_client(&settings)?;
+    let contents = fs::read(path).map_err(|e| read_failure("transcript", path, e))?;
+    let access_token = drive_access_token(app, &settings, &client).await?;
+    let folder_id = drive_folder_id(app, &client, &access_token).await?;
+
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("transcript.md");
+    let metadata = if settings.drive_as_google_doc {
+        serde_json::json!({
+            "name": file_name.trim_end_matches(".md"),
+            "mimeType": GOOGLE_DOC_MIME,
+            "parents": [folder_id],
+        })
+    } else {
+        serde_json::json!({ "name": file_name, "parents": [folder_id] })
+    };
+    let boundary = format!("echo-scribe-{}", unix_timestamp_millis());
+    let mut body = format!(
+        "--{boundary}\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n{}\r\n--{boundary}\r\nContent-Type: text/markdown; charset=UTF-8\r\n\r\n",
+        metadata
+    )
+    .into_bytes();
+    body.extend_from_slice(&contents);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+
+    let response = client
+        .post(DRIVE_UPLOAD_URL)
+        .bearer_auth(&access_token)
+        .query(&[("uploadType", "multipart"), ("fields", "id,webViewLink")])
+        .header(
+            reqwest::header::CONTENT_TYPE,
+            format!("multipart/related; boundary={}", boundary),
+        )
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to upload to Google Drive: {}", e))?;
+    let file: DriveFile = read_google_json(response, "upload to Google Drive").await?;
+    record_audit(app, AuditAction::Export, path, None)?;
+
+    Ok(DriveUpload {
+        path: path.to_string_lossy().to_string(),
+        file_id: file.id,
+        web_view_link: file.web_view_link,
+    })
+}
+
+#[tauri::command]
+async fn set_drive_settings(
+    app: AppHandle,
+    client_id: Option<String>,
+    folder_name: Option<String>,
+    as_google_doc: bool,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    let client_id = sanitize_non_empty(client_id);
+    if client_id != settings.drive_client_id {
+        // Tokens belong to the client that issued them.
+        delete_secret_entry(&app, DRIVE_REFRESH_TOKEN_SECRET)?;
+        settings.drive_connected = false;
+    }
+    let folder_name = sanitize_non_empty(folder_name).unwrap_or_else(default_drive_folder_name);
+    if folder_name != settings.drive_folder_name {
+        settings.drive_folder_id = None;
+    }
+    settings.drive_client_id = client_id;
+    settings.drive_folder_name = folder_name;
+    settings.drive_as_google_doc = as_google_doc;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+// Opens Google sign-in in the browser and keeps the refresh token in the
+// credential store. `client_secret` is the one shown with a Desktop app
+// client; Google does not treat it as confidential.
+#[tauri::command]
+async fn connect_google_drive(
+    app: AppHandle,
+    client_secret: Option<String>,
+) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    let client = http_client(&settings)?;
+    let client_id = drive_client_id(&settings)?;
+    let client_secret = match sanitize_non_empty(client_secret) {
+        Some(secret) => {
+            secret_entry(&app, DRIVE_CLIENT_SECRET_SECRET)?
+                .set_password(&secret)
+                .map_err(|e| format!("Failed to save the Google client secret: {}", e))?;
+            secret
+        }
+        None => read_secret(&app, DRIVE_CLIENT_SECRET_SECRET)?.unwrap_or_default(),
+    };
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+        .await
+        .map_err(|e| format!("Failed to start Google sign-in: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to start Google sign-in: {}", e))?
+        .port();
+    let redirect_uri = format!("http://127.0.0.1:{}", port);
+    // RFC 7636: 32 random bytes give a 43-character verifier.
+    let verifier = random_url_token(32)?;
+    let challenge = base64_url(&Sha256::digest(verifier.as_bytes()));
+    let state = random_url_token(16)?;
+
+    let mut auth_url = tauri::Url::parse(GOOGLE_AUTH_URL).map_err(|e| e.to_string())?;
+    auth_url
+        .query_pairs_mut()
+        .append_pair("client_id", &client_id)
+        .append_pair("redirect_uri", &redirect_uri)
+        .append_pair("response_type", "code")
+        .append_pair("scope", DRIVE_SCOPE)
+        .append_pair("code_challenge", &challenge)
+        .append_pair("code_challenge_method", "S256")
+        .append_pair("state", &state)
+        .append_pair("access_type", "offline")
+        .append_pair("prompt", "consent");
+    tauri_plugin_opener::open_url(auth_url.as_str(), None::<&str>)
+        .map_err(|e| format!("Failed to open Google sign-in: {}", e))?;
+
+    let code = tokio::time::timeout(
+        Duration::from_secs(DRIVE_SIGN_IN_TIMEOUT_SECS),
+        receive_oauth_redirect(listener, &state),
+    )
+    .await
+    .map_err(|_| "Google sign-in timed out. Try again.".to_string())??;
+
+    let response = client
+        .post(GOOGLE_TOKEN_URL)
+        .form(&[
+            ("client_id", client_id.as_str()),
+            ("client_secret", client_secret.as_str()),
+            ("code", code.as_str()),
+            ("code_verifier", verifier.as_str()),
+            ("grant_type", "authorization_code"),
+            ("redirect_uri", redirect_uri.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to finish Google sign-in: {}", e))?;
+    let tokens: GoogleTokenResponse = read_google_json(response, "finish Google sign-in").await?;
+    let refresh_token = tokens
+        .refresh_token
+        .ok_or("Google did not return a refresh token. Remove Echo Scribe's access in your Google account and connect again.")?;
+    secret_entry(&app, DRIVE_REFRESH_TOKEN_SECRET)?
+        .set_password(&refresh_token)
+        .map_err(|e| format!("Failed to save the Google Drive token: {}", e))?;
+
+    let mut settings = load_settings(&app)?;
+    settings.drive_connected = true;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[tauri::command]
+async fn disconnect_google_drive(app: AppHandle) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    delete_secret_entry(&app, DRIVE_REFRESH_TOKEN_SECRET)?;
+    delete_secret_entry(&app, DRIVE_CLIENT_SECRET_SECRET)?;
+    let mut settings = load_settings(&app)?;
+    settings.drive_connected = false;
+    settings.drive_folder_id = None;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+// Uploads an existing transcript to the Drive folder.
+#[tauri::command]
+async fn export_to_drive(app: AppHandle, path: String) -> Result<DriveUpload, String> {
+    let path = PathBuf::from(path);
+    require_unlocked(&app, Some(&path))?;
+    if read_saved_transcript(&path).is_none() {
+        return Err(format!(
+            "Not an Echo Scribe transcript ({}). Only saved transcripts can be exported.",
+            path.display()
+        ));
+    }
+    upload_to_drive(&app, &path).await
+}
+
+// Uploads just-saved transcripts one after another in the background; the
+// job does not wait. Each outcome arrives as a `drive-upload-finished` or
+// `drive-upload-failed` event.
+fn spawn_drive_uploads(app: &AppHandle, paths: Vec<PathBuf>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for path in paths {
+            match upload_to_drive(&app, &path).await {
+                Ok(upload) => {
+                    let _ = app.emit("drive-upload-finished", upload);
+                }
+                Err(error) => {
+                    let _ = app.emit(
+                        "drive-upload-failed",
+                        DriveUploadFailure {
+                            path: path.to_string_lossy().to_string(),
+                            error,
+                        },
+                    );
+                }
+            }
+        }
+    });
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum WebhookEvent {
+    TranscriptSaved,
+    JobFailed,
+    QueueEmpty,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Webhook {
+    url: String,
+    events: Vec<WebhookEvent>,
+    // JSON with `{{placeholder}}`s, for APIs that expect their own body shape.
+    // Without one the fields are posted as a flat JSON object.
+    #[serde(default)]
+    payload_template: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+struct WebhookFields {
+    event: String,
+    job_id: String,
+    title: String,
+    transcript: String,
+    summary: String,
+    client: String,
+    path: String,
+    error: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct WebhookFailure {
+    url: String,
+    event: WebhookEvent,
+    error: String,
+}
+
+impl WebhookFields {
+    fn placeholders(&self) -> [(&'static str, &str); 8] {
+        [
+            ("event", &self.event),
+            ("job_id", &self.job_id),
+            ("title", &self.title),
+            ("transcript", &self.transcript),
+            ("summary", &self.summary),
+            ("client", &self.client),
+            ("path", &self.path),
+            ("error", &self.error),
+        ]
+    }
+}
+
+// Placeholders are replaced with JSON-escaped text, so they belong inside
+// string literals of the template, e.g. `{"text": "{{transcript}}"}`.
+fn render_webhook_payload(
+    template: Option<&str>,
+    fields: &WebhookFields,
+) -> Result<String, String> {
+    let Some(template) = template else {
+        return serde_json::to_string(fields)
+            .map_err(|e| format!("Failed to encode webhook payload: {}", e));
+    };
+    let payload =
+        fields
+            .placeholders()
+            .iter()
+            .fold(template.to_string(), |payload, (name, value)| {
+                let escaped = serde_json::to_string(value).unwrap_or_default();
+                payload.replace(
+                    &format!("{{{{{}}}}}", name),
+                    &escaped[1..escaped.len().saturating_sub(1)],
+                )
+            });
+    serde_json::from_str::<serde_json::Value>(&payload)
+        .map_err(|e| format!("Webhook payload template is not valid JSON: {}", e))?;
+    Ok(payload)
+}
+
+async fn post_webhook(
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    fields: &WebhookFields,
+) -> Result<(), String> {
+    let payload = render_webhook_payload(webhook.payload_template.as_deref(), fields)?;
+    let response = client
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(payload)
+        .send()
+        .await
+        .map_err(|e| format!("Webhook request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Webhook request failed with HTTP status {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
+
+// Posts to every webhook subscribed to `event` in the background. Failures,
+// including offline mode blocking the request, are reported as
+// `webhook-failed` events rather than affecting the job.
+fn send_webhooks(app: &AppHandle, event: WebhookEvent, mut fields: WebhookFields) {
+    let Ok(settings) = load_settings(app) else {
+        return;
+    };
+    let webhooks = settings
+        .webhooks
+        .iter()
+        .filter(|webhook| webhook.events.contains(&event))
+        .cloned()
+        .collect::<Vec<Webhook>>();
+    if webhooks.is_empty() {
+        return;
+    }
+    fields.event = serde_json::to_value(event)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default();
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let client = http_client(&settings);
+        for webhook in webhooks {
+            let result = match &client {
+                Ok(client) => post_webhook(client, &webhook, &fields).await,
+                Err(error) => Err(error.clone()),
+            };
+            if let Err(error) = result {
+                let _ = app.emit(
+                    "webhook-failed",
+                    WebhookFailure {
+                        url: webhook.url,
+                        event,
+                        error,
+                    },
+                );
+            }
+        }
+    });
+}
+
+#[tauri::command]
+async fn set_webhooks(app: AppHandle, webhooks: Vec<Webhook>) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let sample = WebhookFields {
+        event: "transcript_saved".to_string(),
+        job_id: "job-1".to_string(),
+        title: "Title".to_string(),
+        transcript: "Line one\n\"quoted\"".to_string(),
+        summary: "Summary".to_string(),
+        client: "Client".to_string(),
+        path: "/tmp/transcript.md".to_string(),
+        error: String::new(),
+    };
+    let mut cleaned = Vec::with_capacity(webhooks.len());
+    for webhook in webhooks {
+        let url = webhook.url.trim().to_string();
+        let parsed =
+            tauri::Url::parse(&url).map_err(|e| format!("Invalid webhook URL '{}': {}", url, e))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(format!("Webhook URL must use http or https: {}", url));
+        }
+        if webhook.events.is_empty() {
+            return Err(format!("Choose at least one event for webhook {}", url));
+        }
+        let payload_template = sanitize_non_empty(webhook.payload_template);
+        render_webhook_payload(payload_template.as_deref(), &sample)?;
+        cleaned.push(Webhook {
+            url,
+            events: webhook.events,
+            payload_template,
+        });
+    }
+
+    let mut settings = load_settings(&app)?;
+    settings.webhooks = cleaned;
+    save_settings(&app, &settings)?;
+
+    build_setup_state(&app)
+}
+
+#[cfg(target_os = "macos")]
+const URL_SCHEME: &str = "echoscribe";
+const LAST_TRANSCRIPT_FIELDS: [&str; 3] = ["text", "summary", "path"];
+// Callbacks may only hand results to automation apps. Web, file, and script
+// schemes are refused so a link on a web page cannot read transcripts back.
+#[cfg(target_os = "macos")]
+const CALLBACK_URL_SCHEMES: [&str; 2] = ["shortcuts", "drafts"];
+
+#[cfg(target_os = "macos")]
+#[derive(Debug, Clone, Serialize)]
+pub struct UrlCommandEvent {
+    action: String,
+    path: Option<String>,
+    error: Option<String>,
+}
+
+// Files an `echoscribe://transcribe` link asked for, held until the user
+// confirms them in the app.
+#[derive(Default)]
+struct UrlCommandState {
+    pending_files: Mutex<Vec<PathBuf>>,
+}
+
+// Folders a `transcribe` link may read from: the Zoom recordings folder and
+// any mounted recorder volume.
+#[cfg(target_os = "macos")]
+fn url_input_roots(settings: &AppSettings) -> Vec<PathBuf> {
+    let mut roots = zoom_recordings_dir(settings)
+        .into_iter()
+        .collect::<Vec<_>>();
+    roots.extend(
+        mounted_recorder_volumes(settings)
+            .into_iter()
+            .map(|(_, path)| path),
+    );
+    roots
+}
+
+#[cfg(target_os = "macos")]
+fn url_input_path(settings: &AppSettings, path: &str) -> Result<PathBuf, String> {
+    let path = fs::canonicalize(path).map_err(|e| format!("Cannot open {}: {}", path, e))?;
+    let allowed = url_input_roots(settings)
+        .iter()
+        .filter_map(|root| fs::canonicalize(root).ok())
+        .any(|root| path.starts_with(&root));
+    if !allowed {
+        return Err(format!(
+            "{} is outside the Zoom recordings folder and recorder volumes.",
+            path.display()
+        ));
+    }
+    Ok(path)
+}
+
+fn latest_transcript_field(settings: &AppSettings, field: &str) -> Result<String, String> {
+    if !LAST_TRANSCRIPT_FIELDS.contains(&field) {
+        return Err(format!(
+            "Unsupported transcript field '{}'. Valid values: {}",
+            field,
+            LAST_TRANSCRIPT_FIELDS.join(", ")
+        ));
+    }
+    let transcript = scan_saved_transcripts(settings)
+        .pop()
+        .ok_or_else(|| "No saved transcripts yet.".to_string())?;
+    Ok(match field {
+        "summary" => transcript.summary.unwrap_or_default(),
+        "path" => transcript.path.to_string_lossy().to_string(),
+        _ => transcript_text(&transcript.body).to_string(),
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    let mut child = StdCommand::new("pbcopy")
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start pbcopy: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    }
+    child
+        .wait()
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    Ok(())
+}
+
+// Sends a result back to the caller of an x-callback-url, for example the
+// "Open X-Callback URL" action in Shortcuts.
+#[cfg(target_os = "macos")]
+fn open_callback_url(callback: &str, key: &str, value: &str) -> Result<(), String> {
+    let mut url = tauri::Url::parse(callback)
+        .map_err(|e| format!("Invalid callback URL ({}): {}", callback, e))?;
+    if !CALLBACK_URL_SCHEMES.contains(&url.scheme()) {
+        return Err(format!(
+            "Callback scheme '{}' is not allowed. Valid schemes: {}",
+            url.scheme(),
+            CALLBACK_URL_SCHEMES.join(", ")
+        ));
+    }
+    url.query_pairs_mut().append_pair(key, value);
+    tauri_plugin_opener::open_url(url.as_str(), None::<&str>)
+        .map_err(|e| format!("Failed to open callback URL: {}", e))
+}
+
+// Handles `echoscribe://` links:
+// - `record/start`, `record/stop`, `record/toggle` drive the recorder window;
+//   the window asks before it starts recording.
+// - `transcribe?path=<file>` queues a file from an input folder like a
+//   dock-icon drop, once the user confirms it in the window.
+// - `last-transcript?field=text|summary|path` returns the newest transcript
+//   through `x-success`, or copies it to the clipboard.
+#[cfg(target_os = "macos")]
+fn handle_url_command(app: &AppHandle, url: &tauri::Url) -> Result<String, String> {
+    let action = format!("{}{}", url.host_str().unwrap_or_default(), url.path());
+    let action = action.trim_end_matches('/').to_string();
+    let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+
+    match action.as_str() {
+        "record/start" | "record/stop" | "record/toggle" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            let _ = app.emit(
+                "url-command",
+                UrlCommandEvent {
+                    action: action.clone(),
+                    path: None,
+                    error: None,
+                },
+            );
+        }
+        "transcribe" => {
+            let path = params
+                .get("path")
+                .filter(|path| !path.trim().is_empty())
+                .ok_or_else(|| "The transcribe action needs a `path` parameter.".to_string())?;
+            let path = url_input_path(&load_settings(app)?, path)?;
+            app.state::<UrlCommandState>()
+                .pending_files
+                .lock()
+                .map_err(|_| "Link state is unavailable.".to_string())?
+                .push(path.clone());
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            let _ = app.emit(
+                "url-command",
+                UrlCommandEvent {
+                    action: action.clone(),
+                    path: Some(path.to_string_lossy().to_string()),
+                    error: None,
+                },
+            );
+        }
+        "last-transcript" => {
+            let settings = load_settings(app)?;
+            let field = params.get("field").map(String::as_str).unwrap_or("text");
+            let value = match require_unlocked(app, None)
+                .and_then(|()| latest_transcript_field(&settings, field))
+            {
+                Ok(value) => value,
+                Err(error) => {
+                    if let Some(callback) = params.get("x-error") {
+                        open_callback_url(callback, "errorMessage", &error)?;
+                    }
+                    return Err(error);
+                }
+            };
+            match params.get("x-success") {
+                Some(callback) => open_callback_url(callback, "result", &value)?,
+                None => copy_to_clipboard(&value)?,
+            }
+        }
+        _ => return Err(format!("Unknown {}:// action '{}'", URL_SCHEME, action)),
+    }
+    Ok(action)
+}
+
+#[cfg(target_os = "macos")]
+fn handle_opened_urls(app: &AppHandle, urls: Vec<tauri::Url>) {
+    let mut paths = Vec::new();
+    for url in urls {
+        if url.scheme() == URL_SCHEME {
+            if let Err(error) = handle_url_command(app, &url) {
+                let _ = app.emit(
+                    "url-command-failed",
+                    UrlCommandEvent {
+                        action: url.to_string(),
+                        path: None,
+                        error: Some(error),
+                    },
+                );
+            }
+        } else if let Ok(path) = url.to_file_path() {
+            paths.push(path);
+        }
+    }
+    handle_opened_files(app, paths);
+}
+
+// Answers the confirmation the window shows for an `echoscribe://transcribe`
+// link. Only files a link asked for can be queued this way.
+#[tauri::command]
+async fn confirm_url_transcribe(
+    app: AppHandle,
+    path: String,
+    confirmed: bool,
+) -> Result<(), String> {
+    let path = PathBuf::from(path);
+    let requested = {
+        let state = app.state::<UrlCommandState>();
+        let mut pending = state
+            .pending_files
+            .lock()
+            .map_err(|_| "Link state is unavailable.".to_string())?;
+        let index = pending.iter().position(|pending| pending == &path);
+        index.map(|index| pending.remove(index)).is_some()
+    };
+    if !requested {
+        return Err(format!("No link asked to transcribe {}.", path.display()));
+    }
+    if confirmed {
+        handle_opened_files(&app, vec![path]);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_last_transcript(app: AppHandle, field: Option<String>) -> Result<String, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    latest_transcript_field(&settings, field.as_deref().unwrap_or("text"))
+}
+
+const QUICK_ACTION_NAME: &str = "Transcribe with Echo Scribe";
+
+// Info.plist of the Finder Quick Action. It is offered for audio files only.
+#[cfg(target_os = "macos")]
+const QUICK_ACTION_INFO_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>NSServices</key>
+  <array>
+    <dict>
+      <key>NSMenuItem</key>
+      <dict>
+        <key>default</key>
+        <string>{name}</string>
+      </dict>
+      <key>NSMessage</key>
+      <string>runWorkflowAsService</string>
+      <key>NSRequiredContext</key>
+      <dict>
+        <key>NSApplicationIdentifier</key>
+        <string>com.apple.finder</string>
+      </dict>
+      <key>NSSendFileTypes</key>
+      <array>
+        <string>public.audio</string>
+      </array>
+    </dict>
+  </array>
+</dict>
+</plist>
+"#;
+
+// Automator document with a single "Run Shell Script" step that opens the
+// selected files with the app, which queues them like a dock-icon drop.
+#[cfg(target_os = "macos")]
+const QUICK_ACTION_WORKFLOW: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>AMApplicationBuild</key>
+  <string>523</string>
+  <key>AMApplicationVersion</key>
+  <string>2.10</string>
+  <key>AMDocumentVersion</key>
+  <string>2</string>
+  <key>actions</key>
+  <array>
+    <dict>
+      <key>action</key>
+      <dict>
+        <key>AMAccepts</key>
+        <dict>
+          <key>Container</key>
+          <string>List</string>
+          <key>Optional</key>
+          <true/>
+          <key>Types</key>
+          <array>
+            <string>com.apple.cocoa.path</string>
+          </array>
+        </dict>
+        <key>AMActionVersion</key>
+        <string>2.0.3</string>
+        <key>AMApplication</key>
+        <array>
+          <string>Automator</string>
+        </array>
+        <key>AMBundleIdentifier</key>
+        <string>com.apple.RunShellScript</string>
+        <key>AMParameterProperties</key>
+        <dict>
+          <key>COMMAND_STRING</key>
+          <dict/>
+          <key>inputMethod</key>
+          <dict/>
+          <key>shell</key>
+          <dict/>
+        </dict>
+        <key>AMProvides</key>
+        <dict>
+          <key>Container</key>
+          <string>List</string>
+          <key>Types</key>
+          <array>
+            <string>com.apple.cocoa.path</string>
+          </array>
+        </dict>
+        <key>ActionBundlePath</key>
+        <string>/System/Library/Automator/Run Shell Script.action</string>
+        <key>ActionName</key>
+        <string>Run Shell Script</string>
+        <key>ActionParameters</key>
+        <dict>
+          <key>COMMAND_STRING</key>
+          <string>open -b {identifier} "$@"</string>
+          <key>CheckedForUserDefaultShell</key>
+          <true/>
+          <key>inputMethod</key>
+          <integer>1</integer>
+          <key>shell</key>
+          <string>/bin/sh</string>
+          <key>source</key>
+          <string></string>
+        </dict>
+        <key>BundleIdentifier</key>
+        <string>com.apple.RunShellScript</string>
+        <key>CFBundleVersion</key>
+        <string>2.0.3</string>
+        <key>CanShowSelectedItemsWhenRun</key>
+        <false/>
+        <key>CanShowWhenRun</key>
+        <true/>
+        <key>Class Name</key>
+        <string>RunShellScriptAction</string>
+        <key>InputUUID</key>
+        <string>5A4B0E7C-2D1F-4C55-9E41-6C8F0B1D2A01</string>
+        <key>OutputUUID</key>
+        <string>5A4B0E7C-2D1F-4C55-9E41-6C8F0B1D2A02</string>
+        <key>UUID</key>
+        <string>5A4B0E7C-2D1F-4C55-9E41-6C8F0B1D2A03</string>
+      </dict>
+    </dict>
+  </array>
+  <key>connectors</key>
+  <dict/>
+  <key>workflowMetaData</key>
+  <dict>
+    <key>serviceInputTypeIdentifier</key>
+    <string>com.apple.Automator.fileSystemObject</string>
+    <key>serviceOutputTypeIdentifier</key>
+    <string>com.apple.Automator.nothing</string>
+    <key>serviceProcessesInput</key>
+    <integer>0</integer>
+    <key>workflowTypeIdentifier</key>
+    <string>com.apple.Automator.servicesMenu</string>
+  </dict>
+</dict>
+</plist>
+"#;
+
+fn quick_action_dir() -> Result<PathBuf, String> {
+    dirs::home_dir()
+        .map(|home| {
+            home.join("Library")
+                .join("Services")
+                .join(format!("{}.workflow", QUICK_ACTION_NAME))
+        })
+        .ok_or_else(|| "Failed to resolve home directory.".to_string())
+}
+
+// Installs "Transcribe with Echo Scribe" into ~/Library/Services so it shows
+// up under Quick Actions when right-clicking audio files in Finder.
+#[tauri::command]
+async fn install_finder_quick_action(app: AppHandle) -> Result<String, String> {
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = app;
+        return Err("Finder Quick Actions are only supported on macOS.".to_string());
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let workflow_dir = quick_action_dir()?;
+        let contents_dir = workflow_dir.join("Contents");
+        fs::create_dir_all(&contents_dir).map_err(|e| {
+            format!(
+                "Failed to create Quick Action directory ({}): {}",
+                contents_dir.display(),
+                e
+            )
+        })?;
+
+        let files = [
+            (
+                contents_dir.join("Info.plist"),
+                QUICK_ACTION_INFO_PLIST.replace("{name}", QUICK_ACTION_NAME),
+            ),
+            (
+                contents_dir.join("document.wflow"),
+                QUICK_ACTION_WORKFLOW.replace("{identifier}", &app.config().identifier),
+            ),
+        ];
+        for (path, contents) in files {
+            fs::write(&path, contents)
+                .map_err(|e| format!("Failed to write Quick Action ({}): {}", path.display(), e))?;
+        }
+
+        // Ask the services daemon to pick up the new entry right away.
+        let _ = StdCommand::new("/System/Library/CoreServices/pbs")
+            .arg("-update")
+            .status();
+
+        Ok(workflow_dir.to_string_lossy().to_string())
+    }
+}
+
+#[tauri::command]
+async fn remove_finder_quick_action() -> Result<(), String> {
+    let workflow_dir = quick_action_dir()?;
+    if workflow_dir.exists() {
+        fs::remove_dir_all(&workflow_dir).map_err(|e| {
+            format!(
+                "Failed to remove Quick Action ({}): {}",
+                workflow_dir.display(),
+                e
+            )
+        })?;
+    }
+    Ok(())
+}
+
+// macOS system sounds offered for the completion chime.
+const COMPLETION_SOUNDS: [&str; 8] = [
+    "Glass",
+    "Hero",
+    "Ping",
+    "Pop",
+    "Purr",
+    "Submarine",
+    "Funk",
+    "Tink",
+];
+
+fn validate_completion_sound(sound: Option<String>) -> Result<Option<String>, String> {
+    let Some(sound) = sanitize_non_empty(sound) else {
+        return Ok(None);
+    };
+    COMPLETION_SOUNDS
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(&sound))
+        .map(|name| Some(name.to_string()))
+        .ok_or_else(|| {
+            format!(
+                "Unknown completion sound '{}'. Valid values: {}",
+                sound,
+                COMPLETION_SOUNDS.join(", ")
+            )
+        })
+}
+
+// Plays the configured chime for jobs that ran at least the configured time,
+// so short sessions finish quietly.
+fn play_completion_sound(app: &AppHandle, wall_seconds: f64) {
+    let Ok(settings) = load_settings(app) else {
+        return;
+    };
+    let Some(sound) = settings.completion_sound else {
+        return;
+    };
+    if wall_seconds < settings.completion_sound_min_seconds as f64 {
+        return;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let _ = StdCommand::new("afplay")
+            .arg(format!("/System/Library/Sounds/{}.aiff", sound))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = sound;
+}
+
+// Posts a system notification for a finished job. Skipped while the main
+// window is visible and focused, since the result is already on screen.
+fn notify_completion(app: &AppHandle, message: Message, params: &[(&str, &str)], body: &str) {
+    let Ok(settings) = load_settings(app) else {
+        return;
+    };
+    if !settings.notify_on_completion {
+        return;
+    }
+    let window_active = app.get_webview_window("main").is_some_and(|window| {
+        window.is_visible().unwrap_or(false) && window.is_focused().unwrap_or(false)
+    });
+    if window_active {
+        return;
+    }
+
+    let _ = app
+        .notification()
+        .builder()
+        .title(localize_with(&settings.locale, message, params))
+        .body(body)
+        .show();
+}
+
+// A new job lines up behind every waiting job of the same or a higher
+// priority; `reorder_queue` can change the order afterwards.
+const JOB_PRIORITIES: [&str; 3] = ["urgent", "normal", "low"];
+const QUEUE_CHANGED_EVENT: &str = "queue-changed";
+// Benchmarks and rolling chunks also take the slot and do not announce when
+// they give it back, so waiting jobs re-check it this often.
+const QUEUE_POLL_MS: u64 = 500;
+
+fn validate_job_priority(priority: &str) -> &'static str {
+    JOB_PRIORITIES
+        .iter()
+        .copied()
+        .find(|value| *value == priority.trim())
+        .unwrap_or("normal")
+}
+
+fn priority_rank(priority: &str) -> usize {
+    JOB_PRIORITIES
+        .iter()
+        .position(|value| *value == priority)
+        .unwrap_or(1)
+}
+
+fn waiting_jobs(queue: &TranscriptionQueue) -> Vec<QueuedJob> {
+    queue
+        .waiting
+        .lock()
+        .map(|waiting| waiting.clone())
+        .unwrap_or_default()
+}
+
+fn emit_queue_changed(app: &AppHandle, queue: &TranscriptionQueue) {
+    let _ = app.emit(QUEUE_CHANGED_EVENT, waiting_jobs(queue));
+}
+
+fn enqueue_waiting_job(app: &AppHandle, queue: &TranscriptionQueue, job: QueuedJob) {
+    if let Ok(mut waiting) = queue.waiting.lock() {
+        let rank = priority_rank(&job.priority);
+        let position = waiting
+            .iter()
+            .position(|waiting_job| priority_rank(&waiting_job.priority) > rank)
+            .unwrap_or(waiting.len());
+        waiting.insert(position, job);
+    }
+    emit_queue_changed(app, queue);
+}
+
+// Waits until `job_id` is first in line, not counting scheduled jobs, and the
+// slot is free, then takes the job out of the waiting list.
+async fn wait_for_turn<'a>(
+    app: &AppHandle,
+    queue: &'a TranscriptionQueue,
+    job_id: &str,
+) -> tokio::sync::MutexGuard<'a, ()> {
+    loop {
+        let is_next = queue.waiting.lock().map_or(true, |waiting| {
+            waiting
+                .iter()
+                .find(|job| job.scheduled_for.is_none())
+                .map_or(true, |job| job.job_id == job_id)
+        });
+        if is_next {
+            if let Ok(slot) = queue.slot.try_lock() {
+                if let Ok(mut waiting) = queue.waiting.lock() {
+                    waiting.retain(|job| job.job_id != job_id);
+                }
+                emit_queue_changed(app, queue);
+                return slot;
+            }
+        }
+        let _ =
+            tokio::time::timeout(Duration::from_millis(QUEUE_POLL_MS), queue.turn.notified()).await;
+    }
+}
+
+#[tauri::command]
+async fn get_queue(app: AppHandle) -> Result<Vec<QueuedJob>, String> {
+    require_unlocked(&app, None)?;
+    Ok(waiting_jobs(&app.state::<TranscriptionQueue>()))
+}
+
+// Moves the listed jobs, in that order, to the front of the queue; the rest
+// keep their order behind them. The running job is not part of the queue.
+// Scheduled jobs moved this way no longer wait for the deferred window.
+#[tauri::command]
+async fn reorder_queue(app: AppHandle, job_ids: Vec<String>) -> Result<Vec<QueuedJob>, String> {
+    require_unlocked(&app, None)?;
+    let queue = app.state::<TranscriptionQueue>();
+    {
+        let mut waiting = queue
+            .waiting
+            .lock()
+            .map_err(|_| "Failed to lock the job queue.".to_string())?;
+        let mut rest = waiting.clone();
+        let mut reordered = Vec::with_capacity(rest.len());
+        for job_id in &job_ids {
+            let position = rest
+                .iter()
+                .position(|job| &job.job_id == job_id)
+                .ok_or_else(|| format!("Job {} is not waiting in the queue.", job_id))?;
+            let mut job = rest.remove(position);
+            job.scheduled_for = None;
+            reordered.push(job);
+        }
+        reordered.extend(rest);
+        *waiting = reordered;
+    }
+    queue.turn.notify_waiters();
+    emit_queue_changed(&app, &queue);
+    Ok(waiting_jobs(&queue))
+}
+
+async fn run_transcription_job(
+    app: &AppHandle,
+    mut options: TranscriptionOptions,
+    retry_of: Option<String>,
+) -> Result<TranscriptionResult, String> {
+    if let Ok(settings) = load_settings(app) {
+        resolve_auto_model(&settings, &mut options);
+    }
+    let queue = app.state::<TranscriptionQueue>();
+    let job_id = sanitize_non_empty(options.job_id.clone()).unwrap_or_else(|| {
+        format!(
+            "job-{}-{}",
+            unix_timestamp_millis(),
+            queue.next_id.fetch_add(1, Ordering::Relaxed)
+        )
+    });
+    let mut event = JobEvent {
+        job_id: job_id.clone(),
+        model: options.model.clone(),
+        queued_at_ms: unix_timestamp_millis(),
+        ..JobEvent::default()
+    };
+    let _ = app.emit("job-queued", event.clone());
+    queue.pending.fetch_add(1, Ordering::SeqCst);
+
+    if options.background {
+        hold_background_job(app, &mut event).await;
+    }
+    let label = sanitize_non_empty(options.session_title.clone())
+        .or_else(|| sanitize_non_empty(options.client.clone()))
+        .unwrap_or_else(|| job_id.clone());
+    let scheduled_for = load_settings(app)
+        .ok()
+        .and_then(|settings| deferred_until(&settings, &options, retry_of.as_deref()));
+    enqueue_waiting_job(
+        app,
+        &queue,
+        QueuedJob {
+            job_id: job_id.clone(),
+            label,
+            priority: validate_job_priority(&options.priority).to_string(),
+            queued_at_ms: event.queued_at_ms,
+            scheduled_for: scheduled_for.clone(),
+        },
+    );
+    if let Some(scheduled_for) = scheduled_for {
+        hold_until_window(
+            app,
+            &queue,
+            &mut event,
+            scheduled_for,
+            &options,
+            retry_of.as_deref(),
+        )
+        .await;
+    }
+    let slot = wait_for_turn(app, &queue, &job_id).await;
+    let started_at_ms = unix_timestamp_millis();
+    event.started_at_ms = Some(started_at_ms);
+    event.wait_ms = Some(started_at_ms.saturating_sub(event.queued_at_ms));
+    let _ = app.emit("job-started", event.clone());
+
+    // Holds the staged uploads until the job is recorded and, if it failed,
+    // its audio kept for a retry.
+    let staged_uploads = resolve_audio_uploads(app, &mut options);
+    let mut result = match &staged_uploads {
+        Ok(_) => {
+            let split =
+                load_settings(app).is_ok_and(|settings| settings.auto_split_minutes.is_some());
+            if split {
+                let _ = preserve_failed_job(app, &job_id, &options);
+            }
+            run_transcription(app.clone(), &options, job_id).await
+        }
+        Err(error) => Err(error.clone()),
+    };
+
+    let finished_at_ms = unix_timestamp_millis();
+    let run_ms = finished_at_ms.saturating_sub(started_at_ms);
+    event.finished_at_ms = Some(finished_at_ms);
+    event.run_ms = Some(run_ms);
+
+    let wall_seconds = run_ms as f64 / 1000.0;
+    let mut record = JobRecord {
+        job_id: event.job_id.clone(),
+        model: event.model.clone(),
+        outcome: "finished".to_string(),
+        app_version: app.package_info().version.to_string(),
+        queued_at_ms: event.queued_at_ms,
+        started_at_ms,
+        finished_at_ms,
+        audio_seconds: None,
+        wall_seconds,
+        real_time_factor: None,
+        warnings: Vec::new(),
+        error: None,
+        saved_path: None,
+        retry_of,
+        retryable: false,
+        transcript_sha256: None,
+    };
+    match &result {
+        Ok(transcription) => {
+            record.model = transcription.model_used.clone();
+            record.audio_seconds = Some(transcription.duration_seconds);
+            record.real_time_factor = (transcription.duration_seconds > 0)
+                .then(|| round_to(wall_seconds / transcription.duration_seconds as f64, 3));
+            record.warnings = transcription.warnings.clone();
+            record.saved_path = transcription.saved_path.clone();
+            record.transcript_sha256 = transcription
+                .saved_path
+                .is_some()
+                .then(|| transcript_sha256(&transcription.transcript));
+            event.saved_path = transcription.saved_path.clone();
+            if let Some(saved_path) = &transcription.saved_path {
+                notify_completion(app, Message::NotifyTranscriptSaved, &[], saved_path);
+            }
+            if let Ok(dir) = failed_job_dir(app, &record.job_id) {
+                let _ = fs::remove_dir_all(dir);
+            }
+            if let Some(saved_path) = &transcription.saved_path {
+                send_webhooks(
+                    app,
+                    WebhookEvent::TranscriptSaved,
+                    WebhookFields {
+                        job_id: record.job_id.clone(),
+                        title: transcription.title.clone(),
+                        transcript: transcription.transcript.clone(),
+                        summary: transcription.summary.clone().unwrap_or_default(),
+                        client: options.client.clone().unwrap_or_default(),
+                        path: saved_path.clone(),
+                        ..WebhookFields::default()
+                    },
+                );
+                if options.export_to_drive {
+                    let uploads = if transcription.part_paths.is_empty() {
+                        vec![saved_path.clone()]
+                    } else {
+                        transcription.part_paths.clone()
+                    };
+                    spawn_drive_uploads(app, uploads.into_iter().map(PathBuf::from).collect());
+                }
+            }
+            let _ = app.emit("job-finished", event);
+        }
+        Err(error) => {
+            record.outcome = "failed".to_string();
+            record.error = Some(error.clone());
+            record.retryable = preserve_failed_job(app, &record.job_id, &options).is_ok();
+            event.error = Some(error.clone());
+            if is_model_load_failure(error) {
+                check_model_after_failure(app, &options.model);
+            }
+            notify_completion(app, Message::NotifyTranscriptionFailed, &[], error);
+            send_webhooks(
+                app,
+                WebhookEvent::JobFailed,
+                WebhookFields {
+                    job_id: record.job_id.clone(),
+                    title: options.session_title.clone().unwrap_or_default(),
+                    client: options.client.clone().unwrap_or_default(),
+                    error: error.clone(),
+                    ..WebhookFields::default()
+                },
+            );
+            let _ = app.emit("job-failed", event);
+        }
+    }
+
+    play_completion_sound(app, wall_seconds);
+    if let Err(error) = append_job_record(app, record) {
+        if let Ok(transcription) = &mut result {
+            transcription.warnings.push(error);
+        }
+    }
+    drop(slot);
+    queue.turn.notify_waiters();
+    if queue.pending.fetch_sub(1, Ordering::SeqCst) == 1 {
+        send_webhooks(app, WebhookEvent::QueueEmpty, WebhookFields::default());
+    }
+
+    result
+}
+
+fn failed_job_dir(app: &AppHandle, job_id: &str) -> Result<PathBuf, String> {
+    let name = sanitize_filename_component(job_id);
+    if name.is_empty() {
+        return Err(format!("Invalid job id: {}", job_id));
+    }
+    Ok(app_data_dir(app)?.join("failed-jobs").join(name))
+}
+
+const FAILED_JOB_AUDIO_FILES: [&str; 3] = ["primary.wav", "microphone.wav", "system.wav"];
+const JOB_CHECKPOINT_FILE: &str = "checkpoint.json";
+
+// Keeps the audio and options of a job so it can be retried or resumed
+// without re-recording. Audio is stored as WAV files next to `options.json`.
+// Split jobs save theirs before the first part runs; a later failure finds
+// them already there and leaves them alone.
+fn preserve_failed_job(
+    app: &AppHandle,
+    job_id: &str,
+    options: &TranscriptionOptions,
+) -> Result<PathBuf, String> {
+    let audio = options.source_audio();
+    if audio.iter().all(|audio| audio.is_empty()) {
+        return Err("Failed job has no audio to keep.".to_string());
+    }
+
+    let dir = failed_job_dir(app, job_id)?;
+    let path = dir.join("options.json");
+    if path.exists() {
+        return Ok(dir);
+    }
+    fs::create_dir_all(&dir).map_err(|e| {
+        format!(
+            "Failed to create failed job directory ({}): {}",
+            dir.display(),
+            e
+        )
+    })?;
+
+    for (file_name, audio) in FAILED_JOB_AUDIO_FILES.iter().zip(audio) {
+        let path = dir.join(file_name);
+        let saved = match audio {
+            SourceAudio::Bytes(wav_data) if wav_data.is_empty() => continue,
+            SourceAudio::Bytes(wav_data) => fs::write(&path, wav_data),
+            SourceAudio::Staged(source) => fs::copy(source, &path).map(|_| ()),
+        };
+        saved.map_err(|e| format!("Failed to write job audio ({}): {}", path.display(), e))?;
+    }
+
+    let serialized = serde_json::to_string_pretty(options)
+        .map_err(|e| format!("Failed to serialize job options: {}", e))?;
+    fs::write(&path, serialized)
+        .map_err(|e| format!("Failed to write job options ({}): {}", path.display(), e))?;
+
+    Ok(dir)
+}
+
+// Progress of a job split into parts: where each part starts and the
+// segments of every part finished so far, on the recording's timeline.
+#[derive(Default, Serialize, Deserialize)]
+struct JobCheckpoint {
+    part_starts: Vec<u64>,
+    model: String,
+    completed_parts: Vec<Vec<TimestampedSegment>>,
+    diarization_applied: bool,
+    warnings: Vec<String>,
+}
+
+fn load_job_checkpoint(app: &AppHandle, job_id: &str) -> Option<JobCheckpoint> {
+    let raw =
+        fs::read_to_string(failed_job_dir(app, job_id).ok()?.join(JOB_CHECKPOINT_FILE)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+// Only jobs whose audio was saved up front can be resumed, so the
+// checkpoint is skipped when `options.json` is missing.
+fn save_job_checkpoint(
+    app: &AppHandle,
+    job_id: &str,
+    checkpoint: &JobCheckpoint,
+) -> Result<(), String> {
+    let dir = failed_job_dir(app, job_id)?;
+    if !dir.join("options.json").exists() {
+        return Ok(());
+    }
+    let path = dir.join(JOB_CHECKPOINT_FILE);
+    let serialized = serde_json::to_vec(checkpoint)
+        .map_err(|e| format!("Failed to serialize job checkpoint: {}", e))?;
+    fs::write(&path, serialized)
+        .map_err(|e| format!("Failed to write job checkpoint ({}): {}", path.display(), e))
+}
+
+fn load_failed_job(app: &AppHandle, job_id: &str) -> Result<TranscriptionOptions, String> {
+    let dir = failed_job_dir(app, job_id)?;
+    let path = dir.join("options.json");
+    if !path.exists() {
+        return Err(format!(
+            "Job {} has no saved audio to retry. Only failed jobs can be retried.",
+            job_id
+        ));
+    }
+
+    let raw = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read job options ({}): {}", path.display(), e))?;
+    let mut options: TranscriptionOptions =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid job options JSON: {}", e))?;
+    options.audio_upload_id = None;
+    options.microphone_upload_id = None;
+    options.system_upload_id = None;
+    options.job_id = None;
+
+    for (file_name, audio_data) in FAILED_JOB_AUDIO_FILES.iter().zip([
+        &mut options.audio_data,
+        &mut options.microphone_audio_data,
+        &mut options.system_audio_data,
+    ]) {
+        let path = dir.join(file_name);
+        if path.exists() {
+            *audio_data = fs::read(&path)
+                .map_err(|e| format!("Failed to read job audio ({}): {}", path.display(), e))?;
+        }
+    }
+
+    Ok(options)
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RetryOverrides {
+    model: Option<String>,
+    language: Option<String>,
+    diarization_mode: Option<String>,
+}
+
+#[tauri::command]
+async fn retry_job(
+    app: AppHandle,
+    job_id: String,
+    overrides: Option<RetryOverrides>,
+) -> Result<TranscriptionResult, String> {
+    require_unlocked(&app, None)?;
+    let mut options = load_failed_job(&app, &job_id)?;
+    let overrides = overrides.unwrap_or_default();
+    if let Some(model) = sanitize_non_empty(overrides.model) {
+        validate_model(&model)?;
+        options.model = model;
+    }
+    if let Some(language) = sanitize_non_empty(overrides.language) {
+        options.language = language;
+    }
+    if let Some(diarization_mode) = sanitize_non_empty(overrides.diarization_mode) {
+        options.diarization_mode = validate_diarization_mode(&diarization_mode).to_string();
+    }
+
+    let result = run_transcription_job(&app, options, Some(job_id.clone())).await;
+    if result.is_ok() {
+        if let Ok(dir) = failed_job_dir(&app, &job_id) {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+    result
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResumableJob {
+    job_id: String,
+    completed_parts: usize,
+    part_count: usize,
+    model: String,
+}
+
+// Jobs interrupted (by a crash, quit, or failure) after at least one part of
+// a split recording was transcribed.
+#[tauri::command]
+async fn list_resumable_jobs(app: AppHandle) -> Result<Vec<ResumableJob>, String> {
+    require_unlocked(&app, None)?;
+    let root = app_data_dir(&app)?.join("failed-jobs");
+    let Ok(entries) = fs::read_dir(&root) else {
+        return Ok(Vec::new());
+    };
+    let mut jobs = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let job_id = entry.file_name().to_string_lossy().to_string();
+            let checkpoint = load_job_checkpoint(&app, &job_id)?;
+            (checkpoint.completed_parts.len() < checkpoint.part_starts.len()).then(|| {
+                ResumableJob {
+                    job_id,
+                    completed_parts: checkpoint.completed_parts.len(),
+                    part_count: checkpoint.part_starts.len(),
+                    model: checkpoint.model,
+                }
+            })
+        })
+        .collect::<Vec<ResumableJob>>();
+    jobs.sort_by(|a, b| a.job_id.cmp(&b.job_id));
+    Ok(jobs)
+}
+
+// Continues a split job from its first unfinished part, with the options and
+// model it was running with. Unlike `retry_job`, finished parts are not
+// transcribed again.
+#[tauri::command]
+async fn resume_job(app: AppHandle, job_id: String) -> Result<TranscriptionResult, String> {
+    require_unlocked(&app, None)?;
+    if load_job_checkpoint(&app, &job_id).is_none() {
+        return Err(format!(
+            "Job {} has no checkpoint to resume from. Only jobs split into parts can be resumed.",
+            job_id
+        ));
+    }
+    let mut options = load_failed_job(&app, &job_id)?;
+    options.job_id = Some(job_id);
+    run_transcription_job(&app, options, None).await
+}
+
+#[tauri::command]
+async fn discard_failed_job(app: AppHandle, job_id: String) -> Result<(), String> {
+    require_unlocked(&app, None)?;
+    let dir = failed_job_dir(&app, &job_id)?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| {
+            format!(
+                "Failed to remove failed job directory ({}): {}",
+                dir.display(),
+                e
+            )
+        })?;
+    }
+    Ok(())
+}
+
+// Audit log entries, oldest first. `range` takes inclusive `YYYY-MM-DD`
+// dates, matched against each entry's local date; without it the whole log
+// is returned. A line that does not parse (a write cut short) is skipped.
+#[tauri::command]
+async fn get_audit_log(
+    app: AppHandle,
+    range: Option<DigestRange>,
+) -> Result<Vec<AuditEntry>, String> {
+    require_unlocked(&app, None)?;
+    if let Some(range) = &range {
+        for date in [&range.start, &range.end] {
+            if !is_iso_date(date) {
+                return Err(format!("Invalid date '{}'. Use YYYY-MM-DD.", date));
+            }
+        }
+    }
+
+    let path = audit_log_path(&app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read audit log ({}): {}", path.display(), e))?;
+    Ok(raw
+        .lines()
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+        .filter(|entry| {
+            range.as_ref().map_or(true, |range| {
+                let date = entry.at.get(..10).unwrap_or_default();
+                date >= range.start.as_str() && date <= range.end.as_str()
+            })
+        })
+        .collect())
+}
+
+#[tauri::command]
+async fn get_job_history(app: AppHandle, limit: Option<usize>) -> Result<Vec<JobRecord>, String> {
+    require_unlocked(&app, None)?;
+    let mut history = load_job_history(&app)?;
+    history.reverse();
+    if let Some(limit) = limit {
+        history.truncate(limit);
+    }
+    for record in &mut history {
+        record.retryable = record.outcome == "failed"
+            && failed_job_dir(&app, &record.job_id)
+                .map(|dir| dir.join("options.json").exists())
+                .unwrap_or(false);
+    }
+    Ok(history)
+}
+
+// Audio a job was given: bytes sent with its options, or a staged upload
+// still in its file.
+#[derive(Clone, Copy)]
+enum SourceAudio<'a> {
+    Bytes(&'a [u8]),
+    Staged(&'a Path),
+}
+
+impl SourceAudio<'_> {
+    fn is_empty(self) -> bool {
+        match self {
+            Self::Bytes(wav_data) => wav_data.is_empty(),
+            Self::Staged(_) => false,
+        }
+    }
+}
+
+impl TranscriptionOptions {
+    // The recording, microphone and system tracks, in that order.
+    fn source_audio(&self) -> [SourceAudio<'_>; 3] {
+        [
+            (&self.audio_data, &self.audio_path),
+            (&self.microphone_audio_data, &self.microphone_audio_path),
+            (&self.system_audio_data, &self.system_audio_path),
+        ]
+        .map(|(wav_data, path)| match path {
+            Some(path) if wav_data.is_empty() => SourceAudio::Staged(path),
+            _ => SourceAudio::Bytes(wav_data),
+        })
+    }
+}
+
+// One channel of a job's audio, ready for whisper. A staged upload that needs
+// no conversion or trim stays in its file and whisper reads it from there.
+enum JobTrack<'a> {
+    Memory(Cow<'a, [u8]>),
+    File(&'a Path, WavInfo),
+}
+
+impl<'a> JobTrack<'a> {
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Memory(wav_data) => wav_data.is_empty(),
+            Self::File(..) => false,
+        }
+    }
+
+    fn duration_ms(&self) -> u64 {
+        match self {
+            Self::Memory(wav_data) => estimate_duration_ms(wav_data),
+            Self::File(_, info) => info.duration_ms(),
+        }
+    }
+
+    fn byte_len(&self) -> usize {
+        match self {
+            Self::Memory(wav_data) => wav_data.len(),
+            Self::File(_, info) => info.data_offset + info.data_len,
+        }
+    }
+
+    fn bytes(&self) -> Result<Cow<'_, [u8]>, String> {
+        match self {
+            Self::Memory(wav_data) => Ok(Cow::Borrowed(wav_data)),
+            Self::File(path, _) => fs::read(path)
+                .map(Cow::Owned)
+                .map_err(|e| format!("Failed to read uploaded audio ({}): {}", path.display(), e)),
+        }
+    }
+
+    // Loads a staged file, for jobs that cut the track up more than once.
+    fn loaded(self) -> Result<JobTrack<'a>, String> {
+        match self {
+            Self::File(..) => Ok(Self::Memory(Cow::Owned(self.bytes()?.into_owned()))),
+            memory => Ok(memory),
+        }
+    }
+
+    fn trimmed(&self, trim: AudioTrim) -> Result<JobTrack<'_>, String> {
+        Ok(match self {
+            Self::Memory(wav_data) => JobTrack::Memory(trim_wav(wav_data, trim)),
+            Self::File(path, info) if !trim.is_active() => JobTrack::File(path, *info),
+            Self::File(..) => {
+                JobTrack::Memory(Cow::Owned(trim_wav(&self.bytes()?, trim).into_owned()))
+            }
+        })
+    }
+}
+
+struct JobAudio<'a> {
+    raw_primary: SourceAudio<'a>,
+    primary: JobTrack<'a>,
+    microphone: JobTrack<'a>,
+    system: JobTrack<'a>,
+    system_offset_ms: u64,
+    // Where the trimmed primary track starts in `raw_primary`.
+    trim_start_ms: u64,
+    warnings: Vec<String>,
+}
+
+fn trim_track(wav_data: Cow<'_, [u8]>, trim: AudioTrim) -> Cow<'_, [u8]> {
+    match wav_data {
+        Cow::Borrowed(wav_data) => trim_wav(wav_data, trim),
+        Cow::Owned(wav_data) if trim.is_active() => {
+            Cow::Owned(trim_wav(&wav_data, trim).into_owned())
+        }
+        owned => owned,
+    }
+}
+
+// Picks the primary track, converts every channel to 16 kHz mono, applies the
+// trim, and enforces the size and duration limits.
+fn prepare_job_audio<'a>(
+    settings: &AppSettings,
+    options: &'a TranscriptionOptions,
+) -> Result<JobAudio<'a>, String> {
+    let locale = settings.locale.as_str();
+    let [recording, microphone, system] = options.source_audio();
+    let raw_primary = [recording, system, microphone]
+        .into_iter()
+        .find(|audio| !audio.is_empty())
+        .ok_or_else(|| localize(locale, Message::NoAudioData))?;
+    let trim = AudioTrim::from_seconds(options.trim_start_seconds, options.trim_end_seconds)?;
+
+    let mut warnings = Vec::new();
+    let mut convert = |audio: SourceAudio<'a>,
+                       track: AudioTrack,
+                       min_duration_ms: u64,
+                       trim: AudioTrim|
+     -> Result<JobTrack<'a>, String> {
+        let wav_data = match audio {
+            SourceAudio::Bytes(wav_data) if wav_data.is_empty() => {
+                return Ok(JobTrack::Memory(Cow::Borrowed(wav_data)));
+            }
+            SourceAudio::Bytes(wav_data) => Cow::Borrowed(wav_data),
+            SourceAudio::Staged(path) => {
+                let info = validate_wav_file(path, track, min_duration_ms, locale)?;
+                if info.is_whisper_ready() && !trim.is_active() {
+                    return Ok(JobTrack::File(path, info));
+                }
+                Cow::Owned(fs::read(path).map_err(|e| {
+                    format!("Failed to read uploaded audio ({}): {}", path.display(), e)
+                })?)
+            }
+        };
+        let info = validate_wav(&wav_data, track, min_duration_ms, locale)?;
+        let (converted, warning) = convert_for_whisper(&wav_data, info, locale)?;
+        warnings.extend(warning);
+        let converted = match converted {
+            Cow::Owned(converted) => Cow::Owned(converted),
+            Cow::Borrowed(_) => wav_data,
+        };
+        Ok(JobTrack::Memory(trim_track(converted, trim)))
+    };
+    let primary = convert(
+        raw_primary,
+        AudioTrack::Recording,
+        MIN_AUDIO_DURATION_MS,
+        trim,
+    )?;
+    let microphone = convert(microphone, AudioTrack::Microphone, 0, trim)?;
+    let system = convert(
+        system,
+        AudioTrack::SystemAudio,
+        0,
+        trim.shifted(options.system_audio_offset_ms),
+    )?;
+
+    if primary.is_empty() {
+        return Err(localize(locale, Message::TrimLeavesNoAudio));
+    }
+    check_input_limits(
+        settings,
+        locale,
+        primary.duration_ms(),
+        primary.byte_len() + microphone.byte_len() + system.byte_len(),
+    )?;
+
+    Ok(JobAudio {
+        raw_primary,
+        primary,
+        microphone,
+        system,
+        system_offset_ms: options.system_audio_offset_ms.saturating_sub(trim.start_ms),
+        trim_start_ms: trim.start_ms,
+        warnings,
+    })
+}
+
+struct JobSetup {
+    model_used: &'static ModelCatalogEntry,
+    model_path: PathBuf,
+    speaker_mode: String,
+    warnings: Vec<String>,
+}
+
+// Settles the model and speaker mode a job will actually run with: checks the
+// model is downloaded, drops diarization the audio or model cannot support,
+// and falls back to a smaller model when free memory is short.
+fn resolve_job_setup(
+    app: &AppHandle,
+    settings: &AppSettings,
+    options: &TranscriptionOptions,
+    has_dual_source_audio: bool,
+) -> Result<JobSetup, String> {
+    let locale = settings.locale.as_str();
+    let mut model_used = validate_model(&options.model)?;
+    validate_language(&options.language)?;
+    let mut model_path = model_file_path(app, &options.model)?;
+
+    // With auto-download on, run_transcription fetches the model before
+    // getting here; a dry-run plan only notes that it will.
+    let mut warnings = Vec::new();
+    if !model_path.exists() {
+        if !settings.auto_download_models || settings.offline_mode {
+            return Err(localize_with(
+                locale,
+                Message::ModelNotDownloaded,
+                &[("model", options.model.as_str())],
+            ));
+        }
+        warnings.push(localize_with(
+            locale,
+            Message::DownloadingMissingModel,
+            &[("model", options.model.as_str())],
+        ));
+    }
+
+    let mut speaker_mode_used = if options.diarization_mode.trim().is_empty() {
+        validate_diarization_mode(&settings.diarization_mode).to_string()
+    } else {
+        validate_diarization_mode(&options.diarization_mode).to_string()
+    };
+
+    if speaker_mode_used == "source_aware_2speaker" && !has_dual_source_audio {
+        warnings.push(localize(locale, Message::SourceAwareNeedsBothChannels));
+        speaker_mode_used = "none".to_string();
+    }
+
+    if speaker_mode_used == "tdrz_2speaker" {
+        if options.language != "en" {
+            warnings.push(localize(locale, Message::TdrzEnglishOnly));
+            speaker_mode_used = "none".to_string();
+        } else if options.model != "small.en-tdrz" {
+            warnings.push(localize(locale, Message::TdrzModelRequired));
+            speaker_mode_used = "none".to_string();
+        }
+    }
+
+    // Refuse to start a model that would not fit in free memory rather than
+    // letting the OS kill whisper partway through a long session.
+    if let Some(available_mb) = available_memory_bytes().map(|bytes| bytes / (1024 * 1024)) {
+        let required_mb = u64::from(model_used.ram_mb);
+        if available_mb < required_mb {
+            let fallback =
+                if settings.fallback_to_smaller_model && speaker_mode_used != "tdrz_2speaker" {
+                    next_smaller_downloaded_model(app, model_used.id, Some(available_mb))
+                } else {
+                    None
+                };
+            let Some(fallback) = fallback else {
+                let required = format!("{} MB", required_mb);
+                let available = format!("{} MB", available_mb);
+                return Err(localize_with(
+                    locale,
+                    Message::InsufficientMemory,
+                    &[
+                        ("model", model_used.id),
+                        ("required", required.as_str()),
+                        ("available", available.as_str()),
+                        ("suggestion", suggest_model_for_memory(available_mb)),
+                    ],
+                ));
+            };
+
+            warnings.push(localize_with(
+                locale,
+                Message::ModelFallback,
+                &[("model", model_used.id), ("fallback", fallback.id)],
+            ));
+            model_used = fallback;
+            model_path = model_file_path(app, fallback.id)?;
+        } else if available_mb < required_mb * 3 / 2 {
+            let available = format!("{} MB", available_mb);
+            warnings.push(localize_with(
+                locale,
+                Message::LowMemory,
+                &[("model", model_used.id), ("available", available.as_str())],
+            ));
+        }
+    }
+
+    Ok(JobSetup {
+        model_used,
+        model_path,
+        speaker_mode: speaker_mode_used,
+        warnings,
+    })
+}
+
+// Downloads the job's model inline when it is missing and auto-download is
+// enabled, reporting through the usual model-download progress events so the
+// user is not sent back to Setup mid-session. In offline mode, or with the
+// setting off, resolve_job_setup reports the missing model as before.
+async fn ensure_job_model_downloaded(
+    app: &AppHandle,
+    settings: &AppSettings,
+    model: &str,
+) -> Result<(), String> {
+    let model_used = validate_model(model)?;
+    if !settings.auto_download_models
+        || settings.offline_mode
+        || model_file_path(app, model_used.id)?.exists()
+    {
+        return Ok(());
+    }
+
+    emit_progress(
+        app,
+        CHECKING_AUDIO_PROGRESS,
+        &localize_with(
+            &settings.locale,
+            Message::DownloadingMissingModel,
+            &[("model", model_used.id)],
+        ),
+    );
+    download_model(
+        app.clone(),
+        ModelDownloadOptions {
+            model: model_used.id.to_string(),
+            overwrite: false,
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+// Rolling transcription: while a microphone recording is still running, the
+// recorder sends the audio recorded since the last chunk every couple of
+// minutes and whisper transcribes it up to a quiet point near its end. The
+// job started at stop then only transcribes what is left.
+const ROLLING_MIN_CHUNK_MS: u64 = 30_000;
+const ROLLING_SEARCH_WINDOW_MS: u64 = 20_000;
+// Audio right before the end of a chunk may hold a word cut in half; it is
+// left for the next chunk.
+const ROLLING_TAIL_MS: u64 = 2_000;
+const ROLLING_PROGRESS_EVENT: &str = "rolling-transcription-progress";
+
+// What a rolling session was started with. The final job reuses its segments
+// only if it would have transcribed the same way.
+#[derive(Debug, Clone, PartialEq)]
+struct RollingKey {
+    model: String,
+    language: String,
+    diarization_mode: String,
+    client: Option<String>,
+    denoise: bool,
+    normalize_loudness: bool,
+    verbatim: bool,
+    translate: bool,
+    workspace: Option<String>,
+}
+
+impl RollingKey {
+    fn for_options(options: &TranscriptionOptions) -> Self {
+        Self {
+            model: options.model.clone(),
+            language: options.language.clone(),
+            diarization_mode: options.diarization_mode.clone(),
+            client: sanitize_non_empty(options.client.clone()),
+            denoise: options.denoise,
+            normalize_loudness: options.normalize_loudness,
+            verbatim: options.verbatim,
+            translate: options.translate,
+            workspace: sanitize_non_empty(options.workspace.clone()),
+        }
+    }
+}
+
+struct RollingSession {
+    options: Arc<TranscriptionOptions>,
+    // The model whisper actually ran, after any fallback.
+    model_used: Option<String>,
+    // Segments on the recording's timeline up to `covered_ms`.
+    covered_ms: u64,
+    segments: Vec<TimestampedSegment>,
+    diarization_applied: bool,
+    warnings: Vec<String>,
+}
+
+#[derive(Default)]
+struct RollingTranscriptionState {
+    sessions: Mutex<HashMap<String, RollingSession>>,
+    next_id: AtomicU64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RollingProgress {
+    session_id: String,
+    covered_ms: u64,
+    segment_count: usize,
+}
+
+fn take_rolling_session(app: &AppHandle, session_id: &str) -> Option<RollingSession> {
+    app.state::<RollingTranscriptionState>()
+        .sessions
+        .lock()
+        .ok()?
+        .remove(session_id)
+}
+
+// The CoachNotes client a job belongs to, whose per-client settings apply.
+fn job_client(settings: &AppSettings, options: &TranscriptionOptions) -> Option<String> {
+    if !settings.coachnotes_enabled {
+        return None;
+    }
+    sanitize_non_empty(options.client.clone())
+        .or_else(|| sanitize_non_empty(settings.coachnotes_client.clone()))
+}
+
+// Replaces the job's language and translate choice with the client's, when
+// the client has one. Retries and resumed jobs keep what they were saved with.
+fn apply_client_language(settings: &AppSettings, options: &mut TranscriptionOptions) {
+    let Some(client_language) =
+        job_client(settings, options).and_then(|client| settings.client_languages.get(&client))
+    else {
+        return;
+    };
+    options.language = client_language.language.clone();
+    options.translate = client_language.translate;
+}
+
+// Prompt that steers whisper for a job: the client's prompt, and in verbatim
+// English mode the prompt that keeps fillers.
+fn job_prompt(settings: &AppSettings, options: &TranscriptionOptions) -> Option<String> {
+    let client_prompt = job_client(settings, options)
+        .and_then(|client| settings.client_prompts.get(&client).cloned());
+    if options.verbatim && options.language == "en" {
+        Some(match client_prompt {
+            Some(client_prompt) => format!("{} {}", VERBATIM_PROMPT, client_prompt),
+            None => VERBATIM_PROMPT.to_string(),
+        })
+    } else {
+        client_prompt
+    }
+}
+
+// Starts a rolling session for the recording that just began. `options` are
+// the recorder's transcription options without audio; any earlier session is
+// dropped, since only one recording runs at a time.
+#[tauri::command]
+async fn start_rolling_transcription(
+    app: AppHandle,
+    mut options: TranscriptionOptions,
+) -> Result<String, String> {
+    let settings = load_settings(&app)?;
+    if !settings.rolling_transcription {
+        return Err("Rolling transcription is turned off.".to_string());
+    }
+    apply_client_language(&settings, &mut options);
+    validate_model(&options.model)?;
+    validate_language(&options.language)?;
+    if validate_diarization_mode(&options.diarization_mode) == "source_aware_2speaker" {
+        return Err(
+            "Rolling transcription needs a microphone-only recording without source-aware speakers."
+                .to_string(),
+        );
+    }
+
+    let state = app.state::<RollingTranscriptionState>();
+    let session_id = format!(
+        "rolling-{}-{}",
+        unix_timestamp_millis(),
+        state.next_id.fetch_add(1, Ordering::Relaxed)
+    );
+    let mut sessions = state
+        .sessions
+        .lock()
+        .map_err(|_| "Failed to lock rolling transcription state.".to_string())?;
+    sessions.clear();
+    sessions.insert(
+        session_id.clone(),
+        RollingSession {
+            options: Arc::new(options),
+            model_used: None,
+            covered_ms: 0,
+            segments: Vec::new(),
+            diarization_applied: false,
+            warnings: Vec::new(),
+        },
+    );
+    Ok(session_id)
+}
+
+// Transcribes a staged upload holding the recording from `offset_ms` on. The
+// offset has to match where the session left off; the reply says where the
+// next chunk starts.
+#[tauri::command]
+async fn transcribe_rolling_chunk(
+    app: AppHandle,
+    session_id: String,
+    upload_id: String,
+    offset_ms: u64,
+) -> Result<RollingProgress, String> {
+    let path = take_uploaded_audio(&app.state::<AudioUploadState>(), &upload_id)?;
+    let _cleanup = TempFileCleanup::new(vec![path.clone()]);
+    // Chunks cover a few minutes at most, so they are searched in memory.
+    let audio = fs::read(&path)
+        .map_err(|e| format!("Failed to read uploaded audio ({}): {}", path.display(), e))?;
+    let (options, covered_ms) = {
+        let sessions = app
+            .state::<RollingTranscriptionState>()
+            .sessions
+            .lock()
+            .map_err(|_| "Failed to lock rolling transcription state.".to_string())?;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| format!("Unknown rolling transcription session: {}", session_id))?;
+        (session.options.clone(), session.covered_ms)
+    };
+    if offset_ms != covered_ms {
+        return Err(format!(
+            "Rolling chunk starts at {} ms, but the session is transcribed up to {} ms.",
+            offset_ms, covered_ms
+        ));
+    }
+
+    let mut settings = load_settings(&app)?;
+    use_job_workspace(&mut settings, &options)?;
+    let locale = settings.locale.clone();
+    let info = validate_wav(
+        &audio,
+        AudioTrack::Recording,
+        MIN_AUDIO_DURATION_MS,
+        &locale,
+    )?;
+    let (audio, _) = convert_for_whisper(&audio, info, &locale)?;
+    let duration_ms = estimate_duration_ms(&audio);
+    if duration_ms < ROLLING_MIN_CHUNK_MS {
+        return Ok(RollingProgress {
+            session_id,
+            covered_ms,
+            segment_count: 0,
+        });
+    }
+    let cut_ms = quietest_split_point(
+        wav_pcm_data(&audio),
+        duration_ms.saturating_sub(ROLLING_SEARCH_WINDOW_MS),
+        duration_ms - ROLLING_TAIL_MS,
+    );
+
+    let _slot = app.state::<TranscriptionQueue>().slot.lock().await;
+    let JobSetup {
+        model_used,
+        model_path,
+        speaker_mode,
+        mut warnings,
+    } = resolve_job_setup(&app, &settings, &options, false)?;
+    if !model_path.exists() {
+        return Err(localize_with(
+            &locale,
+            Message::ModelNotDownloaded,
+            &[("model", options.model.as_str())],
+        ));
+    }
+    let prompt = job_prompt(&settings, &options);
+    let job = WhisperJob {
+        app: &app,
+        model_path: &model_path,
+        language: &options.language,
+        speaker_mode: &speaker_mode,
+        preprocessing: AudioPreprocessing {
+            denoise: options.denoise,
+            normalize_loudness: options.normalize_loudness,
+        },
+        locale: &locale,
+        prompt: prompt.as_deref(),
+        raw_output_dir: None,
+        partial: None,
+        captions: None,
+        verbatim: options.verbatim,
+        translate: options.translate,
+    };
+    let chunk = trim_wav(
+        &audio,
+        AudioTrim {
+            start_ms: 0,
+            end_ms: Some(cut_ms),
+        },
+    );
+    let (mut segments, applied) = transcribe_part_segments(
+        &job,
+        PartAudio {
+            primary: &JobTrack::Memory(chunk),
+            microphone: &JobTrack::Memory(Cow::Borrowed(&[])),
+            system: &JobTrack::Memory(Cow::Borrowed(&[])),
+            system_offset_ms: 0,
+        },
+        &format!("{}-{}", session_id, offset_ms),
+        (0, 100),
+        &mut warnings,
+    )
+    .await?;
+    shift_segments(&mut segments, offset_ms);
+
+    let progress = {
+        let mut sessions = app
+            .state::<RollingTranscriptionState>()
+            .sessions
+            .lock()
+            .map_err(|_| "Failed to lock rolling transcription state.".to_string())?;
+        let session = sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| format!("Unknown rolling transcription session: {}", session_id))?;
+        if session
+            .model_used
+            .as_deref()
+            .is_some_and(|model| model != model_used.id)
+        {
+            sessions.remove(&session_id);
+            return Err(
+                "Whisper switched models partway through the recording; the recording will be transcribed in full after it stops."
+                    .to_string(),
+            );
+        }
+        session.model_used = Some(model_used.id.to_string());
+        session.covered_ms = offset_ms + cut_ms;
+        session.segments.extend(segments);
+        session.diarization_applied |= applied;
+        session.warnings.extend(warnings);
+        RollingProgress {
+            session_id: session_id.clone(),
+            covered_ms: session.covered_ms,
+            segment_count: session.segments.len(),
+        }
+    };
+    let _ = app.emit(ROLLING_PROGRESS_EVENT, progress.clone());
+    Ok(progress)
+}
+
+#[tauri::command]
+async fn discard_rolling_transcription(app: AppHandle, session_id: String) -> Result<(), String> {
+    take_rolling_session(&app, &session_id);
+    Ok(())
+}
+
+#[tauri::command]
+async fn set_rolling_transcription(app: AppHandle, enabled: bool) -> Result<SetupState, String> {
+    require_unlocked(&app, None)?;
+    let mut settings = load_settings(&app)?;
+    settings.rolling_transcription = enabled;
+    save_settings(&app, &settings)?;
+    build_setup_state(&app)
+}
+
+async fn run_transcription(
+    app: AppHandle,
+    options: &TranscriptionOptions,
+    job_id: String,
+) -> Result<TranscriptionResult, String> {
+    let mut settings = load_settings(&app)?;
+    use_job_workspace(&mut settings, options)?;
+    let locale = settings.locale.clone();
+    emit_progress(
+        &app,
+        CHECKING_AUDIO_PROGRESS,
+        &localize(&locale, Message::CheckingAudio),
+    );
+    let JobAudio {
+        raw_primary: raw_primary_audio,
+        primary: mut primary_audio,
+        microphone: mut microphone_audio,
+        system: mut system_audio,
+        system_offset_ms: system_audio_offset_ms,
+        trim_start_ms,
+        warnings: audio_warnings,
+    } = prepare_job_audio(&settings, options)?;
+
+    ensure_job_model_downloaded(&app, &settings, &options.model).await?;
+
+    let has_dual_source_audio = !microphone_audio.is_empty() && !system_audio.is_empty();
+    let JobSetup {
+        mut model_used,
+        mut model_path,
+        speaker_mode: speaker_mode_used,
+        mut warnings,
+    } = resolve_job_setup(&app, &settings, options, has_dual_source_audio)?;
+    warnings.splice(0..0, audio_warnings);
+
+    let preprocessing = AudioPreprocessing {
+        denoise: options.denoise,
+        normalize_loudness: options.normalize_loudness,
+    };
+    let prompt = job_prompt(&settings, options);
+    let timestamp = unix_timestamp_secs()?;
+    let raw_staging_dir = if settings.keep_raw_whisper_output {
+        let dir = echo_scribe_temp_dir()?.join(format!("raw-{}", job_id));
+        fs::create_dir_all(&dir).map_err(|e| {
+            format!(
+                "Failed to create raw output folder ({}): {}",
+                dir.display(),
+                e
+            )
+        })?;
+        Some(dir)
+    } else {
+        None
+    };
+    let _raw_staging_cleanup = TempFileCleanup::new(raw_staging_dir.iter().cloned().collect());
+    // A resumed job keeps the split it started with, even if the setting has
+    // changed since.
+    let resumed = load_job_checkpoint(&app, &job_id).filter(|checkpoint| {
+        !checkpoint.completed_parts.is_empty()
+            && checkpoint.completed_parts.len() < checkpoint.part_starts.len()
+    });
+    // Split jobs cut every track into parts, so staged uploads are read once
+    // here rather than once per part.
+    if resumed.is_some() || settings.auto_split_minutes.is_some() {
+        primary_audio = primary_audio.loaded()?;
+        microphone_audio = microphone_audio.loaded()?;
+        system_audio = system_audio.loaded()?;
+    }
+    let part_starts = match (&resumed, settings.auto_split_minutes) {
+        (Some(checkpoint), _) => checkpoint.part_starts.clone(),
+        (None, Some(minutes)) => {
+            plan_part_starts(&primary_audio.bytes()?, u64::from(minutes) * 60_000)
+        }
+        (None, None) => vec![0],
+    };
+    let part_count = part_starts.len();
+    let partial = planned_transcript_paths(&settings, options, 1, &mut Vec::new())?
+        .first()
+        .map(|path| PartialTranscript::for_transcript(path));
+    let captions = CaptionStream::new(&app, &job_id);
+    let mut segments = Vec::new();
+    let mut part_ranges = Vec::new();
+    let mut diarization_applied = false;
+    // Segments transcribed while the recording was running are reused when
+    // the job would have transcribed the same audio the same way.
+    let mut rolling = match options
+        .rolling_session
+        .as_deref()
+        .and_then(|session_id| take_rolling_session(&app, session_id))
+    {
+        Some(session)
+            if resumed.is_none()
+                && trim_start_ms == 0
+                && options.trim_end_seconds.is_none()
+                && RollingKey::for_options(&session.options)
+                    == RollingKey::for_options(options)
+                && session
+                    .model_used
+                    .as_deref()
+                    .map_or(true, |model| model == model_used.id) =>
+        {
+            diarization_applied = session.diarization_applied;
+            warnings.extend(session.warnings.iter().cloned());
+            Some(session)
+        }
+        Some(_) => {
+            warnings.push(localize(&locale, Message::RollingTranscriptionDiscarded));
+            None
+        }
+        None => None,
+    };
+    let total_ms = primary_audio.duration_ms();
+    let mut checkpoint = match resumed {
+        Some(checkpoint) => {
+            if let Some(model) = find_model(&checkpoint.model) {
+                model_used = model;
+                model_path = model_file_path(&app, model.id)?;
+            }
+            diarization_applied = checkpoint.diarization_applied;
+            warnings.extend(checkpoint.warnings.iter().cloned());
+            warnings.push(localize_with(
+                &locale,
+                Message::ResumedFromCheckpoint,
+                &[
+                    ("part", &(checkpoint.completed_parts.len() + 1).to_string()),
+                    ("parts", &part_count.to_string()),
+                ],
+            ));
+            checkpoint
+        }
+        None => JobCheckpoint {
+            part_starts: part_starts.clone(),
+            ..JobCheckpoint::default()
+        },
+    };
+    for (index, &part_start) in part_starts.iter().enumerate() {
+        if let Some(completed) = checkpoint.completed_parts.get(index) {
+            part_ranges.push(segments.len()..segments.len() + completed.len());
+            segments.extend(completed.iter().cloned());
+            continue;
+        }
+        let part_end = part_starts.get(index + 1).copied();
+        let rolling_segments: Vec<TimestampedSegment> = match &mut rolling {
+            Some(session) => {
+                let count = session
+                    .segments
+                    .iter()
+                    .take_while(|segment| part_end.map_or(true, |end| segment.start_ms < end))
+                    .count();
+                session.segments.drain(..count).collect()
+            }
+            None => Vec::new(),
+        };
+        let audio_start = rolling
+            .as_ref()
+            .map_or(part_start, |session| session.covered_ms.max(part_start));
+        let covered = audio_start + MIN_AUDIO_DURATION_MS > part_end.unwrap_or(total_ms);
+        let part_trim = AudioTrim {
+            start_ms: audio_start,
+            end_ms: part_end,
+        };
+        let primary_part = primary_audio.trimmed(part_trim)?;
+        let microphone_part = microphone_audio.trimmed(part_trim)?;
+        let system_part = system_audio.trimmed(part_trim.shifted(system_audio_offset_ms))?;
+        let stem = if part_count > 1 {
+            format!("recording-{}-part-{}", timestamp, index + 1)
+        } else {
+            format!("recording-{}", timestamp)
+        };
+        let progress = (
+            (100 * index / part_count) as u32,
+            (100 * (index + 1) / part_count) as u32,
+        );
+
+        let (mut part_segments, applied) = if covered {
+            (Vec::new(), false)
+        } else {
+            loop {
+                if let Some(partial) = &partial {
+                    partial.begin_part(part_start);
+                }
+                captions.begin_part();
+                let job = WhisperJob {
+                    app: &app,
+                    model_path: &model_path,
+                    language: &options.language,
+                    speaker_mode: &speaker_mode_used,
+                    preprocessing,
+                    locale: &locale,
+                    prompt: prompt.as_deref(),
+                    raw_output_dir: raw_staging_dir.as_deref(),
+                    partial: partial.as_ref(),
+                    captions: Some(&captions),
+                    verbatim: options.verbatim,
+                    translate: options.translate,
+                };
+                let error = match transcribe_part_segments(
+                    &job,
+                    PartAudio {
+                        primary: &primary_part,
+                        microphone: &microphone_part,
+                        system: &system_part,
+                        system_offset_ms: system_audio_offset_ms.saturating_sub(audio_start),
+                    },
+                    &stem,
+                    progress,
+                    &mut warnings,
+                )
+                .await
+                {
+                    Ok(output) => break output,
+                    Err(error) => error,
+                };
+
+                // tdrz diarization only works with its own model, so there is
+                // nothing smaller to fall back to.
+                let fallback = if settings.fallback_to_smaller_model
+                    && speaker_mode_used != "tdrz_2speaker"
+                    && is_resource_failure(&error)
+                {
+                    next_smaller_downloaded_model(&app, model_used.id, None)
+                } else {
+                    None
+                };
+                let Some(fallback) = fallback else {
+                    return Err(match &partial {
+                        Some(partial) => partial.annotate_failure(&locale, error),
+                        None => error,
+                    });
+                };
+
+                warnings.push(localize_with(
+                    &locale,
+                    Message::ModelFallback,
+                    &[("model", model_used.id), ("fallback", fallback.id)],
+                ));
+                model_used = fallback;
+                model_path = model_file_path(&app, fallback.id)?;
+            }
+        };
+        shift_segments(&mut part_segments, audio_start);
+        part_segments.splice(0..0, rolling_segments);
+        captions.finish_part(&part_segments);
+
+        diarization_applied |= applied;
+        if part_count > 1 {
+            checkpoint.completed_parts.push(part_segments.clone());
+            checkpoint.model = model_used.id.to_string();
+            checkpoint.diarization_applied = diarization_applied;
+            checkpoint.warnings = warnings.clone();
+            if let Err(error) = save_job_checkpoint(&app, &job_id, &checkpoint) {
+                warnings.push(error);
+            }
+        }
+        part_ranges.push(segments.len()..segments.len() + part_segments.len());
+        segments.extend(part_segments);
+        if let (Some(partial), true) = (&partial, index + 1 < part_count) {
+            partial.finish_part(&segments);
+        }
+    }
+    if settings.spell_correction && !options.verbatim {
+        warnings.extend(correct_segment_spelling(
+            &mut segments,
+            &settings.spell_dictionary,
+            &locale,
+        ));
+    }
+    if settings.text_normalization.any()
+        && !options.verbatim
+        && matches!(options.language.as_str(), "en" | "auto")
+    {
+        normalize_segment_text(&mut segments, settings.text_normalization);
+    }
+    let mut seen_warnings = HashSet::new();
+    warnings.retain(|warning| seen_warnings.insert(warning.clone()));
+
+    let layout = TranscriptLayout {
+        verbatim: options.verbatim,
+        timestamp_interval_ms: settings
+            .timestamp_interval_seconds
+            .map(|seconds| u64::from(seconds) * 1000),
+        markdown_escape: MarkdownEscape::Off,
+    };
+    let markdown_layout = TranscriptLayout {
+        markdown_escape: settings.markdown_escape,
+        ..layout
+    };
+    let transcript = render_transcript(&segments, layout);
+
+    if transcript.is_empty() {
+        return Err(localize(&locale, Message::EmptyTranscript));
+    }
+
+    let session_notes = sanitize_non_empty(options.session_notes.clone());
+    let title = if let Some(session_title) = sanitize_non_empty(options.session_title.clone()) {
+        session_title
+    } else if settings.auto_title {
+        generate_title(&segments).unwrap_or_else(|| DEFAULT_TRANSCRIPT_TITLE.to_string())
+    } else {
+        DEFAULT_TRANSCRIPT_TITLE.to_string()
+    };
+    let title_suffix = if settings.title_in_filename {
+        title_filename_suffix(&title)
+    } else {
+        String::new()
+    };
+
+    let output_mode = validate_output_mode(&options.output_mode);
+    let (now, timezone_fallback) = now_for_settings(&settings);
+    if timezone_fallback {
+        warnings.push(localize(&locale, Message::LocalTimezoneUnavailable));
+    }
+    let date = format_date(now, &settings.date_format);
+    let time_compact = format_time_compact(now);
+    let created_at = format_iso8601(now);
+    let coachnotes_metadata = output_mode == "coachnotes" && settings.coachnotes_enabled;
+    let frontmatter_client = if coachnotes_metadata {
+        sanitize_non_empty(options.client.clone())
+            .or_else(|| sanitize_non_empty(settings.coachnotes_client.clone()))
+    } else {
+        None
+    };
+
+    let save_destination = if options.save_markdown {
+        let path = transcript_destination(
+            &settings,
+            options,
+            (&date, &time_compact, timestamp),
+            &title_suffix,
+            &mut warnings,
+        );
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| {
+                format!(
+                    "Failed to create transcript directory ({}): {}",
+                    dir.display(),
+                    e
+                )
+            })?;
+        }
+        Some(path)
+    } else {
+        None
+    };
+
+    let duration_seconds = [&primary_audio, &microphone_audio, &system_audio]
+        .into_iter()
+        .map(|track| track.duration_ms() / 1000)
+        .max()
+        .unwrap_or(0);
+
+    let topics = extract_topics(&transcript);
+    let summary = if options.summarize {
+        summarize_segments(&segments)
+    } else {
+        None
+    };
+    let speech_stats = compute_speech_statistics(&segments, duration_seconds);
+    let (segment_sentiment, sentiment) = if options.analyze_sentiment {
+        let (scored, summary) = analyze_segment_sentiment(&segments);
+        (scored, Some(summary))
+    } else {
+        (Vec::new(), None)
+    };
+    let custom_fields =
+        merge_custom_frontmatter(&settings.custom_frontmatter, &options.custom_frontmatter);
+    let speaker_labels = if speaker_mode_used == "source_aware_2speaker" {
+        Some(("Coach", "Client"))
+    } else if speaker_mode_used == "tdrz_2speaker" && diarization_applied {
+        Some(("Speaker A", "Speaker B"))
+    } else {
+        Some(("Coach", "Client"))
+    };
+
+    let duplicate_check = validate_duplicate_check(&settings.duplicate_check);
+    let duplicate_of = match (&save_destination, duplicate_check) {
+        (Some(_), "warn" | "skip") => find_duplicate_transcript(&app, &transcript),
+        _ => None,
+    };
+    let skip_duplicate = duplicate_of.is_some() && duplicate_check == "skip";
+    if let Some(duplicate_path) = &duplicate_of {
+        let message = if skip_duplicate {
+            Message::DuplicateTranscriptSkipped
+        } else {
+            Message::DuplicateTranscript
+        };
+        warnings.push(localize_with(
+            &locale,
+            message,
+            &[("path", &duplicate_path.display().to_string())],
+        ));
+    }
+
+    let note_paths = match save_destination {
+        _ if skip_duplicate => Vec::new(),
+        Some(path) if part_count > 1 => part_paths_for(&path, part_count, options.overwrite),
+        Some(path) => vec![path],
+        None => Vec::new(),
+    };
+    if let Some(dir) = note_paths.first().and_then(|path| path.parent()) {
+        warnings.extend(sync_warnings(dir));
+    }
+    let note_file_names = note_paths
+        .iter()
+        .map(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<String>>();
+    let total_duration_ms = primary_audio.duration_ms();
+    let append_target = match note_paths.as_slice() {
+        [planned_path] if coachnotes_metadata && settings.coachnotes_append_same_day => {
+            same_day_transcript(planned_path, &date)
+        }
+        _ => None,
+    };
+    let previous_session = match note_paths.as_slice() {
+        [planned_path] if coachnotes_metadata && append_target.is_none() => {
+            previous_session_transcript(planned_path, &created_at)
+        }
+        _ => None,
+    };
+    let previous_session_name = previous_session.as_ref().and_then(|path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    });
+    let prepared_note = match note_paths.as_slice() {
+        [planned_path] if coachnotes_metadata && append_target.is_none() => planned_path
+            .parent()
+            .and_then(|dir| pending_session_prep(dir, &date)),
+        _ => None,
+    };
+    let session_prep = match &prepared_note {
+        Some(prepared_path) => match fs::read_to_string(prepared_path) {
+            Ok(markdown) => Some(split_frontmatter(&markdown).1.trim().to_string()),
+            Err(e) => {
+                warnings.push(format!(
+                    "Failed to read prepared session note ({}): {}",
+                    prepared_path.display(),
+                    e
+                ));
+                None
+            }
+        },
+        None => None,
+    };
 
-        let time_index = if lines[0].chars().all(|char| char.is_ascii_digit()) {
-            1
+    let mut saved_paths = Vec::new();
+    let mut sidecar_paths = Vec::new();
+    for (index, path) in note_paths.iter().enumerate() {
+        let part_segments = &segments[part_ranges[index].clone()];
+        let part = (part_count > 1).then(|| PartLinks {
+            number: index + 1,
+            count: part_count,
+            previous: index
+                .checked_sub(1)
+                .and_then(|previous| note_file_names.get(previous))
+                .map(String::as_str),
+            next: note_file_names.get(index + 1).map(String::as_str),
+        });
+        let part_title = match part {
+            Some(part) => format!("{} (Part {} of {})", title, part.number, part.count),
+            None => title.clone(),
+        };
+        let part_transcript = render_transcript(part_segments, layout);
+        let part_duration_seconds = if part.is_some() {
+            let part_end_ms = part_starts
+                .get(index + 1)
+                .copied()
+                .unwrap_or(total_duration_ms);
+            part_end_ms.saturating_sub(part_starts[index]) / 1000
         } else {
-            0
+            duration_seconds
+        };
+        let part_topics = extract_topics(&part_transcript);
+        let part_summary = match (&summary, part) {
+            (Some(_), Some(_)) => summarize_segments(part_segments),
+            (summary, _) => summary.clone(),
+        };
+        let part_speech_stats = compute_speech_statistics(part_segments, part_duration_seconds);
+        let (part_segment_sentiment, part_sentiment) = if options.analyze_sentiment {
+            let (scored, summary) = analyze_segment_sentiment(part_segments);
+            (scored, Some(summary))
+        } else {
+            (Vec::new(), None)
         };
 
-        if lines.len() <= time_index {
-            continue;
-        }
+        let markdown_body = if options.generate_chapters {
+            let chapter_starts = detect_chapter_starts(part_segments);
+            if chapter_starts.len() > 1 {
+                render_chaptered_transcript(part_segments, &chapter_starts, markdown_layout)
+            } else {
+                warnings.push(localize(&locale, Message::NoChapterBoundaries));
+                render_transcript(part_segments, markdown_layout)
+            }
+        } else {
+            render_transcript(part_segments, markdown_layout)
+        };
 
-        let Some((start_raw, end_raw)) = lines[time_index].split_once("-->") else {
-            continue;
+        let metadata = MarkdownMetadata {
+            title: &part_title,
+            part,
+            client: frontmatter_client.as_deref(),
+            model: model_used.id,
+            language: &options.language,
+            diarization_mode: &speaker_mode_used,
+            created_at: &created_at,
+            date: &date,
+            duration_seconds: part_duration_seconds,
+            coachnotes: coachnotes_metadata,
+            speaker_labels,
+            topics: &part_topics,
+            summary: part_summary.as_deref(),
+            sentiment: part_sentiment.as_ref(),
+            speech_stats: &part_speech_stats,
+            custom_fields: &custom_fields,
+            notes: session_notes.as_deref(),
+            notes_section: options.include_notes_section,
+            verbatim: options.verbatim,
+            translated: options.translate,
+            previous_session: previous_session_name.as_deref(),
+            inline_session_links: settings.coachnotes_inline_session_links,
+            session_prep: session_prep.as_deref(),
         };
 
-        let Some(start_ms) = parse_srt_timestamp(start_raw) else {
-            continue;
+        let (path, appended_part) = match &append_target {
+            Some(day_path) => {
+                let existing = fs::read_to_string(day_path)
+                    .map_err(|e| read_failure("transcript file", day_path, e))?;
+                let (markdown, part_number) =
+                    append_session_section(&existing, &markdown_body, &metadata)?;
+                fs::write(day_path, markdown).map_err(|e| {
+                    format!(
+                        "Failed to write transcript file ({}): {}",
+                        day_path.display(),
+                        e
+                    )
+                })?;
+                warnings.push(localize_with(
+                    &locale,
+                    Message::AppendedToSameDayTranscript,
+                    &[
+                        ("path", &day_path.display().to_string()),
+                        ("part", &part_number.to_string()),
+                    ],
+                ));
+                if let Err(error) = record_audit(&app, AuditAction::Append, day_path, None) {
+                    warnings.push(error);
+                }
+                (day_path.clone(), Some(part_number))
+            }
+            None => {
+                let markdown = build_markdown_transcript(&markdown_body, &metadata)?;
+                // A prepared note is this session's note, so it is replaced.
+                let (planned_path, overwrite) = match (&prepared_note, &session_prep) {
+                    (Some(prepared_path), Some(_)) => (prepared_path, true),
+                    _ => (path, options.overwrite),
+                };
+                let path =
+                    save_file(planned_path, markdown.as_bytes(), overwrite).map_err(|e| {
+                        format!(
+                            "Failed to write transcript file ({}): {}",
+                            planned_path.display(),
+                            e
+                        )
+                    })?;
+                note_renamed_save(&locale, planned_path, &path, &mut warnings);
+                if let Err(error) = record_audit(&app, AuditAction::Create, &path, None) {
+                    warnings.push(error);
+                }
+                if let Some(previous_path) = &previous_session {
+                    if let Err(error) = link_next_session(&app, previous_path, &path) {
+                        warnings.push(error);
+                    }
+                }
+                (path, None)
+            }
         };
-        let Some(end_ms) = parse_srt_timestamp(end_raw) else {
-            continue;
+        let entry_title = match appended_part {
+            Some(number) => format!("{} (Part {})", part_title, number),
+            None => part_title.clone(),
         };
 
-        let body = sanitize_transcript_text(
-            &lines
-                .iter()
-                .skip(time_index + 1)
-                .copied()
-                .collect::<Vec<&str>>()
-                .join(" ")
-                .trim()
-                .to_string(),
-        );
+        if options.save_json_sidecar {
+            let transcript_path = path.to_string_lossy();
+            let sidecar = TranscriptSidecar {
+                title: &entry_title,
+                part,
+                notes: session_notes.as_deref(),
+                source_app: "Echo Scribe",
+                created_at: &created_at,
+                date: &date,
+                client: frontmatter_client.as_deref(),
+                model: model_used.id,
+                language: &options.language,
+                diarization_mode: &speaker_mode_used,
+                diarization_applied,
+                duration_seconds: part_duration_seconds,
+                transcript_path: &transcript_path,
+                transcript: &part_transcript,
+                segments: part_segments,
+                warnings: &warnings,
+                topics: &part_topics,
+                summary: part_summary.as_deref(),
+                sentiment: part_sentiment.as_ref(),
+                segment_sentiment: &part_segment_sentiment,
+                speech_stats: &part_speech_stats,
+                custom_frontmatter: custom_fields
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.as_str()))
+                    .collect(),
+            };
+            let json = serde_json::to_vec_pretty(&sidecar)
+                .map_err(|e| format!("Failed to encode transcript metadata: {}", e))?;
+            let planned_sidecar_path = match appended_part {
+                Some(number) => path.with_file_name(format!(
+                    "{}-part-{}.json",
+                    path.file_stem().unwrap_or_default().to_string_lossy(),
+                    number
+                )),
+                None => path.with_extension("json"),
+            };
+            let sidecar_path =
+                save_file(&planned_sidecar_path, &json, options.overwrite).map_err(|e| {
+                    format!(
+                        "Failed to write transcript metadata file ({}): {}",
+                        planned_sidecar_path.display(),
+                        e
+                    )
+                })?;
+            note_renamed_save(&locale, &planned_sidecar_path, &sidecar_path, &mut warnings);
+            if let Err(error) = record_audit(&app, AuditAction::Create, &sidecar_path, None) {
+                warnings.push(error);
+            }
+            sidecar_paths.push(sidecar_path.to_string_lossy().to_string());
+        }
 
-        if body.is_empty() {
-            continue;
+        if let (true, Some(part_summary)) = (coachnotes_metadata, part_summary.as_deref()) {
+            let entry = ClientIndexEntry {
+                client: frontmatter_client.as_deref(),
+                date: &date,
+                title: &entry_title,
+                summary: part_summary,
+            };
+            if let Err(error) = append_client_index(&path, &entry) {
+                warnings.push(error);
+            }
         }
 
-        segments.push(TimestampedSegment {
-            speaker: speaker.to_string(),
-            start_ms,
-            end_ms,
-            text: body,
-        });
+        saved_paths.push(path.to_string_lossy().to_string());
+    }
+    if let Some(partial) = &partial {
+        if partial.path.exists() {
+            partial.discard();
+            if let Err(error) = record_audit(&app, AuditAction::Delete, &partial.path, None) {
+                warnings.push(error);
+            }
+        }
     }
+    let saved_path = saved_paths.first().cloned();
+    let sidecar_path = sidecar_paths.first().cloned();
+    if let (Some(staging_dir), Some(saved_path)) = (&raw_staging_dir, &saved_path) {
+        if let Err(error) = save_raw_whisper_outputs(
+            &settings,
+            staging_dir,
+            &format!("recording-{}", timestamp),
+            Path::new(saved_path),
+            part_count,
+            options.overwrite,
+        ) {
+            warnings.push(error);
+        }
+    }
+    let show_notes_path = match (&saved_path, options.show_notes) {
+        (Some(saved_path), true) => match save_show_notes(
+            &app,
+            Path::new(saved_path),
+            part_count,
+            &title,
+            &segments,
+            options.overwrite,
+        ) {
+            Ok(path) => Some(path.to_string_lossy().to_string()),
+            Err(error) => {
+                warnings.push(error);
+                None
+            }
+        },
+        _ => None,
+    };
+    let part_paths = if part_count > 1 {
+        saved_paths
+    } else {
+        Vec::new()
+    };
 
-    if segments.is_empty() {
-        let fallback = normalize_transcript(text);
-        if !fallback.is_empty() {
-            segments.push(TimestampedSegment {
-                speaker: speaker.to_string(),
-                start_ms: 0,
-                end_ms: 0,
-                text: fallback,
-            });
+    let saved_audio_paths = if options.save_raw_audio {
+        let [_, microphone_source, system_source] = options.source_audio();
+        save_raw_audio_copies(
+            &settings,
+            &format!("{}-transcript-{}", date, time_compact),
+            frontmatter_client.as_deref(),
+            raw_primary_audio,
+            microphone_source,
+            system_source,
+            options.overwrite,
+        )?
+    } else {
+        Vec::new()
+    };
+    if let Some(audio_path) = saved_audio_paths.first() {
+        let alignment = AudioAlignment {
+            audio_path: audio_path.clone(),
+            transcript_paths: if part_paths.is_empty() {
+                saved_path.iter().cloned().collect()
+            } else {
+                part_paths.clone()
+            },
+            part_starts: part_ranges.iter().map(|range| range.start).collect(),
+            segments: segments
+                .iter()
+                .map(|segment| {
+                    [
+                        segment.start_ms + trim_start_ms,
+                        segment.end_ms + trim_start_ms,
+                    ]
+                })
+                .collect(),
+        };
+        if let Err(error) = save_audio_alignment(&alignment, options.overwrite) {
+            warnings.push(error);
         }
     }
 
-    segments
+    emit_progress(
+        &app,
+        100,
+        &localize(&locale, Message::TranscriptionComplete),
+    );
+
+    Ok(TranscriptionResult {
+        job_id,
+        duration_seconds,
+        model_used: model_used.id.to_string(),
+        transcript,
+        title,
+        saved_path,
+        sidecar_path,
+        show_notes_path,
+        part_paths,
+        saved_audio_paths,
+        format: "md".to_string(),
+        diarization_applied,
+        speaker_mode_used,
+        warnings,
+        topics,
+        summary,
+        sentiment,
+        segment_sentiment,
+        speech_stats,
+    })
 }
 
-fn coalesce_channel_segments(
-    segments: Vec<TimestampedSegment>,
-    max_gap_ms: u64,
-) -> Vec<TimestampedSegment> {
-    let mut merged: Vec<TimestampedSegment> = Vec::new();
+const ACTION_ITEM_MARKERS: [&str; 14] = [
+    "i will ",
+    "i'll ",
+    "we will ",
+    "we'll ",
+    "i'm going to ",
+    "i am going to ",
+    "i need to ",
+    "we need to ",
+    "follow up",
+    "action item",
+    "next step",
+    "homework",
+    "by next week",
+    "before our next session",
+];
+const ACTION_ITEMS_PER_SESSION: usize = 5;
+const DIGEST_TOPIC_LIMIT: usize = 10;
 
-    for segment in segments {
-        if let Some(last) = merged.last_mut() {
-            if last.speaker == segment.speaker
-                && segment.start_ms >= last.start_ms
-                && segment.start_ms.saturating_sub(last.end_ms) <= max_gap_ms
+// Commitments spoken in a transcript ("I'll send...", "we need to..."),
+// prefixed with the speaker when the line is labelled.
+fn extract_action_items(body: &str, speakers: &[String]) -> Vec<String> {
+    let transcript = body.split("\n## Notes\n").next().unwrap_or(body);
+    let mut items = Vec::new();
+
+    for paragraph in transcript.split("\n\n").map(str::trim) {
+        if paragraph.starts_with('#') || paragraph.starts_with("- [") {
+            continue;
+        }
+        let (speaker, text) = speakers
+            .iter()
+            .find_map(|speaker| {
+                paragraph
+                    .strip_prefix(speaker.as_str())
+                    .and_then(|rest| rest.strip_prefix(": "))
+                    .map(|text| (Some(speaker.as_str()), text))
+            })
+            .unwrap_or((None, paragraph));
+
+        for sentence in text.split_inclusive(['.', '?', '!']).map(str::trim) {
+            let lowered = sentence.to_lowercase();
+            if sentence.ends_with('?')
+                || !ACTION_ITEM_MARKERS
+                    .iter()
+                    .any(|marker| format!(" {}", lowered).contains(&format!(" {}", marker)))
             {
-                last.end_ms = last.end_ms.max(segment.end_ms);
-                last.text = format!("{} {}", last.text, segment.text).trim().to_string();
                 continue;
             }
+            items.push(match speaker {
+                Some(speaker) => format!("{}: {}", speaker, sentence),
+                None => sentence.to_string(),
+            });
+            if items.len() == ACTION_ITEMS_PER_SESSION {
+                return items;
+            }
         }
-
-        merged.push(segment);
     }
 
-    merged
+    items
 }
 
-fn shift_segments(segments: &mut [TimestampedSegment], offset_ms: u64) {
-    if offset_ms == 0 {
-        return;
-    }
+fn markdown_table_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
 
-    for segment in segments {
-        segment.start_ms = segment.start_ms.saturating_add(offset_ms);
-        segment.end_ms = segment.end_ms.saturating_add(offset_ms);
-    }
+#[derive(Debug, Deserialize)]
+pub struct DigestRange {
+    start: String,
+    end: String,
 }
 
-fn extract_word_spans(text: &str) -> Vec<WordSpan> {
-    let mut spans = Vec::new();
-    let mut token_start: Option<usize> = None;
+#[derive(Serialize)]
+struct DigestFrontmatter<'a> {
+    title: &'a str,
+    start_date: &'a str,
+    end_date: &'a str,
+    source_app: &'a str,
+    created_at: &'a str,
+    session_count: usize,
+    total_hours: f64,
+}
 
-    for (index, ch) in text.char_indices() {
-        let is_word = ch.is_ascii_alphanumeric() || ch == '\'';
-        match (token_start, is_word) {
-            (None, true) => token_start = Some(index),
-            (Some(start), false) => {
-                let token = &text[start..index];
-                let normalized = token
-                    .chars()
-                    .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '\'')
-                    .collect::<String>()
-                    .to_ascii_lowercase();
+#[derive(Debug, Serialize)]
+pub struct DigestResult {
+    saved_path: String,
+    session_count: usize,
+    total_hours: f64,
+}
 
-                if !normalized.is_empty() {
-                    spans.push(WordSpan {
-                        start,
-                        end: index,
-                        normalized,
-                    });
-                }
-                token_start = None;
-            }
-            _ => {}
-        }
+fn build_digest_markdown(
+    range: &DigestRange,
+    transcripts: &[SavedTranscript],
+    created_at: &str,
+) -> Result<String, String> {
+    let total_seconds = transcripts
+        .iter()
+        .map(|transcript| transcript.duration_seconds)
+        .sum::<u64>();
+    let total_hours = round_to(total_seconds as f64 / 3600.0, 1);
+
+    let mut clients: BTreeMap<&str, Vec<&SavedTranscript>> = BTreeMap::new();
+    for transcript in transcripts {
+        clients
+            .entry(
+                transcript
+                    .client
+                    .as_deref()
+                    .unwrap_or(DIGEST_UNASSIGNED_CLIENT),
+            )
+            .or_default()
+            .push(transcript);
     }
 
-    if let Some(start) = token_start {
-        let token = &text[start..];
-        let normalized = token
-            .chars()
-            .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '\'')
-            .collect::<String>()
-            .to_ascii_lowercase();
-
-        if !normalized.is_empty() {
-            spans.push(WordSpan {
-                start,
-                end: text.len(),
-                normalized,
-            });
+    let mut topic_sessions: HashMap<&str, usize> = HashMap::new();
+    for transcript in transcripts {
+        for topic in &transcript.topics {
+            *topic_sessions.entry(topic.as_str()).or_default() += 1;
         }
     }
+    let mut topics = topic_sessions.into_iter().collect::<Vec<(&str, usize)>>();
+    topics.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(right.0)));
+    topics.truncate(DIGEST_TOPIC_LIMIT);
+
+    let frontmatter = to_frontmatter_yaml(&DigestFrontmatter {
+        title: "Session Digest",
+        start_date: &range.start,
+        end_date: &range.end,
+        source_app: "Echo Scribe",
+        created_at,
+        session_count: transcripts.len(),
+        total_hours,
+    })?;
+    let mut lines = vec![
+        format!("# Digest: {} to {}", range.start, range.end),
+        String::new(),
+        "## Overview".to_string(),
+        String::new(),
+        format!("- Sessions: {}", transcripts.len()),
+        format!("- Total hours: {}", total_hours),
+        format!("- Clients: {}", clients.len()),
+        String::new(),
+        "## Sessions per client".to_string(),
+        String::new(),
+        "| Client | Sessions | Hours |".to_string(),
+        "| --- | ---: | ---: |".to_string(),
+    ];
+    for (client, sessions) in &clients {
+        let seconds = sessions
+            .iter()
+            .map(|session| session.duration_seconds)
+            .sum::<u64>();
+        lines.push(format!(
+            "| {} | {} | {} |",
+            markdown_table_cell(client),
+            sessions.len(),
+            round_to(seconds as f64 / 3600.0, 1)
+        ));
+    }
 
-    spans
-}
-
-fn collect_word_tokens(text: &str) -> Vec<String> {
-    extract_word_spans(text)
-        .into_iter()
-        .map(|span| span.normalized)
-        .collect()
-}
-
-fn collapse_spacing(text: &str) -> String {
-    let mut output = String::new();
-    let mut previous_was_space = false;
+    lines.push(String::new());
+    lines.push("## Key topics".to_string());
+    lines.push(String::new());
+    if topics.is_empty() {
+        lines.push("No recurring topics.".to_string());
+    }
+    for (topic, count) in topics {
+        let sessions = if count == 1 { "session" } else { "sessions" };
+        lines.push(format!("- {} ({} {})", topic, count, sessions));
+    }
 
-    for ch in text.chars() {
-        if ch.is_whitespace() {
-            if !previous_was_space {
-                output.push(' ');
-                previous_was_space = true;
+    lines.push(String::new());
+    lines.push("## Outstanding action items".to_string());
+    let mut any_action_items = false;
+    for (client, sessions) in &clients {
+        let mut client_lines = Vec::new();
+        for session in sessions {
+            for item in extract_action_items(&session.body, &session.speakers) {
+                client_lines.push(format!("- {} {}: {}", session.date, session.title, item));
             }
-            continue;
         }
-
-        if matches!(ch, '.' | ',' | '!' | '?' | ';' | ':') && output.ends_with(' ') {
-            output.pop();
+        if client_lines.is_empty() {
+            continue;
         }
-
-        output.push(ch);
-        previous_was_space = false;
+        any_action_items = true;
+        lines.push(String::new());
+        lines.push(format!("### {}", client));
+        lines.push(String::new());
+        lines.extend(client_lines);
+    }
+    if !any_action_items {
+        lines.push(String::new());
+        lines.push("No action items found.".to_string());
     }
 
-    output.trim().to_string()
+    Ok(format!("---\n{}---\n{}\n", frontmatter, lines.join("\n")))
 }
 
-fn longest_duplicate_range(
-    spans: &[WordSpan],
-    reference_tokens: &[String],
-    min_match_tokens: usize,
-) -> Option<(usize, usize)> {
-    let mut best_range: Option<(usize, usize)> = None;
-    let mut best_len = 0usize;
-
-    for start_index in 0..spans.len() {
-        for reference_start in 0..reference_tokens.len() {
-            if spans[start_index].normalized != reference_tokens[reference_start] {
-                continue;
-            }
-
-            let mut match_len = 0usize;
-            while start_index + match_len < spans.len()
-                && reference_start + match_len < reference_tokens.len()
-                && spans[start_index + match_len].normalized
-                    == reference_tokens[reference_start + match_len]
-            {
-                match_len += 1;
-            }
+const TRANSCRIPT_SORTS: [&str; 5] = ["date_desc", "date_asc", "duration_desc", "client", "title"];
+const SEARCH_SNIPPET_CHARS: usize = 60;
 
-            if match_len >= min_match_tokens && match_len > best_len {
-                best_len = match_len;
-                let start_byte = spans[start_index].start;
-                let end_byte = spans[start_index + match_len - 1].end;
-                best_range = Some((start_byte, end_byte));
-            }
-        }
-    }
+#[derive(Debug, Default, Deserialize)]
+pub struct TranscriptQuery {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    client: Option<String>,
+    #[serde(default)]
+    date_from: Option<String>,
+    #[serde(default)]
+    date_to: Option<String>,
+    #[serde(default)]
+    has_diarization: Option<bool>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    sort: Option<String>,
+    #[serde(default)]
+    limit: Option<usize>,
+}
 
-    best_range
+#[derive(Debug, Serialize)]
+pub struct TranscriptListing {
+    path: String,
+    title: String,
+    client: Option<String>,
+    date: String,
+    created_at: String,
+    duration_seconds: u64,
+    model: String,
+    diarization_mode: String,
+    tags: Vec<String>,
+    topics: Vec<String>,
+    snippet: Option<String>,
 }
 
-fn segments_overlap_with_margin(
-    left_start: u64,
-    left_end: u64,
-    right_start: u64,
-    right_end: u64,
-    margin_ms: u64,
-) -> bool {
-    let left_start = left_start.saturating_sub(margin_ms);
-    let left_end = left_end.saturating_add(margin_ms);
-    let right_start = right_start.saturating_sub(margin_ms);
-    let right_end = right_end.saturating_add(margin_ms);
+#[derive(Debug, Default, Serialize)]
+pub struct TranscriptFacets {
+    clients: BTreeMap<String, usize>,
+    models: BTreeMap<String, usize>,
+    tags: BTreeMap<String, usize>,
+}
 
-    left_start <= right_end && right_start <= left_end
+#[derive(Debug, Serialize)]
+pub struct TranscriptSearchResult {
+    total: usize,
+    results: Vec<TranscriptListing>,
+    facets: TranscriptFacets,
 }
 
-fn strip_reference_leakage_from_segment(
-    text: &str,
-    reference_segments: &[TimestampedSegment],
-    start_ms: u64,
-    end_ms: u64,
-) -> String {
-    let overlapping_reference = reference_segments
+// Up to SEARCH_SNIPPET_CHARS characters either side of the first
+// case-insensitive match of `needle`.
+fn search_snippet(text: &str, needle: &str) -> Option<String> {
+    let chars = text.chars().collect::<Vec<char>>();
+    let lowered = chars
         .iter()
-        .filter(|segment| {
-            segments_overlap_with_margin(start_ms, end_ms, segment.start_ms, segment.end_ms, 1200)
-        })
-        .map(|segment| segment.text.as_str())
-        .collect::<Vec<&str>>();
-
-    if overlapping_reference.is_empty() {
-        return text.trim().to_string();
+        .map(|char| char.to_lowercase().next().unwrap_or(*char))
+        .collect::<Vec<char>>();
+    let needle = needle.to_lowercase().chars().collect::<Vec<char>>();
+    if needle.is_empty() || needle.len() > lowered.len() {
+        return None;
     }
 
-    let reference_tokens = collect_word_tokens(&overlapping_reference.join(" "));
-    if reference_tokens.len() < 4 {
-        return text.trim().to_string();
-    }
+    let start = lowered
+        .windows(needle.len())
+        .position(|window| window == needle.as_slice())?;
+    let from = start.saturating_sub(SEARCH_SNIPPET_CHARS);
+    let to = (start + needle.len() + SEARCH_SNIPPET_CHARS).min(chars.len());
+    let snippet = chars[from..to]
+        .iter()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+    Some(format!(
+        "{}{}{}",
+        if from > 0 { "..." } else { "" },
+        snippet,
+        if to < chars.len() { "..." } else { "" }
+    ))
+}
 
-    let mut cleaned = text.trim().to_string();
-    loop {
-        let spans = extract_word_spans(&cleaned);
-        if spans.len() < 4 {
-            break;
+fn matches_transcript_query(transcript: &SavedTranscript, query: &TranscriptQuery) -> bool {
+    if let Some(client) = sanitize_non_empty(query.client.clone()) {
+        let same_client = transcript
+            .client
+            .as_deref()
+            .map_or(false, |value| value.eq_ignore_ascii_case(&client));
+        if !same_client {
+            return false;
+        }
+    }
+    if let Some(from) = query.date_from.as_deref() {
+        if transcript.date.as_str() < from {
+            return false;
+        }
+    }
+    if let Some(to) = query.date_to.as_deref() {
+        if transcript.date.as_str() > to {
+            return false;
+        }
+    }
+    if let Some(wanted) = query.has_diarization {
+        if (transcript.diarization_mode != "none") != wanted {
+            return false;
+        }
+    }
+    if let Some(model) = sanitize_non_empty(query.model.clone()) {
+        if transcript.model != model {
+            return false;
         }
-
-        let Some((start_byte, end_byte)) = longest_duplicate_range(&spans, &reference_tokens, 4)
-        else {
-            break;
-        };
-
-        cleaned.replace_range(start_byte..end_byte, " ");
-        cleaned = collapse_spacing(&cleaned);
     }
 
-    cleaned
+    query.tags.iter().all(|tag| {
+        transcript
+            .tags
+            .iter()
+            .any(|value| value.eq_ignore_ascii_case(tag.trim()))
+    })
 }
 
-fn merge_source_segments(
-    microphone_segments: Vec<TimestampedSegment>,
-    system_segments: Vec<TimestampedSegment>,
-) -> String {
-    let mut segments = Vec::new();
-
-    for mut segment in microphone_segments {
-        let cleaned = strip_reference_leakage_from_segment(
-            &segment.text,
-            &system_segments,
-            segment.start_ms,
-            segment.end_ms,
-        );
+fn search_saved_transcripts(
+    transcripts: Vec<SavedTranscript>,
+    query: &TranscriptQuery,
+) -> Result<TranscriptSearchResult, String> {
+    let sort = query.sort.as_deref().unwrap_or("date_desc");
+    if !TRANSCRIPT_SORTS.contains(&sort) {
+        return Err(format!(
+            "Unsupported sort '{}'. Valid values: {}",
+            sort,
+            TRANSCRIPT_SORTS.join(", ")
+        ));
+    }
+    for date in [&query.date_from, &query.date_to].into_iter().flatten() {
+        if !is_iso_date(date) {
+            return Err(format!("Invalid date '{}'. Use YYYY-MM-DD.", date));
+        }
+    }
+    let text = sanitize_non_empty(query.text.clone());
 
-        if extract_word_spans(&cleaned).is_empty() {
+    let mut listings = Vec::new();
+    let mut facets = TranscriptFacets::default();
+    for transcript in transcripts {
+        if !matches_transcript_query(&transcript, query) {
             continue;
         }
+        let snippet = match &text {
+            Some(text) => match search_snippet(&transcript.body, text) {
+                Some(snippet) => Some(snippet),
+                None if transcript
+                    .title
+                    .to_lowercase()
+                    .contains(&text.to_lowercase()) =>
+                {
+                    None
+                }
+                None => continue,
+            },
+            None => None,
+        };
 
-        segment.text = cleaned;
-        segments.push(segment);
-    }
+        if let Some(client) = &transcript.client {
+            *facets.clients.entry(client.clone()).or_default() += 1;
+        }
+        *facets.models.entry(transcript.model.clone()).or_default() += 1;
+        for tag in &transcript.tags {
+            *facets.tags.entry(tag.clone()).or_default() += 1;
+        }
 
-    segments.extend(system_segments);
-    segments.sort_by(|left, right| {
-        left.start_ms
-            .cmp(&right.start_ms)
-            .then_with(|| left.end_ms.cmp(&right.end_ms))
-            .then_with(|| left.speaker.cmp(&right.speaker))
-    });
+        listings.push(TranscriptListing {
+            path: transcript.path.to_string_lossy().to_string(),
+            title: transcript.title,
+            client: transcript.client,
+            date: transcript.date,
+            created_at: transcript.created_at,
+            duration_seconds: transcript.duration_seconds,
+            model: transcript.model,
+            diarization_mode: transcript.diarization_mode,
+            tags: transcript.tags,
+            topics: transcript.topics,
+            snippet,
+        });
+    }
 
-    let mut merged: Vec<TimestampedSegment> = Vec::new();
-    for segment in segments {
-        if let Some(last) = merged.last_mut() {
-            let same_speaker = last.speaker == segment.speaker;
-            let gap_ms = segment.start_ms.saturating_sub(last.end_ms);
-            if same_speaker && gap_ms <= 500 {
-                last.end_ms = last.end_ms.max(segment.end_ms);
-                last.text = format!("{} {}", last.text, segment.text).trim().to_string();
-                continue;
-            }
+    match sort {
+        "date_asc" => listings.sort_by(|left, right| left.created_at.cmp(&right.created_at)),
+        "duration_desc" => {
+            listings.sort_by(|left, right| right.duration_seconds.cmp(&left.duration_seconds))
         }
+        "client" => listings.sort_by(|left, right| {
+            left.client
+                .as_deref()
+                .unwrap_or("")
+                .to_lowercase()
+                .cmp(&right.client.as_deref().unwrap_or("").to_lowercase())
+                .then_with(|| right.created_at.cmp(&left.created_at))
+        }),
+        "title" => listings
+            .sort_by(|left, right| left.title.to_lowercase().cmp(&right.title.to_lowercase())),
+        _ => listings.sort_by(|left, right| right.created_at.cmp(&left.created_at)),
+    }
 
-        merged.push(segment);
+    let total = listings.len();
+    if let Some(limit) = query.limit {
+        listings.truncate(limit);
     }
 
-    merged
-        .into_iter()
-        .map(|segment| format!("{}: {}", segment.speaker, segment.text))
-        .collect::<Vec<String>>()
-        .join("\n\n")
+    Ok(TranscriptSearchResult {
+        total,
+        results: listings,
+        facets,
+    })
 }
 
-fn sanitize_filename_component(value: &str) -> String {
-    let sanitized = value
-        .trim()
-        .chars()
-        .map(|char| {
-            if char.is_ascii_alphanumeric() {
-                char.to_ascii_lowercase()
-            } else {
-                '-'
-            }
-        })
-        .collect::<String>();
+#[tauri::command]
+async fn search_transcripts(
+    app: AppHandle,
+    query: TranscriptQuery,
+) -> Result<TranscriptSearchResult, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    search_saved_transcripts(scan_saved_transcripts(&settings), &query)
+}
 
-    sanitized
-        .split('-')
-        .filter(|part| !part.is_empty())
-        .collect::<Vec<&str>>()
-        .join("-")
+const DEFAULT_RELATED_LIMIT: usize = 5;
+const MAX_RELATED_LIMIT: usize = 50;
+const RELATED_SAME_CLIENT_BONUS: f64 = 0.25;
+const RELATED_SECTION_HEADING: &str = "\n## Related sessions\n";
+// Tags every session carries, which say nothing about what it was about.
+const RELATED_IGNORED_TAGS: [&str; 2] = ["transcript", "coaching-session"];
+
+#[derive(Debug, Serialize)]
+pub struct RelatedTranscript {
+    path: String,
+    title: String,
+    client: Option<String>,
+    date: String,
+    score: f64,
+    shared_terms: Vec<String>,
 }
 
-fn save_raw_audio_copies(
-    settings: &AppSettings,
-    base_name: &str,
-    coachnotes_client: Option<&str>,
-    primary_audio: &[u8],
-    microphone_audio: &[u8],
-    system_audio: &[u8],
-) -> Result<Vec<String>, String> {
-    let audio_dir = resolve_transcript_dir(settings);
-    fs::create_dir_all(&audio_dir).map_err(|e| {
-        format!(
-            "Failed to create transcript directory ({}): {}",
-            audio_dir.display(),
-            e
-        )
-    })?;
+#[derive(Debug, Serialize)]
+pub struct RelatedTranscripts {
+    path: String,
+    related: Vec<RelatedTranscript>,
+    section_written: bool,
+}
 
-    let client_suffix = coachnotes_client
-        .map(sanitize_filename_component)
-        .filter(|value| !value.is_empty())
-        .map(|value| format!("-{}", value))
-        .unwrap_or_default();
+// Topic words and non-default tags, lowercased. Transcripts saved before
+// topics were stored get them extracted from the body.
+fn related_terms(transcript: &SavedTranscript) -> BTreeSet<String> {
+    let topics = if transcript.topics.is_empty() {
+        extract_topics(&transcript.body)
+    } else {
+        transcript.topics.clone()
+    };
+    let tags = transcript
+        .tags
+        .iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty() && !RELATED_IGNORED_TAGS.contains(&tag.as_str()));
+    topics
+        .iter()
+        .flat_map(|topic| topic.split_whitespace())
+        .map(str::to_lowercase)
+        .chain(tags)
+        .collect()
+}
 
-    let stem = format!("{}{}", base_name, client_suffix);
-    let mut saved_paths = Vec::new();
+// Ranks other transcripts by the share of topic words and tags they have in
+// common with `target` (Jaccard), plus a bonus for the same client.
+// Transcripts with nothing in common are left out.
+fn rank_related_transcripts(
+    target: &SavedTranscript,
+    transcripts: Vec<SavedTranscript>,
+    limit: usize,
+) -> Vec<RelatedTranscript> {
+    let target_terms = related_terms(target);
+    let mut related = transcripts
+        .into_iter()
+        .filter(|transcript| transcript.path != target.path)
+        .filter_map(|transcript| {
+            let terms = related_terms(&transcript);
+            let shared_terms = target_terms
+                .intersection(&terms)
+                .cloned()
+                .collect::<Vec<String>>();
+            if shared_terms.is_empty() {
+                return None;
+            }
+            let client_bonus = match (&transcript.client, &target.client) {
+                (Some(client), Some(other)) if client.eq_ignore_ascii_case(other) => {
+                    RELATED_SAME_CLIENT_BONUS
+                }
+                _ => 0.0,
+            };
+            let overlap = shared_terms.len() as f64 / target_terms.union(&terms).count() as f64;
+            let score = overlap + client_bonus;
+            Some(RelatedTranscript {
+                path: transcript.path.to_string_lossy().to_string(),
+                title: transcript.title,
+                client: transcript.client,
+                date: transcript.date,
+                score: (score * 1000.0).round() / 1000.0,
+                shared_terms,
+            })
+        })
+        .collect::<Vec<RelatedTranscript>>();
+
+    related.sort_by(|left, right| {
+        right
+            .score
+            .partial_cmp(&left.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| right.date.cmp(&left.date))
+    });
+    related.truncate(limit);
+    related
+}
 
-    let primary_path = audio_dir.join(format!("{}-recording.wav", stem));
-    fs::write(&primary_path, primary_audio).map_err(|e| {
-        format!(
-            "Failed to write raw audio file ({}): {}",
-            primary_path.display(),
-            e
-        )
-    })?;
-    saved_paths.push(primary_path.to_string_lossy().to_string());
+// Link target for `path` as seen from a note in `from_dir`: the bare file name
+// in the same folder, `../<Client>/<file>` for a sibling client folder, and
+// the absolute path otherwise.
+fn related_link_target(from_dir: &Path, path: &Path) -> String {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let Some(dir) = path.parent() else {
+        return path.to_string_lossy().to_string();
+    };
+    if dir == from_dir {
+        return file_name;
+    }
+    match (dir.parent(), from_dir.parent(), dir.file_name()) {
+        (Some(root), Some(from_root), Some(folder)) if root == from_root => {
+            format!("../{}/{}", folder.to_string_lossy(), file_name)
+        }
+        _ => path.to_string_lossy().to_string(),
+    }
+}
 
-    if !microphone_audio.is_empty() && !system_audio.is_empty() {
-        let microphone_path = audio_dir.join(format!("{}-coach-mic.wav", stem));
-        fs::write(&microphone_path, microphone_audio).map_err(|e| {
+// Replaces the body's trailing "Related sessions" section, or adds one. An
+// empty list removes the section.
+fn with_related_section(body: &str, note_dir: &Path, related: &[RelatedTranscript]) -> String {
+    let base = body
+        .split(RELATED_SECTION_HEADING)
+        .next()
+        .unwrap_or(body)
+        .trim_end();
+    if related.is_empty() {
+        return format!("{}\n", base);
+    }
+    let links = related
+        .iter()
+        .map(|entry| {
+            let client = entry
+                .client
+                .as_deref()
+                .map(|client| format!(", {}", client))
+                .unwrap_or_default();
             format!(
-                "Failed to write microphone audio file ({}): {}",
-                microphone_path.display(),
-                e
+                "- [{}](<{}>) ({}{})",
+                entry.title,
+                related_link_target(note_dir, Path::new(&entry.path)),
+                entry.date,
+                client
             )
-        })?;
-        saved_paths.push(microphone_path.to_string_lossy().to_string());
+        })
+        .collect::<Vec<String>>();
+    format!(
+        "{}\n{}\n{}\n",
+        base,
+        RELATED_SECTION_HEADING,
+        links.join("\n")
+    )
+}
 
-        let system_path = audio_dir.join(format!("{}-client-system.wav", stem));
-        fs::write(&system_path, system_audio).map_err(|e| {
-            format!(
-                "Failed to write system audio file ({}): {}",
-                system_path.display(),
-                e
-            )
-        })?;
-        saved_paths.push(system_path.to_string_lossy().to_string());
+// The `k` saved transcripts most similar to `path` by shared topics, tags and
+// client. With `write_section`, the list is also written to the note as a
+// "Related sessions" section at the end of the body.
+#[tauri::command]
+async fn find_related(
+    app: AppHandle,
+    path: String,
+    k: Option<usize>,
+    write_section: Option<bool>,
+) -> Result<RelatedTranscripts, String> {
+    let path = PathBuf::from(path);
+    require_unlocked(&app, Some(&path))?;
+    let limit = k.unwrap_or(DEFAULT_RELATED_LIMIT);
+    if limit == 0 || limit > MAX_RELATED_LIMIT {
+        return Err(format!("k must be between 1 and {}", MAX_RELATED_LIMIT));
     }
 
-    Ok(saved_paths)
-}
-
-fn estimate_duration_seconds(wav_data: &[u8]) -> u64 {
-    if wav_data.len() <= 44 {
-        return 0;
+    let target = read_saved_transcript(&path).ok_or_else(|| {
+        format!(
+            "Not an Echo Scribe transcript ({}). Only saved transcripts have related sessions.",
+            path.display()
+        )
+    })?;
+    let settings = load_settings(&app)?;
+    let related = rank_related_transcripts(&target, scan_saved_transcripts(&settings), limit);
+
+    let section_written = write_section.unwrap_or(false);
+    if section_written {
+        let markdown = read_markdown_note(&path)?;
+        let (frontmatter, body) = split_frontmatter(&markdown);
+        let note_dir = path.parent().unwrap_or(Path::new(""));
+        let body = with_related_section(body, note_dir, &related);
+        let updated = if frontmatter.is_empty() {
+            body
+        } else {
+            with_updated_body_hash(&markdown, &format!("---\n{}\n---\n{}", frontmatter, body))?
+        };
+        fs::write(&path, updated)
+            .map_err(|e| format!("Failed to write note ({}): {}", path.display(), e))?;
     }
 
-    let sample_bytes = wav_data.len().saturating_sub(44);
-    let samples = sample_bytes / 2;
-    (samples / 16_000) as u64
+    Ok(RelatedTranscripts {
+        path: path.to_string_lossy().to_string(),
+        related,
+        section_written,
+    })
 }
 
-fn yaml_quote(value: &str) -> String {
-    format!(
-        "\"{}\"",
-        value
-            .replace('\\', "\\\\")
-            .replace('"', "\\\"")
-            .replace('\n', "\\n")
-    )
-}
+const DEFAULT_STATISTICS_WEEKS: u32 = 12;
+const MAX_STATISTICS_WEEKS: u32 = 520;
 
-fn normalize_transcript(text: &str) -> String {
-    sanitize_transcript_text(
-        &text
-            .lines()
-            .map(str::trim)
-            .collect::<Vec<&str>>()
-            .join("\n")
-            .trim()
-            .to_string(),
-    )
+#[derive(Debug, Serialize)]
+pub struct WeeklyStatistics {
+    week_start: String,
+    transcriptions: usize,
+    minutes: f64,
 }
 
-fn sanitize_transcript_text(text: &str) -> String {
-    text.replace(BLANK_AUDIO_MARKER, "")
-        .split_whitespace()
-        .collect::<Vec<&str>>()
-        .join(" ")
-        .trim()
-        .to_string()
+#[derive(Debug, Default, Serialize)]
+pub struct ModelUsage {
+    jobs: usize,
+    failed: usize,
+    audio_minutes: f64,
+    average_real_time_factor: Option<f64>,
 }
 
-fn apply_tdrz_speaker_labels(text: &str) -> (String, bool) {
-    if !text.contains(SPEAKER_TURN_MARKER) {
-        return (normalize_transcript(text), false);
+#[derive(Debug, Serialize)]
+pub struct Statistics {
+    transcript_count: usize,
+    total_minutes: f64,
+    average_session_minutes: f64,
+    per_week: Vec<WeeklyStatistics>,
+    clients: BTreeMap<String, usize>,
+    model_usage: BTreeMap<String, ModelUsage>,
+}
+
+fn calendar_date(iso_date: &str) -> Option<time::Date> {
+    if !is_iso_date(iso_date) {
+        return None;
     }
+    let year = iso_date[..4].parse::<i32>().ok()?;
+    let month = time::Month::try_from(iso_date[5..7].parse::<u8>().ok()?).ok()?;
+    let day = iso_date[8..10].parse::<u8>().ok()?;
+    time::Date::from_calendar_date(year, month, day).ok()
+}
 
-    let mut speaker_a_turn = true;
-    let mut segments = Vec::new();
+fn week_monday(date: time::Date) -> time::Date {
+    date - time::Duration::days(i64::from(date.weekday().number_days_from_monday()))
+}
 
-    for block in text.split(SPEAKER_TURN_MARKER) {
-        let cleaned = block
-            .split_whitespace()
-            .collect::<Vec<&str>>()
-            .join(" ")
-            .trim()
-            .to_string();
+fn format_iso_date(date: time::Date) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    )
+}
 
-        if cleaned.is_empty() {
-            continue;
-        }
+// Weekly series ends with the week containing `today` and includes empty
+// weeks, so charts do not need to fill gaps.
+fn build_statistics(
+    transcripts: &[SavedTranscript],
+    jobs: &[JobRecord],
+    today: time::Date,
+    weeks: u32,
+) -> Statistics {
+    let total_seconds = transcripts
+        .iter()
+        .map(|transcript| transcript.duration_seconds)
+        .sum::<u64>();
+
+    let current_week = week_monday(today);
+    let mut per_week = (0..weeks)
+        .rev()
+        .map(|offset| WeeklyStatistics {
+            week_start: format_iso_date(current_week - time::Duration::weeks(i64::from(offset))),
+            transcriptions: 0,
+            minutes: 0.0,
+        })
+        .collect::<Vec<WeeklyStatistics>>();
+    let mut clients = BTreeMap::new();
+    for transcript in transcripts {
+        *clients
+            .entry(
+                transcript
+                    .client
+                    .clone()
+                    .unwrap_or_else(|| DIGEST_UNASSIGNED_CLIENT.to_string()),
+            )
+            .or_default() += 1;
 
-        let speaker = if speaker_a_turn {
-            "Speaker A"
-        } else {
-            "Speaker B"
+        let Some(date) = calendar_date(&transcript.date) else {
+            continue;
         };
-        segments.push(format!("{}: {}", speaker, cleaned));
-        speaker_a_turn = !speaker_a_turn;
+        let week_start = format_iso_date(week_monday(date));
+        if let Some(week) = per_week
+            .iter_mut()
+            .find(|week| week.week_start == week_start)
+        {
+            week.transcriptions += 1;
+            week.minutes += transcript.duration_seconds as f64 / 60.0;
+        }
+    }
+    for week in &mut per_week {
+        week.minutes = round_to(week.minutes, 1);
     }
 
-    if segments.is_empty() {
-        return (normalize_transcript(text), false);
+    let mut model_usage: BTreeMap<String, ModelUsage> = BTreeMap::new();
+    let mut factors: HashMap<&str, Vec<f64>> = HashMap::new();
+    for job in jobs {
+        let usage = model_usage.entry(job.model.clone()).or_default();
+        usage.jobs += 1;
+        if job.outcome == "failed" {
+            usage.failed += 1;
+        }
+        usage.audio_minutes += job.audio_seconds.unwrap_or(0) as f64 / 60.0;
+        if let Some(factor) = job.real_time_factor {
+            factors.entry(job.model.as_str()).or_default().push(factor);
+        }
+    }
+    for (model, usage) in &mut model_usage {
+        usage.audio_minutes = round_to(usage.audio_minutes, 1);
+        usage.average_real_time_factor = factors
+            .get(model.as_str())
+            .filter(|values| !values.is_empty())
+            .map(|values| round_to(values.iter().sum::<f64>() / values.len() as f64, 3));
     }
 
-    (segments.join("\n\n"), true)
+    Statistics {
+        transcript_count: transcripts.len(),
+        total_minutes: round_to(total_seconds as f64 / 60.0, 1),
+        average_session_minutes: if transcripts.is_empty() {
+            0.0
+        } else {
+            round_to(total_seconds as f64 / 60.0 / transcripts.len() as f64, 1)
+        },
+        per_week,
+        clients,
+        model_usage,
+    }
 }
 
-fn build_markdown_transcript(
-    transcript: &str,
-    coachnotes_client: Option<&str>,
-    model: &str,
-    language: &str,
-    diarization_mode: &str,
-    created_at: &str,
-    date: &str,
-    duration_seconds: u64,
-    coachnotes_metadata: bool,
-    speaker_labels: Option<(&str, &str)>,
-) -> String {
-    let client_value = coachnotes_client.unwrap_or("");
-
-    if coachnotes_metadata {
-        let (speaker_1, speaker_2) = speaker_labels.unwrap_or(("Coach", "Client"));
-
-        return format!(
-            "---\nclient: {}\ndate: {}\ntitle: {}\nnote_type: {}\nsource: {}\ntranscript: true\nspeakers:\n  - {}\n  - {}\ntags:\n  - {}\n  - {}\nsource_app: {}\ncreated_at: {}\nmodel: {}\nlanguage: {}\ndiarization_mode: {}\nduration_seconds: {}\n---\n# Transcript\n\n{}\n",
-            yaml_quote(client_value),
-            yaml_quote(date),
-            yaml_quote("Session Transcript"),
-            yaml_quote("transcript"),
-            yaml_quote("coachnotes-voice-app"),
-            yaml_quote(speaker_1),
-            yaml_quote(speaker_2),
-            yaml_quote("transcript"),
-            yaml_quote("coaching-session"),
-            yaml_quote("Echo Scribe"),
-            yaml_quote(created_at),
-            yaml_quote(model),
-            yaml_quote(language),
-            yaml_quote(diarization_mode),
-            duration_seconds,
-            transcript
-        );
+#[tauri::command]
+async fn get_statistics(app: AppHandle, weeks: Option<u32>) -> Result<Statistics, String> {
+    require_unlocked(&app, None)?;
+    let weeks = weeks.unwrap_or(DEFAULT_STATISTICS_WEEKS);
+    if weeks == 0 || weeks > MAX_STATISTICS_WEEKS {
+        return Err(format!(
+            "Statistics weeks must be between 1 and {}",
+            MAX_STATISTICS_WEEKS
+        ));
     }
 
-    format!(
-        "---\ntitle: {}\ndate: {}\nsource_app: {}\ncreated_at: {}\nmodel: {}\nlanguage: {}\ndiarization_mode: {}\nduration_seconds: {}\n---\n# Transcript\n\n{}\n",
-        yaml_quote("Session Transcript"),
-        yaml_quote(date),
-        yaml_quote("Echo Scribe"),
-        yaml_quote(created_at),
-        yaml_quote(model),
-        yaml_quote(language),
-        yaml_quote(diarization_mode),
-        duration_seconds,
-        transcript
-    )
+    let settings = load_settings(&app)?;
+    let transcripts = scan_saved_transcripts(&settings);
+    let jobs = load_job_history(&app)?;
+    let (now, _) = now_for_settings(&settings);
+
+    Ok(build_statistics(&transcripts, &jobs, now.date(), weeks))
 }
 
-fn build_setup_state(app: &AppHandle) -> Result<SetupState, String> {
-    let settings = load_settings(app)?;
-    let models_directory = models_dir(app)?;
-    let transcript_directory = resolve_transcript_dir(&settings);
+const HISTORY_WINDOW_LABEL: &str = "history";
+const CAPTIONS_WINDOW_LABEL: &str = "captions";
+const DEFAULT_PREVIEW_CHARS: usize = 400;
+const MAX_PREVIEW_CHARS: usize = 4000;
+const DEFAULT_PAGE_BYTES: usize = 64 * 1024;
+const MAX_PAGE_BYTES: usize = 1024 * 1024;
 
-    let models = MODEL_CATALOG
-        .iter()
-        .map(|entry| {
-            let path = models_directory.join(format!("ggml-{}.bin", entry.id));
-            ModelState {
-                id: entry.id.to_string(),
-                label: entry.label.to_string(),
-                size_mb: entry.size_mb,
-                downloaded: path.exists(),
-                path: path.to_string_lossy().to_string(),
-            }
-        })
-        .collect::<Vec<ModelState>>();
+#[derive(Debug, Serialize)]
+pub struct TranscriptPreview {
+    path: String,
+    title: String,
+    client: Option<String>,
+    date: String,
+    created_at: String,
+    duration_seconds: u64,
+    word_count: u64,
+    model: String,
+    diarization_mode: String,
+    tags: Vec<String>,
+    summary: Option<String>,
+    size_bytes: u64,
+    modified_at: Option<String>,
+    excerpt: String,
+    truncated: bool,
+}
 
-    let selected_model_downloaded = models
-        .iter()
-        .find(|entry| entry.id == settings.selected_model)
-        .map(|entry| entry.downloaded)
-        .unwrap_or(false);
+#[derive(Debug, Serialize)]
+pub struct TranscriptPage {
+    path: String,
+    offset: usize,
+    // Where the following page starts; `None` on the last page.
+    next_offset: Option<usize>,
+    total_bytes: usize,
+    text: String,
+}
 
-    let sidecar_ready = is_sidecar_available();
-    let runtime_ready = if cfg!(debug_assertions) {
-        true
-    } else {
-        sidecar_ready
-    };
+// A page ends after the last whitespace that fits, so words are not split
+// across pages; a single word longer than the page is cut at a character.
+// A page always holds at least one character, even when `limit` is smaller
+// than that character, so paging always moves forward.
+fn transcript_page_end(text: &str, offset: usize, limit: usize) -> usize {
+    let mut end = offset.saturating_add(limit).min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    if end == offset {
+        return offset + text[offset..].chars().next().map_or(0, char::len_utf8);
+    }
+    if end == text.len() {
+        return end;
+    }
+    text[offset..end]
+        .char_indices()
+        .rev()
+        .find(|(_, char)| char.is_whitespace())
+        .map(|(index, char)| offset + index + char.len_utf8())
+        .filter(|&cut| cut > offset)
+        .unwrap_or(end)
+}
 
-    let coachnotes_root_dir = sanitize_non_empty(settings.coachnotes_root_dir.clone());
-    let coachnotes_clients = if let Some(root) = &coachnotes_root_dir {
-        list_coachnotes_clients_from_root(Path::new(root)).unwrap_or_default()
-    } else {
-        Vec::new()
-    };
+// The transcript text without the `# Transcript` heading.
+fn transcript_text(body: &str) -> &str {
+    let text = body.trim_start();
+    text.strip_prefix("# Transcript").unwrap_or(text).trim()
+}
 
-    Ok(SetupState {
-        selected_model: settings.selected_model,
-        transcript_dir: transcript_directory.to_string_lossy().to_string(),
-        transcript_format: "md".to_string(),
-        models_dir: models_directory.to_string_lossy().to_string(),
-        models,
-        ready: selected_model_downloaded && runtime_ready,
-        sidecar_ready,
-        coachnotes_enabled: settings.coachnotes_enabled,
-        coachnotes_root_dir,
-        coachnotes_clients,
-        coachnotes_client: sanitize_non_empty(settings.coachnotes_client),
-        diarization_mode: settings.diarization_mode,
-        diarization_capabilities: DiarizationCapabilities {
-            tdrz_english_only: true,
-        },
-    })
+// Takes the first `max_chars` characters of the transcript text, cut back to a
+// word boundary so previews never end mid-word.
+fn preview_excerpt(body: &str, max_chars: usize) -> (String, bool) {
+    let text = transcript_text(body);
+    match text.char_indices().nth(max_chars) {
+        None => (text.to_string(), false),
+        Some((end, _)) => {
+            let cut = text[..end].rfind(char::is_whitespace).unwrap_or(end);
+            (format!("{}...", text[..cut].trim_end()), true)
+        }
+    }
 }
 
+// Returns `limit` bytes (64 KiB by default, at most 1 MiB) of a transcript's
+// text from byte `offset`, so long transcripts can be shown a page at a time.
 #[tauri::command]
-async fn get_setup_state(app: AppHandle) -> Result<SetupState, String> {
-    build_setup_state(&app)
+async fn read_transcript_page(
+    app: AppHandle,
+    path: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<TranscriptPage, String> {
+    let path = PathBuf::from(path);
+    require_unlocked(&app, Some(&path))?;
+    let limit = limit.unwrap_or(DEFAULT_PAGE_BYTES);
+    if limit == 0 || limit > MAX_PAGE_BYTES {
+        return Err(format!(
+            "Page size must be between 1 and {} bytes",
+            MAX_PAGE_BYTES
+        ));
+    }
+
+    let markdown = read_markdown_note(&path)?;
+    let (_, body) = split_frontmatter(&markdown);
+    let text = transcript_text(body);
+    let offset = offset.unwrap_or(0);
+    if offset > text.len() || !text.is_char_boundary(offset) {
+        return Err(format!(
+            "Invalid page offset {}. Use 0 or a next_offset from an earlier page.",
+            offset
+        ));
+    }
+
+    let end = transcript_page_end(text, offset, limit);
+    Ok(TranscriptPage {
+        path: path.to_string_lossy().to_string(),
+        offset,
+        next_offset: (end < text.len()).then_some(end),
+        total_bytes: text.len(),
+        text: text[offset..end].to_string(),
+    })
 }
 
 #[tauri::command]
-async fn set_selected_model(app: AppHandle, model: String) -> Result<SetupState, String> {
-    validate_model(&model)?;
+async fn open_history_window(app: AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(HISTORY_WINDOW_LABEL) {
+        window
+            .show()
+            .and_then(|_| window.set_focus())
+            .map_err(|e| format!("Failed to show history window: {}", e))?;
+        return Ok(());
+    }
 
-    let mut settings = load_settings(&app)?;
-    settings.selected_model = model;
-    save_settings(&app, &settings)?;
+    tauri::WebviewWindowBuilder::new(
+        &app,
+        HISTORY_WINDOW_LABEL,
+        tauri::WebviewUrl::App("history.html".into()),
+    )
+    .title("Echo Scribe History")
+    .inner_size(880.0, 680.0)
+    .min_inner_size(640.0, 480.0)
+    .build()
+    .map_err(|e| format!("Failed to open history window: {}", e))?;
 
-    build_setup_state(&app)
+    Ok(())
 }
 
+// A small always-on-top overlay that shows the `captions` event stream.
 #[tauri::command]
-async fn set_transcript_directory(app: AppHandle, directory: String) -> Result<SetupState, String> {
-    let directory = directory.trim();
-    if directory.is_empty() {
-        return Err("Directory path cannot be empty.".to_string());
+async fn open_captions_window(app: AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(CAPTIONS_WINDOW_LABEL) {
+        window
+            .show()
+            .map_err(|e| format!("Failed to show captions window: {}", e))?;
+        return Ok(());
     }
 
-    let directory_path = PathBuf::from(directory);
-    fs::create_dir_all(&directory_path).map_err(|e| {
-        format!(
-            "Could not create transcript directory ({}): {}",
-            directory_path.display(),
-            e
-        )
-    })?;
+    tauri::WebviewWindowBuilder::new(
+        &app,
+        CAPTIONS_WINDOW_LABEL,
+        tauri::WebviewUrl::App("captions.html".into()),
+    )
+    .title("Echo Scribe Captions")
+    .inner_size(720.0, 140.0)
+    .min_inner_size(320.0, 80.0)
+    .always_on_top(true)
+    .visible_on_all_workspaces(true)
+    .skip_taskbar(true)
+    .build()
+    .map_err(|e| format!("Failed to open captions window: {}", e))?;
 
-    let mut settings = load_settings(&app)?;
-    settings.transcript_dir = Some(directory_path.to_string_lossy().to_string());
-    save_settings(&app, &settings)?;
+    Ok(())
+}
 
-    build_setup_state(&app)
+fn read_markdown_note(path: &Path) -> Result<String, String> {
+    if path.extension().map_or(true, |extension| extension != "md") || !path.is_file() {
+        return Err(format!("Not a markdown note: {}", path.display()));
+    }
+    fs::read_to_string(path).map_err(|e| read_failure("note", path, e))
 }
 
-#[tauri::command]
-async fn set_diarization_mode(app: AppHandle, mode: String) -> Result<SetupState, String> {
-    let mut settings = load_settings(&app)?;
-    settings.diarization_mode = validate_diarization_mode(&mode).to_string();
-    settings.diarization_mode_configured = true;
-    save_settings(&app, &settings)?;
+fn parse_frontmatter_mapping(frontmatter: &str) -> Result<serde_yaml::Mapping, String> {
+    if frontmatter.trim().is_empty() {
+        return Ok(serde_yaml::Mapping::new());
+    }
+    serde_yaml::from_str(frontmatter).map_err(|e| format!("Invalid frontmatter YAML: {}", e))
+}
 
-    build_setup_state(&app)
+#[derive(Debug, Serialize)]
+pub struct TranscriptVerification {
+    path: String,
+    // See `body_hash_status`.
+    status: &'static str,
+    expected_sha256: Option<String>,
+    actual_sha256: String,
 }
 
+// Checks a transcript's body against the hash stored when it was saved, to
+// catch later edits or corruption from a sync.
 #[tauri::command]
-async fn get_coachnotes_clients(root_dir: String) -> Result<Vec<String>, String> {
-    let trimmed = root_dir.trim();
-    if trimmed.is_empty() {
-        return Ok(Vec::new());
-    }
+async fn verify_transcript(app: AppHandle, path: String) -> Result<TranscriptVerification, String> {
+    require_unlocked(&app, Some(Path::new(&path)))?;
+    let markdown = read_markdown_note(Path::new(&path))?;
+    let (frontmatter, body) = split_frontmatter(&markdown);
+    Ok(TranscriptVerification {
+        path,
+        status: body_hash_status(&markdown),
+        expected_sha256: frontmatter_value(frontmatter, BODY_HASH_KEY),
+        actual_sha256: body_sha256(body),
+    })
+}
 
-    list_coachnotes_clients_from_root(Path::new(trimmed))
+// Frontmatter of any markdown note as a mapping in file order; a note without
+// frontmatter gives an empty mapping.
+#[tauri::command]
+async fn read_frontmatter(app: AppHandle, path: String) -> Result<serde_yaml::Mapping, String> {
+    require_unlocked(&app, Some(Path::new(&path)))?;
+    let markdown = read_markdown_note(Path::new(&path))?;
+    parse_frontmatter_mapping(split_frontmatter(&markdown).0)
 }
 
+// Replaces a note's frontmatter with `fields`, keeping the body byte for byte.
+// The new block is serialized by a YAML library, so values with colons,
+// quotes or line breaks stay valid, and the file is swapped in with a rename
+// so a failed write never leaves half a note.
 #[tauri::command]
-async fn set_coachnotes_settings(
+async fn write_frontmatter(
     app: AppHandle,
-    input: CoachNotesSettingsInput,
-) -> Result<SetupState, String> {
-    let mut settings = load_settings(&app)?;
-
-    let root = sanitize_non_empty(input.root_dir);
-    if let Some(root_dir) = &root {
-        fs::create_dir_all(root_dir).map_err(|e| {
-            format!(
-                "Failed to ensure CoachNotes root exists ({}): {}",
-                root_dir, e
-            )
-        })?;
+    path: String,
+    fields: serde_yaml::Mapping,
+) -> Result<(), String> {
+    let path = PathBuf::from(path);
+    require_unlocked(&app, Some(&path))?;
+    let markdown = read_markdown_note(&path)?;
+    let (frontmatter, body) = split_frontmatter(&markdown);
+    // A note whose frontmatter does not parse is left alone rather than
+    // overwritten with a guess.
+    parse_frontmatter_mapping(frontmatter)?;
+    if let Some(key) = fields
+        .keys()
+        .find(|key| key.as_str().map_or(true, |key| key.trim().is_empty()))
+    {
+        return Err(format!("Invalid frontmatter key: {:?}", key));
     }
 
-    settings.coachnotes_enabled = input.enabled;
-    settings.coachnotes_root_dir = root;
-    settings.coachnotes_client = sanitize_non_empty(input.client);
-    save_settings(&app, &settings)?;
-
-    build_setup_state(&app)
+    let updated = if fields.is_empty() {
+        body.to_string()
+    } else {
+        format!("---\n{}---\n{}", to_frontmatter_yaml(&fields)?, body)
+    };
+    replace_file(&path, updated.as_bytes())
+        .map_err(|e| format!("Failed to write note ({}): {}", path.display(), e))
 }
 
 #[tauri::command]
-async fn download_model(
+async fn read_transcript_preview(
     app: AppHandle,
-    options: ModelDownloadOptions,
-) -> Result<ModelDownloadResult, String> {
-    let model = validate_model(&options.model)?;
+    path: String,
+    max_chars: Option<usize>,
+) -> Result<TranscriptPreview, String> {
+    require_unlocked(&app, Some(Path::new(&path)))?;
+    let max_chars = max_chars.unwrap_or(DEFAULT_PREVIEW_CHARS);
+    if max_chars == 0 || max_chars > MAX_PREVIEW_CHARS {
+        return Err(format!(
+            "Preview length must be between 1 and {} characters",
+            MAX_PREVIEW_CHARS
+        ));
+    }
 
-    let model_dir = models_dir(&app)?;
-    fs::create_dir_all(&model_dir).map_err(|e| {
+    let path = PathBuf::from(path);
+    let metadata = fs::metadata(&path)
+        .map_err(|e| format!("Failed to read transcript ({}): {}", path.display(), e))?;
+    let transcript = read_saved_transcript(&path).ok_or_else(|| {
         format!(
-            "Failed to create models directory ({}): {}",
-            model_dir.display(),
-            e
+            "Not an Echo Scribe transcript ({}). Only saved transcripts can be previewed.",
+            path.display()
         )
     })?;
 
-    let target_path = model_dir.join(format!("ggml-{}.bin", model.id));
-    let temp_path = target_path.with_extension("bin.part");
+    let settings = load_settings(&app)?;
+    let (now, _) = now_for_settings(&settings);
+    let modified_at = metadata
+        .modified()
+        .ok()
+        .map(|modified| format_iso8601(OffsetDateTime::from(modified).to_offset(now.offset())));
+    let (excerpt, truncated) = preview_excerpt(&transcript.body, max_chars);
+
+    Ok(TranscriptPreview {
+        path: transcript.path.to_string_lossy().to_string(),
+        title: transcript.title,
+        client: transcript.client,
+        date: transcript.date,
+        created_at: transcript.created_at,
+        duration_seconds: transcript.duration_seconds,
+        word_count: transcript.word_count,
+        model: transcript.model,
+        diarization_mode: transcript.diarization_mode,
+        tags: transcript.tags,
+        summary: transcript.summary,
+        size_bytes: metadata.len(),
+        modified_at,
+        excerpt,
+        truncated,
+    })
+}
 
-    let client = reqwest::Client::builder()
-        .build()
-        .map_err(|e| format!("Failed to initialize HTTP client: {}", e))?;
-    let expected_checksum = model.sha256;
+const CSV_EXPORT_KINDS: [&str; 2] = ["sessions", "usage"];
 
-    if target_path.exists() {
-        emit_model_download_progress(&app, model.id, 1, 0, None, "Verifying existing model...");
-        let existing_checksum = sha256_for_file(&target_path).await?;
-        if existing_checksum == expected_checksum {
-            emit_model_download_progress(&app, model.id, 100, 0, None, "Model already downloaded.");
-            return Ok(ModelDownloadResult {
-                model: model.id.to_string(),
-                path: target_path.to_string_lossy().to_string(),
-            });
-        }
-        let _ = fs::remove_file(&target_path);
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
     }
+}
 
-    let _ = fs::remove_file(&temp_path);
-    emit_model_download_progress(&app, model.id, 2, 0, None, "Starting download...");
-
-    let response = client
-        .get(model.url)
-        .send()
-        .await
-        .map_err(|e| format!("Model download failed: {}", e))?;
+fn csv_row(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<String>>()
+        .join(",")
+}
 
-    if !response.status().is_success() {
-        return Err(format!(
-            "Model download failed with HTTP status {}",
-            response.status()
-        ));
+fn sessions_csv(transcripts: &[SavedTranscript]) -> Vec<String> {
+    let mut rows = vec![csv_row(
+        &[
+            "date",
+            "client",
+            "title",
+            "duration_seconds",
+            "duration_minutes",
+            "word_count",
+            "model",
+            "path",
+        ]
+        .map(String::from),
+    )];
+    for transcript in transcripts {
+        rows.push(csv_row(&[
+            transcript.date.clone(),
+            transcript.client.clone().unwrap_or_default(),
+            transcript.title.clone(),
+            transcript.duration_seconds.to_string(),
+            round_to(transcript.duration_seconds as f64 / 60.0, 1).to_string(),
+            transcript.word_count.to_string(),
+            transcript.model.clone(),
+            transcript.path.to_string_lossy().to_string(),
+        ]));
     }
+    rows
+}
 
-    let total_bytes = response.content_length();
-    let mut stream = response.bytes_stream();
-    let mut file = tokio::fs::File::create(&temp_path)
-        .await
-        .map_err(|e| format!("Failed to create temp model file: {}", e))?;
+fn segments_csv(segments: &[TimestampedSegment]) -> Vec<String> {
+    let mut rows = vec![csv_row(
+        &["start", "end", "speaker", "text", "confidence"].map(String::from),
+    )];
+    for segment in segments {
+        rows.push(csv_row(&[
+            format_precise_timestamp(segment.start_ms),
+            format_precise_timestamp(segment.end_ms),
+            segment.speaker.clone(),
+            segment.text.clone(),
+            segment
+                .confidence
+                .map(|confidence| format!("{:.3}", confidence))
+                .unwrap_or_default(),
+        ]));
+    }
+    rows
+}
 
-    let mut hasher = Sha256::new();
-    let mut downloaded_bytes: u64 = 0;
+// Segments grouped per speaker in order of first appearance. Transcripts
+// without diarization have one unnamed speaker, exported as "Transcript".
+fn segments_by_speaker(segments: &[TimestampedSegment]) -> Vec<(String, Vec<&TimestampedSegment>)> {
+    let mut speakers: Vec<(String, Vec<&TimestampedSegment>)> = Vec::new();
+    for segment in segments {
+        let name = if segment.speaker.is_empty() {
+            "Transcript"
+        } else {
+            segment.speaker.as_str()
+        };
+        match speakers.iter_mut().find(|(speaker, _)| speaker == name) {
+            Some((_, tier)) => tier.push(segment),
+            None => speakers.push((name.to_string(), vec![segment])),
+        }
+    }
+    speakers
+}
 
-    while let Some(next) = stream.next().await {
-        let chunk = next.map_err(|e| format!("Download stream failed: {}", e))?;
+fn praat_seconds(ms: u64) -> String {
+    format!("{}.{:03}", ms / 1000, ms % 1000)
+}
 
-        file.write_all(&chunk)
-            .await
-            .map_err(|e| format!("Failed to write model file: {}", e))?;
+fn praat_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
 
-        hasher.update(&chunk);
-        downloaded_bytes += chunk.len() as u64;
+// Long-format Praat TextGrid with one interval tier per speaker. Praat needs
+// each tier covered end to end, so gaps become empty intervals and a segment
+// overlapping the previous one on its tier starts where that one ends.
+fn render_textgrid(segments: &[TimestampedSegment]) -> String {
+    let xmax = segments
+        .iter()
+        .map(|segment| segment.end_ms)
+        .max()
+        .unwrap_or(0);
+    let tiers = segments_by_speaker(segments);
+    let mut lines = vec![
+        "File type = \"ooTextFile\"".to_string(),
+        "Object class = \"TextGrid\"".to_string(),
+        String::new(),
+        "xmin = 0".to_string(),
+        format!("xmax = {}", praat_seconds(xmax)),
+        "tiers? <exists>".to_string(),
+        format!("size = {}", tiers.len()),
+        "item []:".to_string(),
+    ];
 
-        let percent = total_bytes
-            .map(|total| ((downloaded_bytes.saturating_mul(100)) / total.max(1)) as u32)
-            .unwrap_or(0)
-            .min(99);
+    for (tier_index, (speaker, tier_segments)) in tiers.iter().enumerate() {
+        let mut intervals: Vec<(u64, u64, &str)> = Vec::new();
+        let mut cursor = 0;
+        for segment in tier_segments {
+            let start_ms = segment.start_ms.max(cursor);
+            let end_ms = segment.end_ms.min(xmax);
+            if end_ms <= start_ms {
+                continue;
+            }
+            if start_ms > cursor {
+                intervals.push((cursor, start_ms, ""));
+            }
+            intervals.push((start_ms, end_ms, segment.text.as_str()));
+            cursor = end_ms;
+        }
+        if cursor < xmax || intervals.is_empty() {
+            intervals.push((cursor, xmax, ""));
+        }
 
-        emit_model_download_progress(
-            &app,
-            model.id,
-            percent.max(2),
-            downloaded_bytes,
-            total_bytes,
-            "Downloading model...",
-        );
+        lines.push(format!("    item [{}]:", tier_index + 1));
+        lines.push("        class = \"IntervalTier\"".to_string());
+        lines.push(format!("        name = {}", praat_string(speaker)));
+        lines.push("        xmin = 0".to_string());
+        lines.push(format!("        xmax = {}", praat_seconds(xmax)));
+        lines.push(format!("        intervals: size = {}", intervals.len()));
+        for (interval_index, (start_ms, end_ms, text)) in intervals.iter().enumerate() {
+            lines.push(format!("        intervals [{}]:", interval_index + 1));
+            lines.push(format!("            xmin = {}", praat_seconds(*start_ms)));
+            lines.push(format!("            xmax = {}", praat_seconds(*end_ms)));
+            lines.push(format!("            text = {}", praat_string(text)));
+        }
     }
 
-    file.flush()
-        .await
-        .map_err(|e| format!("Failed to flush model file: {}", e))?;
+    format!("{}\n", lines.join("\n"))
+}
 
-    let actual_checksum = format!("{:x}", hasher.finalize());
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
 
-    if actual_checksum != expected_checksum {
-        let _ = fs::remove_file(&temp_path);
-        return Err(format!(
-            "Checksum mismatch for {} model. Expected {}, got {}.",
-            model.id, expected_checksum, actual_checksum
-        ));
+// ELAN annotation document (EAF 3.0) with one tier per speaker. Every
+// annotation gets its own pair of time slots so tiers stay independent when
+// edited in ELAN; slot ids are numbered in time order.
+fn render_elan(segments: &[TimestampedSegment], date: &str) -> String {
+    let tiers = segments_by_speaker(segments);
+    let annotations = tiers
+        .iter()
+        .enumerate()
+        .flat_map(|(tier_index, (_, tier_segments))| {
+            tier_segments
+                .iter()
+                .filter(|segment| segment.end_ms > segment.start_ms)
+                .map(move |segment| (tier_index, *segment))
+        })
+        .collect::<Vec<(usize, &TimestampedSegment)>>();
+
+    let mut slot_times = annotations
+        .iter()
+        .enumerate()
+        .flat_map(|(index, (_, segment))| {
+            [
+                (segment.start_ms, index * 2),
+                (segment.end_ms, index * 2 + 1),
+            ]
+        })
+        .collect::<Vec<(u64, usize)>>();
+    slot_times.sort();
+    let mut slot_ids = vec![0; slot_times.len()];
+    for (position, (_, slot)) in slot_times.iter().enumerate() {
+        slot_ids[*slot] = position + 1;
     }
 
-    if target_path.exists() {
-        let _ = fs::remove_file(&target_path);
+    let mut lines = vec![
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>".to_string(),
+        format!(
+            "<ANNOTATION_DOCUMENT AUTHOR=\"Echo Scribe\" DATE=\"{}\" FORMAT=\"3.0\" VERSION=\"3.0\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xsi:noNamespaceSchemaLocation=\"http://www.mpi.nl/tools/elan/EAFv3.0.xsd\">",
+            xml_escape(date)
+        ),
+        "    <HEADER MEDIA_FILE=\"\" TIME_UNITS=\"milliseconds\"/>".to_string(),
+        "    <TIME_ORDER>".to_string(),
+    ];
+    for (position, (time_ms, _)) in slot_times.iter().enumerate() {
+        lines.push(format!(
+            "        <TIME_SLOT TIME_SLOT_ID=\"ts{}\" TIME_VALUE=\"{}\"/>",
+            position + 1,
+            time_ms
+        ));
     }
+    lines.push("    </TIME_ORDER>".to_string());
 
-    tokio::fs::rename(&temp_path, &target_path)
-        .await
-        .map_err(|e| format!("Failed to finalize model file: {}", e))?;
+    for (tier_index, (speaker, _)) in tiers.iter().enumerate() {
+        let speaker = xml_escape(speaker);
+        lines.push(format!(
+            "    <TIER LINGUISTIC_TYPE_REF=\"default-lt\" PARTICIPANT=\"{}\" TIER_ID=\"{}\">",
+            speaker, speaker
+        ));
+        for (index, (_, segment)) in annotations
+            .iter()
+            .enumerate()
+            .filter(|(_, (tier, _))| *tier == tier_index)
+        {
+            lines.push("        <ANNOTATION>".to_string());
+            lines.push(format!(
+                "            <ALIGNABLE_ANNOTATION ANNOTATION_ID=\"a{}\" TIME_SLOT_REF1=\"ts{}\" TIME_SLOT_REF2=\"ts{}\">",
+                index + 1,
+                slot_ids[index * 2],
+                slot_ids[index * 2 + 1]
+            ));
+            lines.push(format!(
+                "                <ANNOTATION_VALUE>{}</ANNOTATION_VALUE>",
+                xml_escape(&segment.text)
+            ));
+            lines.push("            </ALIGNABLE_ANNOTATION>".to_string());
+            lines.push("        </ANNOTATION>".to_string());
+        }
+        lines.push("    </TIER>".to_string());
+    }
 
-    emit_model_download_progress(
-        &app,
-        model.id,
-        100,
-        downloaded_bytes,
-        total_bytes,
-        "Model download complete.",
+    lines.push(
+        "    <LINGUISTIC_TYPE GRAPHIC_REFERENCES=\"false\" LINGUISTIC_TYPE_ID=\"default-lt\" TIME_ALIGNABLE=\"true\"/>"
+            .to_string(),
     );
+    lines.push("</ANNOTATION_DOCUMENT>".to_string());
+    format!("{}\n", lines.join("\n"))
+}
 
-    Ok(ModelDownloadResult {
-        model: model.id.to_string(),
-        path: target_path.to_string_lossy().to_string(),
-    })
+#[derive(Deserialize)]
+struct SidecarSegments {
+    segments: Vec<TimestampedSegment>,
+}
+
+fn usage_csv(jobs: &[JobRecord], offset: UtcOffset) -> Vec<String> {
+    let timestamp = |ms: u64| {
+        OffsetDateTime::from_unix_timestamp((ms / 1000) as i64)
+            .map(|time| format_iso8601(time.to_offset(offset)))
+            .unwrap_or_default()
+    };
+    let mut rows = vec![csv_row(
+        &[
+            "job_id",
+            "queued_at",
+            "finished_at",
+            "model",
+            "outcome",
+            "audio_seconds",
+            "wall_seconds",
+            "real_time_factor",
+            "saved_path",
+        ]
+        .map(String::from),
+    )];
+    for job in jobs {
+        rows.push(csv_row(&[
+            job.job_id.clone(),
+            timestamp(job.queued_at_ms),
+            timestamp(job.finished_at_ms),
+            job.model.clone(),
+            job.outcome.clone(),
+            job.audio_seconds
+                .map(|seconds| seconds.to_string())
+                .unwrap_or_default(),
+            job.wall_seconds.to_string(),
+            job.real_time_factor
+                .map(|factor| factor.to_string())
+                .unwrap_or_default(),
+            job.saved_path.clone().unwrap_or_default(),
+        ]));
+    }
+    rows
 }
 
+// Writes a spreadsheet-ready CSV and returns the number of data rows. The
+// path comes from a save dialog, so an existing file is replaced.
 #[tauri::command]
-async fn start_system_audio_recording(
+async fn export_csv(
     app: AppHandle,
-    state: State<'_, SystemAudioCaptureState>,
-) -> Result<(), String> {
-    #[cfg(not(target_os = "macos"))]
-    {
-        let _ = state;
-        return Err("System audio capture is only supported on macOS.".to_string());
+    kind: String,
+    path: String,
+    overwrite: Option<bool>,
+) -> Result<usize, String> {
+    require_unlocked(&app, None)?;
+    let settings = load_settings(&app)?;
+    let rows = match kind.as_str() {
+        "sessions" => sessions_csv(&scan_saved_transcripts(&settings)),
+        "usage" => {
+            let (now, _) = now_for_settings(&settings);
+            usage_csv(&load_job_history(&app)?, now.offset())
+        }
+        _ => {
+            return Err(format!(
+                "Unsupported export kind '{}'. Valid values: {}",
+                kind,
+                CSV_EXPORT_KINDS.join(", ")
+            ))
+        }
+    };
+
+    let path = PathBuf::from(path);
+    if path.exists() && !overwrite.unwrap_or(false) {
+        return Err(format!(
+            "CSV file already exists ({}). Pick another name or allow overwriting.",
+            path.display()
+        ));
+    }
+    fs::write(&path, format!("{}\n", rows.join("\n")))
+        .map_err(|e| format!("Failed to write CSV file ({}): {}", path.display(), e))?;
+    if kind == "sessions" {
+        record_audit(
+            &app,
+            AuditAction::Export,
+            &resolve_transcript_dir(&settings),
+            Some(&path),
+        )?;
     }
 
-    #[cfg(target_os = "macos")]
-    {
-        let mut guard = state
-            .session
-            .lock()
-            .map_err(|_| "Failed to lock system audio state.".to_string())?;
+    Ok(rows.len() - 1)
+}
 
-        if guard.is_some() {
-            return Err("System audio capture is already running.".to_string());
-        }
+const JSONL_EXPORT_GRANULARITIES: [&str; 2] = ["transcript", "segment"];
 
-        let timestamp = unix_timestamp_secs()?;
-        let temp_dir = std::env::temp_dir().join("echo-scribe");
-        fs::create_dir_all(&temp_dir)
-            .map_err(|e| format!("Failed to create temporary directory: {}", e))?;
-        let output_path = temp_dir.join(format!("system-audio-{}.wav", timestamp));
+#[derive(Serialize)]
+struct TranscriptJsonLine<'a> {
+    path: Cow<'a, str>,
+    title: &'a str,
+    date: &'a str,
+    created_at: &'a str,
+    client: Option<&'a str>,
+    duration_seconds: u64,
+    word_count: u64,
+    model: &'a str,
+    diarization_mode: &'a str,
+    tags: &'a [String],
+    topics: &'a [String],
+    speakers: &'a [String],
+    summary: Option<&'a str>,
+    text: &'a str,
+}
 
-        let sidecar_path = resolve_system_audio_capture_sidecar_path(&app)?;
-        let mut command = StdCommand::new(&sidecar_path);
-        command
-            .arg("record")
-            .arg("--output")
-            .arg(&output_path)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+#[derive(Serialize)]
+struct SegmentJsonLine<'a> {
+    path: Cow<'a, str>,
+    title: &'a str,
+    date: &'a str,
+    client: Option<&'a str>,
+    model: &'a str,
+    index: usize,
+    speaker: &'a str,
+    start_ms: u64,
+    end_ms: u64,
+    text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidence: Option<f32>,
+}
 
-        let child = command.spawn().map_err(|e| {
-            format!(
-                "Failed to start system audio capture binary ({}): {}",
-                sidecar_path.display(),
-                e
-            )
-        })?;
+#[derive(Serialize)]
+struct JsonlExport {
+    lines: usize,
+    // Transcripts left out of a segment export for lack of a JSON metadata file.
+    skipped: usize,
+}
 
-        *guard = Some(SystemAudioCaptureSession { child, output_path });
-        Ok(())
-    }
+fn encode_json_line<T: Serialize>(line: &T) -> Result<String, String> {
+    serde_json::to_string(line).map_err(|e| format!("Failed to encode JSON line: {}", e))
 }
 
+// Writes the whole corpus as JSON Lines for embedding or search pipelines:
+// one object per transcript with its metadata and text, or one per segment
+// with the transcript's metadata repeated on each line.
 #[tauri::command]
-async fn stop_system_audio_recording(
-    state: State<'_, SystemAudioCaptureState>,
-) -> Result<SystemAudioCaptureResult, String> {
-    #[cfg(not(target_os = "macos"))]
-    {
-        let _ = state;
-        return Err("System audio capture is only supported on macOS.".to_string());
+async fn export_jsonl(
+    app: AppHandle,
+    granularity: String,
+    path: String,
+    overwrite: Option<bool>,
+) -> Result<JsonlExport, String> {
+    require_unlocked(&app, None)?;
+    if !JSONL_EXPORT_GRANULARITIES.contains(&granularity.as_str()) {
+        return Err(format!(
+            "Unsupported JSONL granularity '{}'. Valid values: {}",
+            granularity,
+            JSONL_EXPORT_GRANULARITIES.join(", ")
+        ));
     }
+    let settings = load_settings(&app)?;
+    let mut lines = Vec::new();
+    let mut skipped = 0;
+    for transcript in scan_saved_transcripts(&settings) {
+        if granularity == "transcript" {
+            lines.push(encode_json_line(&TranscriptJsonLine {
+                path: transcript.path.to_string_lossy(),
+                title: &transcript.title,
+                date: &transcript.date,
+                created_at: &transcript.created_at,
+                client: transcript.client.as_deref(),
+                duration_seconds: transcript.duration_seconds,
+                word_count: transcript.word_count,
+                model: &transcript.model,
+                diarization_mode: &transcript.diarization_mode,
+                tags: &transcript.tags,
+                topics: &transcript.topics,
+                speakers: &transcript.speakers,
+                summary: transcript.summary.as_deref(),
+                text: transcript.body.trim(),
+            })?);
+            continue;
+        }
 
-    #[cfg(target_os = "macos")]
-    {
-        let mut session = {
-            let mut guard = state
-                .session
-                .lock()
-                .map_err(|_| "Failed to lock system audio state.".to_string())?;
-
-            guard
-                .take()
-                .ok_or_else(|| "System audio capture is not currently running.".to_string())?
+        let Ok(segments) = read_sidecar_segments(&transcript.path) else {
+            skipped += 1;
+            continue;
         };
-        let _cleanup = TempFileCleanup::new(vec![session.output_path.clone()]);
+        for (index, segment) in segments.iter().enumerate() {
+            lines.push(encode_json_line(&SegmentJsonLine {
+                path: transcript.path.to_string_lossy(),
+                title: &transcript.title,
+                date: &transcript.date,
+                client: transcript.client.as_deref(),
+                model: &transcript.model,
+                index,
+                speaker: &segment.speaker,
+                start_ms: segment.start_ms,
+                end_ms: segment.end_ms,
+                text: &segment.text,
+                confidence: segment.confidence,
+            })?);
+        }
+    }
 
-        let _ = session.child.stdin.take();
+    let path = PathBuf::from(path);
+    if path.exists() && !overwrite.unwrap_or(false) {
+        return Err(format!(
+            "JSONL file already exists ({}). Pick another name or allow overwriting.",
+            path.display()
+        ));
+    }
+    let contents = lines
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+    fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write JSONL file ({}): {}", path.display(), e))?;
+    record_audit(
+        &app,
+        AuditAction::Export,
+        &resolve_transcript_dir(&settings),
+        Some(&path),
+    )?;
+
+    Ok(JsonlExport {
+        lines: lines.len(),
+        skipped,
+    })
+}
 
-        let deadline = Instant::now() + Duration::from_secs(10);
-        let status = loop {
-            if let Some(status) = session
-                .child
-                .try_wait()
-                .map_err(|e| format!("Failed to wait for system audio capture process: {}", e))?
-            {
-                break status;
-            }
+#[tauri::command]
+async fn generate_digest(app: AppHandle, range: DigestRange) -> Result<DigestResult, String> {
+    require_unlocked(&app, None)?;
+    for date in [&range.start, &range.end] {
+        if !is_iso_date(date) {
+            return Err(format!("Invalid date '{}'. Use YYYY-MM-DD.", date));
+        }
+    }
+    if range.start > range.end {
+        return Err("Digest start date must not be after its end date.".to_string());
+    }
 
-            if Instant::now() >= deadline {
-                let _ = session.child.kill();
-                let _ = session.child.wait();
-                return Err(
-                    "Timed out while stopping system audio capture. Please try again.".to_string(),
-                );
-            }
+    let settings = load_settings(&app)?;
+    let transcripts = scan_saved_transcripts(&settings)
+        .into_iter()
+        .filter(|transcript| transcript.date >= range.start && transcript.date <= range.end)
+        .collect::<Vec<SavedTranscript>>();
 
-            std::thread::sleep(Duration::from_millis(50));
-        };
+    let (now, _) = now_for_settings(&settings);
+    let markdown = build_digest_markdown(&range, &transcripts, &format_iso8601(now))?;
+    let transcript_dir = resolve_transcript_dir(&settings);
+    fs::create_dir_all(&transcript_dir).map_err(|e| {
+        format!(
+            "Failed to create transcript folder ({}): {}",
+            transcript_dir.display(),
+            e
+        )
+    })?;
+    let path = transcript_dir.join(format!("digest-{}-to-{}.md", range.start, range.end));
+    let path = write_new_file(&path, markdown.as_bytes())
+        .map_err(|e| format!("Failed to write digest ({}): {}", path.display(), e))?;
+    for transcript in &transcripts {
+        record_audit(&app, AuditAction::Export, &transcript.path, Some(&path))?;
+    }
 
-        let mut stdout = Vec::new();
-        if let Some(mut stdout_reader) = session.child.stdout.take() {
-            let _ = stdout_reader.read_to_end(&mut stdout);
-        }
+    let total_seconds = transcripts
+        .iter()
+        .map(|transcript| transcript.duration_seconds)
+        .sum::<u64>();
 
-        let mut stderr = Vec::new();
-        if let Some(mut stderr_reader) = session.child.stderr.take() {
-            let _ = stderr_reader.read_to_end(&mut stderr);
-        }
+    Ok(DigestResult {
+        saved_path: path.to_string_lossy().to_string(),
+        session_count: transcripts.len(),
+        total_hours: round_to(total_seconds as f64 / 3600.0, 1),
+    })
+}
 
-        if !status.success() {
-            let stderr_text = String::from_utf8_lossy(&stderr).trim().to_string();
-            if stderr_text.is_empty() {
-                return Err("System audio capture exited with an error.".to_string());
-            }
-            return Err(format!("System audio capture failed: {}", stderr_text));
+#[derive(Serialize)]
+struct SpeakerFileFrontmatter<'a> {
+    title: &'a str,
+    speaker: &'a str,
+    source_transcript: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_at: Option<String>,
+    source_app: &'a str,
+}
+
+#[tauri::command]
+async fn export_speaker_files(app: AppHandle, path: String) -> Result<Vec<String>, String> {
+    let transcript_path = PathBuf::from(&path);
+    require_unlocked(&app, Some(&transcript_path))?;
+    let markdown = fs::read_to_string(&transcript_path)
+        .map_err(|e| read_failure("transcript file", &transcript_path, e))?;
+    let (frontmatter, body) = split_frontmatter(&markdown);
+
+    let mut speakers = frontmatter_list(frontmatter, "speakers");
+    for label in DEFAULT_SPEAKER_LABELS {
+        if !speakers.iter().any(|speaker| speaker == label) {
+            speakers.push(label.to_string());
         }
+    }
+    let grouped = collect_speaker_paragraphs(body, &speakers);
+    if grouped.is_empty() {
+        return Err(format!(
+            "Transcript has no speaker-labelled lines to split ({})",
+            transcript_path.display()
+        ));
+    }
 
-        let wav_data = fs::read(&session.output_path).map_err(|e| {
+    let title = frontmatter_value(frontmatter, "title")
+        .unwrap_or_else(|| DEFAULT_TRANSCRIPT_TITLE.to_string());
+    let source_name = transcript_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stem = transcript_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "transcript".to_string());
+
+    let mut saved_paths = Vec::new();
+    for (speaker, paragraphs) in grouped {
+        let fields = to_frontmatter_yaml(&SpeakerFileFrontmatter {
+            title: &format!("{} ({})", title, speaker),
+            speaker: &speaker,
+            source_transcript: &source_name,
+            client: frontmatter_value(frontmatter, "client"),
+            date: frontmatter_value(frontmatter, "date"),
+            created_at: frontmatter_value(frontmatter, "created_at"),
+            source_app: "Echo Scribe",
+        })?;
+        let markdown = format!(
+            "---\n{}---\n# {}\n\n{}\n",
+            fields,
+            speaker,
+            paragraphs.join("\n\n")
+        );
+        let speaker_path = transcript_path.with_file_name(format!(
+            "{}-{}.md",
+            stem,
+            sanitize_filename_component(&speaker)
+        ));
+        let speaker_path = write_new_file(&speaker_path, markdown.as_bytes()).map_err(|e| {
             format!(
-                "Failed to read captured system audio file ({}): {}",
-                session.output_path.display(),
+                "Failed to write speaker file ({}): {}",
+                speaker_path.display(),
                 e
             )
         })?;
+        record_audit(
+            &app,
+            AuditAction::Export,
+            &transcript_path,
+            Some(&speaker_path),
+        )?;
+        saved_paths.push(speaker_path.to_string_lossy().to_string());
+    }
+
+    Ok(saved_paths)
+}
+
+#[tauri::command]
+async fn export_anonymized_transcript(app: AppHandle, path: String) -> Result<String, String> {
+    let transcript_path = PathBuf::from(&path);
+    require_unlocked(&app, Some(&transcript_path))?;
+    let markdown = fs::read_to_string(&transcript_path)
+        .map_err(|e| read_failure("transcript file", &transcript_path, e))?;
+
+    let stem = transcript_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "transcript".to_string());
+    let anonymized_path = transcript_path.with_file_name(format!("{}-anonymized.md", stem));
+    let anonymized_path = write_new_file(
+        &anonymized_path,
+        with_body_hash(&anonymize_transcript(&markdown)?)?.as_bytes(),
+    )
+    .map_err(|e| {
+        format!(
+            "Failed to write anonymized transcript ({}): {}",
+            anonymized_path.display(),
+            e
+        )
+    })?;
+    record_audit(
+        &app,
+        AuditAction::Export,
+        &transcript_path,
+        Some(&anonymized_path),
+    )?;
+
+    Ok(anonymized_path.to_string_lossy().to_string())
+}
+
+// Segment exports read the transcript's JSON metadata file since the
+// Markdown no longer has segment boundaries.
+fn read_sidecar_segments(transcript_path: &Path) -> Result<Vec<TimestampedSegment>, String> {
+    let sidecar_path = transcript_path.with_extension("json");
+    let json = fs::read_to_string(&sidecar_path).map_err(|e| {
+        format!(
+            "No JSON metadata found for this transcript ({}): {}. Turn on \"Save JSON metadata\" before transcribing to export segments.",
+            sidecar_path.display(),
+            e
+        )
+    })?;
+    let sidecar = serde_json::from_str::<SidecarSegments>(&json).map_err(|e| {
+        format!(
+            "Failed to read segments from metadata file ({}): {}",
+            sidecar_path.display(),
+            e
+        )
+    })?;
+    Ok(sidecar.segments)
+}
 
-        if wav_data.len() <= 44 {
-            return Err(
-                "No shared audio was captured. Start playback first, then record again."
-                    .to_string(),
-            );
-        }
+// Saves `<stem><suffix>` next to the transcript without replacing an
+// existing file and returns the path actually written.
+fn write_segment_export(
+    app: &AppHandle,
+    transcript_path: &Path,
+    suffix: &str,
+    contents: &str,
+) -> Result<String, String> {
+    let stem = transcript_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "transcript".to_string());
+    let export_path = transcript_path.with_file_name(format!("{}{}", stem, suffix));
+    let export_path = write_new_file(&export_path, contents.as_bytes()).map_err(|e| {
+        format!(
+            "Failed to write export file ({}): {}",
+            export_path.display(),
+            e
+        )
+    })?;
+    record_audit(
+        app,
+        AuditAction::Export,
+        transcript_path,
+        Some(&export_path),
+    )?;
+
+    Ok(export_path.to_string_lossy().to_string())
+}
 
-        let metadata = if stdout.is_empty() {
-            SystemAudioCaptureMetadata {
-                first_audio_wall_time_ms: 0,
-            }
-        } else {
-            serde_json::from_slice::<SystemAudioCaptureMetadata>(&stdout).map_err(|error| {
-                format!("System audio capture returned invalid metadata: {}", error)
-            })?
-        };
+#[tauri::command]
+async fn export_segments_csv(app: AppHandle, path: String) -> Result<String, String> {
+    let transcript_path = PathBuf::from(&path);
+    require_unlocked(&app, Some(&transcript_path))?;
+    let rows = segments_csv(&read_sidecar_segments(&transcript_path)?);
+    write_segment_export(
+        &app,
+        &transcript_path,
+        "-segments.csv",
+        &format!("{}\n", rows.join("\n")),
+    )
+}
 
-        Ok(SystemAudioCaptureResult {
-            audio_data: wav_data,
-            first_audio_wall_time_ms: metadata.first_audio_wall_time_ms,
-        })
-    }
+#[tauri::command]
+async fn export_textgrid(app: AppHandle, path: String) -> Result<String, String> {
+    let transcript_path = PathBuf::from(&path);
+    require_unlocked(&app, Some(&transcript_path))?;
+    let textgrid = render_textgrid(&read_sidecar_segments(&transcript_path)?);
+    write_segment_export(&app, &transcript_path, ".TextGrid", &textgrid)
 }
 
 #[tauri::command]
-async fn transcribe_recording(
-    app: AppHandle,
-    options: TranscriptionOptions,
-) -> Result<TranscriptionResult, String> {
-    let primary_audio = if !options.audio_data.is_empty() {
-        options.audio_data.as_slice()
-    } else if !options.system_audio_data.is_empty() {
-        options.system_audio_data.as_slice()
-    } else if !options.microphone_audio_data.is_empty() {
-        options.microphone_audio_data.as_slice()
-    } else {
-        return Err("No audio data provided. Record audio first.".to_string());
-    };
+async fn export_elan(app: AppHandle, path: String) -> Result<String, String> {
+    let transcript_path = PathBuf::from(&path);
+    require_unlocked(&app, Some(&transcript_path))?;
+    let (now, _) = now_for_settings(&load_settings(&app)?);
+    let elan = render_elan(
+        &read_sidecar_segments(&transcript_path)?,
+        &format_iso8601(now),
+    );
+    write_segment_export(&app, &transcript_path, ".eaf", &elan)
+}
 
-    validate_model(&options.model)?;
-    let model_path = model_file_path(&app, &options.model)?;
+const BUNDLE_MANIFEST_FILE: &str = "manifest.json";
+const BUNDLE_COPY_CHUNK_BYTES: usize = 1024 * 1024;
+// Exports written next to a transcript, by the suffix after its stem.
+const BUNDLE_EXPORT_SUFFIXES: [&str; 6] =
+    ["-segments.csv", ".TextGrid", ".eaf", ".srt", ".vtt", ".pdf"];
 
-    if !model_path.exists() {
-        return Err(format!(
-            "Model '{}' is not downloaded yet. Use Setup to download it first.",
-            options.model
-        ));
-    }
+#[derive(Debug, Serialize)]
+struct BundleManifest {
+    source_app: &'static str,
+    exported_at: String,
+    title: Option<String>,
+    client: Option<String>,
+    created_at: Option<String>,
+    transcript: String,
+    files: Vec<BundleFile>,
+}
 
-    let mut warnings = Vec::new();
-    let settings = load_settings(&app)?;
-    let mut speaker_mode_used = if options.diarization_mode.trim().is_empty() {
-        validate_diarization_mode(&settings.diarization_mode).to_string()
-    } else {
-        validate_diarization_mode(&options.diarization_mode).to_string()
-    };
+#[derive(Debug, Serialize)]
+struct BundleFile {
+    name: String,
+    // transcript, metadata, export, audio, or alignment.
+    kind: &'static str,
+    bytes: u64,
+    sha256: String,
+}
 
-    let has_dual_source_audio =
-        !options.microphone_audio_data.is_empty() && !options.system_audio_data.is_empty();
+// The session's files as (name in the archive, path, kind): the transcript,
+// its JSON sidecar, exports and show notes saved next to it, and, when asked,
+// the archived recording with its alignment.
+fn bundle_sources(
+    settings: &AppSettings,
+    transcript_path: &Path,
+    include_audio: bool,
+) -> Result<Vec<(String, PathBuf, &'static str)>, String> {
+    let dir = transcript_path.parent().unwrap_or(Path::new("."));
+    let stem = transcript_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "transcript".to_string());
+    let file_name = |path: &Path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
 
-    if speaker_mode_used == "source_aware_2speaker" && !has_dual_source_audio {
-        warnings.push(
-            "Two-speaker source-aware mode requires both microphone and system audio capture. Falling back to standard transcription."
-                .to_string(),
-        );
-        speaker_mode_used = "none".to_string();
+    let mut sources = vec![(
+        file_name(transcript_path),
+        transcript_path.to_path_buf(),
+        "transcript",
+    )];
+    let sidecar_path = transcript_path.with_extension("json");
+    if sidecar_path.is_file() {
+        sources.push((file_name(&sidecar_path), sidecar_path, "metadata"));
     }
-
-    if speaker_mode_used == "tdrz_2speaker" {
-        if options.language != "en" {
-            warnings.push(
-                "Whisper diarization fallback is English-only. Falling back to standard transcription."
-                    .to_string(),
-            );
-            speaker_mode_used = "none".to_string();
-        } else if options.model != "small.en-tdrz" {
-            warnings.push(
-                "Whisper diarization fallback requires the small.en-tdrz model. Falling back to standard transcription."
-                    .to_string(),
-            );
-            speaker_mode_used = "none".to_string();
+    for suffix in BUNDLE_EXPORT_SUFFIXES {
+        let path = dir.join(format!("{}{}", stem, suffix));
+        if path.is_file() {
+            sources.push((file_name(&path), path, "export"));
+        }
+    }
+    let show_notes_dir = dir.join(format!("{}-show-notes", stem));
+    if let Ok(entries) = fs::read_dir(&show_notes_dir) {
+        let mut paths = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file())
+            .collect::<Vec<PathBuf>>();
+        paths.sort();
+        for path in paths {
+            let name = format!("{}-show-notes/{}", stem, file_name(&path));
+            sources.push((name, path, "export"));
         }
     }
 
-    let timestamp = unix_timestamp_secs()?;
-    let mut diarization_applied = false;
-    let transcript = if speaker_mode_used == "source_aware_2speaker" {
-        emit_progress(&app, 5, "Preparing separate speaker channels...");
-
-        let microphone_output = transcribe_with_temp_output(
-            &app,
-            &model_path,
-            &options.microphone_audio_data,
-            &options.language,
-            "none",
-            WhisperFileFormat::Srt,
-            &format!("recording-{}-coach-mic", timestamp),
-        )
-        .await?;
+    if include_audio {
+        let alignment = find_transcript_alignment(settings, transcript_path)?;
+        let audio_path = PathBuf::from(&alignment.audio_path);
+        let alignment_path = audio_alignment_path(&audio_path);
+        sources.push((
+            format!("audio/{}", file_name(&audio_path)),
+            audio_path,
+            "audio",
+        ));
+        sources.push((
+            format!("audio/{}", file_name(&alignment_path)),
+            alignment_path,
+            "alignment",
+        ));
+    }
 
-        emit_progress(&app, 50, "Transcribing client system audio...");
+    Ok(sources)
+}
 
-        let system_output = transcribe_with_temp_output(
-            &app,
-            &model_path,
-            &options.system_audio_data,
-            &options.language,
-            "none",
-            WhisperFileFormat::Srt,
-            &format!("recording-{}-client-system", timestamp),
-        )
-        .await?;
+// Copies `path` into the archive in chunks, hashing as it goes, so a long
+// recording is never held in memory.
+fn write_bundle_entry(
+    zip: &mut zip::ZipWriter<fs::File>,
+    name: &str,
+    path: &Path,
+    kind: &'static str,
+) -> Result<BundleFile, String> {
+    // Audio barely compresses, so it is stored as is.
+    let method = match kind {
+        "audio" => zip::CompressionMethod::Stored,
+        _ => zip::CompressionMethod::Deflated,
+    };
+    zip.start_file(
+        name,
+        zip::write::SimpleFileOptions::default().compression_method(method),
+    )
+    .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
 
-        if !microphone_output.used_sidecar || !system_output.used_sidecar {
-            warnings.push(
-                "Using local whisper binary fallback in debug mode. Release builds use sidecar."
-                    .to_string(),
-            );
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to read bundle file ({}): {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; BUNDLE_COPY_CHUNK_BYTES];
+    let mut bytes = 0u64;
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read bundle file ({}): {}", path.display(), e))?;
+        if read == 0 {
+            break;
         }
+        hasher.update(&buffer[..read]);
+        zip.write_all(&buffer[..read])
+            .map_err(|e| format!("Failed to write {} to bundle: {}", name, e))?;
+        bytes += read as u64;
+    }
 
-        let microphone_segments = parse_srt_segments(&microphone_output.content, "Coach");
-        let mut system_segments =
-            coalesce_channel_segments(parse_srt_segments(&system_output.content, "Client"), 750);
-        shift_segments(&mut system_segments, options.system_audio_offset_ms);
+    Ok(BundleFile {
+        name: name.to_string(),
+        kind,
+        bytes,
+        sha256: format!("{:x}", hasher.finalize()),
+    })
+}
 
-        if microphone_segments.is_empty() {
-            warnings.push(
-                "Microphone channel did not produce timestamped transcript segments.".to_string(),
-            );
-        }
-        if system_segments.is_empty() {
-            warnings.push(
-                "System audio channel did not produce timestamped transcript segments.".to_string(),
-            );
-        }
+fn finish_bundle(mut zip: zip::ZipWriter<fs::File>, manifest_json: &[u8]) -> Result<(), String> {
+    zip.start_file(
+        BUNDLE_MANIFEST_FILE,
+        zip::write::SimpleFileOptions::default(),
+    )
+    .map_err(|e| e.to_string())?;
+    zip.write_all(manifest_json).map_err(|e| e.to_string())?;
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
 
-        emit_progress(&app, 85, "Merging separate speaker transcripts...");
-        diarization_applied = true;
-        merge_source_segments(microphone_segments, system_segments)
+// Packages a session into one ZIP with a `manifest.json` listing every file
+// and its checksum. `dest` is the archive path, or a folder to save
+// `<transcript>-bundle.zip` in.
+#[tauri::command]
+async fn export_bundle(
+    app: AppHandle,
+    path: String,
+    dest: String,
+    include_audio: Option<bool>,
+    overwrite: Option<bool>,
+) -> Result<String, String> {
+    let transcript_path = PathBuf::from(&path);
+    require_unlocked(&app, Some(&transcript_path))?;
+    let settings = load_settings(&app)?;
+    let markdown = fs::read_to_string(&transcript_path)
+        .map_err(|e| read_failure("transcript file", &transcript_path, e))?;
+    let (frontmatter, _) = split_frontmatter(&markdown);
+    let sources = bundle_sources(&settings, &transcript_path, include_audio.unwrap_or(false))?;
+
+    let dest = PathBuf::from(dest.trim());
+    let bundle_path = if dest.is_dir() {
+        let stem = transcript_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "transcript".to_string());
+        first_free_path(&dest.join(format!("{}-bundle.zip", stem)))
     } else {
-        emit_progress(
-            &app,
-            5,
-            if speaker_mode_used == "tdrz_2speaker" {
-                "Preparing diarization fallback..."
-            } else {
-                "Preparing recording..."
-            },
-        );
-
-        let transcript_output = transcribe_with_temp_output(
-            &app,
-            &model_path,
-            primary_audio,
-            &options.language,
-            &speaker_mode_used,
-            WhisperFileFormat::Txt,
-            &format!("recording-{}", timestamp),
+        dest
+    };
+    if bundle_path.exists() && !overwrite.unwrap_or(false) {
+        return Err(format!(
+            "Bundle file already exists ({}). Pick another name or allow overwriting.",
+            bundle_path.display()
+        ));
+    }
+    let file = fs::File::create(&bundle_path).map_err(|e| {
+        format!(
+            "Failed to create bundle file ({}): {}",
+            bundle_path.display(),
+            e
         )
-        .await?;
-
-        if !transcript_output.used_sidecar {
-            warnings.push(
-                "Using local whisper binary fallback in debug mode. Release builds use sidecar."
-                    .to_string(),
-            );
-        }
-
-        emit_progress(&app, 85, "Reading transcript...");
+    })?;
 
-        if speaker_mode_used == "tdrz_2speaker" {
-            let (formatted, applied) = apply_tdrz_speaker_labels(&transcript_output.content);
-            if !applied {
-                warnings.push(
-                    "Whisper diarization fallback did not produce speaker boundaries because whisper.cpp returned no [SPEAKER_TURN] markers. Output is unsegmented. This is common when voices are too similar/overlapped or only one voice is dominant; try clearer turn-taking, louder remote audio, or use source-aware mode with separate system + microphone capture."
-                        .to_string(),
-                );
+    let mut zip = zip::ZipWriter::new(file);
+    let mut files = Vec::new();
+    for (name, source_path, kind) in &sources {
+        match write_bundle_entry(&mut zip, name, source_path, *kind) {
+            Ok(entry) => files.push(entry),
+            Err(error) => {
+                drop(zip);
+                let _ = fs::remove_file(&bundle_path);
+                return Err(error);
             }
-            diarization_applied = applied;
-            formatted
-        } else {
-            normalize_transcript(&transcript_output.content)
         }
+    }
+    let (now, _) = now_for_settings(&settings);
+    let manifest = BundleManifest {
+        source_app: "Echo Scribe",
+        exported_at: format_iso8601(now),
+        title: frontmatter_value(frontmatter, "title"),
+        client: frontmatter_value(frontmatter, "client"),
+        created_at: frontmatter_value(frontmatter, "created_at"),
+        transcript: sources[0].0.clone(),
+        files,
     };
-
-    if transcript.is_empty() {
-        return Err("Whisper returned an empty transcript.".to_string());
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to encode bundle manifest: {}", e))?;
+    if let Err(error) = finish_bundle(zip, &manifest_json) {
+        let _ = fs::remove_file(&bundle_path);
+        return Err(format!(
+            "Failed to write bundle file ({}): {}",
+            bundle_path.display(),
+            error
+        ));
     }
 
-    let output_mode = validate_output_mode(&options.output_mode);
-    let mut save_destination: Option<PathBuf> = None;
-    let now = now_local_or_utc();
-    let date = format_date(now);
-    let time_compact = format_time_compact(now);
-    let created_at = format_iso8601(now);
-    let coachnotes_metadata = output_mode == "coachnotes" && settings.coachnotes_enabled;
-    let frontmatter_client = if coachnotes_metadata {
-        sanitize_non_empty(options.client.clone())
-            .or_else(|| sanitize_non_empty(settings.coachnotes_client.clone()))
-    } else {
-        None
-    };
+    record_audit(
+        &app,
+        AuditAction::Export,
+        &transcript_path,
+        Some(&bundle_path),
+    )?;
+    Ok(bundle_path.to_string_lossy().to_string())
+}
 
-    if options.save_markdown {
-        if output_mode == "coachnotes" && settings.coachnotes_enabled {
-            let root = sanitize_non_empty(settings.coachnotes_root_dir.clone());
-            let selected_client = sanitize_non_empty(options.client.clone())
-                .or_else(|| sanitize_non_empty(settings.coachnotes_client.clone()));
+#[tauri::command]
+async fn get_waveform(
+    app: AppHandle,
+    source: WaveformSource,
+    buckets: u32,
+) -> Result<Waveform, String> {
+    if buckets == 0 || buckets > MAX_WAVEFORM_BUCKETS {
+        return Err(format!(
+            "Waveform buckets must be between 1 and {}",
+            MAX_WAVEFORM_BUCKETS
+        ));
+    }
 
-            match (root, selected_client) {
-                (Some(root), Some(client)) => {
-                    let client_dir = PathBuf::from(root).join(&client);
-                    fs::create_dir_all(&client_dir).map_err(|e| {
-                        format!(
-                            "Failed to create CoachNotes client directory ({}): {}",
-                            client_dir.display(),
-                            e
-                        )
-                    })?;
-                    let path = client_dir.join(format!("{}-transcript-{}.md", date, time_compact));
-                    save_destination = Some(path);
-                }
-                _ => {
-                    warnings.push(
-                        "CoachNotes mode is enabled but root/client is incomplete. Saving to standard transcript folder instead."
-                            .to_string(),
-                    );
-                }
-            }
+    let wav_data = match source {
+        WaveformSource::Path(path) => {
+            let path = PathBuf::from(path);
+            require_unlocked(&app, Some(&path))?;
+            fs::read(&path)
+                .map_err(|e| format!("Failed to read audio file ({}): {}", path.display(), e))?
         }
+        WaveformSource::Bytes(bytes) => bytes,
+    };
 
-        if save_destination.is_none() {
-            let transcript_dir = resolve_transcript_dir(&settings);
-            fs::create_dir_all(&transcript_dir).map_err(|e| {
-                format!(
-                    "Failed to create transcript directory ({}): {}",
-                    transcript_dir.display(),
-                    e
-                )
-            })?;
+    Ok(Waveform {
+        duration_ms: estimate_duration_ms(&wav_data),
+        peaks: waveform_peaks(&wav_pcm_samples(&wav_data), buckets as usize),
+    })
+}
 
-            save_destination = Some(transcript_dir.join(format!("transcript-{}.md", timestamp)));
-        }
+// `path` is an archived recording or a transcript saved with one.
+#[tauri::command]
+async fn get_alignment(app: AppHandle, path: String) -> Result<AudioAlignment, String> {
+    let path = PathBuf::from(&path);
+    require_unlocked(&app, Some(&path))?;
+    if path.extension().is_some_and(|extension| extension == "md") {
+        return find_transcript_alignment(&load_settings(&app)?, &path);
     }
+    read_audio_alignment(&audio_alignment_path(&path))
+}
 
-    let duration_seconds = [
-        estimate_duration_seconds(primary_audio),
-        estimate_duration_seconds(&options.microphone_audio_data),
-        estimate_duration_seconds(&options.system_audio_data),
-    ]
-    .into_iter()
-    .max()
-    .unwrap_or(0);
-
-    let markdown = build_markdown_transcript(
-        &transcript,
-        frontmatter_client.as_deref(),
-        &options.model,
-        &options.language,
-        &speaker_mode_used,
-        &created_at,
-        &date,
-        duration_seconds,
-        coachnotes_metadata,
-        if speaker_mode_used == "source_aware_2speaker" {
-            Some(("Coach", "Client"))
-        } else if speaker_mode_used == "tdrz_2speaker" && diarization_applied {
-            Some(("Speaker A", "Speaker B"))
-        } else {
-            Some(("Coach", "Client"))
-        },
-    );
+// Longest slice `read_audio_range` returns in one call.
+const MAX_AUDIO_RANGE_SECONDS: f64 = 600.0;
+// Room for the RIFF header plus the LIST/fact chunks some recorders add.
+const WAV_HEADER_SCAN_BYTES: u64 = 64 * 1024;
 
-    let saved_path = if let Some(path) = save_destination {
-        fs::write(&path, markdown).map_err(|e| {
+// Returns `start_seconds..end_seconds` of an archived WAV as a standalone WAV
+// in the original encoding. Only the header and the requested frames are
+// read from disk, so the frontend can play a transcript segment from a blob
+// without loading the whole recording into the webview. `end_seconds`
+// defaults to the longest allowed range and is clamped to the recording.
+#[tauri::command]
+async fn read_audio_range(
+    app: AppHandle,
+    path: String,
+    start_seconds: f64,
+    end_seconds: Option<f64>,
+) -> Result<tauri::ipc::Response, String> {
+    let audio_path = PathBuf::from(&path);
+    require_unlocked(&app, Some(&audio_path))?;
+    let end_seconds = end_seconds
+        .unwrap_or(start_seconds + MAX_AUDIO_RANGE_SECONDS)
+        .min(start_seconds + MAX_AUDIO_RANGE_SECONDS);
+    if !start_seconds.is_finite() || !end_seconds.is_finite() || start_seconds < 0.0 {
+        return Err("Audio range must be a non-negative number of seconds.".to_string());
+    }
+    if end_seconds <= start_seconds {
+        return Err("Audio range end must be after its start.".to_string());
+    }
+
+    let mut file = fs::File::open(&audio_path).map_err(|e| {
+        format!(
+            "Failed to open audio file ({}): {}",
+            audio_path.display(),
+            e
+        )
+    })?;
+    let file_len = file
+        .metadata()
+        .map(|metadata| metadata.len())
+        .map_err(|e| {
             format!(
-                "Failed to write transcript file ({}): {}",
-                path.display(),
+                "Failed to read audio file ({}): {}",
+                audio_path.display(),
                 e
             )
         })?;
-
-        Some(path.to_string_lossy().to_string())
-    } else {
-        None
+    let mut header = Vec::new();
+    (&mut file)
+        .take(WAV_HEADER_SCAN_BYTES)
+        .read_to_end(&mut header)
+        .map_err(|e| {
+            format!(
+                "Failed to read audio file ({}): {}",
+                audio_path.display(),
+                e
+            )
+        })?;
+    let info = parse_wav_header(&header)?;
+
+    // The parsed length is clamped to the header bytes read; the declared
+    // chunk size, clamped to the file, is the real one.
+    let data_offset = info.data_offset as u64;
+    let data_len = read_u32_le(&header, info.data_offset - 4)
+        .map(u64::from)
+        .unwrap_or(0)
+        .min(file_len.saturating_sub(data_offset));
+    let frame_bytes = info.frame_bytes() as u64;
+    let last_frame = data_len / frame_bytes * frame_bytes;
+    let frame_offset = |seconds: f64| {
+        ((seconds * f64::from(info.sample_rate)) as u64)
+            .saturating_mul(frame_bytes)
+            .min(last_frame)
     };
+    let start = frame_offset(start_seconds);
+    let end = frame_offset(end_seconds);
 
-    let saved_audio_paths = if options.save_raw_audio {
-        save_raw_audio_copies(
-            &settings,
-            &format!("{}-transcript-{}", date, time_compact),
-            frontmatter_client.as_deref(),
-            primary_audio,
-            &options.microphone_audio_data,
-            &options.system_audio_data,
-        )?
-    } else {
-        Vec::new()
-    };
+    let mut range = encode_wav_header(info, (end - start) as u32);
+    file.seek(SeekFrom::Start(data_offset + start))
+        .map_err(|e| {
+            format!(
+                "Failed to read audio file ({}): {}",
+                audio_path.display(),
+                e
+            )
+        })?;
+    file.take(end - start)
+        .read_to_end(&mut range)
+        .map_err(|e| {
+            format!(
+                "Failed to read audio file ({}): {}",
+                audio_path.display(),
+                e
+            )
+        })?;
 
-    emit_progress(&app, 100, "Transcription complete!");
+    Ok(tauri::ipc::Response::new(range))
+}
 
-    Ok(TranscriptionResult {
-        transcript,
-        saved_path,
-        saved_audio_paths,
-        format: "md".to_string(),
-        diarization_applied,
-        speaker_mode_used,
-        warnings,
-    })
+#[tauri::command]
+async fn convert_audio(
+    app: AppHandle,
+    input: String,
+    output_spec: AudioOutputSpec,
+) -> Result<String, String> {
+    require_unlocked(&app, Some(Path::new(&input)))?;
+    let output = convert_audio_file(&app, Path::new(&input), &output_spec).await?;
+    Ok(output.to_string_lossy().to_string())
 }
 
 #[tauri::command]
-async fn show_in_folder(path: String) -> Result<(), String> {
+async fn show_in_folder(app: AppHandle, path: String) -> Result<(), String> {
+    require_unlocked(&app, Some(Path::new(&path)))?;
     #[cfg(target_os = "macos")]
     {
         StdCommand::new("open")
@@ -2165,23 +19745,196 @@ async fn show_in_folder(path: String) -> Result<(), String> {
 pub fn run() {
     tauri::Builder::default()
         .manage(SystemAudioCaptureState::default())
+        .manage(EngineState::default())
+        .manage(AudioUploadState::default())
+        .manage(TranscriptionQueue::default())
+        .manage(RollingTranscriptionState::default())
+        .manage(SettingsState::default())
+        .manage(AppDataLocationState::default())
+        .manage(AppLockState::default())
+        .manage(EventBridgeState::default())
+        .manage(UrlCommandState::default())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .setup(|app| {
+            let handle = app.handle().clone();
+            load_app_data_location(&handle);
+            // Failures leave entries in the legacy directory, where
+            // `storage_path` still finds them.
+            if let Ok(copies) = migrate_storage_layout(&handle) {
+                tauri::async_runtime::spawn(copy_legacy_storage(handle.clone(), copies));
+            }
+            tauri::async_runtime::spawn(watch_coachnotes_root(handle.clone()));
+            tauri::async_runtime::spawn(watch_recorder_volumes(handle.clone()));
+            tauri::async_runtime::spawn(watch_settings_file(handle.clone()));
+            forward_bridged_events(&handle);
+            let bridge_handle = handle.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Ok(settings) = load_settings(&bridge_handle) {
+                    let _ = restart_event_bridge(&bridge_handle, &settings).await;
+                }
+            });
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(Duration::from_secs(3)).await;
+                let prewarm_on_launch = load_settings(&handle)
+                    .map(|settings| settings.prewarm_on_launch)
+                    .unwrap_or(false);
+                if prewarm_on_launch {
+                    let _ = prewarm_engine_with_status(&handle).await;
+                }
+            });
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) = event {
+                handle_opened_files(window.app_handle(), paths.clone());
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             get_setup_state,
+            get_acceleration_info,
             set_selected_model,
             set_transcript_directory,
+            create_workspace,
+            switch_workspace,
+            delete_workspace,
+            migrate_transcripts,
+            rename_transcripts,
+            undo_transcript_rename,
             set_diarization_mode,
+            set_prewarm_on_launch,
+            set_fallback_to_smaller_model,
+            set_auto_download_models,
+            set_language_model,
+            set_notify_on_completion,
+            set_verify_models_in_background,
+            set_redownload_corrupted_models,
+            set_offline_mode,
+            set_verbatim,
+            set_download_limit,
+            set_huggingface_token,
+            list_zoom_recordings,
+            import_zoom_recordings,
+            set_zoom_settings,
+            set_client_detection,
+            save_recording_preset,
+            delete_recording_preset,
+            set_active_recording_preset,
+            detect_client,
+            list_recorder_files,
+            import_recorder_files,
+            set_recorder_settings,
+            set_share_templates,
+            set_event_bridge,
+            reveal_event_bridge_url,
+            set_webhooks,
+            share_to,
+            set_drive_settings,
+            connect_google_drive,
+            disconnect_google_drive,
+            export_to_drive,
+            verify_models,
+            set_completion_sound,
+            set_battery_saver,
+            set_pause_queue_on_battery,
+            set_deferred_window,
+            set_raw_output_preferences,
+            set_update_channel,
+            check_for_updates,
+            install_update,
+            get_supported_languages,
+            set_extra_whisper_args,
+            set_spell_correction,
+            set_text_normalization,
+            set_client_prompt,
+            set_client_template,
+            set_client_language,
+            set_duplicate_check,
+            move_app_data,
+            prepare_session_note,
+            set_locale,
+            set_date_preferences,
+            set_custom_frontmatter,
+            set_title_preferences,
+            set_auto_split_minutes,
+            set_rolling_transcription,
+            get_queue,
+            reorder_queue,
+            start_rolling_transcription,
+            transcribe_rolling_chunk,
+            discard_rolling_transcription,
+            set_timestamp_interval,
+            set_markdown_escape,
+            set_input_limits,
+            start_audio_upload,
+            append_audio_chunk,
+            discard_audio_upload,
+            get_job_history,
+            get_audit_log,
+            retry_job,
+            resume_job,
+            list_resumable_jobs,
+            discard_failed_job,
+            prewarm_engine,
             get_coachnotes_clients,
+            validate_coachnotes_root,
+            check_sync_health,
             set_coachnotes_settings,
             download_model,
+            run_benchmark,
+            compare_models,
+            evaluate_transcript,
+            set_secret,
+            get_secret,
+            delete_secret,
+            set_app_lock,
+            unlock_app,
+            lock_app,
             start_system_audio_recording,
             stop_system_audio_recording,
             transcribe_recording,
+            plan_transcription,
+            search_transcripts,
+            find_related,
+            generate_digest,
+            get_statistics,
+            export_csv,
+            export_jsonl,
+            open_history_window,
+            open_captions_window,
+            read_transcript_preview,
+            read_transcript_page,
+            read_frontmatter,
+            write_frontmatter,
+            verify_transcript,
+            install_finder_quick_action,
+            remove_finder_quick_action,
+            get_last_transcript,
+            confirm_url_transcribe,
+            export_speaker_files,
+            export_anonymized_transcript,
+            export_segments_csv,
+            export_textgrid,
+            export_elan,
+            export_bundle,
+            get_waveform,
+            read_audio_range,
+            get_alignment,
+            convert_audio,
             show_in_folder
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Opened { urls } = event {
+                handle_opened_urls(app, urls);
+            }
+            #[cfg(not(target_os = "macos"))]
+            let _ = (app, event);
+        });
 }
@@ -0,0 +1,15 @@
+const { listen } = window.__TAURI__.event;
+
+const stableText = document.getElementById('captions-stable');
+const tentativeText = document.getElementById('captions-tentative');
+const captionsStatus = document.getElementById('captions-status');
+
+listen('captions', (event) => {
+  const { stable, tentative, done } = event.payload;
+  stableText.textContent = stable;
+  tentativeText.textContent = tentative ? ` ${tentative}` : '';
+  captionsStatus.hidden = Boolean(stable || tentative) && !done;
+  if (done) {
+    captionsStatus.textContent = 'Transcription finished.';
+  }
+});
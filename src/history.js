@@ -0,0 +1,105 @@
+const { invoke } = window.__TAURI__.core;
+
+const searchInput = document.getElementById('history-search');
+const historyStatus = document.getElementById('history-status');
+const historyList = document.getElementById('history-list');
+const previewCard = document.getElementById('history-preview');
+const previewTitle = document.getElementById('preview-title');
+const previewMeta = document.getElementById('preview-meta');
+const previewSummary = document.getElementById('preview-summary');
+const previewExcerpt = document.getElementById('preview-excerpt');
+const previewRevealBtn = document.getElementById('preview-reveal-btn');
+const previewMoreBtn = document.getElementById('preview-more-btn');
+
+const PREVIEW_CHARS = 1200;
+let previewPath = null;
+// Byte offset of the next page to load; `null` while only the excerpt shows.
+let nextPageOffset = null;
+let searchTimer = null;
+
+function formatMinutes(seconds) {
+  return `${Math.round(seconds / 60)} min`;
+}
+
+async function showPreview(path) {
+  try {
+    const preview = await invoke('read_transcript_preview', { path, maxChars: PREVIEW_CHARS });
+    previewPath = preview.path;
+    previewTitle.textContent = preview.title;
+    previewMeta.textContent = [
+      preview.date,
+      preview.client,
+      formatMinutes(preview.duration_seconds),
+      `${preview.word_count} words`,
+      preview.model,
+    ]
+      .filter(Boolean)
+      .join(' · ');
+    previewSummary.textContent = preview.summary || '';
+    previewExcerpt.textContent = preview.excerpt;
+    nextPageOffset = null;
+    previewMoreBtn.hidden = !preview.truncated;
+    previewCard.hidden = false;
+  } catch (error) {
+    historyStatus.textContent = `Failed to load preview: ${String(error)}`;
+  }
+}
+
+async function loadHistory() {
+  const text = searchInput.value.trim();
+  try {
+    const result = await invoke('search_transcripts', {
+      query: { text: text || null, sort: 'date_desc' },
+    });
+    historyList.replaceChildren(
+      ...result.results.map((listing) => {
+        const item = document.createElement('li');
+        const button = document.createElement('button');
+        button.type = 'button';
+        button.className = 'history-item';
+        button.textContent = `${listing.date} · ${listing.client || 'No client'} · ${listing.title}`;
+        button.title = listing.snippet || listing.path;
+        button.addEventListener('click', () => showPreview(listing.path));
+        item.append(button);
+        return item;
+      }),
+    );
+    historyStatus.textContent = `${result.total} transcripts`;
+  } catch (error) {
+    historyStatus.textContent = `Failed to load transcripts: ${String(error)}`;
+  }
+}
+
+searchInput.addEventListener('input', () => {
+  clearTimeout(searchTimer);
+  searchTimer = setTimeout(loadHistory, 250);
+});
+
+// Pages replace the excerpt and are then appended one at a time, so long
+// transcripts are never sent over in one piece.
+previewMoreBtn.addEventListener('click', async () => {
+  if (!previewPath) return;
+  try {
+    const page = await invoke('read_transcript_page', {
+      path: previewPath,
+      offset: nextPageOffset ?? 0,
+    });
+    if (nextPageOffset === null) {
+      previewExcerpt.textContent = page.text;
+    } else {
+      previewExcerpt.textContent += page.text;
+    }
+    nextPageOffset = page.next_offset;
+    previewMoreBtn.hidden = page.next_offset === null;
+  } catch (error) {
+    historyStatus.textContent = `Failed to load transcript: ${String(error)}`;
+  }
+});
+
+previewRevealBtn.addEventListener('click', async () => {
+  if (previewPath) {
+    await invoke('show_in_folder', { path: previewPath });
+  }
+});
+
+loadHistory();
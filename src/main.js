@@ -1,13 +1,82 @@
 const { invoke } = window.__TAURI__.core;
 const { listen } = window.__TAURI__.event;
-const { open } = window.__TAURI__.dialog;
+const { ask, open, save } = window.__TAURI__.dialog;
 const appWindow = window.__TAURI__.window?.getCurrentWindow?.() || null;
 
 const modelSelect = document.getElementById('model-select');
 const languageSelect = document.getElementById('language-select');
+const localeSelect = document.getElementById('locale-select');
+const dateFormatSelect = document.getElementById('date-format-select');
+const timezoneInput = document.getElementById('timezone-input');
+const duplicateCheckSelect = document.getElementById('duplicate-check-select');
+const customFrontmatterInput = document.getElementById('custom-frontmatter-input');
+const autoTitleCheckbox = document.getElementById('auto-title');
+const titleInFilenameCheckbox = document.getElementById('title-in-filename');
+const renameTranscriptsBtn = document.getElementById('rename-transcripts-btn');
+const undoRenameBtn = document.getElementById('undo-rename-btn');
+let lastRenameManifest = null;
+const autoSplitSelect = document.getElementById('auto-split-select');
+const rollingTranscriptionCheckbox = document.getElementById('rolling-transcription');
+const urgentPriorityCheckbox = document.getElementById('urgent-priority');
+const queueSection = document.getElementById('queue-section');
+const queueList = document.getElementById('queue-list');
+const timestampIntervalSelect = document.getElementById('timestamp-interval-select');
+const markdownEscapeSelect = document.getElementById('markdown-escape-select');
+const maxRecordingSelect = document.getElementById('max-recording-select');
+const maxAudioSizeSelect = document.getElementById('max-audio-size-select');
 const diarizationModeSelect = document.getElementById('diarization-mode-select');
 const saveMarkdownCheckbox = document.getElementById('save-markdown');
 const saveRawAudioCheckbox = document.getElementById('save-raw-audio');
+const saveJsonSidecarCheckbox = document.getElementById('save-json-sidecar');
+const exportToDriveCheckbox = document.getElementById('export-to-drive');
+const keepRawWhisperOutputCheckbox = document.getElementById('keep-raw-whisper-output');
+const rawOutputDirInput = document.getElementById('raw-output-dir');
+const chooseRawOutputDirBtn = document.getElementById('choose-raw-output-dir-btn');
+const clearRawOutputDirBtn = document.getElementById('clear-raw-output-dir-btn');
+const prewarmOnLaunchCheckbox = document.getElementById('prewarm-on-launch');
+const verifyModelsInBackgroundCheckbox = document.getElementById('verify-models-in-background');
+const redownloadCorruptedModelsCheckbox = document.getElementById('redownload-corrupted-models');
+const offlineModeCheckbox = document.getElementById('offline-mode');
+const eventBridgeCheckbox = document.getElementById('event-bridge-enabled');
+const eventBridgePortInput = document.getElementById('event-bridge-port-input');
+const eventBridgeUrl = document.getElementById('event-bridge-url');
+const revealEventBridgeUrlButton = document.getElementById('reveal-event-bridge-url-btn');
+const webhooksInput = document.getElementById('webhooks-input');
+const driveClientIdInput = document.getElementById('drive-client-id-input');
+const driveClientSecretInput = document.getElementById('drive-client-secret-input');
+const driveFolderInput = document.getElementById('drive-folder-input');
+const driveAsGoogleDocCheckbox = document.getElementById('drive-as-google-doc');
+const connectDriveBtn = document.getElementById('connect-drive-btn');
+const disconnectDriveBtn = document.getElementById('disconnect-drive-btn');
+const driveStatus = document.getElementById('drive-status');
+const fallbackSmallerModelCheckbox = document.getElementById('fallback-smaller-model');
+const autoDownloadModelsCheckbox = document.getElementById('auto-download-models');
+const notifyOnCompletionCheckbox = document.getElementById('notify-on-completion');
+const completionSoundSelect = document.getElementById('completion-sound-select');
+const batterySaverCheckbox = document.getElementById('battery-saver');
+const pauseQueueOnBatteryCheckbox = document.getElementById('pause-queue-on-battery');
+const deferredWindowCheckbox = document.getElementById('deferred-window-enabled');
+const deferredWindowStartInput = document.getElementById('deferred-window-start');
+const deferredWindowEndInput = document.getElementById('deferred-window-end');
+const powerStatus = document.getElementById('power-status');
+const completionSoundThresholdSelect = document.getElementById('completion-sound-threshold-select');
+const extraWhisperArgsInput = document.getElementById('extra-whisper-args-input');
+const ignoredWhisperArgs = document.getElementById('ignored-whisper-args');
+const spellCorrectionCheckbox = document.getElementById('spell-correction');
+const spellDictionaryInput = document.getElementById('spell-dictionary-input');
+const normalizeNumbersCheckbox = document.getElementById('normalize-numbers');
+const normalizeCurrencyCheckbox = document.getElementById('normalize-currency');
+const normalizeDatesCheckbox = document.getElementById('normalize-dates');
+const denoiseAudioCheckbox = document.getElementById('denoise-audio');
+const normalizeAudioCheckbox = document.getElementById('normalize-audio');
+const generateChaptersCheckbox = document.getElementById('generate-chapters');
+const analyzeSentimentCheckbox = document.getElementById('analyze-sentiment');
+const summarizeCheckbox = document.getElementById('summarize-session');
+const showNotesCheckbox = document.getElementById('podcast-show-notes');
+const verbatimCheckbox = document.getElementById('verbatim-mode');
+const sessionTitleInput = document.getElementById('session-title-input');
+const sessionNotesInput = document.getElementById('session-notes-input');
+const includeNotesSectionCheckbox = document.getElementById('include-notes-section');
 const setupDetails = document.getElementById('setup-details');
 const captureOptionButtons = Array.from(document.querySelectorAll('[data-capture-option]'));
 
@@ -16,18 +85,85 @@ const setupMessage = document.getElementById('setup-message');
 const sidecarStatus = document.getElementById('sidecar-status');
 const modelStatusText = document.getElementById('model-status-text');
 const downloadModelBtn = document.getElementById('download-model-btn');
+const verifyModelsBtn = document.getElementById('verify-models-btn');
+const downloadLimitSelect = document.getElementById('download-limit-select');
+const huggingfaceTokenInput = document.getElementById('huggingface-token-input');
+const saveHuggingfaceTokenBtn = document.getElementById('save-huggingface-token-btn');
+const removeHuggingfaceTokenBtn = document.getElementById('remove-huggingface-token-btn');
+const huggingfaceTokenStatus = document.getElementById('huggingface-token-status');
 const modelProgressWrap = document.getElementById('model-progress-wrap');
 const modelProgressFill = document.getElementById('model-progress-fill');
 const modelProgressText = document.getElementById('model-progress-text');
 const transcriptDirInput = document.getElementById('transcript-dir');
+const workspaceSelect = document.getElementById('workspace-select');
+const workspaceNameInput = document.getElementById('workspace-name-input');
+const newWorkspaceBtn = document.getElementById('new-workspace-btn');
+const deleteWorkspaceBtn = document.getElementById('delete-workspace-btn');
 const chooseDirBtn = document.getElementById('choose-dir-btn');
+const generateDigestBtn = document.getElementById('generate-digest-btn');
+const exportSessionsCsvBtn = document.getElementById('export-sessions-csv-btn');
+const appDataDirInput = document.getElementById('app-data-dir');
+const moveAppDataBtn = document.getElementById('move-app-data-btn');
+const exportUsageCsvBtn = document.getElementById('export-usage-csv-btn');
+const exportTranscriptsJsonlBtn = document.getElementById('export-transcripts-jsonl-btn');
+const exportSegmentsJsonlBtn = document.getElementById('export-segments-jsonl-btn');
+const openHistoryBtn = document.getElementById('open-history-btn');
+const openCaptionsBtn = document.getElementById('open-captions-btn');
+const updateChannelSelect = document.getElementById('update-channel-select');
+const checkUpdatesBtn = document.getElementById('check-updates-btn');
+const installUpdateBtn = document.getElementById('install-update-btn');
+const updateStatus = document.getElementById('update-status');
+const installQuickActionBtn = document.getElementById('install-quick-action-btn');
+const recorderPatternsInput = document.getElementById('recorder-patterns-input');
+const shareTitleInput = document.getElementById('share-title-input');
+const shareTagsInput = document.getElementById('share-tags-input');
+const recorderMoveOriginalsCheckbox = document.getElementById('recorder-move-originals');
+const importRecorderBtn = document.getElementById('import-recorder-btn');
+const removeQuickActionBtn = document.getElementById('remove-quick-action-btn');
 
 const coachnotesEnabledCheckbox = document.getElementById('coachnotes-enabled');
+const coachnotesAppendSameDayCheckbox = document.getElementById('coachnotes-append-same-day');
+const coachnotesInlineSessionLinksCheckbox = document.getElementById('coachnotes-inline-session-links');
+const zoomDirInput = document.getElementById('zoom-dir-input');
+const zoomClientMapInput = document.getElementById('zoom-client-map-input');
+const clientDetectionEnabledCheckbox = document.getElementById('client-detection-enabled');
+const clientDetectionCalendarCheckbox = document.getElementById('client-detection-calendar');
+const clientDetectionMapInput = document.getElementById('client-detection-map-input');
+const findZoomRecordingsBtn = document.getElementById('find-zoom-recordings-btn');
+const importZoomRecordingsBtn = document.getElementById('import-zoom-recordings-btn');
+const zoomRecordingsList = document.getElementById('zoom-recordings-list');
+const appLockEnabledCheckbox = document.getElementById('app-lock-enabled');
+const appLockTouchIdRow = document.getElementById('app-lock-touch-id-row');
+const appLockTouchIdCheckbox = document.getElementById('app-lock-touch-id');
+const appLockPasscodeInput = document.getElementById('app-lock-passcode-input');
+const unlockAppBtn = document.getElementById('unlock-app-btn');
+const unlockTouchIdBtn = document.getElementById('unlock-touch-id-btn');
+const lockAppBtn = document.getElementById('lock-app-btn');
+const appLockStatus = document.getElementById('app-lock-status');
 const coachnotesRootDirInput = document.getElementById('coachnotes-root-dir');
 const chooseCoachnotesDirBtn = document.getElementById('choose-coachnotes-dir-btn');
+const checkCoachnotesDirBtn = document.getElementById('check-coachnotes-dir-btn');
+const coachnotesHealth = document.getElementById('coachnotes-health');
+const checkSyncBtn = document.getElementById('check-sync-btn');
+const syncHealth = document.getElementById('sync-health');
 const coachnotesClientSelect = document.getElementById('coachnotes-client-select');
+const clientPromptInput = document.getElementById('client-prompt-input');
+const clientLanguageSelect = document.getElementById('client-language-select');
+const clientTranslateCheckbox = document.getElementById('client-translate');
+const languageModelSelect = document.getElementById('language-model-select');
+const clientTemplateInput = document.getElementById('client-template-input');
+const prepareSessionNoteBtn = document.getElementById('prepare-session-note-btn');
 const destinationPreview = document.getElementById('destination-preview');
 const captureModeSelect = document.getElementById('capture-mode-select');
+const recordingPresetSelect = document.getElementById('recording-preset-select');
+const presetNameInput = document.getElementById('preset-name-input');
+const presetDeviceSelect = document.getElementById('preset-device-select');
+const presetSampleRateSelect = document.getElementById('preset-sample-rate-select');
+const presetChannelsSelect = document.getElementById('preset-channels-select');
+const presetAutoStopInput = document.getElementById('preset-auto-stop-input');
+const presetSilenceInput = document.getElementById('preset-silence-input');
+const savePresetBtn = document.getElementById('save-preset-btn');
+const deletePresetBtn = document.getElementById('delete-preset-btn');
 const captureModeHelp = document.getElementById('capture-mode-help');
 const micMeterText = document.getElementById('mic-meter-text');
 const micLevelFill = document.getElementById('mic-level-fill');
@@ -55,6 +191,15 @@ const resultSection = document.getElementById('result-section');
 const warningsList = document.getElementById('warnings-list');
 const transcriptOutput = document.getElementById('transcript-output');
 const openFileBtn = document.getElementById('open-file-btn');
+const exportSpeakersBtn = document.getElementById('export-speakers-btn');
+const exportAnonymizedBtn = document.getElementById('export-anonymized-btn');
+const exportSegmentsBtn = document.getElementById('export-segments-btn');
+const exportTextGridBtn = document.getElementById('export-textgrid-btn');
+const exportElanBtn = document.getElementById('export-elan-btn');
+const exportBundleBtn = document.getElementById('export-bundle-btn');
+const exportDriveBtn = document.getElementById('export-drive-btn');
+const shareBearBtn = document.getElementById('share-bear-btn');
+const shareLogseqBtn = document.getElementById('share-logseq-btn');
 const titlebar = document.getElementById('app-titlebar');
 
 let setupState = null;
@@ -84,7 +229,16 @@ let savedAudioPaths = [];
 let hasTranscriptionResult = false;
 let recordingStartWallTimeMs = 0;
 let lastRecordingAt = null;
+let lastSoundAtMs = 0;
+let rollingSessionId = null;
+let rollingCoveredMs = 0;
+let rollingChunk = null;
+let rollingError = null;
 const METER_FLOOR = 0;
+// Microphone level below which a preset's silence auto-stop keeps counting.
+const AUTO_STOP_SILENCE_LEVEL = 0.02;
+// How much new audio a rolling transcription chunk waits for.
+const ROLLING_CHUNK_MS = 2 * 60 * 1000;
 
 function hasRecordedAudio() {
   return Boolean(
@@ -124,9 +278,85 @@ function startTimer() {
     const seconds = String(totalSeconds % 60).padStart(2, '0');
     timerEl.textContent = `${minutes}:${seconds}`;
     updateOverview();
+    checkAutoStop(elapsedMs);
+    maybeTranscribeRollingChunk();
   }, 200);
 }
 
+async function startRollingTranscription(mode) {
+  rollingSessionId = null;
+  rollingCoveredMs = 0;
+  rollingError = null;
+  if (!setupState?.rolling_transcription || mode !== 'microphone') return;
+  try {
+    rollingSessionId = await invoke('start_rolling_transcription', {
+      options: recorderTranscriptionOptions(),
+    });
+  } catch (error) {
+    rollingError = String(error);
+  }
+}
+
+// Sends the audio recorded since the last chunk. One chunk runs at a time; after
+// a failure no more are sent, and whatever is left is transcribed after stop.
+function maybeTranscribeRollingChunk() {
+  if (!rollingSessionId || rollingChunk || rollingError || !isRecording) return;
+  const coveredSamples = Math.round((rollingCoveredMs * sampleRate) / 1000);
+  if (((totalSamples - coveredSamples) * 1000) / sampleRate < ROLLING_CHUNK_MS) return;
+
+  const sessionId = rollingSessionId;
+  const offsetMs = rollingCoveredMs;
+  const pending = mergeChunks(audioChunks, totalSamples).subarray(coveredSamples);
+  rollingChunk = (async () => {
+    try {
+      const wav = encodeWav(downsampleBuffer(pending, sampleRate, 16000), 16000);
+      const uploadId = await uploadAudio(wav, false);
+      const progress = await invoke('transcribe_rolling_chunk', { sessionId, uploadId, offsetMs });
+      if (sessionId !== rollingSessionId) return;
+      rollingCoveredMs = progress.covered_ms;
+      if (isRecording) {
+        setStatus(
+          `Recording microphone... first ${Math.floor(rollingCoveredMs / 60000)} min already transcribed`,
+          'recording'
+        );
+      }
+    } catch (error) {
+      if (sessionId === rollingSessionId) rollingError = String(error);
+    } finally {
+      rollingChunk = null;
+    }
+  })();
+}
+
+function discardRollingTranscription() {
+  if (rollingSessionId) {
+    invoke('discard_rolling_transcription', { sessionId: rollingSessionId }).catch(() => {});
+  }
+  rollingSessionId = null;
+}
+
+function activeRecordingPreset() {
+  if (!setupState || !setupState.active_recording_preset) return null;
+  return (setupState.recording_presets || []).find(
+    (preset) => preset.name === setupState.active_recording_preset
+  ) || null;
+}
+
+// Ends the recording once the active preset's length or silence limit is reached.
+function checkAutoStop(elapsedMs) {
+  const preset = activeRecordingPreset();
+  if (!preset || !isRecording || isStoppingRecording) return;
+  if (preset.auto_stop_minutes && elapsedMs >= preset.auto_stop_minutes * 60000) {
+    void stopRecording(`Stopped after ${preset.auto_stop_minutes} min by the "${preset.name}" preset.`);
+  } else if (
+    preset.auto_stop_silence_seconds
+    && microphoneCaptureActive
+    && Date.now() - lastSoundAtMs >= preset.auto_stop_silence_seconds * 1000
+  ) {
+    void stopRecording(`Stopped after ${preset.auto_stop_silence_seconds} s of silence by the "${preset.name}" preset.`);
+  }
+}
+
 function stopTimer() {
   if (timerInterval) {
     clearInterval(timerInterval);
@@ -142,9 +372,18 @@ function formatBytes(bytes) {
   return `${value.toFixed(index === 0 ? 0 : 1)} ${units[index]}`;
 }
 
+// Mirrors the backend: with "auto" the model preferred for the session
+// language, then the one set for auto-detect, then base.
+function resolvedModelId() {
+  if (modelSelect.value !== 'auto') return modelSelect.value;
+  const preferred = (setupState && setupState.language_models) || {};
+  return preferred[languageSelect.value] || preferred.auto || 'base';
+}
+
 function selectedModelEntry() {
   if (!setupState) return null;
-  return setupState.models.find((entry) => entry.id === modelSelect.value) || null;
+  const modelId = resolvedModelId();
+  return setupState.models.find((entry) => entry.id === modelId) || null;
 }
 
 function selectedModelReady() {
@@ -152,6 +391,14 @@ function selectedModelReady() {
   return Boolean(entry && entry.downloaded);
 }
 
+// A missing model is fine when the backend will download it before transcribing.
+function selectedModelUsable() {
+  if (selectedModelReady()) return true;
+  return Boolean(
+    selectedModelEntry() && setupState.auto_download_models && !setupState.offline_mode
+  );
+}
+
 function coachnotesEnabled() {
   return Boolean(coachnotesEnabledCheckbox.checked);
 }
@@ -351,6 +598,23 @@ async function applyModelSelection(modelId) {
   renderSetupState();
 }
 
+// The speaker mode picker lists the engines the backend reports, so new
+// engines show up without frontend changes.
+function renderDiarizationOptions() {
+  const engines = setupState.diarization_capabilities?.engines || [];
+  if (engines.length === 0) return;
+  diarizationModeSelect.replaceChildren(
+    ...engines.map((engine) => {
+      const option = document.createElement('option');
+      option.value = engine.mode;
+      option.textContent = engine.ready || !engine.required_model
+        ? engine.label
+        : `${engine.label} (needs ${engine.required_model})`;
+      return option;
+    }),
+  );
+}
+
 function currentSpeakerMode() {
   return diarizationModeSelect.value || 'none';
 }
@@ -394,6 +658,82 @@ function getSelectedCoachnotesClient() {
   return value.length > 0 ? value : null;
 }
 
+function renderClientPrompt() {
+  if (document.activeElement === clientPromptInput) {
+    return;
+  }
+  const client = getSelectedCoachnotesClient();
+  const prompts = (setupState && setupState.client_prompts) || {};
+  clientPromptInput.value = (client && prompts[client]) || '';
+}
+
+// The client language picker offers the same languages as the recorder's.
+function renderClientLanguage() {
+  const client = getSelectedCoachnotesClient();
+  const setting = client ? (setupState?.client_languages || {})[client] : null;
+  const sameAsRecorder = document.createElement('option');
+  sameAsRecorder.value = '';
+  sameAsRecorder.textContent = 'Same as the recorder';
+  clientLanguageSelect.replaceChildren(
+    sameAsRecorder,
+    ...[...languageSelect.options].map((option) => {
+      const copy = document.createElement('option');
+      copy.value = option.value;
+      copy.textContent = option.textContent;
+      return copy;
+    }),
+  );
+  clientLanguageSelect.value = setting ? setting.language : '';
+  clientTranslateCheckbox.checked = Boolean(setting?.translate);
+  clientTranslateCheckbox.disabled = clientLanguageSelect.disabled || !setting;
+}
+
+async function saveClientLanguage() {
+  const client = getSelectedCoachnotesClient();
+  if (!client) return;
+  try {
+    setupState = await invoke('set_client_language', {
+      client,
+      language: clientLanguageSelect.value || null,
+      translate: clientTranslateCheckbox.checked,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save client language: ${String(error)}`, 'error');
+  }
+}
+
+clientLanguageSelect.addEventListener('change', saveClientLanguage);
+clientTranslateCheckbox.addEventListener('change', saveClientLanguage);
+
+function renderLanguageModel() {
+  const language = languageSelect.value;
+  const defaultOption = document.createElement('option');
+  defaultOption.value = '';
+  defaultOption.textContent = 'Default';
+  languageModelSelect.replaceChildren(
+    defaultOption,
+    ...setupState.models
+      .filter((entry) => language === 'en' || !entry.id.includes('.en'))
+      .map((entry) => {
+        const option = document.createElement('option');
+        option.value = entry.id;
+        option.textContent = entry.label;
+        return option;
+      }),
+  );
+  languageModelSelect.value = (setupState.language_models || {})[language] || '';
+}
+
+function renderClientTemplate() {
+  if (document.activeElement === clientTemplateInput) {
+    return;
+  }
+  const client = getSelectedCoachnotesClient();
+  const templates = (setupState && setupState.client_templates) || {};
+  clientTemplateInput.value = (client && templates[client]) || '';
+}
+
 function updateDestinationPreview() {
   if (!saveMarkdownCheckbox.checked && !saveRawAudioCheckbox.checked) {
     destinationPreview.textContent = 'File output is disabled for this run.';
@@ -482,7 +822,7 @@ function renderWarnings(warnings) {
 }
 
 function syncActionButtons() {
-  const modelReady = selectedModelReady();
+  const modelReady = selectedModelUsable();
   const setupReady = Boolean(setupState && setupState.ready);
   const canTranscribe = setupReady && modelReady;
   captureModeSelect.disabled =
@@ -526,14 +866,21 @@ function renderSetupState() {
   if (!setupState) return;
 
   transcriptDirInput.value = setupState.transcript_dir;
+  renderWorkspaces();
+  renderDriveStatus();
+  renderHuggingfaceToken();
   coachnotesEnabledCheckbox.checked = Boolean(setupState.coachnotes_enabled);
+  coachnotesAppendSameDayCheckbox.checked = Boolean(setupState.coachnotes_append_same_day);
+  coachnotesInlineSessionLinksCheckbox.checked = Boolean(setupState.coachnotes_inline_session_links);
   coachnotesRootDirInput.value = setupState.coachnotes_root_dir || '';
+  renderDiarizationOptions();
   diarizationModeSelect.value = setupState.diarization_mode || 'none';
 
   populateCoachnotesClients(
     setupState.coachnotes_clients || [],
     setupState.coachnotes_client || ''
   );
+  renderAppLock();
 
   const entry = selectedModelEntry();
   if (!entry) {
@@ -548,7 +895,9 @@ function renderSetupState() {
     return;
   }
 
-  modelStatusText.textContent = `${entry.label} | ~${entry.size_mb} MB`;
+  const recommendedSuffix =
+    setupState.recommended_model === entry.id ? ' | Recommended for this computer' : '';
+  modelStatusText.textContent = `${entry.label} | ~${entry.size_mb} MB, needs ~${entry.ram_mb} MB RAM${recommendedSuffix}`;
 
   if (setupState.sidecar_ready) {
     sidecarStatus.textContent = 'Bundled whisper sidecar detected.';
@@ -566,9 +915,11 @@ function renderSetupState() {
   } else if (!entry.downloaded) {
     setupPill.textContent = 'Setup required';
     setupPill.className = 'pill warning';
-    setupMessage.textContent = `Model '${entry.id}' is not downloaded yet.`;
+    setupMessage.textContent = setupState.offline_mode
+      ? `Model '${entry.id}' is not downloaded yet. Turn off offline mode to download it.`
+      : `Model '${entry.id}' is not downloaded yet.`;
     downloadModelBtn.textContent = 'Download Selected Model';
-    downloadModelBtn.disabled = modelDownloadInProgress;
+    downloadModelBtn.disabled = modelDownloadInProgress || Boolean(setupState.offline_mode);
   } else {
     setupPill.textContent = 'Runtime issue';
     setupPill.className = 'pill warning';
@@ -578,10 +929,22 @@ function renderSetupState() {
     downloadModelBtn.disabled = true;
   }
 
+  checkUpdatesBtn.disabled = Boolean(setupState.offline_mode);
+
   const coachEnabled = coachnotesEnabled();
   chooseDirBtn.disabled = modelDownloadInProgress || isTranscribing;
   chooseCoachnotesDirBtn.disabled = modelDownloadInProgress || isTranscribing || !coachEnabled;
+  checkCoachnotesDirBtn.disabled = !coachEnabled || !coachnotesRootDirInput.value;
   coachnotesClientSelect.disabled = modelDownloadInProgress || isTranscribing || !coachEnabled;
+  clientPromptInput.disabled = !coachEnabled || !getSelectedCoachnotesClient();
+  clientTemplateInput.disabled = clientPromptInput.disabled;
+  clientLanguageSelect.disabled = clientPromptInput.disabled;
+  prepareSessionNoteBtn.disabled = clientPromptInput.disabled;
+  renderClientPrompt();
+  renderClientTemplate();
+  renderClientLanguage();
+  renderLanguageModel();
+  renderRecordingPresets();
 
   if (setupDetails && (modelDownloadInProgress || !setupState.ready)) {
     setupDetails.open = true;
@@ -592,9 +955,111 @@ function renderSetupState() {
 }
 
 async function refreshSetupState() {
-  setupState = await invoke('get_setup_state');
+  await applySetupState(await invoke('get_setup_state'));
+}
+
+// Fills every settings control from `state`, on first load and whenever
+// `settings-changed` reports a change from any window.
+async function applySetupState(state) {
+  setupState = state;
   modelSelect.value = setupState.selected_model;
+  prewarmOnLaunchCheckbox.checked = Boolean(setupState.prewarm_on_launch);
+  verifyModelsInBackgroundCheckbox.checked = Boolean(setupState.verify_models_in_background);
+  redownloadCorruptedModelsCheckbox.checked = Boolean(setupState.redownload_corrupted_models);
+  offlineModeCheckbox.checked = Boolean(setupState.offline_mode);
+  eventBridgeCheckbox.checked = Boolean(setupState.event_bridge_enabled);
+  eventBridgePortInput.value = String(setupState.event_bridge_port || 8765);
+  eventBridgeUrl.textContent = '';
+  revealEventBridgeUrlButton.hidden = !setupState.event_bridge_enabled;
+  webhooksInput.value = setupState.webhooks?.length ? JSON.stringify(setupState.webhooks, null, 2) : '';
+  driveClientIdInput.value = setupState.drive_client_id || '';
+  driveFolderInput.value = setupState.drive_folder_name || '';
+  driveAsGoogleDocCheckbox.checked = Boolean(setupState.drive_as_google_doc);
+  verbatimCheckbox.checked = Boolean(setupState.verbatim);
+  fallbackSmallerModelCheckbox.checked = Boolean(setupState.fallback_to_smaller_model);
+  autoDownloadModelsCheckbox.checked = Boolean(setupState.auto_download_models);
+  notifyOnCompletionCheckbox.checked = Boolean(setupState.notify_on_completion);
+  completionSoundSelect.value = setupState.completion_sound || '';
+  batterySaverCheckbox.checked = Boolean(setupState.battery_saver);
+  keepRawWhisperOutputCheckbox.checked = Boolean(setupState.keep_raw_whisper_output);
+  rawOutputDirInput.value = setupState.raw_output_dir || '';
+  updateChannelSelect.value = setupState.update_channel || 'stable';
+  pauseQueueOnBatteryCheckbox.checked = Boolean(setupState.pause_queue_on_battery);
+  deferredWindowCheckbox.checked = Boolean(setupState.deferred_window);
+  if (setupState.deferred_window) {
+    deferredWindowStartInput.value = setupState.deferred_window.start;
+    deferredWindowEndInput.value = setupState.deferred_window.end;
+  }
+  powerStatus.textContent = formatPowerState(setupState.power);
+  completionSoundThresholdSelect.value = String(setupState.completion_sound_min_seconds ?? 60);
+  extraWhisperArgsInput.value = (setupState.extra_whisper_args || []).join(' ');
+  const ignoredArgs = setupState.ignored_whisper_args || [];
+  ignoredWhisperArgs.hidden = ignoredArgs.length === 0;
+  ignoredWhisperArgs.textContent = `Ignored from settings.json: ${ignoredArgs.join(', ')}`;
+  spellCorrectionCheckbox.checked = Boolean(setupState.spell_correction);
+  spellDictionaryInput.value = (setupState.spell_dictionary || []).join('\n');
+  normalizeNumbersCheckbox.checked = Boolean(setupState.text_normalization?.numbers);
+  normalizeCurrencyCheckbox.checked = Boolean(setupState.text_normalization?.currency);
+  normalizeDatesCheckbox.checked = Boolean(setupState.text_normalization?.dates);
+  localeSelect.value = setupState.locale || 'en';
+  dateFormatSelect.value = setupState.date_format || 'ymd';
+  timezoneInput.value = setupState.timezone || '';
+  appDataDirInput.value = setupState.app_data_dir || '';
+  duplicateCheckSelect.value = setupState.duplicate_check || 'warn';
+  autoTitleCheckbox.checked = Boolean(setupState.auto_title);
+  titleInFilenameCheckbox.checked = Boolean(setupState.title_in_filename);
+  autoSplitSelect.value = setupState.auto_split_minutes ? String(setupState.auto_split_minutes) : '';
+  rollingTranscriptionCheckbox.checked = Boolean(setupState.rolling_transcription);
+  timestampIntervalSelect.value = setupState.timestamp_interval_seconds
+    ? String(setupState.timestamp_interval_seconds)
+    : '';
+  markdownEscapeSelect.value = setupState.markdown_escape || 'standard';
+  maxRecordingSelect.value = String(setupState.max_recording_minutes ?? 180);
+  maxAudioSizeSelect.value = String(setupState.max_audio_mb ?? 1024);
+  downloadLimitSelect.value = String(setupState.download_limit_kbps ?? 0);
+  customFrontmatterInput.value = Object.entries(setupState.custom_frontmatter || {})
+    .map(([key, value]) => `${key}: ${value}`)
+    .join('\n');
+  recorderPatternsInput.value = (setupState.recorder_volume_patterns || []).join(', ');
+  shareTitleInput.value = setupState.share_title_template || '';
+  shareTagsInput.value = (setupState.share_tags || []).join(', ');
+  recorderMoveOriginalsCheckbox.checked = Boolean(setupState.recorder_move_originals);
+  zoomDirInput.value = setupState.zoom_recordings_dir || '';
+  zoomClientMapInput.value = Object.entries(setupState.zoom_client_map || {})
+    .map(([topic, client]) => `${topic}: ${client}`)
+    .join('\n');
+  clientDetectionEnabledCheckbox.checked = Boolean(setupState.client_detection_enabled);
+  clientDetectionCalendarCheckbox.checked = Boolean(setupState.client_detection_calendar);
+  clientDetectionMapInput.value = Object.entries(setupState.client_detection_map || {})
+    .map(([title, client]) => `${title}: ${client}`)
+    .join('\n');
   renderSetupState();
+  await renderLanguageOptions(setupState.selected_model);
+  await renderInputDevices();
+}
+
+// Rebuilds the language picker from the backend list. Languages the model
+// cannot transcribe (everything but English for `.en` models) are disabled.
+async function renderLanguageOptions(model) {
+  let languages;
+  try {
+    languages = await invoke('get_supported_languages', { model });
+  } catch {
+    return;
+  }
+  const current = languageSelect.value;
+  languageSelect.replaceChildren(
+    ...languages.map((language) => {
+      const option = document.createElement('option');
+      option.value = language.code;
+      option.textContent = language.name;
+      option.disabled = !language.compatible;
+      return option;
+    }),
+  );
+  const keep = languages.find((language) => language.code === current && language.compatible);
+  languageSelect.value = keep ? current : 'en';
+  renderClientLanguage();
 }
 
 async function saveDiarizationMode(mode) {
@@ -607,6 +1072,8 @@ async function saveCoachnotesSettings() {
     enabled: coachnotesEnabled(),
     root_dir: coachnotesRootDirInput.value || null,
     client: getSelectedCoachnotesClient(),
+    append_same_day: coachnotesAppendSameDayCheckbox.checked,
+    inline_session_links: coachnotesInlineSessionLinksCheckbox.checked,
   };
 
   isSavingCoachnotesSettings = true;
@@ -989,21 +1456,58 @@ function shouldUseSourceAwareMicProcessing() {
 
 async function requestMicrophoneStream() {
   const sourceAwareProcessing = shouldUseSourceAwareMicProcessing();
-  return navigator.mediaDevices.getUserMedia({
-    audio: {
-      echoCancellation: sourceAwareProcessing,
-      noiseSuppression: sourceAwareProcessing,
-      autoGainControl: false,
-      channelCount: 1,
-    },
+  const preset = activeRecordingPreset();
+  const audio = {
+    echoCancellation: sourceAwareProcessing,
+    noiseSuppression: sourceAwareProcessing,
+    autoGainControl: false,
+    channelCount: preset ? preset.channels : 1,
+  };
+  if (preset) {
+    audio.sampleRate = preset.sample_rate;
+    if (preset.input_device_id) {
+      audio.deviceId = { exact: preset.input_device_id };
+    }
+  }
+  return navigator.mediaDevices.getUserMedia({ audio });
+}
+
+// Offers the client guessed from the current calendar event or meeting
+// window. Detection problems never hold up the recording.
+async function confirmDetectedClient() {
+  if (!coachnotesEnabled() || !setupState.client_detection_enabled) return;
+  let guess;
+  try {
+    guess = await invoke('detect_client');
+  } catch {
+    return;
+  }
+  if (!guess || guess.client === getSelectedCoachnotesClient()) return;
+
+  const source = guess.source === 'calendar' ? 'calendar event' : 'window';
+  const confirmed = await ask(`File this session under ${guess.client}? Found in the ${source} "${guess.context}".`, {
+    title: 'Client detected',
+    kind: 'info',
+    okLabel: `Use ${guess.client}`,
+    cancelLabel: 'Keep current client',
   });
+  if (!confirmed) return;
+  coachnotesClientSelect.value = guess.client;
+  try {
+    await saveCoachnotesSettings();
+  } catch (error) {
+    setStatus(`Failed to set CoachNotes client: ${String(error)}`, 'error');
+  }
+  renderClientPrompt();
+  renderClientTemplate();
 }
 
 async function startRecording() {
-  if (!selectedModelReady()) {
+  if (!selectedModelUsable()) {
     setStatus('Download the selected model first.', 'error');
     return;
   }
+  await confirmDetectedClient();
 
   const mode = selectedCaptureMode();
   const captureSystemAudio = captureModeNeedsSystemAudio(mode);
@@ -1011,7 +1515,9 @@ async function startRecording() {
 
   try {
     if (captureSystemAudio) {
-      await invoke('start_system_audio_recording');
+      await invoke('start_system_audio_recording', {
+        preset: setupState.active_recording_preset || null,
+      });
       systemCaptureActive = true;
     } else {
       systemCaptureActive = false;
@@ -1028,7 +1534,8 @@ async function startRecording() {
     activeCaptureMode = mode;
 
     if (captureMic) {
-      audioContext = new AudioContext();
+      const preset = activeRecordingPreset();
+      audioContext = new AudioContext(preset ? { sampleRate: preset.sample_rate } : undefined);
       sampleRate = audioContext.sampleRate;
       processorNode = audioContext.createScriptProcessor(4096, 1, 1);
       silentGain = audioContext.createGain();
@@ -1047,7 +1554,11 @@ async function startRecording() {
         copy.set(input);
         audioChunks.push(copy);
         totalSamples += copy.length;
-        setMicLevel(measureAudioLevel(input));
+        const level = measureAudioLevel(input);
+        if (level >= AUTO_STOP_SILENCE_LEVEL) {
+          lastSoundAtMs = Date.now();
+        }
+        setMicLevel(level);
       };
 
       processorNode.connect(silentGain);
@@ -1062,11 +1573,22 @@ async function startRecording() {
     savedAudioPaths = [];
     resetMicMeter();
 
+    await startRollingTranscription(mode);
     recordingStartWallTimeMs = Date.now();
+    lastSoundAtMs = recordingStartWallTimeMs;
     lastRecordingAt = new Date(recordingStartWallTimeMs);
     isRecording = true;
     isStoppingRecording = false;
     openFileBtn.hidden = true;
+    exportSpeakersBtn.hidden = true;
+    exportAnonymizedBtn.hidden = true;
+    exportSegmentsBtn.hidden = true;
+    exportTextGridBtn.hidden = true;
+    exportElanBtn.hidden = true;
+    exportBundleBtn.hidden = true;
+    exportDriveBtn.hidden = true;
+    shareBearBtn.hidden = true;
+    shareLogseqBtn.hidden = true;
     resultSection.hidden = true;
     progressSection.hidden = true;
 
@@ -1098,7 +1620,7 @@ async function startRecording() {
   }
 }
 
-async function stopRecording() {
+async function stopRecording(autoStopReason = null) {
   if (!isRecording || isStoppingRecording) return;
 
   isStoppingRecording = true;
@@ -1109,7 +1631,7 @@ async function stopRecording() {
     let micWav = null;
     let systemWav = null;
     let primaryWav = null;
-    let stopWarnings = [];
+    let stopWarnings = autoStopReason ? [autoStopReason] : [];
     let systemAudioOffsetMs = 0;
 
     if (microphoneCaptureActive) {
@@ -1197,10 +1719,89 @@ async function stopRecording() {
   }
 }
 
+const AUDIO_UPLOAD_CHUNK_BYTES = 4 * 1024 * 1024;
+
+// Without `reportProgress` the backend does not emit upload progress, for
+// uploads that run while the progress panel belongs to something else.
+async function uploadAudio(bytes, reportProgress = true) {
+  if (!bytes || bytes.length === 0) {
+    return null;
+  }
+
+  const uploadId = await invoke('start_audio_upload');
+  const headers = { 'x-upload-id': uploadId };
+  if (reportProgress) {
+    headers['x-upload-total'] = String(bytes.length);
+  }
+  for (let offset = 0; offset < bytes.length; offset += AUDIO_UPLOAD_CHUNK_BYTES) {
+    await invoke('append_audio_chunk', bytes.subarray(offset, offset + AUDIO_UPLOAD_CHUNK_BYTES), {
+      headers,
+    });
+  }
+  return uploadId;
+}
+
+// Jobs at least this long ask for confirmation of the planned destination
+// before whisper starts.
+const CONFIRM_JOB_SECONDS = 20 * 60;
+
+async function confirmTranscriptionPlan(options) {
+  const plan = await invoke('plan_transcription', { options });
+  if (plan.duration_seconds < CONFIRM_JOB_SECONDS) {
+    return true;
+  }
+
+  const lines = [
+    plan.save_paths.length > 0
+      ? `Saves to: ${plan.save_paths.join('\n')}`
+      : 'The transcript will not be saved to a file.',
+    `Model: ${plan.model_used}`,
+    `Speaker mode: ${plan.speaker_mode_used}`,
+  ];
+  if (plan.part_count > 1) {
+    lines.push(`Split into ${plan.part_count} parts`);
+  }
+  lines.push(...plan.warnings);
+  return ask(lines.join('\n\n'), {
+    title: `Transcribe ${Math.round(plan.duration_seconds / 60)} minutes of audio?`,
+    kind: 'info',
+    okLabel: 'Transcribe',
+    cancelLabel: 'Cancel',
+  });
+}
+
+// The recorder's transcription options, without audio.
+function recorderTranscriptionOptions() {
+  return {
+    system_audio_offset_ms: recordedCapture?.systemAudioOffsetMs || 0,
+    model: modelSelect.value,
+    language: languageSelect.value,
+    save_markdown: saveMarkdownCheckbox.checked,
+    save_raw_audio: saveRawAudioCheckbox.checked,
+    output_mode: getOutputMode(),
+    client: getSelectedCoachnotesClient(),
+    diarization_mode: currentSpeakerMode(),
+    denoise: denoiseAudioCheckbox.checked,
+    normalize_loudness: normalizeAudioCheckbox.checked,
+    generate_chapters: generateChaptersCheckbox.checked,
+    analyze_sentiment: analyzeSentimentCheckbox.checked,
+    save_json_sidecar: saveJsonSidecarCheckbox.checked,
+    export_to_drive: exportToDriveCheckbox.checked && Boolean(setupState?.drive_connected),
+    session_title: sessionTitleInput.value.trim() || null,
+    session_notes: sessionNotesInput.value.trim() || null,
+    include_notes_section: includeNotesSectionCheckbox.checked,
+    summarize: summarizeCheckbox.checked,
+    verbatim: verbatimCheckbox.checked,
+    show_notes: showNotesCheckbox.checked,
+    workspace: setupState?.active_workspace || null,
+    priority: urgentPriorityCheckbox.checked ? 'urgent' : 'normal',
+  };
+}
+
 async function transcribeRecording() {
   const primaryWav = currentPrimaryWav();
   if (!primaryWav) return;
-  if (!selectedModelReady()) {
+  if (!selectedModelUsable()) {
     setStatus('Selected model is not downloaded.', 'error');
     return;
   }
@@ -1216,21 +1817,22 @@ async function transcribeRecording() {
   isTranscribing = true;
   syncActionButtons();
 
-  const options = {
-    audio_data: Array.from(primaryWav),
-    microphone_audio_data: recordedCapture?.microphoneWav ? Array.from(recordedCapture.microphoneWav) : [],
-    system_audio_data: recordedCapture?.systemWav ? Array.from(recordedCapture.systemWav) : [],
-    system_audio_offset_ms: recordedCapture?.systemAudioOffsetMs || 0,
-    model: modelSelect.value,
-    language: languageSelect.value,
-    save_markdown: saveMarkdownCheckbox.checked,
-    save_raw_audio: saveRawAudioCheckbox.checked,
-    output_mode: getOutputMode(),
-    client: getSelectedCoachnotesClient(),
-    diarization_mode: currentSpeakerMode(),
-  };
+  const options = recorderTranscriptionOptions();
+  if (rollingChunk) {
+    await rollingChunk;
+  }
+  options.rolling_session = rollingSessionId;
 
   try {
+    options.audio_upload_id = await uploadAudio(primaryWav);
+    options.microphone_upload_id = await uploadAudio(recordedCapture?.microphoneWav);
+    options.system_upload_id = await uploadAudio(recordedCapture?.systemWav);
+    if (!(await confirmTranscriptionPlan(options))) {
+      progressSection.hidden = true;
+      setStatus('Recording ready to transcribe', 'ready');
+      return;
+    }
+    rollingSessionId = null;
     const result = await invoke('transcribe_recording', { options });
     transcriptOutput.textContent = result.transcript || '';
     renderWarnings(result.warnings || []);
@@ -1245,6 +1847,15 @@ async function transcribeRecording() {
     } else {
       openFileBtn.hidden = true;
     }
+    exportSpeakersBtn.hidden = !(savedTranscriptPath && result.speaker_mode_used !== 'none');
+    exportAnonymizedBtn.hidden = !savedTranscriptPath;
+    exportSegmentsBtn.hidden = !(savedTranscriptPath && result.sidecar_path);
+    exportTextGridBtn.hidden = exportSegmentsBtn.hidden;
+    exportElanBtn.hidden = exportSegmentsBtn.hidden;
+    exportBundleBtn.hidden = !savedTranscriptPath;
+    exportDriveBtn.hidden = !(savedTranscriptPath && setupState?.drive_connected);
+    shareBearBtn.hidden = !savedTranscriptPath;
+    shareLogseqBtn.hidden = !savedTranscriptPath;
 
     if (result.speaker_mode_used === 'source_aware_2speaker') {
       setStatus(
@@ -1274,6 +1885,11 @@ async function transcribeRecording() {
     savedAudioPaths = [];
     setStatus(`Transcription failed: ${String(error)}`, 'error');
   } finally {
+    for (const uploadId of [options.audio_upload_id, options.microphone_upload_id, options.system_upload_id]) {
+      if (uploadId) {
+        invoke('discard_audio_upload', { uploadId }).catch(() => {});
+      }
+    }
     isTranscribing = false;
     syncActionButtons();
   }
@@ -1284,16 +1900,28 @@ function discardCurrentSession() {
     return;
   }
 
+  discardRollingTranscription();
   recordedCapture = null;
   savedTranscriptPath = null;
   savedAudioPaths = [];
   hasTranscriptionResult = false;
   lastRecordingAt = null;
   transcriptOutput.textContent = '';
+  sessionTitleInput.value = '';
+  sessionNotesInput.value = '';
   renderWarnings([]);
   progressSection.hidden = true;
   resultSection.hidden = true;
   openFileBtn.hidden = true;
+  exportSpeakersBtn.hidden = true;
+  exportAnonymizedBtn.hidden = true;
+  exportSegmentsBtn.hidden = true;
+  exportTextGridBtn.hidden = true;
+  exportElanBtn.hidden = true;
+  exportBundleBtn.hidden = true;
+  exportDriveBtn.hidden = true;
+  shareBearBtn.hidden = true;
+  shareLogseqBtn.hidden = true;
   resetTimer();
   resetMicMeter();
   setStatus('Ready to record', 'idle');
@@ -1304,6 +1932,7 @@ modelSelect.addEventListener('change', async () => {
   try {
     setupState = await invoke('set_selected_model', { model: modelSelect.value });
     renderSetupState();
+    await renderLanguageOptions(modelSelect.value);
 
     if (diarizationModeSelect.value === 'tdrz_2speaker' && modelSelect.value !== DIARIZATION_MODEL_ID) {
       await saveDiarizationMode('none');
@@ -1314,141 +1943,1319 @@ modelSelect.addEventListener('change', async () => {
   }
 });
 
-chooseDirBtn.addEventListener('click', async () => {
+function localIsoDate(date) {
+  const month = String(date.getMonth() + 1).padStart(2, '0');
+  const day = String(date.getDate()).padStart(2, '0');
+  return `${date.getFullYear()}-${month}-${day}`;
+}
+
+generateDigestBtn.addEventListener('click', async () => {
+  const end = new Date();
+  const start = new Date(end);
+  start.setDate(end.getDate() - 6);
   try {
-    const selected = await open({
-      directory: true,
-      multiple: false,
-      defaultPath: transcriptDirInput.value || undefined,
+    const digest = await invoke('generate_digest', {
+      range: { start: localIsoDate(start), end: localIsoDate(end) },
     });
+    setStatus(
+      `Digest saved (${digest.session_count} sessions, ${digest.total_hours} h): ${digest.saved_path}`,
+      'ready',
+    );
+  } catch (error) {
+    setStatus(`Failed to generate digest: ${String(error)}`, 'error');
+  }
+});
 
-    if (typeof selected !== 'string' || selected.length === 0) {
+async function exportCsv(kind) {
+  try {
+    const path = await save({
+      defaultPath: `echo-scribe-${kind}-${localIsoDate(new Date())}.csv`,
+      filters: [{ name: 'CSV', extensions: ['csv'] }],
+    });
+    if (!path) {
       return;
     }
-
-    setupState = await invoke('set_transcript_directory', { directory: selected });
-    renderSetupState();
+    // The save dialog has already asked before replacing an existing file.
+    const rows = await invoke('export_csv', { kind, path, overwrite: true });
+    setStatus(`Exported ${rows} rows: ${path}`, 'ready');
   } catch (error) {
-    setStatus(`Failed to set transcript folder: ${String(error)}`, 'error');
+    setStatus(`Failed to export CSV: ${String(error)}`, 'error');
   }
-});
+}
 
-coachnotesEnabledCheckbox.addEventListener('change', async () => {
+exportSessionsCsvBtn.addEventListener('click', () => exportCsv('sessions'));
+
+moveAppDataBtn.addEventListener('click', async () => {
+  if (isRecording) {
+    setStatus('Stop recording before moving app data.', 'error');
+    return;
+  }
+  const selected = await open({ directory: true, multiple: false });
+  if (typeof selected !== 'string' || selected.length === 0) {
+    return;
+  }
+  const includeModels = await ask('Move the downloaded models too? They can take several gigabytes.', {
+    title: 'Move app data',
+    kind: 'info',
+    okLabel: 'Move models too',
+    cancelLabel: 'Leave models',
+  });
   try {
-    await saveCoachnotesSettings();
+    setStatus(`Moving app data to ${selected}…`, 'working');
+    const result = await invoke('move_app_data', { newDir: selected, includeModels });
+    setupState = await invoke('get_setup_state');
+    renderSetupState();
+    if (result.warnings.length > 0) {
+      setStatus(result.warnings.join(' '), 'warning');
+    } else {
+      setStatus(`App data moved to ${result.dir}.`, 'ready');
+    }
   } catch (error) {
-    setStatus(`Failed to update CoachNotes mode: ${String(error)}`, 'error');
+    setStatus(`Failed to move app data: ${String(error)}`, 'error');
   }
 });
+exportUsageCsvBtn.addEventListener('click', () => exportCsv('usage'));
 
-chooseCoachnotesDirBtn.addEventListener('click', async () => {
+async function exportJsonl(granularity) {
   try {
-    const selected = await open({
-      directory: true,
-      multiple: false,
-      defaultPath: coachnotesRootDirInput.value || undefined,
+    const path = await save({
+      defaultPath: `echo-scribe-${granularity}s-${localIsoDate(new Date())}.jsonl`,
+      filters: [{ name: 'JSON Lines', extensions: ['jsonl'] }],
     });
-
-    if (typeof selected !== 'string' || selected.length === 0) {
+    if (!path) {
       return;
     }
+    const result = await invoke('export_jsonl', { granularity, path, overwrite: true });
+    const skipped = result.skipped
+      ? ` (${result.skipped} transcript${result.skipped === 1 ? '' : 's'} without JSON metadata skipped)`
+      : '';
+    setStatus(`Exported ${result.lines} lines${skipped}: ${path}`, 'ready');
+  } catch (error) {
+    setStatus(`Failed to export JSONL: ${String(error)}`, 'error');
+  }
+}
 
-    coachnotesRootDirInput.value = selected;
-
-    try {
-      const clients = await invoke('get_coachnotes_clients', { rootDir: selected });
-      populateCoachnotesClients(clients || [], coachnotesClientSelect.value || '');
-    } catch {
-      // keep going - set_coachnotes_settings will validate and return canonical state
-    }
+exportTranscriptsJsonlBtn.addEventListener('click', () => exportJsonl('transcript'));
+exportSegmentsJsonlBtn.addEventListener('click', () => exportJsonl('segment'));
 
-    await saveCoachnotesSettings();
+updateChannelSelect.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_update_channel', { channel: updateChannelSelect.value });
+    renderSetupState();
+    installUpdateBtn.hidden = true;
+    updateStatus.textContent = '';
   } catch (error) {
-    setStatus(`Failed to set CoachNotes folder: ${String(error)}`, 'error');
+    setStatus(`Failed to save update channel: ${String(error)}`, 'error');
   }
 });
 
-coachnotesClientSelect.addEventListener('change', async () => {
+checkUpdatesBtn.addEventListener('click', async () => {
+  updateStatus.textContent = 'Checking for updates...';
   try {
-    await saveCoachnotesSettings();
+    const info = await invoke('check_for_updates');
+    installUpdateBtn.hidden = !info.available;
+    updateStatus.textContent = info.available
+      ? `Version ${info.version} is available (current ${info.current_version}).`
+      : `Echo Scribe ${info.current_version} is up to date on the ${info.channel} channel.`;
   } catch (error) {
-    setStatus(`Failed to set CoachNotes client: ${String(error)}`, 'error');
+    installUpdateBtn.hidden = true;
+    updateStatus.textContent = String(error);
   }
 });
 
-downloadModelBtn.addEventListener('click', async () => {
-  const entry = selectedModelEntry();
-  if (!entry || entry.downloaded || modelDownloadInProgress) {
-    return;
+installUpdateBtn.addEventListener('click', async () => {
+  installUpdateBtn.disabled = true;
+  try {
+    await invoke('install_update');
+  } catch (error) {
+    updateStatus.textContent = String(error);
+  } finally {
+    installUpdateBtn.disabled = false;
   }
+});
 
-  modelDownloadInProgress = true;
-  modelProgressWrap.hidden = false;
-  modelProgressFill.style.width = '0%';
-  modelProgressText.textContent = `Preparing ${entry.id} model download...`;
-  renderSetupState();
+listen('update-download-progress', (event) => {
+  const { percent, downloaded_bytes: downloaded, total_bytes: total } = event.payload;
+  const size = total ? `${formatBytes(downloaded)} / ${formatBytes(total)}` : formatBytes(downloaded);
+  updateStatus.textContent = `Downloading update... ${percent}% (${size})`;
+});
 
+installQuickActionBtn.addEventListener('click', async () => {
   try {
-    await invoke('download_model', { options: { model: entry.id } });
-    setupState = await invoke('get_setup_state');
-    setStatus('Model downloaded. You can start recording.', 'ready');
+    const path = await invoke('install_finder_quick_action');
+    setStatus(`Quick Action installed: ${path}`, 'ready');
   } catch (error) {
-    setStatus(`Model download failed: ${String(error)}`, 'error');
-  } finally {
-    modelDownloadInProgress = false;
-    renderSetupState();
+    setStatus(`Failed to install Quick Action: ${String(error)}`, 'error');
   }
 });
 
-saveMarkdownCheckbox.addEventListener('change', () => {
-  updateDestinationPreview();
-  syncActionButtons();
+removeQuickActionBtn.addEventListener('click', async () => {
+  try {
+    await invoke('remove_finder_quick_action');
+    setStatus('Quick Action removed.', 'ready');
+  } catch (error) {
+    setStatus(`Failed to remove Quick Action: ${String(error)}`, 'error');
+  }
 });
 
-saveRawAudioCheckbox.addEventListener('change', () => {
-  updateDestinationPreview();
-  syncActionButtons();
+openHistoryBtn.addEventListener('click', async () => {
+  try {
+    await invoke('open_history_window');
+  } catch (error) {
+    setStatus(`Failed to open history: ${String(error)}`, 'error');
+  }
 });
 
-languageSelect.addEventListener('change', async () => {
-  if (diarizationModeSelect.value === 'tdrz_2speaker') {
-    await ensureTwoSpeakerRequirements();
+openCaptionsBtn.addEventListener('click', async () => {
+  try {
+    await invoke('open_captions_window');
+  } catch (error) {
+    setStatus(`Failed to open captions: ${String(error)}`, 'error');
   }
 });
 
-captureModeSelect.addEventListener('change', () => {
-  updateCaptureModeHelp();
-  updateDestinationPreview();
-});
+// After a folder change, offers to move the transcripts left in the old one.
+async function offerTranscriptMigration(oldDir, newDir) {
+  if (!oldDir || !newDir || oldDir === newDir) return;
 
-for (const button of captureOptionButtons) {
-  button.addEventListener('click', () => {
-    const nextMode = button.dataset.captureOption;
-    if (!nextMode || nextMode === captureModeSelect.value) {
-      return;
-    }
+  let plan;
+  try {
+    plan = await invoke('migrate_transcripts', { oldDir, newDir, dryRun: true });
+  } catch {
+    return;
+  }
+  if (plan.moves.length === 0) return;
 
-    captureModeSelect.value = nextMode;
-    captureModeSelect.dispatchEvent(new Event('change'));
-  });
+  const confirmed = await ask(
+    `Move ${plan.moves.length} transcript file${plan.moves.length === 1 ? '' : 's'} from ${oldDir} to ${newDir}?`,
+    { title: 'Move existing transcripts?', kind: 'info', okLabel: 'Move', cancelLabel: 'Keep in place' }
+  );
+  if (!confirmed) return;
+
+  try {
+    const result = await invoke('migrate_transcripts', { oldDir, newDir, dryRun: false });
+    const skipped = result.conflicts.length + result.errors.length;
+    const moved = result.moves.length - result.errors.length;
+    setStatus(
+      skipped > 0
+        ? `Moved ${moved} files; ${skipped} left in ${oldDir} (name already taken or move failed).`
+        : `Moved ${moved} files to ${newDir}.`,
+      skipped > 0 ? 'warning' : 'ready'
+    );
+  } catch (error) {
+    setStatus(`Failed to move transcripts: ${String(error)}`, 'error');
+  }
 }
 
-diarizationModeSelect.addEventListener('change', async () => {
-  const nextMode = diarizationModeSelect.value;
+function renderWorkspaces() {
+  workspaceSelect.replaceChildren(
+    ...(setupState.workspaces || []).map((name) => {
+      const option = document.createElement('option');
+      option.value = name;
+      option.textContent = name;
+      return option;
+    }),
+  );
+  workspaceSelect.value = setupState.active_workspace;
+  workspaceSelect.disabled = isRecording || isTranscribing;
+  deleteWorkspaceBtn.disabled = (setupState.workspaces || []).length < 2;
+}
 
-  if (nextMode === 'tdrz_2speaker') {
-    const enabled = await ensureTwoSpeakerRequirements();
-    if (!enabled) {
-      await saveDiarizationMode('none');
-      return;
-    }
+workspaceSelect.addEventListener('change', async () => {
+  try {
+    await applySetupState(await invoke('switch_workspace', { name: workspaceSelect.value }));
+    setStatus(`Switched to the "${setupState.active_workspace}" workspace.`, 'ready');
+  } catch (error) {
+    renderWorkspaces();
+    setStatus(`Failed to switch workspace: ${String(error)}`, 'error');
   }
+});
 
+newWorkspaceBtn.addEventListener('click', async () => {
+  const name = workspaceNameInput.value.trim();
+  if (!name) {
+    setStatus('Enter a name for the new workspace.', 'error');
+    return;
+  }
   try {
-    await saveDiarizationMode(nextMode);
+    await applySetupState(await invoke('create_workspace', { name }));
+    workspaceNameInput.value = '';
+    setStatus(`Created the "${setupState.active_workspace}" workspace.`, 'ready');
   } catch (error) {
-    setStatus(`Failed to update speaker mode: ${String(error)}`, 'error');
-    return;
+    setStatus(`Failed to create workspace: ${String(error)}`, 'error');
+  }
+});
+
+deleteWorkspaceBtn.addEventListener('click', async () => {
+  const name = setupState.active_workspace;
+  const confirmed = await ask(
+    `Delete the "${name}" workspace? Its transcripts stay where they are.`,
+    { title: 'Delete workspace', kind: 'warning', okLabel: 'Delete', cancelLabel: 'Cancel' },
+  );
+  if (!confirmed) return;
+  try {
+    await applySetupState(await invoke('delete_workspace', { name }));
+    setStatus(`Switched to the "${setupState.active_workspace}" workspace.`, 'ready');
+  } catch (error) {
+    setStatus(`Failed to delete workspace: ${String(error)}`, 'error');
+  }
+});
+
+chooseDirBtn.addEventListener('click', async () => {
+  try {
+    const selected = await open({
+      directory: true,
+      multiple: false,
+      defaultPath: transcriptDirInput.value || undefined,
+    });
+
+    if (typeof selected !== 'string' || selected.length === 0) {
+      return;
+    }
+
+    const previousDir = transcriptDirInput.value;
+    setupState = await invoke('set_transcript_directory', { directory: selected });
+    renderSetupState();
+    await offerTranscriptMigration(previousDir, setupState.transcript_dir);
+  } catch (error) {
+    setStatus(`Failed to set transcript folder: ${String(error)}`, 'error');
+  }
+});
+
+async function saveRawOutputPreferences(directory) {
+  try {
+    setupState = await invoke('set_raw_output_preferences', {
+      keep: keepRawWhisperOutputCheckbox.checked,
+      directory,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save whisper output setting: ${String(error)}`, 'error');
+  }
+}
+
+keepRawWhisperOutputCheckbox.addEventListener('change', () =>
+  saveRawOutputPreferences(rawOutputDirInput.value || null),
+);
+
+chooseRawOutputDirBtn.addEventListener('click', async () => {
+  const selected = await open({
+    directory: true,
+    multiple: false,
+    defaultPath: rawOutputDirInput.value || transcriptDirInput.value || undefined,
+  });
+  if (typeof selected === 'string' && selected.length > 0) {
+    await saveRawOutputPreferences(selected);
+  }
+});
+
+clearRawOutputDirBtn.addEventListener('click', () => saveRawOutputPreferences(null));
+
+coachnotesEnabledCheckbox.addEventListener('change', async () => {
+  try {
+    await saveCoachnotesSettings();
+  } catch (error) {
+    setStatus(`Failed to update CoachNotes mode: ${String(error)}`, 'error');
+  }
+});
+
+coachnotesAppendSameDayCheckbox.addEventListener('change', async () => {
+  try {
+    await saveCoachnotesSettings();
+  } catch (error) {
+    setStatus(`Failed to update same-day appending: ${String(error)}`, 'error');
+  }
+});
+
+coachnotesInlineSessionLinksCheckbox.addEventListener('change', async () => {
+  try {
+    await saveCoachnotesSettings();
+  } catch (error) {
+    setStatus(`Failed to update session links: ${String(error)}`, 'error');
+  }
+});
+
+function formatCoachnotesHealth(report) {
+  if (report.problems.length > 0) {
+    return report.problems.join(' ');
+  }
+  const clients = `${report.clients.length} client folder${report.clients.length === 1 ? '' : 's'}`;
+  const deleted = report.deleted_notes_exists ? ', Deleted Notes present' : '';
+  return `Folder looks good: ${clients}${deleted}.`;
+}
+
+checkCoachnotesDirBtn.addEventListener('click', async () => {
+  try {
+    const report = await invoke('validate_coachnotes_root', {
+      rootDir: coachnotesRootDirInput.value || null,
+    });
+    coachnotesHealth.textContent = formatCoachnotesHealth(report);
+  } catch (error) {
+    coachnotesHealth.textContent = String(error);
+  }
+});
+
+function formatSyncHealth(report) {
+  const providers = report.folders
+    .filter((folder) => folder.provider)
+    .map((folder) => folder.provider);
+  if (report.problems.length > 0) {
+    const conflicts = report.conflicts.map((conflict) => conflict.path);
+    return [...report.problems, ...conflicts].join(' ');
+  }
+  if (providers.length === 0) {
+    return 'No cloud-synced folders detected.';
+  }
+  return `All files are downloaded and there are no conflict copies (${[...new Set(providers)].join(', ')}).`;
+}
+
+checkSyncBtn.addEventListener('click', async () => {
+  try {
+    const report = await invoke('check_sync_health');
+    syncHealth.textContent = formatSyncHealth(report);
+  } catch (error) {
+    syncHealth.textContent = String(error);
+  }
+});
+
+chooseCoachnotesDirBtn.addEventListener('click', async () => {
+  try {
+    const selected = await open({
+      directory: true,
+      multiple: false,
+      defaultPath: coachnotesRootDirInput.value || undefined,
+    });
+
+    if (typeof selected !== 'string' || selected.length === 0) {
+      return;
+    }
+
+    const previousRoot = coachnotesRootDirInput.value;
+    coachnotesRootDirInput.value = selected;
+
+    try {
+      const clients = await invoke('get_coachnotes_clients', { rootDir: selected });
+      populateCoachnotesClients(clients || [], coachnotesClientSelect.value || '');
+    } catch {
+      // keep going - set_coachnotes_settings will validate and return canonical state
+    }
+
+    await saveCoachnotesSettings();
+    await offerTranscriptMigration(previousRoot, setupState.coachnotes_root_dir);
+  } catch (error) {
+    setStatus(`Failed to set CoachNotes folder: ${String(error)}`, 'error');
+  }
+});
+
+coachnotesClientSelect.addEventListener('change', async () => {
+  try {
+    await saveCoachnotesSettings();
+  } catch (error) {
+    setStatus(`Failed to set CoachNotes client: ${String(error)}`, 'error');
+  }
+  renderClientPrompt();
+  renderClientTemplate();
+  renderClientLanguage();
+});
+
+clientPromptInput.addEventListener('change', async () => {
+  const client = getSelectedCoachnotesClient();
+  if (!client) return;
+  try {
+    setupState = await invoke('set_client_prompt', { client, prompt: clientPromptInput.value });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save client prompt: ${String(error)}`, 'error');
+  }
+});
+
+clientTemplateInput.addEventListener('change', async () => {
+  const client = getSelectedCoachnotesClient();
+  if (!client) return;
+  try {
+    setupState = await invoke('set_client_template', { client, template: clientTemplateInput.value });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save session template: ${String(error)}`, 'error');
+  }
+});
+
+prepareSessionNoteBtn.addEventListener('click', async () => {
+  const client = getSelectedCoachnotesClient();
+  if (!client) return;
+  try {
+    const note = await invoke('prepare_session_note', { client });
+    const goals = note.carried_goals === 1 ? '1 goal' : `${note.carried_goals} goals`;
+    setStatus(`Session note ready (${goals} carried over): ${note.path}`, 'ready');
+  } catch (error) {
+    setStatus(`Failed to prepare session note: ${String(error)}`, 'error');
+  }
+});
+
+// `overwrite` replaces a file on disk that fails verification, as when a
+// damaged model is downloaded again.
+async function downloadModel(modelId, overwrite = false) {
+  modelDownloadInProgress = true;
+  modelProgressWrap.hidden = false;
+  modelProgressFill.style.width = '0%';
+  modelProgressText.textContent = `Preparing ${modelId} model download...`;
+  renderSetupState();
+
+  try {
+    await invoke('download_model', { options: { model: modelId, overwrite } });
+    setupState = await invoke('get_setup_state');
+    setStatus('Model downloaded. You can start recording.', 'ready');
+  } catch (error) {
+    setStatus(`Model download failed: ${String(error)}`, 'error');
+  } finally {
+    modelDownloadInProgress = false;
+    renderSetupState();
+  }
+}
+
+downloadModelBtn.addEventListener('click', async () => {
+  const entry = selectedModelEntry();
+  if (!entry || entry.downloaded || modelDownloadInProgress) {
+    return;
+  }
+  await downloadModel(entry.id);
+});
+
+verifyModelsBtn.addEventListener('click', async () => {
+  if (modelDownloadInProgress) return;
+  verifyModelsBtn.disabled = true;
+  setStatus('Verifying downloaded models...', 'working');
+  try {
+    const results = await invoke('verify_models');
+    const damaged = results.filter((result) => !result.verified).map((result) => result.model);
+    if (results.length === 0) {
+      setStatus('No models are downloaded yet.', 'idle');
+    } else if (damaged.length === 0) {
+      setStatus(`All ${results.length} downloaded models passed verification.`, 'ready');
+    } else {
+      setStatus(`Damaged models: ${damaged.join(', ')}`, 'error');
+    }
+  } catch (error) {
+    setStatus(`Model verification failed: ${String(error)}`, 'error');
+  } finally {
+    verifyModelsBtn.disabled = false;
+  }
+});
+
+saveMarkdownCheckbox.addEventListener('change', () => {
+  updateDestinationPreview();
+  syncActionButtons();
+});
+
+localeSelect.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_locale', { locale: localeSelect.value });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save language: ${String(error)}`, 'error');
+  }
+});
+
+async function saveDatePreferences() {
+  try {
+    setupState = await invoke('set_date_preferences', {
+      timezone: timezoneInput.value.trim() || null,
+      dateFormat: dateFormatSelect.value,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save date settings: ${String(error)}`, 'error');
+  }
+}
+
+function parseFrontmatterFields(text) {
+  const fields = {};
+  for (const line of text.split('\n')) {
+    const separator = line.indexOf(':');
+    if (separator <= 0) {
+      continue;
+    }
+    fields[line.slice(0, separator).trim()] = line.slice(separator + 1).trim();
+  }
+  return fields;
+}
+
+customFrontmatterInput.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_custom_frontmatter', {
+      fields: parseFrontmatterFields(customFrontmatterInput.value),
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save frontmatter fields: ${String(error)}`, 'error');
+  }
+});
+
+async function saveZoomSettings() {
+  try {
+    setupState = await invoke('set_zoom_settings', {
+      directory: zoomDirInput.value.trim() || null,
+      clientMap: parseFrontmatterFields(zoomClientMapInput.value),
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save Zoom settings: ${String(error)}`, 'error');
+  }
+}
+
+zoomDirInput.addEventListener('change', saveZoomSettings);
+zoomClientMapInput.addEventListener('change', saveZoomSettings);
+
+async function saveClientDetection() {
+  try {
+    setupState = await invoke('set_client_detection', {
+      enabled: clientDetectionEnabledCheckbox.checked,
+      useCalendar: clientDetectionCalendarCheckbox.checked,
+      clientMap: parseFrontmatterFields(clientDetectionMapInput.value),
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save client detection settings: ${String(error)}`, 'error');
+  }
+}
+
+clientDetectionEnabledCheckbox.addEventListener('change', saveClientDetection);
+clientDetectionCalendarCheckbox.addEventListener('change', saveClientDetection);
+clientDetectionMapInput.addEventListener('change', saveClientDetection);
+
+function renderZoomRecordings(meetings) {
+  zoomRecordingsList.replaceChildren();
+  for (const meeting of meetings) {
+    meeting.audio_files.forEach((file, index) => {
+      const label = document.createElement('label');
+      const checkbox = document.createElement('input');
+      checkbox.type = 'checkbox';
+      checkbox.value = file;
+      checkbox.checked = index === 0;
+      const name = file.split(/[\\/]/).pop();
+      const client = meeting.client ? ` → ${meeting.client}` : '';
+      label.append(checkbox, `${meeting.date} ${meeting.time} ${meeting.topic}${client} (${name})`);
+      zoomRecordingsList.append(label);
+    });
+  }
+  importZoomRecordingsBtn.hidden = meetings.length === 0;
+}
+
+findZoomRecordingsBtn.addEventListener('click', async () => {
+  try {
+    const meetings = await invoke('list_zoom_recordings');
+    renderZoomRecordings(meetings);
+    if (meetings.length === 0) {
+      setStatus('No Zoom recordings found.', 'idle');
+    }
+  } catch (error) {
+    setStatus(`Failed to list Zoom recordings: ${String(error)}`, 'error');
+  }
+});
+
+importZoomRecordingsBtn.addEventListener('click', async () => {
+  const files = [...zoomRecordingsList.querySelectorAll('input:checked')].map((box) => box.value);
+  if (files.length === 0) return;
+  try {
+    const events = await invoke('import_zoom_recordings', { files });
+    const failed = events.filter((event) => event.error);
+    if (failed.length > 0) {
+      setStatus(failed.map((event) => event.error).join('\n'), 'error');
+    } else {
+      setStatus(`Queued ${events.length} Zoom recordings for transcription.`, 'working');
+    }
+    renderZoomRecordings([]);
+  } catch (error) {
+    setStatus(`Failed to import Zoom recordings: ${String(error)}`, 'error');
+  }
+});
+
+function renderAppLock() {
+  const enabled = Boolean(setupState.app_lock_enabled);
+  const locked = Boolean(setupState.app_locked);
+  appLockEnabledCheckbox.checked = enabled;
+  appLockEnabledCheckbox.disabled = locked;
+  appLockTouchIdCheckbox.checked = Boolean(setupState.app_lock_touch_id);
+  appLockTouchIdCheckbox.disabled = locked;
+  appLockTouchIdRow.hidden = !setupState.touch_id_available;
+  unlockAppBtn.hidden = !locked;
+  unlockTouchIdBtn.hidden = !(locked && setupState.app_lock_touch_id);
+  lockAppBtn.hidden = !enabled || locked;
+  if (locked) {
+    appLockStatus.textContent = 'Locked. Enter the passcode to see CoachNotes sessions.';
+  } else if (enabled) {
+    appLockStatus.textContent = 'Unlocked. Enter a new passcode to change it.';
+  } else {
+    appLockStatus.textContent = '';
+  }
+}
+
+async function saveAppLock(passcode = null) {
+  try {
+    setupState = await invoke('set_app_lock', {
+      enabled: appLockEnabledCheckbox.checked,
+      passcode,
+      touchId: appLockTouchIdCheckbox.checked,
+    });
+    appLockPasscodeInput.value = '';
+    renderSetupState();
+  } catch (error) {
+    renderAppLock();
+    setStatus(`Failed to save app lock: ${String(error)}`, 'error');
+  }
+}
+
+async function unlockApp(passcode) {
+  try {
+    setupState = await invoke('unlock_app', { passcode });
+    appLockPasscodeInput.value = '';
+    renderSetupState();
+    setStatus('Unlocked.', 'ready');
+  } catch (error) {
+    setStatus(String(error), 'error');
+    return;
+  }
+  // The queue and interrupted jobs stay hidden until the app is unlocked.
+  invoke('get_queue')
+    .then((jobs) => renderQueue(jobs || []))
+    .catch(() => {});
+  offerResumableJobs().catch((error) => {
+    setStatus(`Failed to check for interrupted jobs: ${String(error)}`, 'error');
+  });
+}
+
+appLockEnabledCheckbox.addEventListener('change', () => {
+  saveAppLock(appLockPasscodeInput.value || null);
+});
+appLockTouchIdCheckbox.addEventListener('change', () => saveAppLock());
+appLockPasscodeInput.addEventListener('keydown', (event) => {
+  if (event.key !== 'Enter' || !appLockPasscodeInput.value) return;
+  if (setupState?.app_locked) {
+    unlockApp(appLockPasscodeInput.value);
+  } else if (setupState?.app_lock_enabled) {
+    saveAppLock(appLockPasscodeInput.value);
+  }
+});
+unlockAppBtn.addEventListener('click', () => unlockApp(appLockPasscodeInput.value));
+unlockTouchIdBtn.addEventListener('click', () => unlockApp(null));
+lockAppBtn.addEventListener('click', async () => {
+  try {
+    setupState = await invoke('lock_app');
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to lock: ${String(error)}`, 'error');
+  }
+});
+
+async function saveRecorderSettings() {
+  try {
+    setupState = await invoke('set_recorder_settings', {
+      patterns: recorderPatternsInput.value.split(','),
+      moveOriginals: recorderMoveOriginalsCheckbox.checked,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save recorder settings: ${String(error)}`, 'error');
+  }
+}
+
+recorderPatternsInput.addEventListener('change', saveRecorderSettings);
+
+async function saveShareTemplates() {
+  try {
+    setupState = await invoke('set_share_templates', {
+      titleTemplate: shareTitleInput.value,
+      tags: shareTagsInput.value.split(','),
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save share settings: ${String(error)}`, 'error');
+  }
+}
+
+shareTitleInput.addEventListener('change', saveShareTemplates);
+shareTagsInput.addEventListener('change', saveShareTemplates);
+recorderMoveOriginalsCheckbox.addEventListener('change', saveRecorderSettings);
+
+async function importRecorder(volume) {
+  try {
+    const events = await invoke('import_recorder_files', { volume });
+    const failed = events.filter((event) => event.error);
+    if (failed.length > 0) {
+      setStatus(failed.map((event) => event.error).join('\n'), 'error');
+    } else {
+      setStatus(`Queued ${events.length} recordings from ${volume} for transcription.`, 'working');
+    }
+  } catch (error) {
+    setStatus(`Failed to import from ${volume}: ${String(error)}`, 'error');
+  }
+}
+
+importRecorderBtn.addEventListener('click', async () => {
+  try {
+    const volumes = (await invoke('list_recorder_files')).filter((volume) => volume.files.length > 0);
+    if (volumes.length === 0) {
+      setStatus('No new recordings on a connected recorder.', 'idle');
+      return;
+    }
+    for (const volume of volumes) {
+      await importRecorder(volume.name);
+    }
+  } catch (error) {
+    setStatus(`Failed to check recorders: ${String(error)}`, 'error');
+  }
+});
+
+async function saveTitlePreferences() {
+  try {
+    setupState = await invoke('set_title_preferences', {
+      autoTitle: autoTitleCheckbox.checked,
+      titleInFilename: titleInFilenameCheckbox.checked,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save title settings: ${String(error)}`, 'error');
+  }
+}
+
+renameTranscriptsBtn.addEventListener('click', async () => {
+  try {
+    const plan = await invoke('rename_transcripts', { dryRun: true });
+    const notes = plan.renames.filter((rename) => rename.from.endsWith('.md')).length;
+    if (notes === 0) {
+      setStatus('All saved transcripts already match the current naming.', 'ready');
+      return;
+    }
+    const confirmed = await ask(
+      `Rename ${notes} transcript${notes === 1 ? '' : 's'} and ${plan.renames.length - notes} related files? Links between them are updated, and the rename can be undone.`,
+      { title: 'Rename transcripts?', kind: 'info', okLabel: 'Rename', cancelLabel: 'Cancel' }
+    );
+    if (!confirmed) return;
+
+    const result = await invoke('rename_transcripts', { dryRun: false });
+    lastRenameManifest = result.manifest_path;
+    undoRenameBtn.hidden = !lastRenameManifest;
+    setStatus(
+      result.errors.length > 0
+        ? `Renamed ${result.renames.length} files. ${result.errors.join(' ')}`
+        : `Renamed ${result.renames.length} files.`,
+      result.errors.length > 0 ? 'warning' : 'ready'
+    );
+  } catch (error) {
+    setStatus(`Failed to rename transcripts: ${String(error)}`, 'error');
+  }
+});
+
+undoRenameBtn.addEventListener('click', async () => {
+  if (!lastRenameManifest) return;
+  try {
+    const result = await invoke('undo_transcript_rename', { manifestPath: lastRenameManifest });
+    if (result.errors.length === 0) {
+      lastRenameManifest = null;
+      undoRenameBtn.hidden = true;
+      setStatus(`Restored ${result.renames.length} original file names.`, 'ready');
+    } else {
+      setStatus(`Undo was incomplete. ${result.errors.join(' ')}`, 'warning');
+    }
+  } catch (error) {
+    setStatus(`Failed to undo rename: ${String(error)}`, 'error');
+  }
+});
+
+autoSplitSelect.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_auto_split_minutes', {
+      minutes: autoSplitSelect.value ? Number(autoSplitSelect.value) : null,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save split setting: ${String(error)}`, 'error');
+  }
+});
+
+rollingTranscriptionCheckbox.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_rolling_transcription', {
+      enabled: rollingTranscriptionCheckbox.checked,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save rolling transcription: ${String(error)}`, 'error');
+  }
+});
+
+timestampIntervalSelect.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_timestamp_interval', {
+      seconds: timestampIntervalSelect.value ? Number(timestampIntervalSelect.value) : null,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save timestamp markers: ${String(error)}`, 'error');
+  }
+});
+
+markdownEscapeSelect.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_markdown_escape', { level: markdownEscapeSelect.value });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save markdown escaping: ${String(error)}`, 'error');
+  }
+});
+
+async function saveInputLimits() {
+  try {
+    setupState = await invoke('set_input_limits', {
+      maxRecordingMinutes: Number(maxRecordingSelect.value),
+      maxAudioMb: Number(maxAudioSizeSelect.value),
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save limits: ${String(error)}`, 'error');
+  }
+}
+
+maxRecordingSelect.addEventListener('change', saveInputLimits);
+
+downloadLimitSelect.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_download_limit', { kbps: Number(downloadLimitSelect.value) });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save download limit: ${String(error)}`, 'error');
+  }
+});
+maxAudioSizeSelect.addEventListener('change', saveInputLimits);
+
+function renderHuggingfaceToken() {
+  const saved = Boolean(setupState.huggingface_token_set);
+  removeHuggingfaceTokenBtn.hidden = !saved;
+  huggingfaceTokenStatus.textContent = saved
+    ? 'A token is saved in the system credential store and sent with model downloads from huggingface.co.'
+    : 'Only needed for gated or private models. Create a read token in your Hugging Face account settings.';
+}
+
+async function saveHuggingfaceToken(token) {
+  try {
+    setupState = await invoke('set_huggingface_token', { token });
+    huggingfaceTokenInput.value = '';
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save Hugging Face token: ${String(error)}`, 'error');
+  }
+}
+
+saveHuggingfaceTokenBtn.addEventListener('click', () => {
+  const token = huggingfaceTokenInput.value.trim();
+  if (token) saveHuggingfaceToken(token);
+});
+removeHuggingfaceTokenBtn.addEventListener('click', () => saveHuggingfaceToken(''));
+
+autoTitleCheckbox.addEventListener('change', saveTitlePreferences);
+titleInFilenameCheckbox.addEventListener('change', saveTitlePreferences);
+dateFormatSelect.addEventListener('change', saveDatePreferences);
+timezoneInput.addEventListener('change', saveDatePreferences);
+
+duplicateCheckSelect.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_duplicate_check', { mode: duplicateCheckSelect.value });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save duplicate check: ${String(error)}`, 'error');
+  }
+});
+
+prewarmOnLaunchCheckbox.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_prewarm_on_launch', { enabled: prewarmOnLaunchCheckbox.checked });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save warm-up setting: ${String(error)}`, 'error');
+  }
+});
+
+fallbackSmallerModelCheckbox.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_fallback_to_smaller_model', {
+      enabled: fallbackSmallerModelCheckbox.checked,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save model fallback setting: ${String(error)}`, 'error');
+  }
+});
+
+autoDownloadModelsCheckbox.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_auto_download_models', {
+      enabled: autoDownloadModelsCheckbox.checked,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save model download setting: ${String(error)}`, 'error');
+  }
+});
+
+verifyModelsInBackgroundCheckbox.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_verify_models_in_background', {
+      enabled: verifyModelsInBackgroundCheckbox.checked,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save model check setting: ${String(error)}`, 'error');
+  }
+});
+
+redownloadCorruptedModelsCheckbox.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_redownload_corrupted_models', {
+      enabled: redownloadCorruptedModelsCheckbox.checked,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save model re-download setting: ${String(error)}`, 'error');
+  }
+});
+
+verbatimCheckbox.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_verbatim', { enabled: verbatimCheckbox.checked });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save verbatim setting: ${String(error)}`, 'error');
+  }
+});
+
+offlineModeCheckbox.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_offline_mode', { enabled: offlineModeCheckbox.checked });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save offline mode: ${String(error)}`, 'error');
+  }
+});
+
+async function saveEventBridge() {
+  try {
+    setupState = await invoke('set_event_bridge', {
+      enabled: eventBridgeCheckbox.checked,
+      port: Number(eventBridgePortInput.value) || null,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save event bridge: ${String(error)}`, 'error');
+  }
+}
+
+eventBridgeCheckbox.addEventListener('change', saveEventBridge);
+eventBridgePortInput.addEventListener('change', saveEventBridge);
+revealEventBridgeUrlButton.addEventListener('click', async () => {
+  try {
+    eventBridgeUrl.textContent = `Connect to ${await invoke('reveal_event_bridge_url')}`;
+  } catch (error) {
+    setStatus(`Failed to show the event bridge URL: ${String(error)}`, 'error');
+  }
+});
+
+webhooksInput.addEventListener('change', async () => {
+  try {
+    const text = webhooksInput.value.trim();
+    setupState = await invoke('set_webhooks', { webhooks: text ? JSON.parse(text) : [] });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save webhooks: ${String(error)}`, 'error');
+  }
+});
+
+notifyOnCompletionCheckbox.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_notify_on_completion', {
+      enabled: notifyOnCompletionCheckbox.checked,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save notification setting: ${String(error)}`, 'error');
+  }
+});
+
+function formatPowerState(power) {
+  if (!power) return '';
+  const source = power.on_battery ? 'On battery' : 'On power adapter';
+  const percent = power.battery_percent == null ? '' : ` (${power.battery_percent}%)`;
+  const lowPower = power.low_power_mode ? ', Low Power Mode on' : '';
+  return `${source}${percent}${lowPower}`;
+}
+
+batterySaverCheckbox.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_battery_saver', { enabled: batterySaverCheckbox.checked });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save battery saver setting: ${String(error)}`, 'error');
+  }
+});
+
+pauseQueueOnBatteryCheckbox.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_pause_queue_on_battery', {
+      enabled: pauseQueueOnBatteryCheckbox.checked,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save queue setting: ${String(error)}`, 'error');
+  }
+});
+
+async function saveDeferredWindow() {
+  try {
+    setupState = await invoke('set_deferred_window', {
+      window: deferredWindowCheckbox.checked
+        ? { start: deferredWindowStartInput.value, end: deferredWindowEndInput.value }
+        : null,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save the overnight window: ${String(error)}`, 'error');
+  }
+}
+
+deferredWindowCheckbox.addEventListener('change', saveDeferredWindow);
+[deferredWindowStartInput, deferredWindowEndInput].forEach((input) => {
+  input.addEventListener('change', () => {
+    if (deferredWindowCheckbox.checked) {
+      saveDeferredWindow();
+    }
+  });
+});
+
+async function saveCompletionSound() {
+  try {
+    setupState = await invoke('set_completion_sound', {
+      sound: completionSoundSelect.value || null,
+      minSeconds: Number(completionSoundThresholdSelect.value),
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save completion sound: ${String(error)}`, 'error');
+  }
+}
+
+completionSoundSelect.addEventListener('change', saveCompletionSound);
+completionSoundThresholdSelect.addEventListener('change', saveCompletionSound);
+
+extraWhisperArgsInput.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_extra_whisper_args', {
+      args: extraWhisperArgsInput.value.split(/\s+/).filter(Boolean),
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save whisper flags: ${String(error)}`, 'error');
+    renderSetupState();
+  }
+});
+
+async function saveSpellCorrection() {
+  try {
+    setupState = await invoke('set_spell_correction', {
+      enabled: spellCorrectionCheckbox.checked,
+      dictionary: spellDictionaryInput.value.split('\n').map((term) => term.trim()).filter(Boolean),
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save spelling dictionary: ${String(error)}`, 'error');
+  }
+}
+
+spellCorrectionCheckbox.addEventListener('change', saveSpellCorrection);
+spellDictionaryInput.addEventListener('change', saveSpellCorrection);
+
+async function saveTextNormalization() {
+  try {
+    setupState = await invoke('set_text_normalization', {
+      normalization: {
+        numbers: normalizeNumbersCheckbox.checked,
+        currency: normalizeCurrencyCheckbox.checked,
+        dates: normalizeDatesCheckbox.checked,
+      },
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save number formatting: ${String(error)}`, 'error');
+  }
+}
+
+normalizeNumbersCheckbox.addEventListener('change', saveTextNormalization);
+normalizeCurrencyCheckbox.addEventListener('change', saveTextNormalization);
+normalizeDatesCheckbox.addEventListener('change', saveTextNormalization);
+
+saveRawAudioCheckbox.addEventListener('change', () => {
+  updateDestinationPreview();
+  syncActionButtons();
+});
+
+languageSelect.addEventListener('change', async () => {
+  renderLanguageModel();
+  syncActionButtons();
+  if (diarizationModeSelect.value === 'tdrz_2speaker') {
+    await ensureTwoSpeakerRequirements();
+  }
+});
+
+languageModelSelect.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_language_model', {
+      language: languageSelect.value,
+      model: languageModelSelect.value || null,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save language model: ${String(error)}`, 'error');
+  }
+});
+
+function renderRecordingPresets() {
+  const presets = setupState.recording_presets || [];
+  const noneOption = document.createElement('option');
+  noneOption.value = '';
+  noneOption.textContent = 'None';
+  recordingPresetSelect.replaceChildren(
+    noneOption,
+    ...presets.map((preset) => {
+      const option = document.createElement('option');
+      option.value = preset.name;
+      option.textContent = preset.name;
+      return option;
+    }),
+  );
+  recordingPresetSelect.value = setupState.active_recording_preset || '';
+  recordingPresetSelect.disabled = isRecording;
+  deletePresetBtn.disabled = !setupState.active_recording_preset;
+
+  const preset = activeRecordingPreset();
+  if (!preset) return;
+  presetNameInput.value = preset.name;
+  presetDeviceSelect.value = preset.input_device_id || '';
+  presetSampleRateSelect.value = String(preset.sample_rate);
+  presetChannelsSelect.value = String(preset.channels);
+  presetAutoStopInput.value = preset.auto_stop_minutes ? String(preset.auto_stop_minutes) : '';
+  presetSilenceInput.value = preset.auto_stop_silence_seconds ? String(preset.auto_stop_silence_seconds) : '';
+}
+
+// Device labels stay empty until microphone access has been granted once.
+async function renderInputDevices() {
+  let devices = [];
+  try {
+    devices = (await navigator.mediaDevices.enumerateDevices())
+      .filter((device) => device.kind === 'audioinput' && device.deviceId !== 'default');
+  } catch {
+    return;
+  }
+  const defaultOption = document.createElement('option');
+  defaultOption.value = '';
+  defaultOption.textContent = 'System default';
+  presetDeviceSelect.replaceChildren(
+    defaultOption,
+    ...devices.map((device, index) => {
+      const option = document.createElement('option');
+      option.value = device.deviceId;
+      option.textContent = device.label || `Microphone ${index + 1}`;
+      return option;
+    }),
+  );
+  presetDeviceSelect.value = activeRecordingPreset()?.input_device_id || '';
+}
+
+function applyRecordingPreset() {
+  const preset = activeRecordingPreset();
+  if (!preset || preset.capture_mode === captureModeSelect.value) return;
+  captureModeSelect.value = preset.capture_mode;
+  captureModeSelect.dispatchEvent(new Event('change'));
+}
+
+recordingPresetSelect.addEventListener('change', async () => {
+  try {
+    setupState = await invoke('set_active_recording_preset', {
+      name: recordingPresetSelect.value || null,
+    });
+    renderSetupState();
+    applyRecordingPreset();
+  } catch (error) {
+    setStatus(`Failed to select recording preset: ${String(error)}`, 'error');
+  }
+});
+
+savePresetBtn.addEventListener('click', async () => {
+  const name = presetNameInput.value.trim();
+  if (!name) {
+    setStatus('Enter a name for the recording preset.', 'error');
+    return;
+  }
+  try {
+    setupState = await invoke('save_recording_preset', {
+      preset: {
+        name,
+        capture_mode: selectedCaptureMode(),
+        input_device_id: presetDeviceSelect.value || null,
+        sample_rate: Number(presetSampleRateSelect.value),
+        channels: Number(presetChannelsSelect.value),
+        auto_stop_minutes: Number(presetAutoStopInput.value) || null,
+        auto_stop_silence_seconds: Number(presetSilenceInput.value) || null,
+      },
+    });
+    renderSetupState();
+    setStatus(`Saved recording preset "${name}".`, 'ready');
+  } catch (error) {
+    setStatus(`Failed to save recording preset: ${String(error)}`, 'error');
+  }
+});
+
+deletePresetBtn.addEventListener('click', async () => {
+  const name = recordingPresetSelect.value;
+  if (!name) return;
+  try {
+    setupState = await invoke('delete_recording_preset', { name });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to delete recording preset: ${String(error)}`, 'error');
+  }
+});
+
+captureModeSelect.addEventListener('change', () => {
+  updateCaptureModeHelp();
+  updateDestinationPreview();
+});
+
+for (const button of captureOptionButtons) {
+  button.addEventListener('click', () => {
+    const nextMode = button.dataset.captureOption;
+    if (!nextMode || nextMode === captureModeSelect.value) {
+      return;
+    }
+
+    captureModeSelect.value = nextMode;
+    captureModeSelect.dispatchEvent(new Event('change'));
+  });
+}
+
+diarizationModeSelect.addEventListener('change', async () => {
+  const nextMode = diarizationModeSelect.value;
+
+  if (nextMode === 'tdrz_2speaker') {
+    const enabled = await ensureTwoSpeakerRequirements();
+    if (!enabled) {
+      await saveDiarizationMode('none');
+      return;
+    }
+  }
+
+  try {
+    await saveDiarizationMode(nextMode);
+  } catch (error) {
+    setStatus(`Failed to update speaker mode: ${String(error)}`, 'error');
+    return;
   }
 
   if (nextMode === 'source_aware_2speaker') {
@@ -1485,6 +3292,157 @@ openFileBtn.addEventListener('click', async () => {
   }
 });
 
+exportSpeakersBtn.addEventListener('click', async () => {
+  if (!savedTranscriptPath) return;
+  try {
+    const paths = await invoke('export_speaker_files', { path: savedTranscriptPath });
+    setStatus(`Saved ${paths.length} speaker file${paths.length === 1 ? '' : 's'}.`, 'ready');
+  } catch (error) {
+    setStatus(`Could not export speaker files: ${String(error)}`, 'error');
+  }
+});
+
+exportAnonymizedBtn.addEventListener('click', async () => {
+  if (!savedTranscriptPath) return;
+  try {
+    const path = await invoke('export_anonymized_transcript', { path: savedTranscriptPath });
+    setStatus(`Saved anonymized copy: ${path}`, 'ready');
+  } catch (error) {
+    setStatus(`Could not export anonymized transcript: ${String(error)}`, 'error');
+  }
+});
+
+exportSegmentsBtn.addEventListener('click', async () => {
+  if (!savedTranscriptPath) return;
+  try {
+    const path = await invoke('export_segments_csv', { path: savedTranscriptPath });
+    setStatus(`Saved segments CSV: ${path}`, 'ready');
+  } catch (error) {
+    setStatus(`Could not export segments: ${String(error)}`, 'error');
+  }
+});
+
+exportTextGridBtn.addEventListener('click', async () => {
+  if (!savedTranscriptPath) return;
+  try {
+    const path = await invoke('export_textgrid', { path: savedTranscriptPath });
+    setStatus(`Saved TextGrid: ${path}`, 'ready');
+  } catch (error) {
+    setStatus(`Could not export TextGrid: ${String(error)}`, 'error');
+  }
+});
+
+exportElanBtn.addEventListener('click', async () => {
+  if (!savedTranscriptPath) return;
+  try {
+    const path = await invoke('export_elan', { path: savedTranscriptPath });
+    setStatus(`Saved ELAN file: ${path}`, 'ready');
+  } catch (error) {
+    setStatus(`Could not export ELAN file: ${String(error)}`, 'error');
+  }
+});
+
+exportBundleBtn.addEventListener('click', async () => {
+  if (!savedTranscriptPath) return;
+  try {
+    const name = savedTranscriptPath.split(/[\\/]/).pop().replace(/\.md$/, '');
+    const dest = await save({
+      defaultPath: `${name}-bundle.zip`,
+      filters: [{ name: 'ZIP archive', extensions: ['zip'] }],
+    });
+    if (!dest) {
+      return;
+    }
+    // The save dialog has already asked before replacing an existing file.
+    const path = await invoke('export_bundle', {
+      path: savedTranscriptPath,
+      dest,
+      includeAudio: savedAudioPaths.length > 0,
+      overwrite: true,
+    });
+    setStatus(`Saved session bundle: ${path}`, 'ready');
+  } catch (error) {
+    setStatus(`Could not export session bundle: ${String(error)}`, 'error');
+  }
+});
+
+exportDriveBtn.addEventListener('click', async () => {
+  if (!savedTranscriptPath) return;
+  setStatus('Uploading transcript to Google Drive...', 'working');
+  try {
+    const upload = await invoke('export_to_drive', { path: savedTranscriptPath });
+    setStatus(`Uploaded to Google Drive${upload.web_view_link ? `: ${upload.web_view_link}` : '.'}`, 'ready');
+  } catch (error) {
+    setStatus(`Could not upload to Google Drive: ${String(error)}`, 'error');
+  }
+});
+
+function renderDriveStatus() {
+  const connected = Boolean(setupState.drive_connected);
+  connectDriveBtn.textContent = connected ? 'Reconnect' : 'Connect Google Drive';
+  disconnectDriveBtn.hidden = !connected;
+  exportToDriveCheckbox.disabled = !connected;
+  driveStatus.textContent = connected
+    ? `Connected. Transcripts go to "${setupState.drive_folder_name}" in My Drive.`
+    : 'Not connected. Create a Desktop app OAuth client in Google Cloud Console and enter its ID and secret.';
+}
+
+async function saveDriveSettings() {
+  try {
+    setupState = await invoke('set_drive_settings', {
+      clientId: driveClientIdInput.value.trim() || null,
+      folderName: driveFolderInput.value.trim() || null,
+      asGoogleDoc: driveAsGoogleDocCheckbox.checked,
+    });
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Failed to save Google Drive settings: ${String(error)}`, 'error');
+  }
+}
+
+driveClientIdInput.addEventListener('change', saveDriveSettings);
+driveFolderInput.addEventListener('change', saveDriveSettings);
+driveAsGoogleDocCheckbox.addEventListener('change', saveDriveSettings);
+
+connectDriveBtn.addEventListener('click', async () => {
+  connectDriveBtn.disabled = true;
+  setStatus('Finish signing in to Google in your browser...', 'working');
+  try {
+    setupState = await invoke('connect_google_drive', {
+      clientSecret: driveClientSecretInput.value.trim() || null,
+    });
+    driveClientSecretInput.value = '';
+    renderSetupState();
+    setStatus('Google Drive connected.', 'ready');
+  } catch (error) {
+    setStatus(`Could not connect Google Drive: ${String(error)}`, 'error');
+  } finally {
+    connectDriveBtn.disabled = false;
+  }
+});
+
+disconnectDriveBtn.addEventListener('click', async () => {
+  try {
+    setupState = await invoke('disconnect_google_drive');
+    renderSetupState();
+  } catch (error) {
+    setStatus(`Could not disconnect Google Drive: ${String(error)}`, 'error');
+  }
+});
+
+async function shareTranscript(target, label) {
+  if (!savedTranscriptPath) return;
+  try {
+    await invoke('share_to', { app: target, path: savedTranscriptPath });
+    setStatus(`Sent transcript to ${label}.`, 'ready');
+  } catch (error) {
+    setStatus(`Could not send transcript to ${label}: ${String(error)}`, 'error');
+  }
+}
+
+shareBearBtn.addEventListener('click', () => shareTranscript('bear', 'Bear'));
+shareLogseqBtn.addEventListener('click', () => shareTranscript('logseq', 'Logseq'));
+
 if (titlebar && appWindow && typeof appWindow.startDragging === 'function') {
   titlebar.addEventListener('mousedown', (event) => {
     if (event.button !== 0) {
@@ -1499,12 +3457,166 @@ if (titlebar && appWindow && typeof appWindow.startDragging === 'function') {
   });
 }
 
+listen('transcript-migration-progress', (event) => {
+  const { done, total } = event.payload;
+  setStatus(`Moving transcripts... ${done}/${total}`, 'working');
+});
+
 listen('progress', (event) => {
   const { percent, message } = event.payload;
   progressFill.style.width = `${percent}%`;
   progressText.textContent = message;
 });
 
+listen('settings-changed', async (event) => {
+  await applySetupState(event.payload);
+});
+
+listen('settings-reload-failed', (event) => {
+  const { path, error } = event.payload;
+  setStatus(`Ignored an outside edit to ${path}: ${error}. The current settings are still in use.`, 'warning');
+});
+
+listen('coachnotes-clients-changed', (event) => {
+  const { root_dir: rootDir, clients } = event.payload;
+  if (!setupState || rootDir !== coachnotesRootDirInput.value) return;
+  setupState.coachnotes_clients = clients;
+  populateCoachnotesClients(clients, coachnotesClientSelect.value || '');
+  updateDestinationPreview();
+});
+
+function renderQueue(jobs) {
+  queueList.innerHTML = '';
+  queueSection.hidden = jobs.length === 0;
+  jobs.forEach((job, index) => {
+    const item = document.createElement('li');
+    item.className = 'queue-item';
+    const label = document.createElement('span');
+    label.textContent = job.priority === 'normal' ? job.label : `${job.label} (${job.priority})`;
+    if (job.scheduled_for) {
+      label.textContent += `, scheduled for ${job.scheduled_for}`;
+    }
+    item.append(label);
+    if (index > 0 || job.scheduled_for) {
+      const runNext = document.createElement('button');
+      runNext.className = 'btn btn-ghost btn-sm';
+      runNext.textContent = 'Run next';
+      runNext.addEventListener('click', async () => {
+        try {
+          renderQueue(await invoke('reorder_queue', { jobIds: [job.job_id] }));
+        } catch (error) {
+          setStatus(`Could not reorder the queue: ${String(error)}`, 'warning');
+        }
+      });
+      item.append(runNext);
+    }
+    queueList.append(item);
+  });
+}
+
+listen('queue-changed', (event) => {
+  renderQueue(event.payload || []);
+});
+
+invoke('get_queue')
+  .then((jobs) => renderQueue(jobs || []))
+  .catch(() => {});
+
+listen('file-drop-queued', (event) => {
+  const { source_path: sourcePath, planned_path: plannedPath } = event.payload;
+  setStatus(`Queued ${sourcePath} for transcription. Saving to ${plannedPath}`, 'working');
+});
+
+listen('recorder-mounted', async (event) => {
+  const { name, files } = event.payload;
+  const confirmed = await ask(
+    `${name} has ${files.length} new recording${files.length === 1 ? '' : 's'}. Import and transcribe them now?`,
+    { title: 'Recorder connected', kind: 'info', okLabel: 'Import & transcribe all', cancelLabel: 'Not now' }
+  );
+  if (confirmed) {
+    await importRecorder(name);
+  }
+});
+
+listen('webhook-failed', (event) => {
+  const { url, event: name, error } = event.payload;
+  setStatus(`Webhook ${url} (${name}) failed: ${error}`, 'warning');
+});
+
+listen('drive-upload-finished', (event) => {
+  const { web_view_link: link } = event.payload;
+  setStatus(`Uploaded to Google Drive${link ? `: ${link}` : '.'}`, 'ready');
+});
+
+listen('drive-upload-failed', (event) => {
+  const { path, error } = event.payload;
+  setStatus(`Google Drive upload of ${path} failed: ${error}`, 'warning');
+});
+
+listen('file-drop-rejected', (event) => {
+  setStatus(event.payload.error, 'error');
+});
+
+listen('job-paused', (event) => {
+  const { paused_reason: pausedReason, scheduled_for: scheduledFor } = event.payload;
+  const reason =
+    pausedReason === 'scheduled'
+      ? `scheduled for ${scheduledFor}.`
+      : pausedReason === 'on_battery'
+        ? 'paused: on battery. It starts when power is connected.'
+        : 'held by battery saver for up to 15 minutes.';
+  setStatus(`Queued transcription ${reason}`, 'warning');
+});
+
+listen('job-resumed', () => {
+  setStatus('Queued transcription resumed.', 'working');
+});
+
+listen('url-command', async (event) => {
+  const { action, path } = event.payload;
+  if (action === 'transcribe') {
+    const confirmed = await ask(`An automation link asked to transcribe ${path}. Queue it?`, {
+      title: 'Echo Scribe',
+      kind: 'warning',
+      okLabel: 'Transcribe',
+      cancelLabel: 'Ignore',
+    });
+    try {
+      await invoke('confirm_url_transcribe', { path, confirmed });
+    } catch (error) {
+      setStatus(`Automation link failed: ${error}`, 'error');
+    }
+    return;
+  }
+  const shouldRecord = action === 'record/toggle' ? !isRecording : action === 'record/start';
+  if (shouldRecord && !isRecording) {
+    const confirmed = await ask('An automation link asked to start recording. Start now?', {
+      title: 'Echo Scribe',
+      kind: 'warning',
+      okLabel: 'Start recording',
+      cancelLabel: 'Ignore',
+    });
+    if (confirmed && !isRecording) {
+      void startRecording();
+    }
+  } else if (!shouldRecord && isRecording) {
+    void stopRecording();
+  }
+});
+
+listen('url-command-failed', (event) => {
+  setStatus(`Automation link failed: ${event.payload.error}`, 'error');
+});
+
+listen('storage-migration-progress', (event) => {
+  const { percent, message } = event.payload;
+  setStatus(message, percent >= 100 ? 'ready' : 'working');
+});
+
+listen('storage-migration-failed', (event) => {
+  setStatus(`${event.payload} It stays in the old folder for now.`, 'error');
+});
+
 listen('model-download-progress', (event) => {
   const payload = event.payload;
   const percent = Math.max(0, Math.min(100, payload.percent || 0));
@@ -1516,6 +3628,61 @@ listen('model-download-progress', (event) => {
   modelProgressText.textContent = `${payload.message} (${downloaded} / ${total})`;
 });
 
+listen('model-verified', (event) => {
+  const { verified, message } = event.payload;
+  if (verified) {
+    modelProgressText.textContent = message;
+  } else {
+    setStatus(message, 'error');
+  }
+});
+
+listen('model-corrupted', async (event) => {
+  const { model, redownloading, message } = event.payload;
+  if (redownloading) {
+    setStatus(message, 'warning');
+    return;
+  }
+  if (modelDownloadInProgress) {
+    setStatus(message, 'error');
+    return;
+  }
+  const confirmed = await ask(message, {
+    title: 'Damaged model',
+    kind: 'warning',
+    okLabel: 'Download again',
+    cancelLabel: 'Not now',
+  });
+  if (confirmed) {
+    await downloadModel(model, true);
+  } else {
+    setStatus(message, 'error');
+  }
+});
+
+// Offers to pick up split jobs that stopped partway, from their first
+// unfinished part.
+async function offerResumableJobs() {
+  const jobs = await invoke('list_resumable_jobs');
+  for (const job of jobs) {
+    const confirmed = await ask(
+      `A transcription stopped after ${job.completed_parts} of ${job.part_count} parts. Resume it from part ${job.completed_parts + 1}?`,
+      { title: 'Resume transcription?', kind: 'info', okLabel: 'Resume', cancelLabel: 'Not now' }
+    );
+    if (!confirmed) continue;
+    setStatus(`Resuming transcription from part ${job.completed_parts + 1}...`, 'working');
+    try {
+      const result = await invoke('resume_job', { jobId: job.job_id });
+      setStatus(
+        result.saved_path ? `Resumed transcription saved to ${result.saved_path}` : 'Resumed transcription complete',
+        'ready'
+      );
+    } catch (error) {
+      setStatus(`Resume failed: ${String(error)}`, 'error');
+    }
+  }
+}
+
 async function boot() {
   resetTimer();
   resetMicMeter();
@@ -1532,6 +3699,14 @@ async function boot() {
   } catch (error) {
     setStatus(`Setup load failed: ${String(error)}`, 'error');
   }
+
+  try {
+    if (!setupState?.app_locked) {
+      await offerResumableJobs();
+    }
+  } catch (error) {
+    setStatus(`Failed to check for interrupted jobs: ${String(error)}`, 'error');
+  }
 }
 
 boot();